@@ -0,0 +1,200 @@
+//! [`AudioBuffer`], a newtype over [`AudioData`] with conversions to and
+//! from common PCM representations.
+//!
+//! All integer conversions assume signed 16-bit PCM scaled so that
+//! `i16::MAX` is full scale, matching [`to_i16_pcm`] and [`from_i16_pcm`].
+//! Byte conversions use little-endian order, as in WAV files. Buffers carry
+//! no channel information: multi-channel audio stays interleaved exactly as
+//! it was.
+
+use crate::audio_effects::{from_i16_pcm, to_i16_pcm};
+use crate::error::{VocalizeError, VocalizeResult};
+use crate::tts_engine::AudioData;
+use bytes::Bytes;
+use std::ops::{Deref, DerefMut};
+
+/// Owned f32 samples in `[-1.0, 1.0]` with PCM conversions
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AudioBuffer(pub AudioData);
+
+impl AudioBuffer {
+    /// Consume the buffer and return its samples
+    #[must_use]
+    pub fn into_inner(self) -> AudioData {
+        self.0
+    }
+
+    /// Encode the samples as 16-bit little-endian PCM bytes
+    ///
+    /// Out-of-range samples are clamped.
+    #[must_use]
+    pub fn to_pcm16_le_bytes(&self) -> Vec<u8> {
+        to_i16_pcm(&self.0).into_iter().flat_map(i16::to_le_bytes).collect()
+    }
+
+    /// Decode 16-bit little-endian PCM bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` has an odd length
+    pub fn from_pcm16_le_bytes(bytes: &[u8]) -> VocalizeResult<Self> {
+        if bytes.len() % 2 != 0 {
+            return Err(VocalizeError::invalid_input(format!(
+                "16-bit PCM data must have an even number of bytes, got {}",
+                bytes.len()
+            )));
+        }
+        let pcm: Vec<i16> = bytes
+            .chunks_exact(2)
+            .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        Ok(Self(from_i16_pcm(&pcm)))
+    }
+}
+
+impl Deref for AudioBuffer {
+    type Target = AudioData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for AudioBuffer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<AudioData> for AudioBuffer {
+    fn from(samples: AudioData) -> Self {
+        Self(samples)
+    }
+}
+
+impl From<AudioBuffer> for AudioData {
+    fn from(buffer: AudioBuffer) -> Self {
+        buffer.0
+    }
+}
+
+/// Decode signed 16-bit PCM samples
+impl From<&[i16]> for AudioBuffer {
+    fn from(pcm: &[i16]) -> Self {
+        Self(from_i16_pcm(pcm))
+    }
+}
+
+/// Encode as signed 16-bit PCM samples, clamping out-of-range values
+impl From<&AudioBuffer> for Vec<i16> {
+    fn from(buffer: &AudioBuffer) -> Self {
+        to_i16_pcm(&buffer.0)
+    }
+}
+
+/// Decode 16-bit little-endian PCM bytes; fails on an odd byte count
+impl TryFrom<&[u8]> for AudioBuffer {
+    type Error = VocalizeError;
+
+    fn try_from(bytes: &[u8]) -> VocalizeResult<Self> {
+        Self::from_pcm16_le_bytes(bytes)
+    }
+}
+
+/// Decode 16-bit little-endian PCM bytes; fails on an odd byte count
+impl TryFrom<Vec<u8>> for AudioBuffer {
+    type Error = VocalizeError;
+
+    fn try_from(bytes: Vec<u8>) -> VocalizeResult<Self> {
+        Self::from_pcm16_le_bytes(&bytes)
+    }
+}
+
+/// Encode as 16-bit little-endian PCM bytes
+impl From<&AudioBuffer> for Vec<u8> {
+    fn from(buffer: &AudioBuffer) -> Self {
+        buffer.to_pcm16_le_bytes()
+    }
+}
+
+/// Decode 16-bit little-endian PCM bytes; fails on an odd byte count
+impl TryFrom<Bytes> for AudioBuffer {
+    type Error = VocalizeError;
+
+    fn try_from(bytes: Bytes) -> VocalizeResult<Self> {
+        Self::from_pcm16_le_bytes(&bytes)
+    }
+}
+
+/// Encode as 16-bit little-endian PCM bytes
+impl From<&AudioBuffer> for Bytes {
+    fn from(buffer: &AudioBuffer) -> Self {
+        Self::from(buffer.to_pcm16_le_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One quantization step of 16-bit PCM
+    const STEP: f32 = 1.0 / 32767.0;
+
+    fn samples() -> AudioBuffer {
+        AudioBuffer(vec![0.0, 0.5, -0.5, 1.0, -1.0, 0.123, -0.987])
+    }
+
+    fn assert_close(actual: &[f32], expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() <= STEP, "{a} vs {e}");
+        }
+    }
+
+    #[test]
+    fn test_audio_data_round_trip() {
+        let data: AudioData = samples().into();
+        let buffer = AudioBuffer::from(data.clone());
+        assert_eq!(buffer.len(), data.len());
+        assert_eq!(buffer.into_inner(), data);
+    }
+
+    #[test]
+    fn test_i16_round_trip() {
+        let buffer = samples();
+        let pcm: Vec<i16> = (&buffer).into();
+        assert_eq!(pcm[3], i16::MAX);
+        assert_eq!(pcm[4], -i16::MAX);
+
+        let decoded = AudioBuffer::from(pcm.as_slice());
+        assert_close(&decoded, &buffer);
+    }
+
+    #[test]
+    fn test_pcm_bytes_round_trip() {
+        let buffer = samples();
+        let bytes: Vec<u8> = (&buffer).into();
+        assert_eq!(bytes.len(), buffer.len() * 2);
+        // Little-endian full scale
+        assert_eq!(&bytes[6..8], &i16::MAX.to_le_bytes());
+
+        let decoded = AudioBuffer::try_from(bytes.as_slice()).unwrap();
+        assert_close(&decoded, &buffer);
+        let decoded = AudioBuffer::try_from(bytes).unwrap();
+        assert_close(&decoded, &buffer);
+
+        assert!(AudioBuffer::try_from(&[0u8, 1, 2][..]).is_err());
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let buffer = samples();
+        let bytes = Bytes::from(&buffer);
+        assert_eq!(bytes.len(), buffer.len() * 2);
+
+        let decoded = AudioBuffer::try_from(bytes).unwrap();
+        assert_close(&decoded, &buffer);
+
+        assert!(AudioBuffer::try_from(Bytes::from_static(&[1])).is_err());
+    }
+}
@@ -1,13 +1,19 @@
 //! Audio device management for real-time audio playback.
 
+use crate::audio_effects::{to_i16_pcm, StreamingResampler};
 use crate::error::{VocalizeError, VocalizeResult};
 use crate::tts_engine::AudioData;
-use std::sync::atomic::{AtomicBool, Ordering};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{debug, info};
 
+/// Maximum amount of audio the playback queue holds, in seconds
+const MAX_QUEUED_SECONDS: usize = 300;
+
 /// Playback state enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PlaybackState {
@@ -32,6 +38,70 @@ impl std::fmt::Display for PlaybackState {
     }
 }
 
+/// Sample encoding an output device accepts
+///
+/// Synthesized audio is always f32; devices that want integer samples get
+/// it converted in the playback callback, see [`SampleFormat::convert`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SampleFormat {
+    /// 32-bit float in `[-1.0, 1.0]`
+    #[default]
+    F32,
+    /// Signed 16-bit integer
+    I16,
+    /// Unsigned 16-bit integer, with silence at 32768
+    U16,
+}
+
+/// Samples in a device's native format
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeviceSamples {
+    /// 32-bit float samples
+    F32(Vec<f32>),
+    /// Signed 16-bit samples
+    I16(Vec<i16>),
+    /// Unsigned 16-bit samples
+    U16(Vec<u16>),
+}
+
+impl DeviceSamples {
+    /// Number of samples
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::F32(samples) => samples.len(),
+            Self::I16(samples) => samples.len(),
+            Self::U16(samples) => samples.len(),
+        }
+    }
+
+    /// Whether there are no samples
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl SampleFormat {
+    /// Convert f32 samples to this format
+    ///
+    /// Integer formats clamp out-of-range samples to full scale, with the
+    /// same scaling as [`to_i16_pcm`]; `U16` is `I16` shifted up by 32768.
+    #[must_use]
+    pub fn convert(self, audio: &[f32]) -> DeviceSamples {
+        match self {
+            Self::F32 => DeviceSamples::F32(audio.to_vec()),
+            Self::I16 => DeviceSamples::I16(to_i16_pcm(audio)),
+            Self::U16 => DeviceSamples::U16(
+                to_i16_pcm(audio)
+                    .into_iter()
+                    .map(|sample| (i32::from(sample) + 32768) as u16)
+                    .collect(),
+            ),
+        }
+    }
+}
+
 /// Audio device information
 #[derive(Debug, Clone, PartialEq)]
 pub struct AudioDeviceInfo {
@@ -60,8 +130,19 @@ pub struct AudioConfig {
     pub buffer_size: u32,
     /// Playback latency target
     pub latency: Duration,
+    /// Sample format the device was negotiated to
+    pub sample_format: SampleFormat,
+    /// How long [`AudioDevice::wait_for_completion`] waits without playback
+    /// making progress before giving up
+    ///
+    /// The timer restarts whenever more samples are played, so clips of any
+    /// length can finish.
+    pub completion_timeout: Duration,
 }
 
+/// Default [`AudioConfig::completion_timeout`]
+pub const DEFAULT_COMPLETION_TIMEOUT: Duration = Duration::from_secs(30);
+
 impl Default for AudioConfig {
     fn default() -> Self {
         Self {
@@ -70,16 +151,153 @@ impl Default for AudioConfig {
             channels: crate::DEFAULT_CHANNELS,
             buffer_size: 1024,
             latency: Duration::from_millis(50),
+            sample_format: SampleFormat::F32,
+            completion_timeout: DEFAULT_COMPLETION_TIMEOUT,
         }
     }
 }
 
+/// A clip waiting in the playback queue
+#[derive(Debug)]
+struct QueuedClip {
+    audio: AudioData,
+    /// Silent samples played before `audio`
+    gap_samples: usize,
+    /// Value of [`PlaybackShared::interrupts`] when the clip was queued
+    generation: usize,
+}
+
+impl QueuedClip {
+    fn len(&self) -> usize {
+        self.gap_samples + self.audio.len()
+    }
+}
+
+/// Playback state shared between the device and its queue worker
+#[derive(Debug)]
+struct PlaybackShared {
+    sample_rate: u32,
+    buffer_size: usize,
+    /// Format the buffers recorded in `device_buffers` are converted to
+    #[cfg(test)]
+    sample_format: SampleFormat,
+    state: RwLock<PlaybackState>,
+    is_running: AtomicBool,
+    interrupted: AtomicBool,
+    /// Number of interrupts so far, only changed with `queue` locked
+    interrupts: AtomicUsize,
+    samples_played: AtomicUsize,
+    queue: Mutex<VecDeque<QueuedClip>>,
+    queue_active: AtomicBool,
+    closed: AtomicBool,
+    #[cfg(test)]
+    played: Mutex<Vec<f32>>,
+    /// Buffers as handed to the device, after format conversion
+    #[cfg(test)]
+    device_buffers: Mutex<Vec<DeviceSamples>>,
+}
+
+impl PlaybackShared {
+    fn new(config: &AudioConfig) -> Self {
+        Self {
+            sample_rate: config.sample_rate,
+            buffer_size: (config.buffer_size as usize).max(1),
+            #[cfg(test)]
+            sample_format: config.sample_format,
+            state: RwLock::new(PlaybackState::Stopped),
+            is_running: AtomicBool::new(false),
+            interrupted: AtomicBool::new(false),
+            interrupts: AtomicUsize::new(0),
+            samples_played: AtomicUsize::new(0),
+            queue: Mutex::new(VecDeque::new()),
+            queue_active: AtomicBool::new(false),
+            closed: AtomicBool::new(false),
+            #[cfg(test)]
+            played: Mutex::new(Vec::new()),
+            #[cfg(test)]
+            device_buffers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Simulate consuming audio data in `buffer_size` chunks, as a playback
+    /// callback would; tests also record each chunk converted to the
+    /// device's sample format
+    async fn play_chunk(&self, audio_data: &[f32]) {
+        debug!("Mock playing {} samples", audio_data.len());
+
+        // Simulate playback by setting state to playing briefly
+        *self.state.write().await = PlaybackState::Playing;
+        self.is_running.store(true, Ordering::Relaxed);
+
+        // Simulate playback time, capped at 100ms per call for tests
+        let duration = Duration::from_secs_f64(
+            audio_data.len() as f64 / f64::from(self.sample_rate),
+        )
+        .min(Duration::from_millis(100));
+        let chunk_count = audio_data.len().div_ceil(self.buffer_size);
+        let chunk_duration = duration / chunk_count as u32;
+
+        for buffer in audio_data.chunks(self.buffer_size) {
+            if self.interrupted.load(Ordering::Relaxed) {
+                debug!(
+                    "Playback interrupted after {} samples",
+                    self.samples_played.load(Ordering::Relaxed)
+                );
+                return;
+            }
+            // Without an audio backend nothing consumes the converted buffer,
+            // so it is only produced for tests to inspect
+            #[cfg(test)]
+            {
+                self.played.lock().extend_from_slice(buffer);
+                self.device_buffers.lock().push(self.sample_format.convert(buffer));
+            }
+            self.samples_played.fetch_add(buffer.len(), Ordering::Relaxed);
+            tokio::time::sleep(chunk_duration).await;
+        }
+    }
+
+    /// Return to the stopped state after playback finishes
+    async fn finish_playback(&self) {
+        *self.state.write().await = PlaybackState::Stopped;
+        self.is_running.store(false, Ordering::Relaxed);
+    }
+
+    /// Drain the queue one clip at a time until it is empty
+    async fn run_queue(self: Arc<Self>) {
+        loop {
+            let next = self.queue.lock().pop_front();
+            if let Some(clip) = next {
+                // Clips queued after the last interrupt play even if this
+                // worker is still winding down from it
+                if clip.generation == self.interrupts.load(Ordering::Acquire) {
+                    self.interrupted.store(false, Ordering::Relaxed);
+                }
+                if clip.gap_samples > 0 {
+                    self.play_chunk(&vec![0.0; clip.gap_samples]).await;
+                }
+                self.play_chunk(&clip.audio).await;
+                continue;
+            }
+
+            self.finish_playback().await;
+
+            // A clip may have been enqueued while we were stopping
+            let queue = self.queue.lock();
+            if queue.is_empty() {
+                self.queue_active.store(false, Ordering::Release);
+                break;
+            }
+        }
+        debug!("Playback queue drained");
+    }
+}
+
 /// Mock audio device for testing and platforms without audio support
 #[derive(Debug)]
 pub struct AudioDevice {
     config: AudioConfig,
-    state: Arc<RwLock<PlaybackState>>,
-    is_running: Arc<AtomicBool>,
+    shared: Arc<PlaybackShared>,
     #[cfg_attr(not(test), allow(dead_code))]
     mock_mode: bool,
 }
@@ -88,20 +306,20 @@ impl AudioDevice {
     /// Create a new audio device for testing (synchronous)
     #[cfg(test)]
     pub fn new_mock() -> Self {
+        let config = AudioConfig::default();
         Self {
-            config: AudioConfig::default(),
-            state: Arc::new(RwLock::new(PlaybackState::Stopped)),
-            is_running: Arc::new(AtomicBool::new(false)),
+            shared: Arc::new(PlaybackShared::new(&config)),
+            config,
             mock_mode: true,
         }
     }
 
     /// Create a new mock audio device for Python bindings
     pub fn new_mock_for_bindings() -> Self {
+        let config = AudioConfig::default();
         Self {
-            config: AudioConfig::default(),
-            state: Arc::new(RwLock::new(PlaybackState::Stopped)),
-            is_running: Arc::new(AtomicBool::new(false)),
+            shared: Arc::new(PlaybackShared::new(&config)),
+            config,
             mock_mode: true,
         }
     }
@@ -124,9 +342,8 @@ impl AudioDevice {
         info!("Creating mock audio device with config: {:?}", config);
 
         Ok(Self {
+            shared: Arc::new(PlaybackShared::new(&config)),
             config,
-            state: Arc::new(RwLock::new(PlaybackState::Stopped)),
-            is_running: Arc::new(AtomicBool::new(false)),
             mock_mode: true,
         })
     }
@@ -162,7 +379,7 @@ impl AudioDevice {
     ///
     /// Returns an error if the audio stream cannot be created or started
     pub async fn start(&self) -> VocalizeResult<()> {
-        let current_state = *self.state.read().await;
+        let current_state = *self.shared.state.read().await;
         if current_state == PlaybackState::Playing {
             debug!("Audio device already playing");
             return Ok(());
@@ -170,9 +387,9 @@ impl AudioDevice {
 
         info!("Starting mock audio playback");
 
-        let mut state = self.state.write().await;
+        let mut state = self.shared.state.write().await;
         *state = PlaybackState::Playing;
-        self.is_running.store(true, Ordering::Relaxed);
+        self.shared.is_running.store(true, Ordering::Relaxed);
 
         Ok(())
     }
@@ -185,8 +402,8 @@ impl AudioDevice {
     pub async fn stop(&self) -> VocalizeResult<()> {
         info!("Stopping mock audio playback");
 
-        self.is_running.store(false, Ordering::Relaxed);
-        let mut state = self.state.write().await;
+        self.shared.is_running.store(false, Ordering::Relaxed);
+        let mut state = self.shared.state.write().await;
         *state = PlaybackState::Stopped;
 
         Ok(())
@@ -198,13 +415,13 @@ impl AudioDevice {
     ///
     /// Returns an error if the audio stream cannot be paused
     pub async fn pause(&self) -> VocalizeResult<()> {
-        let current_state = *self.state.read().await;
+        let current_state = *self.shared.state.read().await;
         if current_state != PlaybackState::Playing {
             return Err(VocalizeError::audio_device("Cannot pause: not currently playing"));
         }
 
         info!("Pausing mock audio playback");
-        let mut state = self.state.write().await;
+        let mut state = self.shared.state.write().await;
         *state = PlaybackState::Paused;
 
         Ok(())
@@ -216,13 +433,13 @@ impl AudioDevice {
     ///
     /// Returns an error if the audio stream cannot be resumed
     pub async fn resume(&self) -> VocalizeResult<()> {
-        let current_state = *self.state.read().await;
+        let current_state = *self.shared.state.read().await;
         if current_state != PlaybackState::Paused {
             return Err(VocalizeError::audio_device("Cannot resume: not currently paused"));
         }
 
         info!("Resuming mock audio playback");
-        let mut state = self.state.write().await;
+        let mut state = self.shared.state.write().await;
         *state = PlaybackState::Playing;
 
         Ok(())
@@ -230,31 +447,182 @@ impl AudioDevice {
 
     /// Play audio data
     ///
+    /// Samples are consumed in `buffer_size` chunks; a call to
+    /// [`interrupt`](Self::interrupt) stops playback before the next chunk.
+    ///
     /// # Errors
     ///
     /// Returns an error if the audio cannot be queued for playback
     pub async fn play(&self, audio_data: &AudioData) -> VocalizeResult<()> {
+        self.ensure_open()?;
         if audio_data.is_empty() {
             return Err(VocalizeError::invalid_input("Audio data cannot be empty"));
         }
 
-        debug!("Mock playing {} samples", audio_data.len());
+        self.shared.interrupted.store(false, Ordering::Relaxed);
+        self.shared.play_chunk(audio_data).await;
+        self.shared.finish_playback().await;
 
-        // Simulate playback by setting state to playing briefly
-        let mut state = self.state.write().await;
-        *state = PlaybackState::Playing;
-        self.is_running.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Play a sequence of synthesized audio chunks back-to-back
+    ///
+    /// Chunks are assumed to be at the synthesis rate
+    /// ([`DEFAULT_SAMPLE_RATE`](crate::DEFAULT_SAMPLE_RATE)); see
+    /// [`play_stream_at`](Self::play_stream_at).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any chunk is empty
+    pub async fn play_stream(&self, chunks: &[AudioData]) -> VocalizeResult<()> {
+        self.play_stream_at(chunks, crate::DEFAULT_SAMPLE_RATE).await
+    }
 
-        // Simulate playback time
-        let duration = Duration::from_millis((audio_data.len() as f64 / self.config.sample_rate as f64 * 1000.0) as u64);
-        tokio::time::sleep(duration.min(Duration::from_millis(100))).await; // Cap at 100ms for tests
+    /// Play a sequence of audio chunks recorded at `source_rate` Hz
+    ///
+    /// If `source_rate` differs from the device rate, chunks are converted
+    /// with a [`StreamingResampler`] as they are played, so there are no
+    /// discontinuities at chunk boundaries. Interruption is checked between
+    /// chunks as well as within them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any chunk is empty or `source_rate` is zero
+    pub async fn play_stream_at(&self, chunks: &[AudioData], source_rate: u32) -> VocalizeResult<()> {
+        self.ensure_open()?;
+        if chunks.iter().any(Vec::is_empty) {
+            return Err(VocalizeError::invalid_input("Audio chunks cannot be empty"));
+        }
+        if source_rate == 0 {
+            return Err(VocalizeError::invalid_input("Source sample rate must be non-zero"));
+        }
 
-        *state = PlaybackState::Stopped;
-        self.is_running.store(false, Ordering::Relaxed);
+        let mut resampler = StreamingResampler::new(source_rate, self.shared.sample_rate);
+        if !resampler.is_passthrough() {
+            debug!("Resampling stream from {} Hz to {} Hz", source_rate, self.shared.sample_rate);
+        }
+
+        self.shared.interrupted.store(false, Ordering::Relaxed);
+        for chunk in chunks {
+            if self.shared.interrupted.load(Ordering::Relaxed) {
+                debug!("Playback stream interrupted");
+                break;
+            }
+            let resampled = resampler.process(chunk);
+            if !resampled.is_empty() {
+                self.shared.play_chunk(&resampled).await;
+            }
+        }
+        let tail = resampler.flush();
+        if !tail.is_empty() && !self.shared.interrupted.load(Ordering::Relaxed) {
+            self.shared.play_chunk(&tail).await;
+        }
+        self.shared.finish_playback().await;
 
         Ok(())
     }
 
+    /// Add a clip to the playback queue
+    ///
+    /// Queued clips are played sequentially in FIFO order by a background
+    /// task, which is started on demand and exits once the queue is drained.
+    /// Must be called from within a tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the clip is empty or the queue is full
+    pub fn enqueue(&self, audio: AudioData) -> VocalizeResult<()> {
+        self.enqueue_with_gap(audio, Duration::ZERO)
+    }
+
+    /// Add a clip to the playback queue, preceded by `gap` of silence
+    ///
+    /// The gap is rounded to the nearest whole sample at the configured
+    /// sample rate, so consecutive clips are separated by exactly
+    /// `gap * sample_rate` silent samples.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the clip is empty or the queue is full
+    pub fn enqueue_with_gap(&self, audio: AudioData, gap: Duration) -> VocalizeResult<()> {
+        self.ensure_open()?;
+        if audio.is_empty() {
+            return Err(VocalizeError::invalid_input("Audio data cannot be empty"));
+        }
+
+        let mut clip = QueuedClip {
+            audio,
+            gap_samples: self.gap_samples(gap),
+            generation: 0,
+        };
+
+        {
+            let mut queue = self.shared.queue.lock();
+            clip.generation = self.shared.interrupts.load(Ordering::Acquire);
+            let queued: usize = queue.iter().map(QueuedClip::len).sum();
+            if queued + clip.len() > self.queue_capacity() {
+                return Err(VocalizeError::audio_device("Playback queue is full"));
+            }
+            queue.push_back(clip);
+
+            if self.shared.queue_active.swap(true, Ordering::AcqRel) {
+                return Ok(());
+            }
+        }
+
+        debug!("Starting playback queue worker");
+        tokio::spawn(Arc::clone(&self.shared).run_queue());
+
+        Ok(())
+    }
+
+    /// Get the number of clips waiting in the playback queue
+    ///
+    /// The clip currently being played is not counted.
+    #[must_use]
+    pub fn queue_len(&self) -> usize {
+        self.shared.queue.lock().len()
+    }
+
+    /// Drop all clips waiting in the playback queue
+    ///
+    /// The clip currently being played is left to finish; use
+    /// [`interrupt`](Self::interrupt) to stop it as well.
+    pub fn clear_queue(&self) {
+        let dropped = {
+            let mut queue = self.shared.queue.lock();
+            let dropped = queue.len();
+            queue.clear();
+            dropped
+        };
+        debug!("Cleared {} queued clips", dropped);
+    }
+
+    /// Interrupt the current playback immediately
+    ///
+    /// Stops the current stream, drops any samples that have not been
+    /// consumed yet, clears the playback queue and transitions to
+    /// [`PlaybackState::Stopped`].
+    pub async fn interrupt(&self) {
+        info!("Interrupting mock audio playback");
+
+        self.shared.interrupted.store(true, Ordering::Relaxed);
+        {
+            let mut queue = self.shared.queue.lock();
+            queue.clear();
+            self.shared.interrupts.fetch_add(1, Ordering::AcqRel);
+        }
+        self.shared.is_running.store(false, Ordering::Relaxed);
+        *self.shared.state.write().await = PlaybackState::Stopped;
+    }
+
+    /// Check whether the last playback was interrupted
+    #[must_use]
+    pub fn was_interrupted(&self) -> bool {
+        self.shared.interrupted.load(Ordering::Relaxed)
+    }
+
     /// Play audio data and wait for completion
     ///
     /// # Errors
@@ -265,29 +633,43 @@ impl AudioDevice {
         self.wait_for_completion().await
     }
 
-    /// Wait for current audio to finish playing
+    /// Wait for current audio and any queued clips to finish playing
+    ///
+    /// Gives up once playback has made no progress for
+    /// [`AudioConfig::completion_timeout`]; time spent actually playing
+    /// does not count, however long the audio is.
     ///
     /// # Errors
     ///
-    /// Returns an error if waiting times out
+    /// Returns an error if playback stalls for longer than the timeout
     pub async fn wait_for_completion(&self) -> VocalizeResult<()> {
         debug!("Waiting for mock audio completion");
 
-        let timeout = Duration::from_secs(30);
-        let start_time = std::time::Instant::now();
+        let timeout = self.config.completion_timeout;
+        let mut last_progress = std::time::Instant::now();
+        let mut last_played = self.shared.samples_played.load(Ordering::Relaxed);
 
         loop {
-            if start_time.elapsed() > timeout {
-                return Err(VocalizeError::timeout("Audio playback timeout"));
+            let played = self.shared.samples_played.load(Ordering::Relaxed);
+            if played != last_played {
+                last_played = played;
+                last_progress = std::time::Instant::now();
+            } else if last_progress.elapsed() > timeout {
+                return Err(VocalizeError::timeout(format!(
+                    "Audio playback made no progress for {:.1}s",
+                    timeout.as_secs_f64()
+                )));
             }
 
-            let state = *self.state.read().await;
-            if state == PlaybackState::Stopped || state == PlaybackState::Error {
-                break;
-            }
+            if !self.shared.queue_active.load(Ordering::Acquire) {
+                let state = *self.shared.state.read().await;
+                if state == PlaybackState::Stopped || state == PlaybackState::Error {
+                    break;
+                }
 
-            if !self.is_running.load(Ordering::Relaxed) {
-                break;
+                if !self.shared.is_running.load(Ordering::Relaxed) {
+                    break;
+                }
             }
 
             tokio::time::sleep(Duration::from_millis(10)).await;
@@ -297,28 +679,51 @@ impl AudioDevice {
         Ok(())
     }
 
+    /// Shut the device down gracefully
+    ///
+    /// Stops accepting new audio, lets the current clip and anything already
+    /// queued finish playing, then stops the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if draining the queue times out
+    pub async fn shutdown(self) -> VocalizeResult<()> {
+        info!("Shutting down mock audio device");
+        self.shared.closed.store(true, Ordering::SeqCst);
+        self.wait_for_completion().await?;
+        self.stop().await
+    }
+
+    /// Reject new audio once the device has been shut down
+    fn ensure_open(&self) -> VocalizeResult<()> {
+        if self.shared.closed.load(Ordering::SeqCst) {
+            return Err(VocalizeError::audio_device("Audio device is shut down"));
+        }
+        Ok(())
+    }
+
     /// Get current playback state
     #[must_use]
     pub async fn get_state(&self) -> PlaybackState {
-        *self.state.read().await
+        *self.shared.state.read().await
     }
 
     /// Check if audio is currently playing
     #[must_use]
     pub async fn is_playing(&self) -> bool {
-        *self.state.read().await == PlaybackState::Playing
+        *self.shared.state.read().await == PlaybackState::Playing
     }
 
     /// Check if audio is paused
     #[must_use]
     pub async fn is_paused(&self) -> bool {
-        *self.state.read().await == PlaybackState::Paused
+        *self.shared.state.read().await == PlaybackState::Paused
     }
 
     /// Check if audio is stopped
     #[must_use]
     pub async fn is_stopped(&self) -> bool {
-        matches!(*self.state.read().await, PlaybackState::Stopped | PlaybackState::Error)
+        matches!(*self.shared.state.read().await, PlaybackState::Stopped | PlaybackState::Error)
     }
 
     /// Get current audio configuration
@@ -333,17 +738,40 @@ impl AudioDevice {
         Some("Mock Audio Device".to_string())
     }
 
-    /// Get audio queue status (mock implementation)
+    /// Get total number of samples handed to the device since creation
+    #[must_use]
+    pub fn samples_played(&self) -> usize {
+        self.shared.samples_played.load(Ordering::Relaxed)
+    }
+
+    /// Get audio queue status
+    ///
+    /// Returns the number of samples waiting in the queue and the number of
+    /// samples that can still be enqueued.
     #[must_use]
     pub async fn get_queue_status(&self) -> (usize, usize) {
-        (0, 1024) // Mock values: no data in queue, 1024 space available
+        let queued: usize = self.shared.queue.lock().iter().map(QueuedClip::len).sum();
+        (queued, self.queue_capacity().saturating_sub(queued))
+    }
+
+    /// Convert a gap duration to a whole number of samples
+    fn gap_samples(&self, gap: Duration) -> usize {
+        let nanos = gap.as_nanos() * u128::from(self.config.sample_rate);
+        usize::try_from((nanos + 500_000_000) / 1_000_000_000).unwrap_or(usize::MAX)
+    }
+
+    /// Maximum number of samples the playback queue can hold
+    fn queue_capacity(&self) -> usize {
+        self.config.sample_rate as usize * MAX_QUEUED_SECONDS
     }
 }
 
 impl Drop for AudioDevice {
     fn drop(&mut self) {
-        // Stop the audio stream when dropping
-        self.is_running.store(false, Ordering::Relaxed);
+        // Stop the audio stream and any queue worker when dropping
+        self.shared.queue.lock().clear();
+        self.shared.interrupted.store(true, Ordering::Relaxed);
+        self.shared.is_running.store(false, Ordering::Relaxed);
     }
 }
 
@@ -401,6 +829,7 @@ mod tests {
             channels: 2,
             buffer_size: 2048,
             latency: Duration::from_millis(100),
+            sample_format: SampleFormat::F32,
         };
 
         let device = AudioDevice::with_config(config).await.expect("Should create device");
@@ -408,6 +837,71 @@ mod tests {
         assert_eq!(device.get_config().channels, 2);
     }
 
+    #[test]
+    fn test_sample_format_convert() {
+        let audio = [0.0, 0.5, -1.0, 1.0, 2.0];
+        assert_eq!(SampleFormat::F32.convert(&audio), DeviceSamples::F32(audio.to_vec()));
+        assert_eq!(
+            SampleFormat::I16.convert(&audio),
+            DeviceSamples::I16(vec![0, 16383, -32767, 32767, 32767])
+        );
+        assert_eq!(
+            SampleFormat::U16.convert(&audio),
+            DeviceSamples::U16(vec![32768, 49151, 1, 65535, 65535])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_audio_device_converts_to_device_format() {
+        let config = AudioConfig {
+            buffer_size: 2,
+            sample_format: SampleFormat::I16,
+            ..AudioConfig::default()
+        };
+        let device = AudioDevice::with_config(config).await.unwrap();
+
+        device.play(&vec![0.5, -0.5, 1.0]).await.unwrap();
+
+        let buffers = device.shared.device_buffers.lock().clone();
+        assert_eq!(
+            buffers,
+            vec![DeviceSamples::I16(vec![16383, -16383]), DeviceSamples::I16(vec![32767])]
+        );
+        assert_eq!(device.samples_played(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_completion_outlasts_timeout_while_progressing() {
+        // Shorter than the clip takes to play, so a fixed deadline would expire
+        let config = AudioConfig {
+            buffer_size: 24_000,
+            completion_timeout: Duration::from_millis(50),
+            ..AudioConfig::default()
+        };
+        let device = AudioDevice::with_config(config).await.unwrap();
+        let five_minutes = vec![0.1; 5 * 60 * crate::DEFAULT_SAMPLE_RATE as usize];
+        device.enqueue(five_minutes.clone()).unwrap();
+
+        let start = std::time::Instant::now();
+        device.wait_for_completion().await.expect("A long clip should not time out");
+        assert!(start.elapsed() > Duration::from_millis(50));
+        assert_eq!(device.samples_played(), five_minutes.len());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_completion_times_out_when_stalled() {
+        let config = AudioConfig {
+            completion_timeout: Duration::from_millis(50),
+            ..AudioConfig::default()
+        };
+        let device = AudioDevice::with_config(config).await.unwrap();
+        // Started but never fed, so playback makes no progress
+        device.start().await.unwrap();
+
+        let error = device.wait_for_completion().await.unwrap_err();
+        assert!(matches!(error, VocalizeError::TimeoutError { .. }), "{error:?}");
+    }
+
     #[test]
     fn test_get_available_devices() {
         let devices = AudioDevice::get_available_devices().expect("Should get devices");
@@ -450,6 +944,71 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_audio_device_samples_played() {
+        let device = AudioDevice::new().await.expect("Should create device");
+        assert_eq!(device.samples_played(), 0);
+
+        device.play(&vec![0.1; 100]).await.expect("Should play");
+        device.play(&vec![0.2; 50]).await.expect("Should play");
+        assert_eq!(device.samples_played(), 150);
+    }
+
+    #[tokio::test]
+    async fn test_audio_device_interrupt_mid_playback() {
+        let device = Arc::new(AudioDevice::new().await.expect("Should create device"));
+        // 10 seconds of audio, consumed in ~235 buffers over the capped 100ms
+        let audio_data = vec![0.1; 240_000];
+
+        let player = {
+            let device = Arc::clone(&device);
+            tokio::spawn(async move { device.play(&audio_data).await })
+        };
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        device.interrupt().await;
+        let played_at_interrupt = device.samples_played();
+
+        player.await.unwrap().expect("Interrupted playback should not error");
+
+        assert!(device.was_interrupted());
+        assert!(device.is_stopped().await);
+        assert!(device.samples_played() < 240_000);
+        // At most one more buffer is consumed after the interrupt
+        assert!(device.samples_played() - played_at_interrupt <= 1024);
+    }
+
+    #[tokio::test]
+    async fn test_audio_device_play_stream() {
+        let device = AudioDevice::new().await.expect("Should create device");
+        let chunks = vec![vec![0.1; 100], vec![0.2; 200]];
+
+        device.play_stream(&chunks).await.expect("Should play stream");
+        assert_eq!(device.samples_played(), 300);
+        assert!(device.is_stopped().await);
+    }
+
+    #[tokio::test]
+    async fn test_audio_device_play_stream_resamples() {
+        let config = AudioConfig {
+            sample_rate: 48000,
+            ..AudioConfig::default()
+        };
+        let device = AudioDevice::with_config(config).await.expect("Should create device");
+        let source: AudioData = (0..2400)
+            .map(|i| (std::f32::consts::TAU * 440.0 * i as f32 / 24_000.0).sin() * 0.5)
+            .collect();
+        let chunks: Vec<AudioData> = source.chunks(500).map(<[f32]>::to_vec).collect();
+
+        device.play_stream_at(&chunks, 24_000).await.expect("Should play stream");
+
+        let expected = crate::audio_effects::resample(&source, 24_000, 48_000);
+        let played = device.shared.played.lock().clone();
+        assert_eq!(played.len(), 4800);
+        assert!(played.iter().zip(&expected).all(|(a, b)| (a - b).abs() < 1e-6));
+        assert!(device.play_stream_at(&chunks, 0).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_audio_device_play_empty() {
         let device = AudioDevice::new().await.expect("Should create device");
@@ -492,12 +1051,142 @@ mod tests {
         assert_eq!(info.unwrap(), "Mock Audio Device");
     }
 
+    /// Wait until the queue worker has started playing its first clip
+    async fn wait_until_playing(device: &AudioDevice) {
+        while device.samples_played() == 0 {
+            tokio::task::yield_now().await;
+        }
+    }
+
     #[tokio::test]
     async fn test_audio_device_get_queue_status() {
         let device = AudioDevice::new().await.expect("Should create device");
         let (data, space) = device.get_queue_status().await;
         assert_eq!(data, 0);
-        assert_eq!(space, 1024);
+        assert_eq!(space, crate::DEFAULT_SAMPLE_RATE as usize * MAX_QUEUED_SECONDS);
+    }
+
+    #[tokio::test]
+    async fn test_audio_device_enqueue_plays_in_fifo_order() {
+        let device = AudioDevice::new().await.expect("Should create device");
+        let clips = vec![vec![0.1; 3000], vec![0.2; 2000], vec![0.3; 1000]];
+
+        for clip in &clips {
+            device.enqueue(clip.clone()).expect("Should enqueue");
+        }
+        device.wait_for_completion().await.expect("Should drain queue");
+
+        assert_eq!(device.queue_len(), 0);
+        assert_eq!(device.samples_played(), 6000);
+        assert!(device.is_stopped().await);
+        assert_eq!(*device.shared.played.lock(), clips.concat());
+    }
+
+    #[tokio::test]
+    async fn test_audio_device_queue_status_reports_pending_samples() {
+        let device = AudioDevice::new().await.expect("Should create device");
+        let capacity = crate::DEFAULT_SAMPLE_RATE as usize * MAX_QUEUED_SECONDS;
+
+        // The first clip is taken by the worker; the rest wait in the queue
+        device.enqueue(vec![0.1; 240_000]).expect("Should enqueue");
+        device.enqueue(vec![0.2; 500]).expect("Should enqueue");
+        device.enqueue(vec![0.3; 700]).expect("Should enqueue");
+        wait_until_playing(&device).await;
+
+        assert_eq!(device.queue_len(), 2);
+        assert_eq!(device.get_queue_status().await, (1200, capacity - 1200));
+
+        device.clear_queue();
+        assert_eq!(device.queue_len(), 0);
+        assert_eq!(device.get_queue_status().await, (0, capacity));
+
+        device.wait_for_completion().await.expect("Should finish current clip");
+        assert_eq!(device.samples_played(), 240_000);
+    }
+
+    #[tokio::test]
+    async fn test_audio_device_enqueue_rejects_invalid_clips() {
+        let device = AudioDevice::new().await.expect("Should create device");
+        let capacity = crate::DEFAULT_SAMPLE_RATE as usize * MAX_QUEUED_SECONDS;
+
+        assert!(device.enqueue(vec![]).is_err());
+        assert!(device.enqueue(vec![0.0; capacity + 1]).is_err());
+        assert_eq!(device.queue_len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_audio_device_enqueue_with_gap_inserts_exact_silence() {
+        let device = AudioDevice::new().await.expect("Should create device");
+        let gap = Duration::from_millis(125);
+        let expected_gap = (gap.as_secs_f64() * f64::from(crate::DEFAULT_SAMPLE_RATE)) as usize;
+
+        device.enqueue(vec![0.5; 1000]).expect("Should enqueue");
+        device.enqueue_with_gap(vec![-0.5; 800], gap).expect("Should enqueue");
+        device.wait_for_completion().await.expect("Should drain queue");
+
+        let played = device.shared.played.lock().clone();
+        assert_eq!(played.len(), 1000 + expected_gap + 800);
+        assert!(played[..1000].iter().all(|&s| s == 0.5));
+        assert!(played[1000..1000 + expected_gap].iter().all(|&s| s == 0.0));
+        assert!(played[1000 + expected_gap..].iter().all(|&s| s == -0.5));
+    }
+
+    #[tokio::test]
+    async fn test_audio_device_gap_rounds_to_nearest_sample() {
+        let device = AudioDevice::new().await.expect("Should create device");
+        assert_eq!(device.gap_samples(Duration::ZERO), 0);
+        assert_eq!(device.gap_samples(Duration::from_secs(1)), 24_000);
+        // 24000 Hz * 20.8us = 0.4992 samples
+        assert_eq!(device.gap_samples(Duration::from_nanos(20_800)), 0);
+        // 24000 Hz * 62.5us = 1.5 samples
+        assert_eq!(device.gap_samples(Duration::from_nanos(62_500)), 2);
+    }
+
+    #[tokio::test]
+    async fn test_audio_device_interrupt_clears_queue() {
+        let device = AudioDevice::new().await.expect("Should create device");
+
+        device.enqueue(vec![0.1; 240_000]).expect("Should enqueue");
+        device.enqueue(vec![0.2; 240_000]).expect("Should enqueue");
+        wait_until_playing(&device).await;
+
+        device.interrupt().await;
+        device.wait_for_completion().await.expect("Should stop");
+
+        assert_eq!(device.queue_len(), 0);
+        assert!(device.samples_played() < 240_000);
+    }
+
+    #[tokio::test]
+    async fn test_audio_device_enqueue_after_interrupt_plays() {
+        let device = AudioDevice::new().await.expect("Should create device");
+
+        device.enqueue(vec![0.1; 240_000]).expect("Should enqueue");
+        wait_until_playing(&device).await;
+
+        // The worker is still winding down from the interrupt
+        device.interrupt().await;
+        device.enqueue(vec![0.7; 500]).expect("Should enqueue");
+        device.wait_for_completion().await.expect("Should drain queue");
+
+        let played = device.shared.played.lock().clone();
+        assert!(played.len() < 240_000 + 500);
+        assert_eq!(played[played.len() - 500..], [0.7; 500]);
+        assert!(!device.was_interrupted());
+    }
+
+    #[tokio::test]
+    async fn test_audio_device_shutdown_drains_queue() {
+        let device = AudioDevice::new().await.expect("Should create device");
+        let shared = Arc::clone(&device.shared);
+
+        device.enqueue(vec![0.1; 3000]).expect("Should enqueue");
+        device.enqueue(vec![0.2; 2000]).expect("Should enqueue");
+        device.shutdown().await.expect("Should shut down");
+
+        assert_eq!(shared.samples_played.load(Ordering::Relaxed), 5000);
+        assert!(shared.queue.lock().is_empty());
+        assert_eq!(*shared.state.read().await, PlaybackState::Stopped);
     }
 
     #[test]
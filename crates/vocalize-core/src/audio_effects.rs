@@ -0,0 +1,1169 @@
+//! Sample-level conversions and processing for synthesized audio.
+
+use crate::error::{VocalizeError, VocalizeResult};
+use crate::tts_engine::AudioData;
+
+/// Full-scale value used when converting between f32 and i16 PCM
+const I16_SCALE: f32 = 32767.0;
+
+/// Convert f32 samples in `[-1.0, 1.0]` to signed 16-bit PCM
+///
+/// Out-of-range samples are clamped; NaN becomes silence.
+#[must_use]
+pub fn to_i16_pcm(audio: &[f32]) -> Vec<i16> {
+    audio
+        .iter()
+        .map(|&sample| (sample.clamp(-1.0, 1.0) * I16_SCALE) as i16)
+        .collect()
+}
+
+/// Convert signed 16-bit PCM to f32 samples in `[-1.0, 1.0]`
+#[must_use]
+pub fn from_i16_pcm(pcm: &[i16]) -> AudioData {
+    pcm.iter()
+        .map(|&sample| (f32::from(sample) / I16_SCALE).max(-1.0))
+        .collect()
+}
+
+/// Default peak level for normalized output, leaving headroom for lossy encoders
+pub const DEFAULT_TARGET_PEAK_DBFS: f32 = -1.0;
+
+/// Convert a level in decibels to a linear gain factor
+#[must_use]
+pub fn db_to_gain(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
+}
+
+/// Convert a linear gain factor to decibels
+///
+/// Zero maps to negative infinity.
+#[must_use]
+pub fn gain_to_db(gain: f32) -> f32 {
+    20.0 * gain.log10()
+}
+
+/// Root-mean-square level of `audio`, or `0.0` for an empty buffer
+#[must_use]
+pub fn rms(audio: &[f32]) -> f32 {
+    if audio.is_empty() {
+        return 0.0;
+    }
+    let sum_of_squares: f64 = audio.iter().map(|&sample| f64::from(sample) * f64::from(sample)).sum();
+    (sum_of_squares / audio.len() as f64).sqrt() as f32
+}
+
+/// Largest absolute sample in `audio`, or `0.0` for an empty buffer
+#[must_use]
+pub fn peak(audio: &[f32]) -> f32 {
+    audio.iter().map(|sample| sample.abs()).fold(0.0_f32, f32::max)
+}
+
+/// Level statistics for a block of audio, as shown by a level meter
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AudioStats {
+    /// Root-mean-square level
+    pub rms: f32,
+    /// Largest absolute sample
+    pub peak: f32,
+    /// RMS level in dBFS; negative infinity for silence
+    pub dbfs: f32,
+    /// Number of samples at or beyond full scale
+    pub clip_count: usize,
+}
+
+/// Compute [`AudioStats`] for `audio`
+#[must_use]
+pub fn analyze(audio: &[f32]) -> AudioStats {
+    let rms = rms(audio);
+    AudioStats {
+        rms,
+        peak: peak(audio),
+        dbfs: gain_to_db(rms),
+        clip_count: audio.iter().filter(|sample| sample.abs() >= 1.0).count(),
+    }
+}
+
+/// Analysis frame length for voice-activity detection
+const VAD_FRAME_MS: u32 = 20;
+
+/// RMS level above which a frame counts as speech
+const VAD_ENERGY_THRESHOLD_DBFS: f32 = -40.0;
+
+/// Lower RMS level accepted for noisy, high zero-crossing frames
+///
+/// Unvoiced consonants such as "s" and "f" are quiet but cross zero far
+/// more often than voiced speech or low-frequency hum.
+const VAD_UNVOICED_THRESHOLD_DBFS: f32 = -50.0;
+
+/// Zero crossings per sample above which a quiet frame is treated as unvoiced speech
+const VAD_UNVOICED_ZCR: f32 = 0.25;
+
+/// Pauses shorter than this are kept inside a single speech region
+const VAD_MIN_GAP_MS: u32 = 200;
+
+/// Regions shorter than this are discarded as clicks
+const VAD_MIN_REGION_MS: u32 = 60;
+
+/// Find the sample ranges of `audio` that contain speech
+///
+/// Each frame is classified from its RMS energy and zero-crossing rate;
+/// neighbouring speech frames separated by short pauses are merged, and
+/// very short bursts are dropped. Returns half-open `(start, end)` sample
+/// ranges in order.
+#[must_use]
+pub fn detect_speech_regions(audio: &[f32], sample_rate: u32) -> Vec<(usize, usize)> {
+    let ms_to_samples = |ms: u32| (u64::from(sample_rate) * u64::from(ms) / 1000).max(1) as usize;
+    let frame_len = ms_to_samples(VAD_FRAME_MS);
+    let min_gap = ms_to_samples(VAD_MIN_GAP_MS);
+    let min_region = ms_to_samples(VAD_MIN_REGION_MS);
+    let energy_threshold = db_to_gain(VAD_ENERGY_THRESHOLD_DBFS);
+    let unvoiced_threshold = db_to_gain(VAD_UNVOICED_THRESHOLD_DBFS);
+
+    let mut regions: Vec<(usize, usize)> = Vec::new();
+    for (index, frame) in audio.chunks(frame_len).enumerate() {
+        let level = rms(frame);
+        let crossings = frame
+            .windows(2)
+            .filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0))
+            .count();
+        let zcr = crossings as f32 / frame.len() as f32;
+
+        let is_speech =
+            level >= energy_threshold || (level >= unvoiced_threshold && zcr >= VAD_UNVOICED_ZCR);
+        if !is_speech {
+            continue;
+        }
+
+        let start = index * frame_len;
+        let end = start + frame.len();
+        match regions.last_mut() {
+            Some(last) if start - last.1 < min_gap => last.1 = end,
+            _ => regions.push((start, end)),
+        }
+    }
+
+    regions.retain(|&(start, end)| end - start >= min_region);
+    regions
+}
+
+/// Magnitude spectrogram of `audio` for inspecting model output
+///
+/// Each frame is `fft_size` samples under a Hann window, advanced by `hop`
+/// samples, and holds `fft_size / 2 + 1` magnitudes from DC up to the
+/// Nyquist frequency; use [`spectrogram_bin_frequency`] to map a bin index
+/// to Hz. Audio shorter than one frame is zero-padded. Returns no frames
+/// for empty audio or if any of the parameters is zero.
+#[must_use]
+pub fn spectrogram(audio: &[f32], sample_rate: u32, fft_size: usize, hop: usize) -> Vec<Vec<f32>> {
+    if audio.is_empty() || sample_rate == 0 || fft_size == 0 || hop == 0 {
+        return Vec::new();
+    }
+
+    let fft = rustfft::FftPlanner::<f32>::new().plan_fft_forward(fft_size);
+    let window: Vec<f32> = (0..fft_size)
+        .map(|i| 0.5 - 0.5 * (std::f32::consts::TAU * i as f32 / fft_size as f32).cos())
+        .collect();
+
+    let frame_count = if audio.len() < fft_size { 1 } else { (audio.len() - fft_size) / hop + 1 };
+    let mut buffer = vec![rustfft::num_complex::Complex32::default(); fft_size];
+    (0..frame_count)
+        .map(|frame| {
+            let start = frame * hop;
+            let samples = &audio[start..audio.len().min(start + fft_size)];
+            for (i, bin) in buffer.iter_mut().enumerate() {
+                let sample = samples.get(i).copied().unwrap_or(0.0);
+                *bin = rustfft::num_complex::Complex32::new(sample * window[i], 0.0);
+            }
+            fft.process(&mut buffer);
+            buffer[..=fft_size / 2].iter().map(|bin| bin.norm()).collect()
+        })
+        .collect()
+}
+
+/// Centre frequency in Hz of spectrogram bin `bin`
+#[must_use]
+pub fn spectrogram_bin_frequency(bin: usize, sample_rate: u32, fft_size: usize) -> f32 {
+    bin as f32 * sample_rate as f32 / fft_size as f32
+}
+
+/// Silence low-level noise in `audio` while leaving louder passages intact
+///
+/// The gate opens when the signal envelope reaches `threshold` (a linear
+/// amplitude) and closes when it falls below. Gain changes are smoothed
+/// over `attack_ms` when opening and `release_ms` when closing, so
+/// signals hovering around the threshold fade rather than chatter. The
+/// gate starts open.
+pub fn noise_gate(audio: &mut [f32], threshold: f32, attack_ms: f32, release_ms: f32, sample_rate: u32) {
+    // One-pole smoothing coefficient reaching ~63% of a step after `ms`
+    let coefficient = |ms: f32| {
+        let samples = ms.max(0.0) * sample_rate as f32 / 1000.0;
+        if samples < 1.0 { 0.0 } else { (-1.0 / samples).exp() }
+    };
+    let attack = coefficient(attack_ms);
+    let release = coefficient(release_ms);
+
+    let mut envelope = 0.0_f32;
+    let mut gain = 1.0_f32;
+    for sample in audio.iter_mut() {
+        // Peak follower that decays over the release time, bridging the
+        // zero crossings within each waveform cycle
+        envelope = sample.abs().max(envelope * release);
+
+        let (target, smoothing) = if envelope >= threshold { (1.0, attack) } else { (0.0, release) };
+        gain = target + (gain - target) * smoothing;
+        *sample *= gain;
+    }
+}
+
+/// Samples over which [`duck`] lowers the background once speech starts
+/// (10ms at 24 kHz)
+const DUCK_ATTACK_SAMPLES: f32 = 240.0;
+
+/// Samples over which [`duck`] restores the background after speech stops
+/// (200ms at 24 kHz)
+const DUCK_RELEASE_SAMPLES: f32 = 4_800.0;
+
+/// Lower `background` by `reduction_db` wherever `speech` is active
+///
+/// Speech counts as active while its envelope reaches `threshold` (a linear
+/// amplitude). The background gain falls quickly when speech starts and
+/// recovers slowly after it stops, so short pauses between words do not
+/// pump the background up and down. Both buffers must share a sample rate;
+/// background past the end of `speech` is treated as a gap.
+pub fn duck(background: &mut [f32], speech: &[f32], threshold: f32, reduction_db: f32) {
+    let ducked_gain = db_to_gain(-reduction_db.max(0.0));
+    let attack = (-1.0 / DUCK_ATTACK_SAMPLES).exp();
+    let release = (-1.0 / DUCK_RELEASE_SAMPLES).exp();
+
+    let mut envelope = 0.0_f32;
+    let mut gain = 1.0_f32;
+    for (i, sample) in background.iter_mut().enumerate() {
+        let level = speech.get(i).map_or(0.0, |s| s.abs());
+        envelope = level.max(envelope * release);
+
+        let target = if envelope >= threshold { ducked_gain } else { 1.0 };
+        let smoothing = if target < gain { attack } else { release };
+        gain = target + (gain - target) * smoothing;
+        *sample *= gain;
+    }
+}
+
+/// Widest span, in samples either side of a click, that [`declick`] will rewrite
+const DECLICK_MAX_HALF_WIDTH: usize = 64;
+
+/// Smooth sample-to-sample jumps larger than `max_jump` in `audio`
+///
+/// Each discontinuity is replaced by a straight line between the samples
+/// either side of it, widening the span until the line's slope stays
+/// within `max_jump` per sample (up to [`DECLICK_MAX_HALF_WIDTH`] samples
+/// each way). Returns the number of discontinuities smoothed.
+pub fn declick(audio: &mut [f32], max_jump: f32) -> usize {
+    if max_jump.is_nan() || max_jump <= 0.0 || audio.len() < 2 {
+        return 0;
+    }
+
+    let mut smoothed = 0;
+    let mut i = 1;
+    while i < audio.len() {
+        if (audio[i] - audio[i - 1]).abs() <= max_jump {
+            i += 1;
+            continue;
+        }
+
+        let mut half_width = 1;
+        let (start, end) = loop {
+            let start = (i - 1).saturating_sub(half_width);
+            let end = (i + half_width).min(audio.len() - 1);
+            let slope = (audio[end] - audio[start]).abs() / (end - start) as f32;
+            let at_limit = half_width >= DECLICK_MAX_HALF_WIDTH || (start == 0 && end == audio.len() - 1);
+            if slope <= max_jump || at_limit {
+                break (start, end);
+            }
+            half_width *= 2;
+        };
+
+        let (from, to) = (audio[start], audio[end]);
+        let span = (end - start) as f32;
+        for (offset, sample) in audio[start + 1..end].iter_mut().enumerate() {
+            *sample = from + (to - from) * (offset + 1) as f32 / span;
+        }
+        smoothed += 1;
+        i = end + 1;
+    }
+    smoothed
+}
+
+/// Append `next` to `audio`, blending the last `fade_len` samples of
+/// `audio` with the first `fade_len` samples of `next`
+///
+/// The blend is a linear crossfade; `fade_len` is capped at the length of
+/// either clip, and a zero length simply concatenates.
+pub fn crossfade_concat(audio: &mut AudioData, next: &[f32], fade_len: usize) {
+    let fade_len = fade_len.min(audio.len()).min(next.len());
+    let start = audio.len() - fade_len;
+    for (i, (sample, &incoming)) in audio[start..].iter_mut().zip(next).enumerate() {
+        let weight = (i + 1) as f32 / (fade_len + 1) as f32;
+        *sample = *sample * (1.0 - weight) + incoming * weight;
+    }
+    audio.extend_from_slice(&next[fade_len..]);
+}
+
+/// Split `audio` into segments of `segment_samples`, each starting
+/// `overlap` samples before the previous one ended
+///
+/// Every segment but the last is exactly `segment_samples` long; the last
+/// holds whatever remains. `overlap` is capped at `segment_samples - 1` so
+/// that each segment advances. Empty audio or a zero segment length yields
+/// no segments.
+#[must_use]
+pub fn split(audio: &[f32], segment_samples: usize, overlap: usize) -> Vec<AudioData> {
+    if segment_samples == 0 {
+        return Vec::new();
+    }
+    let step = segment_samples - overlap.min(segment_samples - 1);
+
+    let mut segments = Vec::new();
+    let mut start = 0;
+    while start < audio.len() {
+        let end = (start + segment_samples).min(audio.len());
+        segments.push(audio[start..end].to_vec());
+        if end == audio.len() {
+            break;
+        }
+        start += step;
+    }
+    segments
+}
+
+/// WSOLA frame length for [`time_stretch`]: 40ms at 24 kHz
+const STRETCH_FRAME: usize = 960;
+
+/// How far [`time_stretch`] may shift a frame to line it up with the last
+const STRETCH_TOLERANCE: usize = 240;
+
+/// Change the tempo of `audio` by `factor` without changing its pitch
+///
+/// Uses waveform-similarity overlap-add (WSOLA): Hann-windowed frames are
+/// read `factor` times further apart than they are written, each shifted by
+/// up to [`STRETCH_TOLERANCE`] samples to best continue the previous frame.
+/// A factor above 1 speeds audio up; the result is `audio.len() / factor`
+/// samples long. A factor of 1, or one that is not finite and positive,
+/// returns the audio unchanged.
+#[must_use]
+pub fn time_stretch(audio: &[f32], factor: f32) -> AudioData {
+    if !(factor.is_finite() && factor > 0.0) || (factor - 1.0).abs() < f32::EPSILON || audio.is_empty() {
+        return audio.to_vec();
+    }
+    let hop = STRETCH_FRAME / 2;
+    let out_len = (audio.len() as f64 / f64::from(factor)).round() as usize;
+    let sample = |i: usize| audio.get(i).copied().unwrap_or(0.0);
+    let window: Vec<f32> = (0..STRETCH_FRAME)
+        .map(|i| 0.5 - 0.5 * (std::f32::consts::TAU * i as f32 / STRETCH_FRAME as f32).cos())
+        .collect();
+
+    let mut output = vec![0.0; out_len + STRETCH_FRAME];
+    let mut weights = vec![0.0_f32; out_len + STRETCH_FRAME];
+    let mut previous: Option<usize> = None;
+    for out_pos in (0..out_len).step_by(hop) {
+        let nominal = (out_pos as f64 * f64::from(factor)).round() as usize;
+        let start = match previous {
+            None => nominal,
+            Some(previous) => {
+                // Pick the frame whose start best matches the audio that
+                // naturally follows the previous frame; ties keep the nominal
+                let natural = previous + hop;
+                let similarity = |candidate: usize| -> f32 {
+                    (0..hop).map(|i| sample(candidate + i) * sample(natural + i)).sum()
+                };
+                let mut best = (nominal, similarity(nominal));
+                for candidate in nominal.saturating_sub(STRETCH_TOLERANCE)..=nominal + STRETCH_TOLERANCE {
+                    let score = similarity(candidate);
+                    if score > best.1 {
+                        best = (candidate, score);
+                    }
+                }
+                best.0
+            }
+        };
+
+        for (i, &weight) in window.iter().enumerate() {
+            output[out_pos + i] += sample(start + i) * weight;
+            weights[out_pos + i] += weight;
+        }
+        previous = Some(start);
+    }
+
+    output.truncate(out_len);
+    for (sample, &weight) in output.iter_mut().zip(&weights) {
+        if weight > 1e-3 {
+            *sample /= weight;
+        }
+    }
+    output
+}
+
+/// Blocks quieter than this are ignored when measuring loudness
+const LOUDNESS_ABSOLUTE_GATE_LUFS: f64 = -70.0;
+
+/// Blocks this far below the ungated loudness are ignored
+const LOUDNESS_RELATIVE_GATE_LU: f64 = 10.0;
+
+/// Loudness of a block whose K-weighted mean square is `mean_square`
+fn block_loudness(mean_square: f64) -> f64 {
+    -0.691 + 10.0 * mean_square.log10()
+}
+
+/// Apply the BS.1770 K-weighting pre-filter (high shelf followed by high pass)
+fn k_weight(audio: &[f32], sample_rate: u32) -> Vec<f64> {
+    let rate = f64::from(sample_rate);
+
+    // Stage 1: high shelf modelling the acoustic effect of the head
+    let k = (std::f64::consts::PI * 1_681.974_450_955_533 / rate).tan();
+    let q = 0.707_175_236_955_419_6;
+    let vh = 10.0_f64.powf(3.999_843_853_973_347 / 20.0);
+    let vb = vh.powf(0.499_666_774_154_541_6);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = [
+        (vh + vb * k / q + k * k) / a0,
+        2.0 * (k * k - vh) / a0,
+        (vh - vb * k / q + k * k) / a0,
+        2.0 * (k * k - 1.0) / a0,
+        (1.0 - k / q + k * k) / a0,
+    ];
+
+    // Stage 2: RLB high pass
+    let k = (std::f64::consts::PI * 38.135_470_876_024_44 / rate).tan();
+    let q = 0.500_327_037_323_877_3;
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = [1.0, -2.0, 1.0, 2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0];
+
+    let biquad = |input: &[f64], [b0, b1, b2, a1, a2]: [f64; 5]| {
+        let (mut x1, mut x2, mut y1, mut y2) = (0.0, 0.0, 0.0, 0.0);
+        input
+            .iter()
+            .map(|&x| {
+                let y = b0 * x + b1 * x1 + b2 * x2 - a1 * y1 - a2 * y2;
+                (x2, x1, y2, y1) = (x1, x, y1, y);
+                y
+            })
+            .collect::<Vec<f64>>()
+    };
+
+    let input: Vec<f64> = audio.iter().map(|&sample| f64::from(sample)).collect();
+    biquad(&biquad(&input, shelf), high_pass)
+}
+
+/// Integrated loudness of mono `audio` in LUFS, following ITU-R BS.1770
+///
+/// Uses 400 ms blocks with 75% overlap, an absolute gate at -70 LUFS and a
+/// relative gate 10 LU below the ungated level. Clips shorter than one
+/// block are measured as a single block. Silence returns negative infinity.
+#[must_use]
+pub fn loudness_lufs(audio: &[f32], sample_rate: u32) -> f32 {
+    if audio.is_empty() || sample_rate == 0 {
+        return f32::NEG_INFINITY;
+    }
+
+    let weighted = k_weight(audio, sample_rate);
+    let block_len = (sample_rate as usize * 4 / 10).clamp(1, weighted.len());
+    let step = (block_len / 4).max(1);
+    let mean_square = |block: &[f64]| block.iter().map(|x| x * x).sum::<f64>() / block.len() as f64;
+
+    let blocks: Vec<f64> = (0..=weighted.len() - block_len)
+        .step_by(step)
+        .map(|start| mean_square(&weighted[start..start + block_len]))
+        .filter(|&z| block_loudness(z) > LOUDNESS_ABSOLUTE_GATE_LUFS)
+        .collect();
+    if blocks.is_empty() {
+        return f32::NEG_INFINITY;
+    }
+
+    let ungated = block_loudness(blocks.iter().sum::<f64>() / blocks.len() as f64);
+    let gated: Vec<f64> = blocks
+        .into_iter()
+        .filter(|&z| block_loudness(z) > ungated - LOUDNESS_RELATIVE_GATE_LU)
+        .collect();
+    block_loudness(gated.iter().sum::<f64>() / gated.len() as f64) as f32
+}
+
+/// Concatenate `buffers` after scaling each to `target_lufs` integrated loudness
+///
+/// Segments synthesized with different voices come out at audibly
+/// different levels; matching them before joining keeps a multi-voice
+/// document at a steady volume. Silent segments are passed through
+/// unchanged and boosted samples are clamped to `[-1.0, 1.0]`.
+#[must_use]
+pub fn concat_loudness_matched(buffers: &[AudioData], target_lufs: f32, sample_rate: u32) -> AudioData {
+    let mut output = AudioData::with_capacity(buffers.iter().map(Vec::len).sum());
+    for buffer in buffers {
+        let loudness = loudness_lufs(buffer, sample_rate);
+        if loudness.is_finite() {
+            let gain = db_to_gain(target_lufs - loudness);
+            output.extend(buffer.iter().map(|&sample| (sample * gain).clamp(-1.0, 1.0)));
+        } else {
+            output.extend_from_slice(buffer);
+        }
+    }
+    output
+}
+
+/// Scale `audio` so its largest absolute sample sits at `target_peak_dbfs`
+///
+/// Silent audio is left untouched.
+pub fn normalize_peak(audio: &mut [f32], target_peak_dbfs: f32) {
+    let peak = peak(audio);
+    if peak > 0.0 {
+        let gain = db_to_gain(target_peak_dbfs) / peak;
+        for sample in audio.iter_mut() {
+            *sample = (*sample * gain).clamp(-1.0, 1.0);
+        }
+    }
+}
+
+/// What to do with NaN or infinite samples in model output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NonFinitePolicy {
+    /// Reject the audio with a synthesis error
+    #[default]
+    Error,
+    /// Replace each non-finite sample with silence
+    ReplaceWithZero,
+}
+
+/// Check `audio` for NaN/Inf samples and apply `policy` to them
+///
+/// Returns the number of non-finite samples found.
+///
+/// # Errors
+///
+/// Returns a synthesis error describing the first bad sample if any are
+/// found and the policy is [`NonFinitePolicy::Error`]
+pub fn scrub_non_finite(audio: &mut [f32], policy: NonFinitePolicy) -> VocalizeResult<usize> {
+    let Some(first) = audio.iter().position(|sample| !sample.is_finite()) else {
+        return Ok(0);
+    };
+    let count = audio[first..].iter().filter(|sample| !sample.is_finite()).count();
+
+    match policy {
+        NonFinitePolicy::Error => Err(VocalizeError::synthesis(format!(
+            "Model output contains {count} non-finite samples (first: {} at index {first} of {})",
+            audio[first],
+            audio.len()
+        ))),
+        NonFinitePolicy::ReplaceWithZero => {
+            tracing::warn!("Replacing {} non-finite samples in model output with silence", count);
+            for sample in &mut audio[first..] {
+                if !sample.is_finite() {
+                    *sample = 0.0;
+                }
+            }
+            Ok(count)
+        }
+    }
+}
+
+/// Zero crossings of the interpolation kernel on each side at full bandwidth
+const RESAMPLER_HALF_TAPS: usize = 16;
+
+/// Resample a complete clip from `from_rate` to `to_rate`
+///
+/// Produces exactly the same samples as feeding the clip through a
+/// [`StreamingResampler`] and flushing it.
+#[must_use]
+pub fn resample(audio: &[f32], from_rate: u32, to_rate: u32) -> AudioData {
+    let mut resampler = StreamingResampler::new(from_rate, to_rate);
+    let mut output = resampler.process(audio);
+    output.extend(resampler.flush());
+    output
+}
+
+/// Band-limited sample rate converter for audio that arrives in chunks
+///
+/// Uses a Hann-windowed sinc kernel whose cutoff drops to the target
+/// Nyquist frequency when downsampling. Input history and the output phase
+/// carry over between [`process`](Self::process) calls, so chunk boundaries
+/// leave no seams; [`flush`](Self::flush) drains the kernel's look-ahead at
+/// the end of a stream.
+#[derive(Debug, Clone)]
+pub struct StreamingResampler {
+    from_rate: u64,
+    to_rate: u64,
+    /// Kernel cutoff as a fraction of the input Nyquist frequency
+    cutoff: f64,
+    /// Kernel half-width in input samples
+    half_width: usize,
+    /// Retained input, starting at absolute input index `buffer_start`
+    buffer: Vec<f32>,
+    buffer_start: u64,
+    /// Total input samples received
+    input_len: u64,
+    /// Absolute index of the next output sample
+    next_output: u64,
+}
+
+impl StreamingResampler {
+    /// Create a resampler converting from `from_rate` to `to_rate` Hz
+    ///
+    /// # Panics
+    ///
+    /// Panics if either rate is zero
+    #[must_use]
+    pub fn new(from_rate: u32, to_rate: u32) -> Self {
+        assert!(from_rate > 0 && to_rate > 0, "Sample rates must be non-zero");
+
+        let divisor = gcd(from_rate, to_rate);
+        let cutoff = (f64::from(to_rate) / f64::from(from_rate)).min(1.0);
+        Self {
+            from_rate: u64::from(from_rate / divisor),
+            to_rate: u64::from(to_rate / divisor),
+            cutoff,
+            half_width: (RESAMPLER_HALF_TAPS as f64 / cutoff).ceil() as usize,
+            buffer: Vec::new(),
+            buffer_start: 0,
+            input_len: 0,
+            next_output: 0,
+        }
+    }
+
+    /// Check whether the input and output rates are equal
+    #[must_use]
+    pub fn is_passthrough(&self) -> bool {
+        self.from_rate == self.to_rate
+    }
+
+    /// Resample the next chunk of input
+    ///
+    /// Output lags the input by the kernel's half-width, so the final
+    /// samples only appear after [`flush`](Self::flush).
+    pub fn process(&mut self, chunk: &[f32]) -> AudioData {
+        if self.is_passthrough() {
+            return chunk.to_vec();
+        }
+
+        self.buffer.extend_from_slice(chunk);
+        self.input_len += chunk.len() as u64;
+
+        let mut output = Vec::with_capacity(
+            (chunk.len() as u64 * self.to_rate / self.from_rate) as usize + 1,
+        );
+        // Emit every output whose kernel window lies entirely in the input seen so far
+        while self.center(self.next_output) + self.half_width as u64 + 1 <= self.input_len {
+            output.push(self.interpolate(self.next_output));
+            self.next_output += 1;
+        }
+        self.discard_history();
+
+        output
+    }
+
+    /// Emit the remaining output, treating input past the end as silence,
+    /// and reset for a new stream
+    pub fn flush(&mut self) -> AudioData {
+        if self.is_passthrough() {
+            return Vec::new();
+        }
+
+        // Total output length is ceil(input_len * to / from)
+        let total = (self.input_len * self.to_rate).div_ceil(self.from_rate);
+        let output = (self.next_output..total)
+            .map(|index| self.interpolate(index))
+            .collect();
+
+        self.buffer.clear();
+        self.buffer_start = 0;
+        self.input_len = 0;
+        self.next_output = 0;
+
+        output
+    }
+
+    /// Input sample index at or just before output sample `index`
+    fn center(&self, index: u64) -> u64 {
+        index * self.from_rate / self.to_rate
+    }
+
+    /// Compute output sample `index` from the retained input
+    fn interpolate(&self, index: u64) -> f32 {
+        let position = index * self.from_rate;
+        let center = position / self.to_rate;
+        let fraction = (position % self.to_rate) as f64 / self.to_rate as f64;
+
+        let first = (center + 1).saturating_sub(self.half_width as u64);
+        let last = center + self.half_width as u64;
+
+        let mut sum = 0.0;
+        for input_index in first.max(self.buffer_start)..=last.min(self.input_len.saturating_sub(1)) {
+            let sample = self.buffer[(input_index - self.buffer_start) as usize];
+            let offset = center as f64 + fraction - input_index as f64;
+            sum += f64::from(sample) * self.kernel(offset);
+        }
+        sum as f32
+    }
+
+    /// Windowed-sinc low-pass kernel evaluated `offset` input samples away
+    fn kernel(&self, offset: f64) -> f64 {
+        let half_width = self.half_width as f64;
+        if offset.abs() >= half_width {
+            return 0.0;
+        }
+
+        let x = std::f64::consts::PI * self.cutoff * offset;
+        let sinc = if x.abs() < 1e-12 { 1.0 } else { x.sin() / x };
+        let window = 0.5 * (1.0 + (std::f64::consts::PI * offset / half_width).cos());
+        self.cutoff * sinc * window
+    }
+
+    /// Drop input that no future output sample can reach
+    fn discard_history(&mut self) {
+        let needed_from = (self.center(self.next_output) + 1).saturating_sub(self.half_width as u64);
+        if needed_from > self.buffer_start {
+            let drop = (needed_from - self.buffer_start) as usize;
+            self.buffer.drain(..drop.min(self.buffer.len()));
+            self.buffer_start += drop as u64;
+        }
+    }
+}
+
+/// Greatest common divisor
+const fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frequency: f32, sample_rate: u32, len: usize) -> AudioData {
+        (0..len)
+            .map(|i| (std::f32::consts::TAU * frequency * i as f32 / sample_rate as f32).sin() * 0.8)
+            .collect()
+    }
+
+    #[test]
+    fn test_i16_pcm_round_trip_within_quantization_error() {
+        let audio: AudioData = (0..1000)
+            .map(|i| (i as f32 * 0.05).sin() * 0.9)
+            .chain([0.0, 1.0, -1.0])
+            .collect();
+
+        let round_trip = from_i16_pcm(&to_i16_pcm(&audio));
+
+        assert_eq!(round_trip.len(), audio.len());
+        for (original, converted) in audio.iter().zip(&round_trip) {
+            assert!((original - converted).abs() <= 1.0 / I16_SCALE);
+        }
+    }
+
+    #[test]
+    fn test_to_i16_pcm_clamps() {
+        assert_eq!(to_i16_pcm(&[2.0, -2.0, 0.0, f32::NAN]), vec![32767, -32767, 0, 0]);
+    }
+
+    #[test]
+    fn test_from_i16_pcm_range() {
+        assert_eq!(from_i16_pcm(&[i16::MAX, 0, i16::MIN]), vec![1.0, 0.0, -1.0]);
+    }
+
+    #[test]
+    fn test_normalize_peak_leaves_headroom() {
+        let mut audio = vec![0.1, -0.4, 0.2];
+        normalize_peak(&mut audio, DEFAULT_TARGET_PEAK_DBFS);
+
+        let peak = audio.iter().map(|s| s.abs()).fold(0.0_f32, f32::max);
+        assert!((peak - 0.891_250_9).abs() < 1e-6);
+        assert!((audio[0] / audio[1] - 0.1 / -0.4).abs() < 1e-6);
+
+        normalize_peak(&mut audio, -6.0);
+        let peak = audio.iter().map(|s| s.abs()).fold(0.0_f32, f32::max);
+        assert!((peak - db_to_gain(-6.0)).abs() < 1e-6);
+
+        let mut silence = vec![0.0; 4];
+        normalize_peak(&mut silence, DEFAULT_TARGET_PEAK_DBFS);
+        assert_eq!(silence, vec![0.0; 4]);
+    }
+
+    #[test]
+    fn test_sine_rms_is_amplitude_over_root_two() {
+        // 100 whole periods of a 0.8 amplitude tone
+        let audio = sine(240.0, 24_000, 10_000);
+        let stats = analyze(&audio);
+
+        assert!((stats.rms - 0.8 / std::f32::consts::SQRT_2).abs() < 1e-4);
+        assert!((stats.peak - 0.8).abs() < 1e-4);
+        assert!((stats.dbfs - gain_to_db(stats.rms)).abs() < 1e-6);
+        assert_eq!(stats.clip_count, 0);
+        assert_eq!(rms(&audio), stats.rms);
+        assert_eq!(peak(&audio), stats.peak);
+    }
+
+    #[test]
+    fn test_analyze_counts_clipped_samples() {
+        let stats = analyze(&[0.5, 1.0, -1.0, 1.5, -0.999, -3.0]);
+
+        assert_eq!(stats.clip_count, 4);
+        assert_eq!(stats.peak, 3.0);
+
+        let silence = analyze(&[]);
+        assert_eq!(silence.rms, 0.0);
+        assert_eq!(silence.peak, 0.0);
+        assert_eq!(silence.dbfs, f32::NEG_INFINITY);
+        assert_eq!(silence.clip_count, 0);
+    }
+
+    #[test]
+    fn test_detect_speech_regions_splits_on_silence() {
+        // 0.5s tone, 0.5s silence, 0.3s tone
+        let mut audio = sine(220.0, 24_000, 12_000);
+        audio.extend(std::iter::repeat(0.0).take(12_000));
+        audio.extend(sine(330.0, 24_000, 7_200));
+
+        let regions = detect_speech_regions(&audio, 24_000);
+
+        assert_eq!(regions, vec![(0, 12_000), (24_000, 31_200)]);
+    }
+
+    #[test]
+    fn test_detect_speech_regions_ignores_quiet_and_short_sounds() {
+        let sample_rate = 24_000;
+        assert!(detect_speech_regions(&[], sample_rate).is_empty());
+
+        // Low hum well below the energy threshold
+        let hum: AudioData = sine(50.0, sample_rate, 24_000).iter().map(|s| s * 0.001).collect();
+        assert!(detect_speech_regions(&hum, sample_rate).is_empty());
+
+        // A single 20ms click in silence
+        let mut click = vec![0.0; 24_000];
+        click[12_000..12_480].copy_from_slice(&sine(1000.0, sample_rate, 480));
+        assert!(detect_speech_regions(&click, sample_rate).is_empty());
+
+        // Short pauses between words stay inside one region
+        let mut words = sine(220.0, sample_rate, 4_800);
+        words.extend(std::iter::repeat(0.0).take(2_400));
+        words.extend(sine(220.0, sample_rate, 4_800));
+        assert_eq!(detect_speech_regions(&words, sample_rate), vec![(0, 12_000)]);
+    }
+
+    #[test]
+    fn test_spectrogram_peak_at_tone_frequency() {
+        let sample_rate = 24_000;
+        let audio = sine(1_500.0, sample_rate, 4_800);
+
+        let frames = spectrogram(&audio, sample_rate, 512, 256);
+
+        assert_eq!(frames.len(), (4_800 - 512) / 256 + 1);
+        for frame in &frames {
+            assert_eq!(frame.len(), 257);
+            let dominant = frame
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(bin, _)| bin)
+                .unwrap();
+            // 1500 Hz sits exactly on bin 32 at 46.875 Hz per bin
+            assert_eq!(dominant, 32);
+            assert!((spectrogram_bin_frequency(dominant, sample_rate, 512) - 1_500.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_spectrogram_edge_cases() {
+        assert!(spectrogram(&[], 24_000, 512, 256).is_empty());
+        assert!(spectrogram(&[0.5; 100], 24_000, 0, 256).is_empty());
+        assert!(spectrogram(&[0.5; 100], 24_000, 512, 0).is_empty());
+
+        // Short clips are zero-padded into a single frame
+        let frames = spectrogram(&[0.5; 100], 24_000, 512, 256);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].len(), 257);
+    }
+
+    #[test]
+    fn test_noise_gate_attenuates_quiet_sections() {
+        let sample_rate = 24_000;
+        // Speech-level tone, low hiss-level tone, speech-level tone
+        let loud = sine(220.0, sample_rate, 12_000);
+        let quiet: AudioData = sine(3_000.0, sample_rate, 12_000).iter().map(|s| s * 0.01).collect();
+        let original: AudioData = loud.iter().chain(&quiet).chain(&loud).copied().collect();
+
+        let mut audio = original.clone();
+        noise_gate(&mut audio, 0.05, 5.0, 50.0, sample_rate);
+
+        // Once the envelope and gain have both released, the hiss is at least 40 dB down
+        let hiss = &audio[12_000 + 9_600..24_000];
+        assert!(peak(hiss) < 0.01 * db_to_gain(-40.0), "hiss peak {}", peak(hiss));
+
+        // After the attack time speech passes at full level
+        for start in [0, 24_000] {
+            let range = start + 2_400..start + 12_000;
+            for (gated, expected) in audio[range.clone()].iter().zip(&original[range]) {
+                assert!((gated - expected).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_noise_gate_fades_instead_of_switching() {
+        let sample_rate = 24_000;
+        let mut audio = sine(220.0, sample_rate, 4_800);
+        audio.extend(std::iter::repeat(0.001).take(14_400));
+
+        noise_gate(&mut audio, 0.05, 5.0, 50.0, sample_rate);
+
+        // Gain decays smoothly across the release instead of dropping to zero
+        let tail = &audio[4_800..];
+        assert!(tail[0] > 0.0009);
+        assert!(tail.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(tail[tail.len() - 1] < 0.001 * 0.01);
+    }
+
+    #[test]
+    fn test_duck_attenuates_background_under_speech() {
+        let sample_rate = 24_000;
+        let mut background = vec![0.5; 96_000];
+        // A second of silence, a second of speech, two seconds of gap
+        let mut speech = vec![0.0; 24_000];
+        speech.extend(sine(220.0, sample_rate, 24_000));
+        speech.extend(std::iter::repeat(0.0).take(48_000));
+
+        duck(&mut background, &speech, 0.05, 12.0);
+
+        // Untouched before speech starts
+        assert!(background[..24_000].iter().all(|&s| s == 0.5));
+        // Fully ducked once the attack has passed
+        let ducked = 0.5 * db_to_gain(-12.0);
+        for &sample in &background[24_000 + 2_400..48_000] {
+            assert!((sample - ducked).abs() < 1e-3, "{sample} vs {ducked}");
+        }
+        // Restored smoothly in the gap
+        let gap = &background[48_000..];
+        assert!(gap.windows(2).all(|pair| pair[1] >= pair[0]));
+        assert!((gap[gap.len() - 1] - 0.5).abs() < 5e-3, "{}", gap[gap.len() - 1]);
+    }
+
+    #[test]
+    fn test_split_into_fixed_segments() {
+        let audio: AudioData = (0..10).map(|i| i as f32).collect();
+
+        let segments = split(&audio, 4, 0);
+        assert_eq!(segments.iter().map(Vec::len).collect::<Vec<_>>(), vec![4, 4, 2]);
+        assert_eq!(segments.concat(), audio);
+
+        // Exact multiples leave no empty trailing segment
+        assert_eq!(split(&audio, 5, 0).len(), 2);
+        assert_eq!(split(&audio, 20, 0), vec![audio.clone()]);
+        assert!(split(&[], 4, 0).is_empty());
+        assert!(split(&audio, 0, 0).is_empty());
+    }
+
+    #[test]
+    fn test_time_stretch_keeps_pitch() {
+        let audio: AudioData = (0..24_000)
+            .map(|i| (std::f32::consts::TAU * 200.0 * i as f32 / 24_000.0).sin() * 0.5)
+            .collect();
+        let crossings = |audio: &[f32]| audio.windows(2).filter(|pair| (pair[0] < 0.0) != (pair[1] < 0.0)).count();
+
+        for factor in [0.75, 1.5] {
+            let stretched = time_stretch(&audio, factor);
+            assert_eq!(stretched.len(), (24_000.0 / factor).round() as usize);
+
+            // Same pitch means the same zero-crossing rate per sample
+            let rate = crossings(&stretched) as f32 / stretched.len() as f32;
+            let original_rate = crossings(&audio) as f32 / audio.len() as f32;
+            assert!((rate / original_rate - 1.0).abs() < 0.05, "factor {factor}: {rate} vs {original_rate}");
+        }
+
+        assert_eq!(time_stretch(&audio, 1.0), audio);
+        assert_eq!(time_stretch(&audio, 0.0), audio);
+        assert_eq!(time_stretch(&audio, f32::NAN), audio);
+        assert!(time_stretch(&[], 2.0).is_empty());
+    }
+
+    #[test]
+    fn test_split_with_overlap() {
+        let audio: AudioData = (0..10).map(|i| i as f32).collect();
+
+        let segments = split(&audio, 4, 1);
+        assert_eq!(segments, vec![
+            vec![0.0, 1.0, 2.0, 3.0],
+            vec![3.0, 4.0, 5.0, 6.0],
+            vec![6.0, 7.0, 8.0, 9.0],
+        ]);
+        for pair in segments.windows(2) {
+            assert_eq!(pair[0][pair[0].len() - 1..], pair[1][..1]);
+        }
+
+        // Overlap is capped so segments always advance
+        let segments = split(&audio, 4, 10);
+        assert_eq!(segments.len(), 7);
+        assert_eq!(segments[1][0], 1.0);
+    }
+
+    #[test]
+    fn test_declick_smooths_step_discontinuity() {
+        let mut audio: AudioData = sine(220.0, 24_000, 2_000).iter().map(|s| s * 0.4).collect();
+        // Simulate a chunk boundary with a DC offset mismatch
+        for sample in &mut audio[1_000..] {
+            *sample += 0.5;
+        }
+        let max_jump = 0.1;
+        assert!((audio[1_000] - audio[999]).abs() > max_jump);
+
+        let original = audio.clone();
+        assert_eq!(declick(&mut audio, max_jump), 1);
+
+        for pair in audio.windows(2) {
+            assert!((pair[1] - pair[0]).abs() <= max_jump, "{pair:?}");
+        }
+        // Only a short span around the step is rewritten
+        assert_eq!(audio[..1_000 - DECLICK_MAX_HALF_WIDTH - 1], original[..1_000 - DECLICK_MAX_HALF_WIDTH - 1]);
+        assert_eq!(audio[1_000 + DECLICK_MAX_HALF_WIDTH + 1..], original[1_000 + DECLICK_MAX_HALF_WIDTH + 1..]);
+    }
+
+    #[test]
+    fn test_declick_leaves_smooth_audio_alone() {
+        let mut audio = sine(220.0, 24_000, 2_000);
+        let original = audio.clone();
+
+        assert_eq!(declick(&mut audio, 0.1), 0);
+        assert_eq!(audio, original);
+        assert_eq!(declick(&mut [0.0, 1.0], 0.0), 0);
+    }
+
+    #[test]
+    fn test_crossfade_concat_blends_overlap() {
+        let mut audio = vec![1.0; 10];
+        crossfade_concat(&mut audio, &[0.0; 10], 4);
+
+        assert_eq!(audio.len(), 16);
+        assert_eq!(audio[..6], [1.0; 6]);
+        for (sample, expected) in audio[6..10].iter().zip([0.8, 0.6, 0.4, 0.2]) {
+            assert!((sample - expected).abs() < 1e-6);
+        }
+        assert_eq!(audio[10..], [0.0; 6]);
+
+        // Fade length is capped by the shorter clip
+        let mut short = vec![1.0; 2];
+        crossfade_concat(&mut short, &[0.0; 3], 10);
+        assert_eq!(short.len(), 3);
+
+        let mut plain = vec![1.0];
+        crossfade_concat(&mut plain, &[0.5], 0);
+        assert_eq!(plain, vec![1.0, 0.5]);
+    }
+
+    #[test]
+    fn test_loudness_lufs_full_scale_sine() {
+        // BS.1770 calibration: a full-scale 1 kHz sine reads -3.01 LUFS
+        let audio: AudioData = sine(1000.0, 48_000, 48_000).iter().map(|s| s / 0.8).collect();
+        let loudness = loudness_lufs(&audio, 48_000);
+        assert!((loudness + 3.01).abs() < 0.1, "{loudness}");
+
+        assert_eq!(loudness_lufs(&[0.0; 48_000], 48_000), f32::NEG_INFINITY);
+        assert_eq!(loudness_lufs(&[], 48_000), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_concat_loudness_matched_equalizes_segments() {
+        let quiet: AudioData = sine(440.0, 24_000, 24_000).iter().map(|s| s * 0.05).collect();
+        let loud: AudioData = sine(880.0, 24_000, 24_000).iter().map(|s| s * 0.9).collect();
+        let before = (loudness_lufs(&quiet, 24_000) - loudness_lufs(&loud, 24_000)).abs();
+        assert!(before > 20.0, "{before}");
+
+        let output = concat_loudness_matched(&[quiet, loud, vec![0.0; 100]], -23.0, 24_000);
+        assert_eq!(output.len(), 48_100);
+
+        let first = loudness_lufs(&output[..24_000], 24_000);
+        let second = loudness_lufs(&output[24_000..48_000], 24_000);
+        assert!((first + 23.0).abs() < 0.1, "{first}");
+        assert!((second + 23.0).abs() < 0.1, "{second}");
+        assert_eq!(output[48_000..], [0.0; 100]);
+    }
+
+    #[test]
+    fn test_scrub_non_finite_error_policy() {
+        let mut audio = vec![0.1, f32::NAN, 0.2, f32::INFINITY];
+        let error = scrub_non_finite(&mut audio, NonFinitePolicy::Error).unwrap_err();
+
+        assert_eq!(
+            error,
+            VocalizeError::synthesis(
+                "Model output contains 2 non-finite samples (first: NaN at index 1 of 4)"
+            )
+        );
+        assert!(audio[1].is_nan());
+    }
+
+    #[test]
+    fn test_scrub_non_finite_replace_policy() {
+        let mut audio = vec![0.1, f32::NAN, 0.2, f32::NEG_INFINITY];
+        let replaced = scrub_non_finite(&mut audio, NonFinitePolicy::ReplaceWithZero).unwrap();
+
+        assert_eq!(replaced, 2);
+        assert_eq!(audio, vec![0.1, 0.0, 0.2, 0.0]);
+
+        let mut clean = vec![0.1, -0.2];
+        assert_eq!(scrub_non_finite(&mut clean, NonFinitePolicy::Error).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_streaming_resampler_matches_batch() {
+        let input = sine(440.0, 24_000, 4800);
+        for (from, to) in [(24_000, 48_000), (24_000, 44_100), (48_000, 16_000)] {
+            let batch = resample(&input, from, to);
+            assert_eq!(batch.len(), (input.len() as u64 * u64::from(to)).div_ceil(u64::from(from)) as usize);
+
+            // Uneven chunk sizes, including empty and single-sample chunks
+            let mut resampler = StreamingResampler::new(from, to);
+            let mut streamed = Vec::new();
+            let mut rest = &input[..];
+            for size in [1, 7, 0, 333, 1, 1000].into_iter().cycle() {
+                if rest.is_empty() {
+                    break;
+                }
+                let (chunk, tail) = rest.split_at(size.min(rest.len()));
+                streamed.extend(resampler.process(chunk));
+                rest = tail;
+            }
+            streamed.extend(resampler.flush());
+
+            assert_eq!(streamed.len(), batch.len());
+            for (a, b) in streamed.iter().zip(&batch) {
+                assert!((a - b).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_resample_preserves_sine() {
+        let input = sine(440.0, 24_000, 4800);
+        let output = resample(&input, 24_000, 48_000);
+        let expected = sine(440.0, 48_000, output.len());
+
+        // Skip the edges, where the kernel runs off the ends of the input
+        for (actual, expected) in output[200..output.len() - 200].iter().zip(&expected[200..]) {
+            assert!((actual - expected).abs() < 0.01, "{actual} vs {expected}");
+        }
+    }
+
+    #[test]
+    fn test_resampler_passthrough_and_reuse() {
+        let input = sine(440.0, 24_000, 100);
+        let mut same = StreamingResampler::new(24_000, 24_000);
+        assert!(same.is_passthrough());
+        assert_eq!(same.process(&input), input);
+        assert!(same.flush().is_empty());
+
+        // Flushing resets the resampler for the next stream
+        let mut resampler = StreamingResampler::new(24_000, 48_000);
+        let mut first = resampler.process(&input);
+        first.extend(resampler.flush());
+        let mut second = resampler.process(&input);
+        second.extend(resampler.flush());
+        assert_eq!(first, second);
+    }
+}
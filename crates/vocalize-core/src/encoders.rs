@@ -0,0 +1,376 @@
+//! Compressed audio encoders, each gated behind its own cargo feature.
+//!
+//! WAV is always available through [`crate::wav_writer`]; the encoders here
+//! pull in native codec libraries and are only compiled when the matching
+//! feature (`mp3`, `flac`, `ogg`, `opus`) is enabled.
+
+use crate::audio_writer::{AudioFormat, EncodingSettings};
+use crate::error::{VocalizeError, VocalizeResult};
+
+/// Name of the cargo feature that enables the encoder for `format`
+pub(crate) const fn feature_name(format: AudioFormat) -> Option<&'static str> {
+    match format {
+        AudioFormat::Wav => None,
+        AudioFormat::Mp3 => Some("mp3"),
+        AudioFormat::Flac => Some("flac"),
+        AudioFormat::Ogg => Some("ogg"),
+        AudioFormat::Opus => Some("opus"),
+    }
+}
+
+/// Error returned when a format's encoder was not compiled in
+pub(crate) fn not_enabled(format: AudioFormat) -> VocalizeError {
+    VocalizeError::configuration(format!(
+        "{format} encoding is not enabled in this build; rebuild vocalize-core with the `{}` feature",
+        feature_name(format).unwrap_or("default")
+    ))
+}
+
+/// Encode interleaved samples into an in-memory file of the given format
+///
+/// # Errors
+///
+/// Returns an error if the encoder is not enabled, the settings are not
+/// supported by the codec, or encoding fails
+pub(crate) fn encode(
+    format: AudioFormat,
+    audio_data: &[f32],
+    settings: &EncodingSettings,
+) -> VocalizeResult<Vec<u8>> {
+    match format {
+        #[cfg(feature = "mp3")]
+        AudioFormat::Mp3 => encode_mp3(audio_data, settings),
+        #[cfg(feature = "flac")]
+        AudioFormat::Flac => encode_flac(audio_data, settings),
+        #[cfg(feature = "ogg")]
+        AudioFormat::Ogg => encode_vorbis(audio_data, settings),
+        #[cfg(feature = "opus")]
+        AudioFormat::Opus => encode_opus(audio_data, settings),
+        _ => {
+            let _ = (audio_data, settings);
+            Err(not_enabled(format))
+        }
+    }
+}
+
+/// Target bitrate in kbps, if `quality` was given as a bitrate
+#[cfg(any(feature = "mp3", feature = "ogg", feature = "opus"))]
+fn bitrate_kbps(settings: &EncodingSettings) -> Option<u32> {
+    settings
+        .quality
+        .filter(|&quality| quality >= 32.0)
+        .map(|quality| quality as u32)
+}
+
+/// Split interleaved samples into one buffer per channel
+#[cfg(feature = "ogg")]
+fn deinterleave(audio_data: &[f32], channels: usize) -> Vec<Vec<f32>> {
+    let mut planar = vec![Vec::with_capacity(audio_data.len() / channels); channels];
+    for frame in audio_data.chunks_exact(channels) {
+        for (channel, &sample) in planar.iter_mut().zip(frame) {
+            channel.push(sample.clamp(-1.0, 1.0));
+        }
+    }
+    planar
+}
+
+/// Encode to MP3 with LAME
+#[cfg(feature = "mp3")]
+fn encode_mp3(audio_data: &[f32], settings: &EncodingSettings) -> VocalizeResult<Vec<u8>> {
+    use mp3lame_encoder::{Bitrate, Builder, FlushNoGap, InterleavedPcm, MonoPcm};
+
+    if settings.channels > 2 {
+        return Err(VocalizeError::invalid_input(format!(
+            "MP3 supports 1 or 2 channels, got {}",
+            settings.channels
+        )));
+    }
+
+    let bitrate = match bitrate_kbps(settings).unwrap_or(128) {
+        0..=39 => Bitrate::Kbps32,
+        40..=47 => Bitrate::Kbps40,
+        48..=63 => Bitrate::Kbps48,
+        64..=79 => Bitrate::Kbps64,
+        80..=95 => Bitrate::Kbps80,
+        96..=111 => Bitrate::Kbps96,
+        112..=127 => Bitrate::Kbps112,
+        128..=159 => Bitrate::Kbps128,
+        160..=191 => Bitrate::Kbps160,
+        192..=223 => Bitrate::Kbps192,
+        224..=255 => Bitrate::Kbps224,
+        256..=319 => Bitrate::Kbps256,
+        _ => Bitrate::Kbps320,
+    };
+
+    let encoder_error = |e: &dyn std::fmt::Display| {
+        VocalizeError::audio_processing(format!("MP3 encoder error: {e}"))
+    };
+
+    let mut builder = Builder::new()
+        .ok_or_else(|| VocalizeError::audio_processing("Failed to create MP3 encoder"))?;
+    builder
+        .set_num_channels(settings.channels as u8)
+        .map_err(|e| encoder_error(&e))?;
+    builder
+        .set_sample_rate(settings.sample_rate)
+        .map_err(|e| encoder_error(&e))?;
+    builder.set_brate(bitrate).map_err(|e| encoder_error(&e))?;
+    let mut encoder = builder.build().map_err(|e| encoder_error(&e))?;
+
+    let pcm = crate::audio_effects::to_i16_pcm(audio_data);
+
+    let mut output = Vec::new();
+    output.reserve(mp3lame_encoder::max_required_buffer_size(pcm.len()));
+    if settings.channels == 1 {
+        encoder.encode_to_vec(MonoPcm(&pcm), &mut output)
+    } else {
+        encoder.encode_to_vec(InterleavedPcm(&pcm), &mut output)
+    }
+    .map_err(|e| encoder_error(&e))?;
+
+    output.reserve(7200);
+    encoder
+        .flush_to_vec::<FlushNoGap>(&mut output)
+        .map_err(|e| encoder_error(&e))?;
+
+    Ok(output)
+}
+
+/// Encode to FLAC with flacenc
+#[cfg(feature = "flac")]
+fn encode_flac(audio_data: &[f32], settings: &EncodingSettings) -> VocalizeResult<Vec<u8>> {
+    use flacenc::bitsink::ByteSink;
+    use flacenc::component::BitRepr;
+    use flacenc::error::Verify;
+
+    // FLAC tops out at 24 bits per sample in practice
+    let bits_per_sample = usize::from(settings.bit_depth.min(24));
+    let scale = ((1_i64 << (bits_per_sample - 1)) - 1) as f32;
+    let samples: Vec<i32> = audio_data
+        .iter()
+        .map(|&sample| (sample.clamp(-1.0, 1.0) * scale) as i32)
+        .collect();
+
+    let config = flacenc::config::Encoder::default()
+        .into_verified()
+        .map_err(|(_, e)| VocalizeError::audio_processing(format!("Invalid FLAC config: {e}")))?;
+    let source = flacenc::source::MemSource::from_samples(
+        &samples,
+        usize::from(settings.channels),
+        bits_per_sample,
+        settings.sample_rate as usize,
+    );
+    let stream = flacenc::encode_with_fixed_block_size(&config, source, config.block_size)
+        .map_err(|e| VocalizeError::audio_processing(format!("FLAC encoder error: {e}")))?;
+
+    let mut sink = ByteSink::new();
+    stream
+        .write(&mut sink)
+        .map_err(|e| VocalizeError::audio_processing(format!("FLAC encoder error: {e}")))?;
+
+    Ok(sink.as_slice().to_vec())
+}
+
+/// Encode to Ogg Vorbis with libvorbis
+#[cfg(feature = "ogg")]
+fn encode_vorbis(audio_data: &[f32], settings: &EncodingSettings) -> VocalizeResult<Vec<u8>> {
+    use std::num::{NonZeroU32, NonZeroU8};
+    use vorbis_rs::{VorbisBitrateManagementStrategy, VorbisEncoderBuilder};
+
+    let sample_rate = NonZeroU32::new(settings.sample_rate)
+        .ok_or_else(|| VocalizeError::invalid_input("Sample rate must be non-zero"))?;
+    let channels = u8::try_from(settings.channels)
+        .ok()
+        .and_then(NonZeroU8::new)
+        .ok_or_else(|| VocalizeError::invalid_input("Invalid channel count for Vorbis"))?;
+
+    let strategy = match (bitrate_kbps(settings), settings.quality) {
+        (Some(kbps), _) => VorbisBitrateManagementStrategy::Abr {
+            average_bitrate: NonZeroU32::new(kbps * 1000).unwrap_or(NonZeroU32::MIN),
+        },
+        (None, Some(quality)) => VorbisBitrateManagementStrategy::QualityVbr {
+            target_quality: quality,
+        },
+        (None, None) => VorbisBitrateManagementStrategy::default(),
+    };
+
+    let vorbis_error =
+        |e: vorbis_rs::VorbisError| VocalizeError::audio_processing(format!("Vorbis encoder error: {e}"));
+
+    let mut output = Vec::new();
+    let mut builder =
+        VorbisEncoderBuilder::new(sample_rate, channels, &mut output).map_err(vorbis_error)?;
+    builder.bitrate_management_strategy(strategy);
+    let mut encoder = builder.build().map_err(vorbis_error)?;
+
+    let planar = deinterleave(audio_data, usize::from(settings.channels));
+    encoder.encode_audio_block(&planar).map_err(vorbis_error)?;
+    encoder.finish().map_err(vorbis_error)?;
+
+    Ok(output)
+}
+
+/// Encode to Opus in an Ogg container
+#[cfg(feature = "opus")]
+fn encode_opus(audio_data: &[f32], settings: &EncodingSettings) -> VocalizeResult<Vec<u8>> {
+    use ogg::writing::{PacketWriteEndInfo, PacketWriter};
+
+    const SERIAL: u32 = 0x766f_6361; // "voca"
+    const MAX_PACKET_SIZE: usize = 4000;
+
+    if !matches!(settings.sample_rate, 8000 | 12000 | 16000 | 24000 | 48000) {
+        return Err(VocalizeError::invalid_input(format!(
+            "Opus supports 8000, 12000, 16000, 24000 or 48000 Hz, got {}",
+            settings.sample_rate
+        )));
+    }
+    let opus_channels = match settings.channels {
+        1 => opus::Channels::Mono,
+        2 => opus::Channels::Stereo,
+        other => {
+            return Err(VocalizeError::invalid_input(format!(
+                "Opus supports 1 or 2 channels, got {other}"
+            )));
+        }
+    };
+
+    let opus_error = |e: opus::Error| VocalizeError::audio_processing(format!("Opus encoder error: {e}"));
+    let io_error = |e: std::io::Error| VocalizeError::audio_processing(format!("Ogg muxing error: {e}"));
+
+    let mut encoder =
+        opus::Encoder::new(settings.sample_rate, opus_channels, opus::Application::Audio)
+            .map_err(opus_error)?;
+    if let Some(kbps) = bitrate_kbps(settings) {
+        encoder
+            .set_bitrate(opus::Bitrate::Bits(i32::try_from(kbps * 1000).unwrap_or(i32::MAX)))
+            .map_err(opus_error)?;
+    }
+
+    // Granule positions and pre-skip are always counted at 48 kHz
+    let rate_factor = u64::from(48_000 / settings.sample_rate);
+    let channels = usize::from(settings.channels);
+    let pre_skip = u64::try_from(encoder.get_lookahead().map_err(opus_error)?).unwrap_or(0) * rate_factor;
+
+    let mut head = Vec::with_capacity(19);
+    head.extend_from_slice(b"OpusHead");
+    head.push(1); // version
+    head.push(settings.channels as u8);
+    head.extend_from_slice(&(pre_skip as u16).to_le_bytes());
+    head.extend_from_slice(&settings.sample_rate.to_le_bytes());
+    head.extend_from_slice(&0_i16.to_le_bytes()); // output gain
+    head.push(0); // mono/stereo channel mapping
+
+    let vendor = b"vocalize";
+    let mut tags = Vec::with_capacity(16 + vendor.len());
+    tags.extend_from_slice(b"OpusTags");
+    tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    tags.extend_from_slice(vendor);
+    tags.extend_from_slice(&0_u32.to_le_bytes()); // no user comments
+
+    let mut output = Vec::new();
+    let mut writer = PacketWriter::new(&mut output);
+    writer
+        .write_packet(head, SERIAL, PacketWriteEndInfo::EndPage, 0)
+        .map_err(io_error)?;
+    writer
+        .write_packet(tags, SERIAL, PacketWriteEndInfo::EndPage, 0)
+        .map_err(io_error)?;
+
+    // 20ms frames, with the final frame zero-padded
+    let frame_len = settings.sample_rate as usize / 50 * channels;
+    let frame_count = audio_data.len().div_ceil(frame_len).max(1);
+    let mut frame = vec![0.0_f32; frame_len];
+    let mut granule = pre_skip;
+
+    for index in 0..frame_count {
+        let start = (index * frame_len).min(audio_data.len());
+        let end = (start + frame_len).min(audio_data.len());
+        frame.fill(0.0);
+        frame[..end - start].copy_from_slice(&audio_data[start..end]);
+
+        let packet = encoder
+            .encode_vec_float(&frame, MAX_PACKET_SIZE)
+            .map_err(opus_error)?;
+        let is_last = index + 1 == frame_count;
+        granule += if is_last {
+            ((end - start) / channels) as u64 * rate_factor
+        } else {
+            (frame_len / channels) as u64 * rate_factor
+        };
+        let end_info = if is_last {
+            PacketWriteEndInfo::EndStream
+        } else {
+            PacketWriteEndInfo::NormalPacket
+        };
+        writer
+            .write_packet(packet, SERIAL, end_info, granule)
+            .map_err(io_error)?;
+    }
+    drop(writer);
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feature_names() {
+        assert_eq!(feature_name(AudioFormat::Wav), None);
+        assert_eq!(feature_name(AudioFormat::Mp3), Some("mp3"));
+        assert_eq!(feature_name(AudioFormat::Flac), Some("flac"));
+        assert_eq!(feature_name(AudioFormat::Ogg), Some("ogg"));
+        assert_eq!(feature_name(AudioFormat::Opus), Some("opus"));
+    }
+
+    #[test]
+    fn test_not_enabled_error_names_feature() {
+        let error = not_enabled(AudioFormat::Flac);
+        assert!(error.to_string().contains("`flac` feature"));
+    }
+
+    #[cfg(not(feature = "mp3"))]
+    #[test]
+    fn test_encode_mp3_disabled() {
+        let result = encode(AudioFormat::Mp3, &[0.1, 0.2], &EncodingSettings::default());
+        assert!(result.unwrap_err().to_string().contains("not enabled"));
+    }
+
+    #[cfg(feature = "mp3")]
+    #[test]
+    fn test_encode_mp3() {
+        let audio = vec![0.25; 24_000];
+        let bytes = encode(AudioFormat::Mp3, &audio, &EncodingSettings::new(24_000, 1)).unwrap();
+        // MPEG frame sync
+        assert!(bytes.len() > 2 && bytes[0] == 0xFF && bytes[1] & 0xE0 == 0xE0);
+    }
+
+    #[cfg(feature = "flac")]
+    #[test]
+    fn test_encode_flac() {
+        let audio = vec![0.25; 2 * 4800];
+        let bytes = encode(AudioFormat::Flac, &audio, &EncodingSettings::new(48_000, 2)).unwrap();
+        assert!(bytes.starts_with(b"fLaC"));
+    }
+
+    #[cfg(feature = "ogg")]
+    #[test]
+    fn test_encode_vorbis() {
+        let audio = vec![0.25; 24_000];
+        let bytes = encode(AudioFormat::Ogg, &audio, &EncodingSettings::new(24_000, 1)).unwrap();
+        assert!(bytes.starts_with(b"OggS"));
+    }
+
+    #[cfg(feature = "opus")]
+    #[test]
+    fn test_encode_opus() {
+        let audio = vec![0.25; 2 * 1000];
+        let bytes = encode(AudioFormat::Opus, &audio, &EncodingSettings::new(48_000, 2)).unwrap();
+        assert!(bytes.starts_with(b"OggS"));
+        assert!(bytes.windows(8).any(|window| window == b"OpusHead"));
+
+        let invalid_rate = EncodingSettings::new(44_100, 1);
+        assert!(encode(AudioFormat::Opus, &audio, &invalid_rate).is_err());
+    }
+}
@@ -36,10 +36,12 @@ pub enum VocalizeError {
     },
 
     /// Voice not found error
-    #[error("Voice '{voice_id}' not found")]
+    #[error("Voice '{voice_id}' not found{}", available_voices_hint(.available_voices))]
     VoiceNotFound {
         /// The voice ID that was not found
         voice_id: String,
+        /// Voices that would have been accepted, if known
+        available_voices: Vec<String>,
     },
 
     /// Invalid input error
@@ -90,6 +92,13 @@ pub enum VocalizeError {
         /// Error message describing the concurrency issue
         message: String,
     },
+
+    /// The engine is shutting down and accepts no new work
+    #[error("Shutting down: {message}")]
+    ShutdownError {
+        /// Error message describing the refused work
+        message: String,
+    },
 }
 
 impl VocalizeError {
@@ -130,6 +139,16 @@ impl VocalizeError {
     pub fn voice_not_found<S: Into<String>>(voice_id: S) -> Self {
         Self::VoiceNotFound {
             voice_id: voice_id.into(),
+            available_voices: Vec::new(),
+        }
+    }
+
+    /// Create a voice not found error listing the voices that are supported
+    #[must_use]
+    pub fn voice_not_supported<S: Into<String>>(voice_id: S, available_voices: Vec<String>) -> Self {
+        Self::VoiceNotFound {
+            voice_id: voice_id.into(),
+            available_voices,
         }
     }
 
@@ -197,6 +216,14 @@ impl VocalizeError {
         }
     }
 
+    /// Create a new shutdown error
+    #[must_use]
+    pub fn shutdown<S: Into<String>>(message: S) -> Self {
+        Self::ShutdownError {
+            message: message.into(),
+        }
+    }
+
     /// Check if this error is retriable
     #[must_use]
     pub const fn is_retriable(&self) -> bool {
@@ -217,6 +244,23 @@ impl VocalizeError {
         )
     }
 
+    /// Check if this error only affects the request that raised it
+    ///
+    /// Recoverable errors come from the particular text or a transient
+    /// condition, so other requests to the same engine may still succeed.
+    /// Missing models or voices, bad configuration and I/O failures are not
+    /// recoverable.
+    #[must_use]
+    pub const fn is_recoverable(&self) -> bool {
+        matches!(
+            self,
+            Self::SynthesisError { .. }
+                | Self::InvalidInput { .. }
+                | Self::AudioProcessingError { .. }
+                | Self::TimeoutError { .. }
+        )
+    }
+
     /// Get the error category for logging/metrics
     #[must_use]
     pub const fn category(&self) -> &'static str {
@@ -233,6 +277,7 @@ impl VocalizeError {
             Self::MemoryError { .. } => "memory",
             Self::TimeoutError { .. } => "timeout",
             Self::ConcurrencyError { .. } => "concurrency",
+            Self::ShutdownError { .. } => "shutdown",
         }
     }
 }
@@ -264,6 +309,15 @@ impl From<anyhow::Error> for VocalizeError {
     }
 }
 
+/// Format the list of accepted voices for a voice not found message
+fn available_voices_hint(available_voices: &[String]) -> String {
+    if available_voices.is_empty() {
+        String::new()
+    } else {
+        format!("; available voices: {}", available_voices.join(", "))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -282,6 +336,20 @@ mod tests {
         assert_eq!(err.to_string(), "Voice 'test_voice' not found");
     }
 
+    #[test]
+    fn test_voice_not_supported_lists_voices() {
+        let err = VocalizeError::voice_not_supported(
+            "nope",
+            vec!["af_bella".to_string(), "am_adam".to_string()],
+        );
+        assert_eq!(
+            err.to_string(),
+            "Voice 'nope' not found; available voices: af_bella, am_adam"
+        );
+        assert_eq!(err.category(), "voice");
+        assert!(err.is_user_error());
+    }
+
     #[test]
     fn test_error_categories() {
         assert_eq!(VocalizeError::synthesis("test").category(), "synthesis");
@@ -295,6 +363,7 @@ mod tests {
         assert_eq!(VocalizeError::memory("test").category(), "memory");
         assert_eq!(VocalizeError::timeout("test").category(), "timeout");
         assert_eq!(VocalizeError::concurrency("test").category(), "concurrency");
+        assert_eq!(VocalizeError::shutdown("test").category(), "shutdown");
     }
 
     #[test]
@@ -315,6 +384,17 @@ mod tests {
         assert!(!VocalizeError::network("test").is_user_error());
     }
 
+    #[test]
+    fn test_recoverable_errors() {
+        assert!(VocalizeError::synthesis("test").is_recoverable());
+        assert!(VocalizeError::invalid_input("test").is_recoverable());
+        assert!(VocalizeError::timeout("test").is_recoverable());
+        assert!(!VocalizeError::model("test").is_recoverable());
+        assert!(!VocalizeError::voice_not_found("test").is_recoverable());
+        assert!(!VocalizeError::network("test").is_recoverable());
+        assert!(!VocalizeError::shutdown("test").is_recoverable());
+    }
+
     #[test]
     fn test_from_io_error() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "File not found");
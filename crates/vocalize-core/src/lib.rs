@@ -6,7 +6,8 @@
 //!
 //! - Fast neural TTS synthesis using Kokoro models
 //! - Cross-platform audio device support
-//! - Multiple audio format output (WAV, MP3, FLAC, OGG)
+//! - Multiple audio format output (WAV built in; MP3, FLAC, OGG and Opus behind
+//!   the `mp3`, `flac`, `ogg` and `opus` features)
 //! - Real-time streaming synthesis
 //! - Voice blending and customization
 //!
@@ -36,24 +37,36 @@
 #![warn(clippy::nursery)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod audio_buffer;
 pub mod audio_device;
+pub mod audio_effects;
 pub mod audio_writer;
+mod encoders;
 pub mod error;
 pub mod model;
 pub mod models;
 pub mod onnx_engine;
+pub mod pronunciation;
+pub mod session;
+mod synthesis_cache;
+pub mod text;
+pub mod timing;
 pub mod tts_engine;
 pub mod voice_manager;
 pub mod wav_writer;
 
 // Re-export main types for convenience
-pub use audio_device::{AudioConfig, AudioDevice, AudioDeviceInfo, PlaybackState};
-pub use audio_writer::{AudioFormat, AudioWriter, EncodingSettings};
+pub use audio_buffer::AudioBuffer;
+pub use audio_device::{AudioConfig, AudioDevice, AudioDeviceInfo, DeviceSamples, PlaybackState, SampleFormat};
+pub use audio_writer::{AudioFormat, AudioWarning, AudioWriter, EncodingSettings};
 pub use error::{VocalizeError, VocalizeResult};
 pub use model::{ModelId, ModelInfo, ModelManager, ModelConfig};
 pub use models::{TtsModel, ModelRegistry};
-pub use onnx_engine::OnnxTtsEngine;
-pub use tts_engine::{AudioData, SynthesisParams, TtsEngine, TtsConfig};
+pub use onnx_engine::{OnnxTtsEngine, SelfTestReport};
+pub use pronunciation::PronunciationDictionary;
+pub use session::TtsSession;
+pub use timing::{StageTimer, TimingBreakdown};
+pub use tts_engine::{synthesize_once, synthesize_once_with_config, AudioData, StreamingConfig, SynthesisParams, TtsEngine, TtsConfig};
 pub use voice_manager::{Gender, Voice, VoiceManager, VoiceStyle};
 
 /// Version information for the vocalize-core crate
@@ -65,5 +78,8 @@ pub const DEFAULT_SAMPLE_RATE: u32 = 24_000;
 /// Default number of audio channels (mono)
 pub const DEFAULT_CHANNELS: u16 = 1;
 
-/// Maximum text length for synthesis (to prevent memory issues)
+/// Maximum text length for synthesis, in characters (to prevent memory issues)
+///
+/// Characters are Unicode scalar values, so the limit is the same for every
+/// script regardless of its UTF-8 encoded size.
 pub const MAX_TEXT_LENGTH: usize = 100_000;
\ No newline at end of file
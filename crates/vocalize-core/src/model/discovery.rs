@@ -3,10 +3,12 @@
 
 #![allow(missing_docs)]
 
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
 use directories::ProjectDirs;
+use crate::error::{VocalizeError, VocalizeResult};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ModelManifest {
@@ -21,11 +23,141 @@ pub struct ModelManifest {
     pub description: Option<String>,
 }
 
+/// Smallest plausible size of a TTS model file, in bytes
+pub const MIN_ONNX_MODEL_SIZE: u64 = 1_000_000;
+/// Largest plausible size of a TTS model file, in bytes
+pub const MAX_ONNX_MODEL_SIZE: u64 = 2_000_000_000;
+
+/// Check that `path` looks like a usable ONNX model file
+///
+/// The path must exist, be a regular file with an `.onnx` extension, have a
+/// size between [`MIN_ONNX_MODEL_SIZE`] and [`MAX_ONNX_MODEL_SIZE`], and
+/// start with a protobuf header rather than zero bytes.
+pub fn validate_onnx_file(path: &Path) -> VocalizeResult<()> {
+    let display = path.display();
+    if !path.exists() {
+        return Err(VocalizeError::model(format!("ONNX model file does not exist: {display}")));
+    }
+    if !path.is_file() {
+        return Err(VocalizeError::model(format!("ONNX model path is not a file: {display}")));
+    }
+    if path.extension().map_or(true, |ext| ext != "onnx") {
+        return Err(VocalizeError::model(format!("ONNX model file must have a .onnx extension: {display}")));
+    }
+
+    let size = std::fs::metadata(path)?.len();
+    if !(MIN_ONNX_MODEL_SIZE..=MAX_ONNX_MODEL_SIZE).contains(&size) {
+        return Err(VocalizeError::model(format!(
+            "ONNX model file size {size} bytes is outside the expected range \
+             {MIN_ONNX_MODEL_SIZE}..={MAX_ONNX_MODEL_SIZE} bytes: {display}"
+        )));
+    }
+
+    // ONNX files are protobuf messages, which never start with a zero byte
+    let mut header = [0u8; 16];
+    std::fs::File::open(path)?.read_exact(&mut header)?;
+    if header[0] == 0 {
+        return Err(VocalizeError::model(format!("File does not look like an ONNX model: {display}")));
+    }
+
+    Ok(())
+}
+
+/// Voice embedding file names, in priority order
+const KOKORO_VOICES_FILES: &[&str] = &[
+    "voices-v1.0.bin",
+    "voices.bin",
+    "embeddings.bin",
+    "voice_embeddings.bin",
+];
+
+/// Tokenizer file names, in priority order
+const KOKORO_TOKENIZER_FILES: &[&str] = &[
+    "tokenizer.json",
+    "vocab.json",
+    "tokenizer_config.json",
+];
+
+/// Files an exact Kokoro installation must contain
+pub(crate) const EXACT_KOKORO_FILES: [&str; 3] = ["kokoro-v1.0.onnx", "voices-v1.0.bin", "tokenizer.json"];
+
+/// Directories under a cache dir that may hold a Kokoro model, in priority order
+fn kokoro_candidate_dirs(cache_dir: &Path) -> [PathBuf; 2] {
+    [
+        cache_dir.join("models--hexgrad--Kokoro-82M").join("local"),
+        cache_dir.join("models--direct_download").join("local"), // Python-managed location
+    ]
+}
+
+/// Check that `dir` holds every exact Kokoro file and a valid ONNX model
+fn check_exact_kokoro_dir(dir: &Path) -> std::result::Result<KokoroModelFiles, String> {
+    // REQUIRE EXACT FILES (no patterns, no approximations)
+    let [model_file, voices_file, tokenizer_file] = EXACT_KOKORO_FILES.map(|name| dir.join(name));
+    for (name, path) in EXACT_KOKORO_FILES.iter().zip([&model_file, &voices_file, &tokenizer_file]) {
+        if !path.is_file() {
+            return Err(format!("Missing required file '{name}'"));
+        }
+    }
+    
+    // Validate ONNX file is actually valid
+    validate_onnx_file(&model_file).map_err(|e| format!("Invalid ONNX file: {e}"))?;
+    
+    Ok(KokoroModelFiles {
+        model_file,
+        voices_file: Some(voices_file),
+        tokenizer_file: Some(tokenizer_file),
+        manifest: None, // Manifest is optional in zero-fallback mode
+    })
+}
+
+/// One directory examined by [`ModelDiscovery::report`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiscoveryEntry {
+    /// Directory that was examined
+    pub directory: PathBuf,
+    /// Required model files present in the directory
+    pub files: Vec<PathBuf>,
+    /// Whether the directory holds a complete, valid model
+    pub qualified: bool,
+    /// Whether this is the model discovery picks
+    pub selected: bool,
+    /// Why the directory was rejected, if it was
+    pub reason: Option<String>,
+}
+
 pub struct ModelDiscovery {
     cache_dirs: Vec<PathBuf>,
 }
 
 impl ModelDiscovery {
+    /// Discover models only under the given directories, in priority order
+    ///
+    /// Unlike [`new`](Self::new), neither the default cache directories nor
+    /// `VOCALIZE_MODEL_CACHE` are searched.
+    pub fn with_paths(paths: Vec<PathBuf>) -> Self {
+        Self { cache_dirs: paths }
+    }
+    
+    /// Also search `path`, after every directory already configured
+    pub fn add_path(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.cache_dirs.push(path.into());
+        self
+    }
+    
+    /// Directories searched for models, in priority order
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.cache_dirs
+    }
+    
+    /// Directory a hand-copied Kokoro install should go in to be found first
+    ///
+    /// This is the highest-priority candidate directory of the first search
+    /// path, or `None` if no paths are searched.
+    pub fn manual_install_dir(&self) -> Option<PathBuf> {
+        let [preferred, _] = kokoro_candidate_dirs(self.cache_dirs.first()?);
+        Some(preferred)
+    }
+    
     pub fn new() -> Self {
         let mut cache_dirs = Vec::new();
         
@@ -87,67 +219,64 @@ impl ModelDiscovery {
     
     /// ZERO-FALLBACK: Find EXACT Kokoro model with ALL required files
     fn find_exact_kokoro_model(&self, cache_dir: &Path) -> Option<KokoroModelFiles> {
-        // Check multiple possible locations for Kokoro model
-        let possible_paths = [
-            cache_dir.join("models--hexgrad--Kokoro-82M").join("local"),
-            cache_dir.join("models--direct_download").join("local"), // Python-managed location
-        ];
-        
-        let mut exact_kokoro_path = None;
-        for path in &possible_paths {
-            if path.exists() {
-                exact_kokoro_path = Some(path.clone());
-                tracing::debug!("Found Kokoro model directory: {:?}", path);
-                break;
-            }
-        }
-        
-        let exact_kokoro_path = exact_kokoro_path?;
-        
-        // REQUIRE EXACT FILES (no patterns, no approximations)
-        let required_files = [
-            ("kokoro-v1.0.onnx", "model_file"),
-            ("voices-v1.0.bin", "voices_file"), 
-            ("tokenizer.json", "tokenizer_file"),
-        ];
-        
-        let mut model_file = None;
-        let mut voices_file = None;
-        let mut tokenizer_file = None;
-        
-        // Check ALL required files exist with EXACT names
-        for (exact_filename, file_type) in &required_files {
-            let exact_path = exact_kokoro_path.join(exact_filename);
-            if !exact_path.exists() || !exact_path.is_file() {
-                tracing::error!("❌ ZERO-FALLBACK: Missing required file '{}' in {:?}", exact_filename, exact_kokoro_path);
-                return None;
+        // Only the first existing candidate directory is considered
+        let exact_kokoro_path = kokoro_candidate_dirs(cache_dir)
+            .into_iter()
+            .find(|path| path.exists())?;
+        tracing::debug!("Found Kokoro model directory: {:?}", exact_kokoro_path);
+        
+        match check_exact_kokoro_dir(&exact_kokoro_path) {
+            Ok(model_files) => {
+                tracing::info!("✅ ZERO-FALLBACK: Found EXACT Kokoro model with ALL required files: {:?}", exact_kokoro_path);
+                Some(model_files)
             }
-            
-            match *file_type {
-                "model_file" => model_file = Some(exact_path),
-                "voices_file" => voices_file = Some(exact_path),
-                "tokenizer_file" => tokenizer_file = Some(exact_path),
-                _ => {}
+            Err(reason) => {
+                tracing::error!("❌ ZERO-FALLBACK: {} in {:?}", reason, exact_kokoro_path);
+                None
             }
         }
+    }
+    
+    /// Describe every directory [`find_best_kokoro_model`](Self::find_best_kokoro_model)
+    /// looks at, what it found there, and why each was accepted or rejected
+    ///
+    /// At most one entry is `selected`: the model discovery would load.
+    pub fn report(&self) -> Vec<DiscoveryEntry> {
+        let mut entries = Vec::new();
+        let mut selected = false;
         
-        // REQUIRE ALL files to be present
-        let model_file = model_file?;
-        
-        // Validate ONNX file is actually valid
-        if !self.is_valid_onnx_file(&model_file) {
-            tracing::error!("❌ ZERO-FALLBACK: Invalid ONNX file: {:?}", model_file);
-            return None;
+        for cache_dir in &self.cache_dirs {
+            let mut checked: Option<PathBuf> = None;
+            for directory in kokoro_candidate_dirs(cache_dir) {
+                let files = EXACT_KOKORO_FILES
+                    .iter()
+                    .map(|name| directory.join(name))
+                    .filter(|path| path.is_file())
+                    .collect();
+                
+                let result = if let Some(checked) = &checked {
+                    Err(format!("Not checked: {} takes precedence", checked.display()))
+                } else if !directory.exists() {
+                    Err("Directory does not exist".to_string())
+                } else {
+                    checked = Some(directory.clone());
+                    check_exact_kokoro_dir(&directory).map(|_| ())
+                };
+                
+                let qualified = result.is_ok();
+                let is_selected = qualified && !selected;
+                selected |= is_selected;
+                entries.push(DiscoveryEntry {
+                    directory,
+                    files,
+                    qualified,
+                    selected: is_selected,
+                    reason: result.err(),
+                });
+            }
         }
         
-        tracing::info!("✅ ZERO-FALLBACK: Found EXACT Kokoro model with ALL required files: {:?}", exact_kokoro_path);
-        
-        Some(KokoroModelFiles {
-            model_file,
-            voices_file,
-            tokenizer_file,
-            manifest: None, // Manifest is optional in zero-fallback mode
-        })
+        entries
     }
     
     fn scan_directory_for_kokoro(&self, base_dir: &Path) -> Result<Vec<KokoroModelFiles>> {
@@ -204,19 +333,6 @@ impl ModelDiscovery {
             "*.onnx"
         ];
         
-        let voices_patterns = [
-            "voices-v1.0.bin",
-            "voices.bin", 
-            "embeddings.bin",
-            "voice_embeddings.bin"
-        ];
-        
-        let tokenizer_patterns = [
-            "tokenizer.json",
-            "vocab.json",
-            "tokenizer_config.json"
-        ];
-        
         // Find model file
         let model_file = self.find_file_by_patterns(dir, &model_patterns)?;
         
@@ -227,8 +343,8 @@ impl ModelDiscovery {
         }
         
         // Find additional files
-        let voices_file = self.find_file_by_patterns(dir, &voices_patterns);
-        let tokenizer_file = self.find_file_by_patterns(dir, &tokenizer_patterns);
+        let voices_file = self.find_file_by_patterns(dir, KOKORO_VOICES_FILES);
+        let tokenizer_file = self.find_file_by_patterns(dir, KOKORO_TOKENIZER_FILES);
         let manifest = self.load_manifest(dir);
         
         tracing::info!("✅ Found Kokoro model: {:?}", model_file);
@@ -268,7 +384,10 @@ impl ModelDiscovery {
     }
     
     fn glob_search(&self, base_dir: &Path, pattern: &str) -> Result<Vec<PathBuf>> {
-        let full_pattern = base_dir.join(pattern);
+        // Escape the literal base directory so `[`, `*` or `?` in a cache
+        // path are not treated as glob syntax
+        let escaped_base = glob::Pattern::escape(&base_dir.to_string_lossy());
+        let full_pattern = Path::new(&escaped_base).join(pattern);
         let pattern_str = full_pattern.to_string_lossy();
         
         let mut matches = Vec::new();
@@ -285,42 +404,13 @@ impl ModelDiscovery {
     }
     
     fn is_valid_onnx_file(&self, path: &Path) -> bool {
-        // Basic ONNX file validation
-        if !path.exists() || !path.is_file() {
-            return false;
-        }
-        
-        // Check file extension
-        if let Some(ext) = path.extension() {
-            if ext != "onnx" {
-                return false;
-            }
-        } else {
-            return false;
-        }
-        
-        // Check file size (should be reasonable for a TTS model)
-        if let Ok(metadata) = std::fs::metadata(path) {
-            let size = metadata.len();
-            // Expect at least 1MB and at most 2GB
-            if size < 1_000_000 || size > 2_000_000_000 {
-                tracing::warn!("ONNX file size suspicious: {} bytes", size);
-                return false;
-            }
-        }
-        
-        // Try to read the first few bytes to check ONNX magic
-        if let Ok(mut file) = std::fs::File::open(path) {
-            use std::io::Read;
-            let mut buffer = [0u8; 16];
-            if file.read_exact(&mut buffer).is_ok() {
-                // ONNX files typically start with protobuf headers
-                // This is a simple heuristic check
-                return buffer[0] != 0 && buffer.iter().any(|&b| b > 0);
+        match validate_onnx_file(path) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::debug!("Skipping ONNX candidate: {}", e);
+                false
             }
         }
-        
-        true // If we can't validate, assume it's valid
     }
     
     fn load_manifest(&self, dir: &Path) -> Option<ModelManifest> {
@@ -378,6 +468,31 @@ pub struct KokoroModelFiles {
 }
 
 impl KokoroModelFiles {
+    /// Describe the Kokoro installation around an explicit model file
+    ///
+    /// Unlike discovery, nothing else is searched: the ONNX file must be
+    /// valid and a voices file must sit next to it.
+    pub fn from_model_file(model_file: &Path) -> VocalizeResult<Self> {
+        validate_onnx_file(model_file)?;
+
+        let dir = model_file.parent().unwrap_or(Path::new("."));
+        let find = |names: &[&str]| names.iter().map(|name| dir.join(name)).find(|path| path.is_file());
+        let voices_file = find(KOKORO_VOICES_FILES).ok_or_else(|| {
+            VocalizeError::model(format!(
+                "Incomplete Kokoro model directory {}: expected one of {} next to the model file",
+                dir.display(),
+                KOKORO_VOICES_FILES.join(", ")
+            ))
+        })?;
+
+        Ok(Self {
+            model_file: model_file.to_path_buf(),
+            voices_file: Some(voices_file),
+            tokenizer_file: find(KOKORO_TOKENIZER_FILES),
+            manifest: None,
+        })
+    }
+
     /// Get the base directory containing the model files
     pub fn base_directory(&self) -> &Path {
         self.model_file.parent().unwrap_or(Path::new("."))
@@ -417,4 +532,125 @@ impl Default for ModelDiscovery {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_onnx_file() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let missing = temp_dir.path().join("missing.onnx");
+        assert!(validate_onnx_file(&missing).unwrap_err().to_string().contains("does not exist"));
+        assert!(validate_onnx_file(temp_dir.path()).unwrap_err().to_string().contains("not a file"));
+
+        let wrong_extension = temp_dir.path().join("model.bin");
+        std::fs::write(&wrong_extension, vec![8u8; 2_000_000]).unwrap();
+        assert!(validate_onnx_file(&wrong_extension).unwrap_err().to_string().contains(".onnx extension"));
+
+        let truncated = temp_dir.path().join("truncated.onnx");
+        std::fs::write(&truncated, b"not a model").unwrap();
+        assert!(validate_onnx_file(&truncated).unwrap_err().to_string().contains("expected range"));
+
+        let zeroed = temp_dir.path().join("zeroed.onnx");
+        std::fs::write(&zeroed, vec![0u8; 2_000_000]).unwrap();
+        assert!(validate_onnx_file(&zeroed).unwrap_err().to_string().contains("does not look like"));
+
+        let model = temp_dir.path().join("model.onnx");
+        std::fs::write(&model, vec![8u8; 2_000_000]).unwrap();
+        assert!(validate_onnx_file(&model).is_ok());
+    }
+
+    /// Lay out a Python-managed Kokoro model under `cache_dir`
+    fn write_kokoro_model(cache_dir: &Path, with_voices: bool) -> PathBuf {
+        let dir = cache_dir.join("models--direct_download").join("local");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("kokoro-v1.0.onnx"), vec![8u8; 2_000_000]).unwrap();
+        std::fs::write(dir.join("tokenizer.json"), b"{}").unwrap();
+        if with_voices {
+            std::fs::write(dir.join("voices-v1.0.bin"), b"voices").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_custom_search_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let empty = temp_dir.path().join("empty");
+        let custom = temp_dir.path().join("custom");
+        let model_dir = write_kokoro_model(&custom, true);
+
+        let mut discovery = ModelDiscovery::with_paths(vec![empty.clone()]);
+        assert!(discovery.find_best_kokoro_model().is_none());
+
+        discovery.add_path(&custom);
+        assert_eq!(discovery.paths(), [empty, custom]);
+        let found = discovery.find_best_kokoro_model().unwrap();
+        assert_eq!(found.model_file, model_dir.join("kokoro-v1.0.onnx"));
+    }
+
+    #[test]
+    fn test_glob_metacharacters_in_cache_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = temp_dir.path().join("models[v1]");
+        let model_dir = write_kokoro_model(&cache_dir, true);
+
+        let discovery = ModelDiscovery::with_paths(vec![cache_dir]);
+        let found = discovery.find_kokoro_models();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].model_file, model_dir.join("kokoro-v1.0.onnx"));
+    }
+
+    #[test]
+    fn test_discovery_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let incomplete = temp_dir.path().join("incomplete");
+        let complete = temp_dir.path().join("complete");
+        write_kokoro_model(&incomplete, false);
+        write_kokoro_model(&complete, true);
+
+        let discovery = ModelDiscovery::with_paths(vec![incomplete.clone(), complete.clone()]);
+        let report = discovery.report();
+        assert_eq!(report.len(), 4);
+
+        let entry = |cache_dir: &Path| {
+            let dir = cache_dir.join("models--direct_download").join("local");
+            report.iter().find(|e| e.directory == dir).unwrap().clone()
+        };
+        let rejected = entry(&incomplete);
+        assert!(!rejected.qualified && !rejected.selected);
+        assert_eq!(rejected.files.len(), 2);
+        assert!(rejected.reason.unwrap().contains("voices-v1.0.bin"));
+
+        let accepted = entry(&complete);
+        assert!(accepted.qualified && accepted.selected);
+        assert_eq!(accepted.files.len(), 3);
+        assert_eq!(accepted.reason, None);
+
+        let missing = &report[0];
+        assert_eq!(missing.reason.as_deref(), Some("Directory does not exist"));
+
+        // The report agrees with what discovery actually picks
+        let best = discovery.find_best_kokoro_model().unwrap();
+        assert_eq!(best.base_directory(), accepted.directory);
+    }
+
+    #[test]
+    fn test_kokoro_files_from_model_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let model = temp_dir.path().join("kokoro-v1.0.onnx");
+        std::fs::write(&model, vec![8u8; 2_000_000]).unwrap();
+
+        let error = KokoroModelFiles::from_model_file(&model).unwrap_err();
+        assert!(error.to_string().contains("Incomplete Kokoro model directory"));
+
+        std::fs::write(temp_dir.path().join("voices.bin"), b"voices").unwrap();
+        let files = KokoroModelFiles::from_model_file(&model).unwrap();
+        assert_eq!(files.voices_file, Some(temp_dir.path().join("voices.bin")));
+        assert_eq!(files.tokenizer_file, None);
+        assert!(files.is_complete());
+    }
+}
@@ -8,6 +8,6 @@ pub mod manager;
 /// Model types and enums
 pub mod types;
 
-pub use discovery::{ModelDiscovery, KokoroModelFiles, ModelManifest};
+pub use discovery::{ModelDiscovery, DiscoveryEntry, KokoroModelFiles, ModelManifest, validate_onnx_file};
 pub use manager::ModelManager;
 pub use types::{ModelId, ModelInfo, ModelConfig};
\ No newline at end of file
@@ -49,6 +49,22 @@ pub struct ModelInfo {
     pub repo_id: String,
     /// Required model files
     pub files: Vec<String>,
+    /// Number of entries in the token embedding table; valid token IDs
+    /// are in `0..vocab_size`
+    pub vocab_size: usize,
+    /// Names the model's audio output may have, in order of preference;
+    /// other outputs (e.g. predicted durations) are ignored
+    pub audio_outputs: Vec<String>,
+    /// Expected fingerprint of the self-test output, for models whose
+    /// inference is deterministic; `None` skips the comparison
+    ///
+    /// Every built-in model leaves this `None` on purpose: ONNX Runtime
+    /// output differs between versions, execution providers and CPU
+    /// kernels, so a pinned fingerprint would fail healthy installs. The
+    /// remaining self-test checks still catch empty, silent and corrupt
+    /// output, and the measured fingerprint is reported for comparison.
+    #[serde(default)]
+    pub self_test_fingerprint: Option<u64>,
 }
 
 impl ModelInfo {
@@ -66,6 +82,10 @@ impl ModelInfo {
                 "kokoro-v1.0.onnx".to_string(),  // 2025 working model file
                 "voices-v1.0.bin".to_string(),   // Unified voice data
             ],
+            vocab_size: 178,
+            audio_outputs: vec!["audio".to_string(), "waveform".to_string()],
+            // Intentionally unpinned, see `self_test_fingerprint`
+            self_test_fingerprint: None,
         }
     }
     
@@ -83,6 +103,9 @@ impl ModelInfo {
                 "model.onnx".to_string(),
                 "tokenizer.json".to_string(),
             ],
+            vocab_size: 704,
+            audio_outputs: vec!["audio".to_string(), "output".to_string()],
+            self_test_fingerprint: None,
         }
     }
     
@@ -101,6 +124,9 @@ impl ModelInfo {
                 "config.json".to_string(),
                 "tokenizer.json".to_string(),
             ],
+            vocab_size: 256,
+            audio_outputs: vec!["audio".to_string(), "output".to_string()],
+            self_test_fingerprint: None,
         }
     }
 }
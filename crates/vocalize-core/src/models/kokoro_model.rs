@@ -23,6 +23,8 @@ pub struct KokoroModel {
     onnx_engine: Option<Arc<Mutex<OnnxTtsEngine>>>,
     /// Cache directory for model files
     cache_dir: PathBuf,
+    /// Explicit ONNX file to load instead of the one in the cache
+    model_file: Option<PathBuf>,
 }
 
 impl KokoroModel {
@@ -34,11 +36,29 @@ impl KokoroModel {
             loaded: false,
             onnx_engine: None,
             cache_dir,
+            model_file: None,
+        }
+    }
+    
+    /// Create a Kokoro model that loads exactly `model_file`
+    ///
+    /// The voices file is expected in the same directory.
+    pub fn from_model_file(model_file: PathBuf) -> Self {
+        let cache_dir = model_file.parent().map(PathBuf::from).unwrap_or_default();
+        Self {
+            model_file: Some(model_file),
+            ..Self::new(cache_dir)
         }
     }
     
     /// Get the path to the cached model files
     fn get_model_paths(&self) -> VocalizeResult<(PathBuf, PathBuf)> {
+        if let Some(model_file) = &self.model_file {
+            let files = crate::model::KokoroModelFiles::from_model_file(model_file)?;
+            let voices_file = files.voices_file.unwrap_or_default();
+            return Ok((files.model_file, voices_file));
+        }
+        
         let model_dir = self.cache_dir
             .join("models--direct_download")
             .join("local");
@@ -83,7 +103,7 @@ impl TtsModel for KokoroModel {
         tracing::info!("Loading Kokoro TTS model from cache");
         
         // Validate model files exist
-        let (_model_file, _voices_file) = self.get_model_paths()?;
+        let (model_file, voices_file) = self.get_model_paths()?;
         
         // Create a runtime for async operations
         let rt = tokio::runtime::Runtime::new()
@@ -96,7 +116,12 @@ impl TtsModel for KokoroModel {
         
         // Load the Kokoro model
         rt.block_on(async {
-            onnx_engine.load_model(ModelId::Kokoro).await
+            if self.model_file.is_some() {
+                onnx_engine.set_voices_file(voices_file);
+                onnx_engine.load_model_from_path(ModelId::Kokoro, &model_file).await
+            } else {
+                onnx_engine.load_model(ModelId::Kokoro).await
+            }
         }).map_err(|e| VocalizeError::synthesis(&format!("Failed to load Kokoro model: {}", e)))?;
         
         // Store the loaded engine
@@ -0,0 +1,280 @@
+//! Deterministic mock TTS model for integration testing
+//!
+//! Generates a reproducible tone-plus-noise signal whose length is
+//! proportional to the input text, so engine tests can exercise the full
+//! synthesis path without downloading the Kokoro model. Only compiled for
+//! tests or with the `test-model` feature.
+
+use crate::models::TtsModel;
+use crate::{AudioData, SynthesisParams, VocalizeError, VocalizeResult};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+
+/// Model ID under which the mock model is registered
+pub const MOCK_MODEL_ID: &str = "mock";
+
+/// Samples generated per input character at normal speed (10ms at 24 kHz)
+pub const MOCK_SAMPLES_PER_CHAR: usize = 240;
+
+/// Deterministic mock TTS model
+#[derive(Debug)]
+pub struct MockTtsModel {
+    /// Whether the model is currently loaded
+    loaded: bool,
+    /// Output sample rate
+    sample_rate: u32,
+    /// Artificial time spent in each `synthesize` call
+    latency: std::time::Duration,
+    /// Number of `synthesize` calls, shared with [`call_counter`](Self::call_counter)
+    calls: Arc<AtomicUsize>,
+    /// Number of `synthesize` calls currently running
+    active: Arc<AtomicUsize>,
+    /// Most `synthesize` calls seen running at once, shared with
+    /// [`peak_concurrency`](Self::peak_concurrency)
+    peak: Arc<AtomicUsize>,
+    /// Barrier every synthesis waits on before producing audio
+    barrier: Option<Arc<Barrier>>,
+    /// Return no samples, simulating a model that emits an empty tensor
+    empty_output: bool,
+    /// Voice IDs reported by `supported_voices`
+    voices: Vec<String>,
+    /// Fail any synthesis whose text contains this marker
+    fail_on: Option<String>,
+}
+
+impl MockTtsModel {
+    /// Create a new mock model producing audio at the default sample rate
+    pub fn new() -> Self {
+        Self {
+            loaded: false,
+            sample_rate: crate::DEFAULT_SAMPLE_RATE,
+            latency: std::time::Duration::ZERO,
+            calls: Arc::new(AtomicUsize::new(0)),
+            active: Arc::new(AtomicUsize::new(0)),
+            peak: Arc::new(AtomicUsize::new(0)),
+            barrier: None,
+            empty_output: false,
+            voices: vec!["mock_female".to_string(), "mock_male".to_string()],
+            fail_on: None,
+        }
+    }
+
+    /// Create a mock model offering `voices` instead of the default pair,
+    /// for exercising voice selection
+    pub fn with_voices(voices: &[&str]) -> Self {
+        Self {
+            voices: voices.iter().map(ToString::to_string).collect(),
+            ..Self::new()
+        }
+    }
+
+    /// Create a mock model that blocks for `latency` on every synthesis,
+    /// for exercising concurrency limits and timeouts
+    pub fn with_latency(latency: std::time::Duration) -> Self {
+        Self {
+            latency,
+            ..Self::new()
+        }
+    }
+
+    /// Create a mock model whose every synthesis waits on `barrier`, so
+    /// tests can hold calls in flight or require them to overlap
+    pub fn with_barrier(barrier: Arc<Barrier>) -> Self {
+        Self {
+            barrier: Some(barrier),
+            ..Self::new()
+        }
+    }
+
+    /// Create a mock model that succeeds but produces no samples, for
+    /// exercising handling of degenerate model output
+    pub fn with_empty_output() -> Self {
+        Self {
+            empty_output: true,
+            ..Self::new()
+        }
+    }
+
+    /// Create a mock model that fails to synthesize any text containing
+    /// `marker`, for exercising per-request error handling
+    pub fn with_failure_on(marker: &str) -> Self {
+        Self {
+            fail_on: Some(marker.to_string()),
+            ..Self::new()
+        }
+    }
+
+    /// Handle to the number of `synthesize` calls made on this model,
+    /// which stays readable after the model is boxed into a registry
+    pub fn call_counter(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.calls)
+    }
+
+    /// Handle to the most `synthesize` calls that have run at once on this
+    /// model
+    pub fn peak_concurrency(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.peak)
+    }
+
+    /// Number of samples produced for `text` at the given speed
+    pub fn expected_len(text: &str, speed: f32) -> usize {
+        let samples = text.chars().count() * MOCK_SAMPLES_PER_CHAR;
+        (samples as f32 / speed).ceil() as usize
+    }
+}
+
+impl Default for MockTtsModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// FNV-1a hash, stable across runs and platforms
+fn stable_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+impl TtsModel for MockTtsModel {
+    fn model_id(&self) -> &str {
+        MOCK_MODEL_ID
+    }
+
+    fn model_name(&self) -> &str {
+        "Mock TTS"
+    }
+
+    fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+
+    fn load(&mut self) -> VocalizeResult<()> {
+        self.loaded = true;
+        Ok(())
+    }
+
+    fn unload(&mut self) {
+        self.loaded = false;
+    }
+
+    fn synthesize(&self, text: &str, voice_id: &str, params: &SynthesisParams) -> VocalizeResult<AudioData> {
+        if !self.is_loaded() {
+            return Err(VocalizeError::synthesis("Mock model is not loaded"));
+        }
+        if text.is_empty() {
+            return Err(VocalizeError::invalid_input("Text cannot be empty"));
+        }
+
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(active, Ordering::SeqCst);
+        if let Some(barrier) = &self.barrier {
+            barrier.wait();
+        }
+        if !self.latency.is_zero() {
+            std::thread::sleep(self.latency);
+        }
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        if self.fail_on.as_deref().is_some_and(|marker| text.contains(marker)) {
+            return Err(VocalizeError::synthesis(format!("Mock model cannot synthesize {text:?}")));
+        }
+
+        if self.empty_output {
+            return Ok(AudioData::new());
+        }
+
+        // Each voice gets its own tone; pitch shifts it by up to an octave
+        let base_frequency = 110.0 + (stable_hash(voice_id.as_bytes()) % 220) as f32;
+        let frequency = base_frequency * 2.0_f32.powf(params.pitch);
+        let step = std::f32::consts::TAU * frequency / self.sample_rate as f32;
+
+        // Noise is seeded from the text so identical requests give identical audio
+        let mut seed = stable_hash(text.as_bytes()) | 1;
+        let len = Self::expected_len(text, params.speed);
+
+        Ok((0..len)
+            .map(|i| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                let noise = (seed >> 40) as f32 / (1_u64 << 24) as f32 - 0.5;
+                0.5 * (step * i as f32).sin() + 0.05 * noise
+            })
+            .collect())
+    }
+
+    fn supported_voices(&self) -> Vec<String> {
+        self.voices.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Gender, Voice, VoiceStyle};
+
+    fn params() -> SynthesisParams {
+        SynthesisParams::new(Voice::new(
+            "mock_female".to_string(),
+            "Mock Female".to_string(),
+            "en-US".to_string(),
+            Gender::Female,
+            VoiceStyle::Natural,
+        ))
+    }
+
+    #[test]
+    fn test_mock_model_requires_load() {
+        let mut model = MockTtsModel::new();
+        assert!(model.synthesize("Hello", "mock_female", &params()).is_err());
+
+        model.load().unwrap();
+        assert!(model.is_loaded());
+        assert!(model.synthesize("Hello", "mock_female", &params()).is_ok());
+
+        model.unload();
+        assert!(!model.is_loaded());
+    }
+
+    #[test]
+    fn test_mock_model_counts_calls() {
+        let mut model = MockTtsModel::new();
+        let calls = model.call_counter();
+        model.load().unwrap();
+
+        model.synthesize("Hello", "mock_female", &params()).unwrap();
+        model.synthesize("Hello", "mock_female", &params()).unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_mock_model_is_deterministic() {
+        let mut model = MockTtsModel::new();
+        model.load().unwrap();
+
+        let first = model.synthesize("Hello world", "mock_female", &params()).unwrap();
+        let second = model.synthesize("Hello world", "mock_female", &params()).unwrap();
+        let other_voice = model.synthesize("Hello world", "mock_male", &params()).unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first, other_voice);
+        assert!(first.iter().all(|&sample| sample.abs() <= 1.0));
+    }
+
+    #[test]
+    fn test_mock_model_length_proportional_to_text() {
+        let mut model = MockTtsModel::new();
+        model.load().unwrap();
+
+        let short = model.synthesize("Hi", "mock_female", &params()).unwrap();
+        let long = model.synthesize("Hi there", "mock_female", &params()).unwrap();
+        assert_eq!(short.len(), 2 * MOCK_SAMPLES_PER_CHAR);
+        assert_eq!(long.len(), 8 * MOCK_SAMPLES_PER_CHAR);
+
+        let fast = model
+            .synthesize("Hi there", "mock_female", &params().with_speed(2.0).unwrap())
+            .unwrap();
+        assert_eq!(fast.len(), 4 * MOCK_SAMPLES_PER_CHAR);
+    }
+}
@@ -10,6 +10,8 @@ use crate::error::{VocalizeError, VocalizeResult};
 use crate::{SynthesisParams, AudioData};
 
 pub mod kokoro_model;
+#[cfg(any(test, feature = "test-model"))]
+pub mod mock_model;
 
 /// Information about a TTS model
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -22,6 +24,9 @@ pub struct ModelInfo {
     pub version: String,
     /// Model size in bytes
     pub size: usize,
+    /// Measured on-disk size of `install_path` in bytes
+    #[serde(default)]
+    pub disk_size: u64,
     /// Download URL for the model
     pub download_url: String,
     /// License type (e.g., "MIT", "Apache-2.0")
@@ -34,6 +39,53 @@ pub struct ModelInfo {
     pub supported_languages: Vec<String>,
     /// Supported voice IDs
     pub supported_voices: Vec<String>,
+    /// Sample rate of the audio the model produces, in Hz
+    #[serde(default = "default_output_sample_rate")]
+    pub output_sample_rate: u32,
+}
+
+/// Memory an ONNX session needs beyond the model weights, for its runtime
+/// arenas, optimized graph and intermediate tensors
+pub const SESSION_OVERHEAD_BYTES: usize = 64 * 1024 * 1024;
+
+/// Output rate assumed for registry entries written before it was recorded
+const fn default_output_sample_rate() -> u32 {
+    crate::DEFAULT_SAMPLE_RATE
+}
+
+impl ModelInfo {
+    /// Measure the actual on-disk size of the model installation
+    ///
+    /// Sums the sizes of all files under `install_path`. Returns 0 if the
+    /// path does not exist.
+    #[must_use]
+    pub fn measure_disk_size(&self) -> u64 {
+        directory_size(&self.install_path)
+    }
+}
+
+/// Recursively sum the sizes of all files under a directory
+fn directory_size(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+
+    if metadata.is_file() {
+        return metadata.len();
+    }
+
+    if !metadata.is_dir() {
+        return 0;
+    }
+
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| directory_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
 }
 
 /// Trait that all TTS models must implement
@@ -76,11 +128,32 @@ pub trait TtsModel: Send + Sync + std::fmt::Debug {
     fn supported_voices(&self) -> Vec<String>;
 }
 
+/// Result of cross-checking a model's declared voices against its voices file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoiceAudit {
+    /// Combined voices file that was parsed
+    pub voices_file: PathBuf,
+    /// Voice IDs found in the voices file, in file order
+    pub file_voices: Vec<String>,
+    /// Voices the registry declares but the voices file lacks
+    pub missing: Vec<String>,
+    /// Voices in the voices file the registry does not declare
+    pub extra: Vec<String>,
+}
+
+impl VoiceAudit {
+    /// Whether the declared voices and the voices file agree exactly
+    #[must_use]
+    pub fn is_consistent(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty()
+    }
+}
+
 /// Registry for managing installed and available TTS models
 #[derive(Debug)]
 pub struct ModelRegistry {
     /// Currently installed models
-    installed_models: HashMap<String, ModelInfo>,
+    pub(crate) installed_models: HashMap<String, ModelInfo>,
     /// Currently loaded models in memory
     pub loaded_models: HashMap<String, Box<dyn TtsModel>>,
     /// The currently active model ID
@@ -89,6 +162,30 @@ pub struct ModelRegistry {
     registry_path: PathBuf,
     /// Base directory for model storage
     cache_dir: PathBuf,
+    /// Refuse to download missing models
+    offline: bool,
+    /// Kokoro ONNX file to load instead of the discovered one
+    model_path_override: Option<PathBuf>,
+    /// Directories discovery searches instead of the standard locations
+    search_paths: Option<Vec<PathBuf>>,
+    /// Installs that ran to completion rather than being coalesced
+    #[cfg(test)]
+    pub(crate) installs_performed: usize,
+    /// Model instances created and loaded by [`Self::load_model`]
+    #[cfg(test)]
+    pub(crate) models_loaded: usize,
+}
+
+/// Lock serializing installs into `install_path` across the process
+fn install_lock(install_path: &Path) -> std::sync::Arc<tokio::sync::Mutex<()>> {
+    static INSTALL_LOCKS: std::sync::OnceLock<parking_lot::Mutex<HashMap<PathBuf, std::sync::Arc<tokio::sync::Mutex<()>>>>> =
+        std::sync::OnceLock::new();
+    INSTALL_LOCKS
+        .get_or_init(Default::default)
+        .lock()
+        .entry(install_path.to_path_buf())
+        .or_default()
+        .clone()
 }
 
 impl ModelRegistry {
@@ -98,6 +195,51 @@ impl ModelRegistry {
     /// 
     /// * `cache_dir` - Directory where models will be stored
     pub fn new(cache_dir: &Path) -> VocalizeResult<Self> {
+        let mut registry = Self::load(cache_dir)?;
+        
+        // Auto-detect cached Kokoro model from Python downloads
+        registry.detect_cached_kokoro_model()?;
+        
+        Ok(registry)
+    }
+    
+    /// Create a registry that discovers existing Kokoro installs only under
+    /// `search_paths`, in priority order
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in the same cases as [`Self::new`].
+    pub fn with_search_paths(cache_dir: &Path, search_paths: Vec<PathBuf>) -> VocalizeResult<Self> {
+        let mut registry = Self::load(cache_dir)?;
+        registry.search_paths = Some(search_paths);
+        registry.detect_cached_kokoro_model()?;
+        Ok(registry)
+    }
+    
+    /// Create a registry that uses exactly the given Kokoro ONNX file
+    ///
+    /// Model discovery is skipped entirely, and the override is not written
+    /// to the registry file, so it only affects this registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `model_file` is not a valid ONNX file or has no
+    /// voices file next to it.
+    pub fn with_model_path_override(cache_dir: &Path, model_file: &Path) -> VocalizeResult<Self> {
+        let kokoro_files = crate::model::KokoroModelFiles::from_model_file(model_file)?;
+        let manifest = crate::model::ModelDiscovery::new().create_manifest_for_model(&kokoro_files);
+        
+        let mut registry = Self::load(cache_dir)?;
+        let kokoro_info = registry.kokoro_model_info(&kokoro_files, &manifest);
+        registry.installed_models.insert("kokoro".to_string(), kokoro_info);
+        registry.model_path_override = Some(kokoro_files.model_file);
+        
+        tracing::info!("Using Kokoro model override: {}", model_file.display());
+        Ok(registry)
+    }
+    
+    /// Create the registry and load any saved entries, without discovery
+    fn load(cache_dir: &Path) -> VocalizeResult<Self> {
         let registry_path = cache_dir.join("models.json");
         let cache_dir = cache_dir.to_path_buf();
         
@@ -110,16 +252,26 @@ impl ModelRegistry {
             active_model: None,
             registry_path,
             cache_dir,
+            offline: false,
+            model_path_override: None,
+            search_paths: None,
+            #[cfg(test)]
+            installs_performed: 0,
+            #[cfg(test)]
+            models_loaded: 0,
         };
         
         // Load existing registry if it exists
         registry.load_registry()?;
         
-        // Auto-detect cached Kokoro model from Python downloads
-        registry.detect_cached_kokoro_model()?;
-        
         Ok(registry)
     }
+    
+    /// Kokoro ONNX file this registry was forced to use, if any
+    #[must_use]
+    pub fn model_path_override(&self) -> Option<&Path> {
+        self.model_path_override.as_deref()
+    }
 
     
     /// Get the list of all available models that can be installed
@@ -130,6 +282,7 @@ impl ModelRegistry {
                 name: "Kokoro TTS".to_string(),
                 version: "v1.0".to_string(),
                 size: 410_000_000, // ~410MB (310MB model + 26MB voices)
+                disk_size: 0,
                 download_url: "direct_download".to_string(), // Managed by Python
                 license: "Apache 2.0".to_string(),
                 installed: false,
@@ -150,6 +303,25 @@ impl ModelRegistry {
                     "bf_alice".to_string(),
                     "bm_daniel".to_string(),
                 ],
+                output_sample_rate: 24_000,
+            },
+            #[cfg(any(test, feature = "test-model"))]
+            ModelInfo {
+                id: mock_model::MOCK_MODEL_ID.to_string(),
+                name: "Mock TTS".to_string(),
+                version: "v1.0".to_string(),
+                size: 0,
+                disk_size: 0,
+                download_url: "builtin".to_string(), // Generated in-process, nothing to download
+                license: "Apache 2.0".to_string(),
+                installed: false,
+                install_path: PathBuf::new(),
+                supported_languages: vec!["en-US".to_string()],
+                supported_voices: vec![
+                    "mock_female".to_string(),
+                    "mock_male".to_string(),
+                ],
+                output_sample_rate: crate::DEFAULT_SAMPLE_RATE,
             },
         ]
     }
@@ -173,6 +345,21 @@ impl ModelRegistry {
             .ok_or_else(|| VocalizeError::synthesis("Active model not loaded"))
     }
     
+    /// Get the currently active model for read-only use
+    /// 
+    /// # Errors
+    /// 
+    /// Returns an error if no model is active or if the active model
+    /// is not loaded.
+    pub fn active_model(&self) -> VocalizeResult<&dyn TtsModel> {
+        let active_id = self.active_model.as_ref()
+            .ok_or_else(|| VocalizeError::synthesis("No active TTS model"))?;
+            
+        self.loaded_models.get(active_id)
+            .map(AsRef::as_ref)
+            .ok_or_else(|| VocalizeError::synthesis("Active model not loaded"))
+    }
+    
     /// Load the registry from disk
     fn load_registry(&mut self) -> VocalizeResult<()> {
         if self.registry_path.exists() {
@@ -184,6 +371,16 @@ impl ModelRegistry {
         Ok(())
     }
     
+    /// `model_id`'s entry in the registry file, if it is installed there
+    fn saved_entry(&self, model_id: &str) -> VocalizeResult<Option<ModelInfo>> {
+        if !self.registry_path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&self.registry_path)?;
+        let mut saved: HashMap<String, ModelInfo> = serde_json::from_str(&content).unwrap_or_default();
+        Ok(saved.remove(model_id).filter(|model| model.installed && model.install_path.exists()))
+    }
+    
     /// Save the registry to disk
     fn save_registry(&self) -> VocalizeResult<()> {
         let content = serde_json::to_string_pretty(&self.installed_models)?;
@@ -197,6 +394,13 @@ impl ModelRegistry {
     /// 
     /// Returns an error if the model ID is not found in available models,
     /// if the download fails, or if the installation process fails.
+    ///
+    /// Installing a model that is already installed does nothing. Installs
+    /// of the same model into the same cache directory are serialized across
+    /// every registry in the process, so concurrent callers wait for the
+    /// first install and then find the model installed (via the registry
+    /// file, if another registry installed it) instead of downloading it
+    /// again.
     pub async fn install_model(&mut self, model_id: &str) -> VocalizeResult<()> {
         let available_models = Self::get_available_models();
         let model_info = available_models
@@ -205,10 +409,40 @@ impl ModelRegistry {
             .ok_or_else(|| VocalizeError::model_not_found(model_id))?;
         
         let install_path = self.cache_dir.join("models").join(model_id);
+        let install_lock = install_lock(&install_path);
+        let _install_guard = install_lock.lock().await;
+        if self.installed_models.get(model_id).is_some_and(|model| model.install_path.exists()) {
+            tracing::debug!("Model '{}' is already installed", model_id);
+            return Ok(());
+        }
+        if let Some(saved) = self.saved_entry(model_id)? {
+            tracing::info!("Model '{}' was installed by another registry; skipping download", model_id);
+            self.installed_models.insert(model_id.to_string(), saved);
+            return Ok(());
+        }
+        
+        if self.offline && model_info.download_url != "builtin" {
+            // Pick up files copied in by hand since the registry was created
+            if model_id == "kokoro" {
+                self.installed_models.remove(model_id);
+                self.detect_cached_kokoro_model()?;
+                if self.installed_models.contains_key(model_id) {
+                    return Ok(());
+                }
+            }
+            return Err(self.offline_install_error(model_id));
+        }
         std::fs::create_dir_all(&install_path)?;
         
-        // Download model (placeholder implementation)
-        self.download_model(&model_info.download_url, &install_path).await?;
+        // Download model (placeholder implementation); builtin models have no files
+        if model_info.download_url != "builtin" {
+            self.download_model(&model_info.download_url, &install_path).await?;
+        }
+        
+        #[cfg(test)]
+        {
+            self.installs_performed += 1;
+        }
         
         // Update registry
         let mut installed_info = model_info;
@@ -222,6 +456,46 @@ impl ModelRegistry {
         Ok(())
     }
     
+    /// Error for a model that offline mode refuses to download, telling the
+    /// user where discovery will find the files if they copy them in by hand
+    fn offline_install_error(&self, model_id: &str) -> VocalizeError {
+        let manual_install = match self.discovery().manual_install_dir() {
+            Some(dir) => format!(
+                "Install it manually by copying {} into {}",
+                crate::model::discovery::EXACT_KOKORO_FILES.join(", "),
+                dir.display()
+            ),
+            None => "Install it manually".to_string(),
+        };
+        VocalizeError::network(format!(
+            "Offline mode is enabled, so model '{model_id}' will not be downloaded. \
+             {manual_install} (run 'vocalize models download {model_id}' on a machine \
+             with network access to get them), or disable offline mode."
+        ))
+    }
+    
+    /// Discovery over the configured search paths, or the standard locations
+    fn discovery(&self) -> crate::model::ModelDiscovery {
+        match &self.search_paths {
+            Some(paths) => crate::model::ModelDiscovery::with_paths(paths.clone()),
+            None => crate::model::ModelDiscovery::new(),
+        }
+    }
+    
+    /// Enable or disable offline mode
+    ///
+    /// In offline mode [`install_model`](Self::install_model) fails for any
+    /// model that would need to be downloaded.
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+    }
+
+    /// Whether offline mode is enabled
+    #[must_use]
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+    
     /// Remove an installed model
     /// 
     /// # Errors
@@ -285,14 +559,24 @@ impl ModelRegistry {
         let mut model: Box<dyn TtsModel> = match model_id {
             "kokoro" => {
                 use crate::models::kokoro_model::KokoroModel;
-                Box::new(KokoroModel::new(self.cache_dir.clone()))
+                match &self.model_path_override {
+                    Some(model_file) => Box::new(KokoroModel::from_model_file(model_file.clone())),
+                    None => Box::new(KokoroModel::new(self.cache_dir.clone())),
+                }
             },
+            #[cfg(any(test, feature = "test-model"))]
+            mock_model::MOCK_MODEL_ID => Box::new(mock_model::MockTtsModel::new()),
             _ => return Err(VocalizeError::model(format!("Unknown model type: {}", model_id))),
         };
         
         // Load the model
         model.load()?;
         
+        #[cfg(test)]
+        {
+            self.models_loaded += 1;
+        }
+        
         // Add to loaded models
         self.loaded_models.insert(model_id.to_string(), model);
         
@@ -305,11 +589,75 @@ impl ModelRegistry {
         Ok(())
     }
     
+    /// Unload a model and load it again from disk
+    ///
+    /// The registry entry is refreshed first (re-running discovery, or
+    /// re-reading the override file), so replaced or re-downloaded model
+    /// files are picked up. The model keeps its active status.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model is not installed, its files can no
+    /// longer be found, or it fails to load.
+    pub fn reload_model(&mut self, model_id: &str) -> VocalizeResult<()> {
+        self.refresh_model_info(model_id)?;
+        
+        if let Some(mut model) = self.loaded_models.remove(model_id) {
+            model.unload();
+        }
+        
+        tracing::info!("Reloading model '{}' from disk", model_id);
+        self.load_model(model_id)
+    }
+    
+    /// Re-run discovery for an installed model's files and update its entry
+    fn refresh_model_info(&mut self, model_id: &str) -> VocalizeResult<()> {
+        if !self.installed_models.contains_key(model_id) {
+            return Err(VocalizeError::model_not_found(model_id));
+        }
+        if model_id != "kokoro" {
+            return Ok(());
+        }
+        
+        if let Some(model_file) = self.model_path_override.clone() {
+            let kokoro_files = crate::model::KokoroModelFiles::from_model_file(&model_file)?;
+            let manifest = crate::model::ModelDiscovery::new().create_manifest_for_model(&kokoro_files);
+            let kokoro_info = self.kokoro_model_info(&kokoro_files, &manifest);
+            self.installed_models.insert("kokoro".to_string(), kokoro_info);
+            return Ok(());
+        }
+        
+        let previous = self.installed_models.remove("kokoro");
+        self.detect_cached_kokoro_model()?;
+        if !self.installed_models.contains_key("kokoro") {
+            if let Some(previous) = previous {
+                self.installed_models.insert("kokoro".to_string(), previous);
+            }
+            return Err(VocalizeError::model(
+                "Kokoro model files were not found during rediscovery; \
+                 run 'vocalize models download kokoro' to reinstall"
+            ));
+        }
+        Ok(())
+    }
+    
     /// Get list of installed models
     pub fn get_installed_models(&self) -> Vec<&ModelInfo> {
         self.installed_models.values().collect()
     }
     
+    /// Get list of installed models with `disk_size` measured from disk
+    pub fn get_installed_models_with_disk_size(&self) -> Vec<ModelInfo> {
+        self.installed_models
+            .values()
+            .map(|model| {
+                let mut model = model.clone();
+                model.disk_size = model.measure_disk_size();
+                model
+            })
+            .collect()
+    }
+    
     /// Check if a model is installed
     pub fn is_model_installed(&self, model_id: &str) -> bool {
         self.installed_models.contains_key(model_id)
@@ -319,6 +667,83 @@ impl ModelRegistry {
     pub fn is_model_loaded(&self, model_id: &str) -> bool {
         self.loaded_models.contains_key(model_id)
     }
+
+    /// Cross-check a model's declared voices against its voices file
+    ///
+    /// Parses `voices-v1.0.bin` (NPZ or the combined binary format) in the
+    /// model's install directory and compares it with
+    /// [`ModelInfo::supported_voices`]. Voices declared but absent
+    /// from the file would fail at synthesis time; voices present but not
+    /// declared are usable yet hidden from voice listings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model is not installed, or its voices file is
+    /// missing or corrupt
+    pub fn verify_voices(&self, model_id: &str) -> VocalizeResult<VoiceAudit> {
+        let model = self.installed_models
+            .get(model_id)
+            .ok_or_else(|| VocalizeError::model_not_found(model_id))?;
+
+        let voices_file = model.install_path.join("voices-v1.0.bin");
+        if !voices_file.is_file() {
+            return Err(VocalizeError::file(format!(
+                "Model '{}' has no voices file at {}",
+                model_id,
+                voices_file.display()
+            )));
+        }
+        let file_voices = crate::onnx_engine::list_combined_voices(&voices_file)?;
+
+        let missing = model.supported_voices
+            .iter()
+            .filter(|voice| !file_voices.contains(voice))
+            .cloned()
+            .collect();
+        let extra = file_voices
+            .iter()
+            .filter(|voice| !model.supported_voices.contains(voice))
+            .cloned()
+            .collect();
+
+        Ok(VoiceAudit { voices_file, file_voices, missing, extra })
+    }
+
+    /// Estimate the peak RAM, in bytes, needed to load an installed model
+    ///
+    /// Assumes the engine's default session pool size; see
+    /// [`estimate_load_memory_with_pool`](Self::estimate_load_memory_with_pool).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model is not installed
+    pub fn estimate_load_memory(&self, model_id: &str) -> VocalizeResult<usize> {
+        self.estimate_load_memory_with_pool(model_id, crate::onnx_engine::default_session_pool_size())
+    }
+
+    /// Estimate the peak RAM, in bytes, needed to load a model into a pool
+    /// of `pool_size` sessions
+    ///
+    /// Every session holds its own copy of the weights, so the estimate is
+    /// `pool_size * (model size + SESSION_OVERHEAD_BYTES)`. The model size
+    /// is measured on disk, falling back to the registry's declared size
+    /// when nothing is there yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model is not installed or `pool_size` is 0
+    pub fn estimate_load_memory_with_pool(&self, model_id: &str, pool_size: usize) -> VocalizeResult<usize> {
+        let model = self.installed_models
+            .get(model_id)
+            .ok_or_else(|| VocalizeError::model_not_found(model_id))?;
+        if pool_size == 0 {
+            return Err(VocalizeError::invalid_input("Pool size must be greater than 0"));
+        }
+
+        let measured = usize::try_from(model.measure_disk_size()).unwrap_or(usize::MAX);
+        let model_size = if measured > 0 { measured } else { model.size };
+        Ok(model_size.saturating_add(SESSION_OVERHEAD_BYTES).saturating_mul(pool_size))
+    }
     
     /// Download model from URL (handled by Python model manager)
     async fn download_model(&self, _url: &str, _install_path: &std::path::Path) -> VocalizeResult<()> {
@@ -339,7 +764,7 @@ impl ModelRegistry {
         }
         
         // Use smart model discovery
-        let discovery = crate::model::ModelDiscovery::new();
+        let discovery = self.discovery();
         
         if let Some(kokoro_files) = discovery.find_best_kokoro_model() {
             tracing::info!("🎯 Smart discovery found Kokoro model: {:?}", kokoro_files.model_file);
@@ -356,26 +781,7 @@ impl ModelRegistry {
             // Calculate model size
             let total_size = kokoro_files.total_size();
             
-            // Detect available voices
-            let supported_voices = self.detect_available_voices(&kokoro_files);
-            
-            let kokoro_info = ModelInfo {
-                id: "kokoro".to_string(),
-                name: manifest.description.clone().unwrap_or_else(|| "Kokoro TTS".to_string()),
-                version: manifest.version.clone(),
-                size: total_size as usize,
-                download_url: "auto-detected".to_string(),
-                license: manifest.license.clone(),
-                installed: true,
-                install_path: kokoro_files.base_directory().to_path_buf(),
-                supported_languages: vec![
-                    "en-US".to_string(), 
-                    "en-GB".to_string(),
-                    "ja-JP".to_string(),
-                    "zh-CN".to_string()
-                ],
-                supported_voices,
-            };
+            let kokoro_info = self.kokoro_model_info(&kokoro_files, &manifest);
             
             // Register the model
             self.installed_models.insert("kokoro".to_string(), kokoro_info);
@@ -397,6 +803,39 @@ impl ModelRegistry {
         Ok(())
     }
     
+    /// Registry entry for a Kokoro installation found on disk
+    fn kokoro_model_info(
+        &self,
+        kokoro_files: &crate::model::KokoroModelFiles,
+        manifest: &crate::model::ModelManifest,
+    ) -> ModelInfo {
+        // Calculate model size
+        let total_size = kokoro_files.total_size();
+        
+        // Detect available voices
+        let supported_voices = self.detect_available_voices(kokoro_files);
+        
+        ModelInfo {
+            id: "kokoro".to_string(),
+            name: manifest.description.clone().unwrap_or_else(|| "Kokoro TTS".to_string()),
+            version: manifest.version.clone(),
+            size: total_size as usize,
+            disk_size: total_size,
+            download_url: "auto-detected".to_string(),
+            license: manifest.license.clone(),
+            installed: true,
+            install_path: kokoro_files.base_directory().to_path_buf(),
+            supported_languages: vec![
+                "en-US".to_string(), 
+                "en-GB".to_string(),
+                "ja-JP".to_string(),
+                "zh-CN".to_string()
+            ],
+            supported_voices,
+            output_sample_rate: 24_000,
+        }
+    }
+    
     /// Detect available voices for a Kokoro model
     fn detect_available_voices(&self, kokoro_files: &crate::model::KokoroModelFiles) -> Vec<String> {
         let mut voices = Vec::new();
@@ -464,12 +903,14 @@ mod tests {
             name: "Test Model".to_string(),
             version: "v1".to_string(),
             size: 1000,
+            disk_size: 0,
             download_url: "http://example.com".to_string(),
             license: "MIT".to_string(),
             installed: true,
             install_path: PathBuf::from("/test/path"),
             supported_languages: vec!["en".to_string()],
             supported_voices: vec!["voice1".to_string()],
+            output_sample_rate: 24_000,
         };
         
         let json = serde_json::to_string(&model).unwrap();
@@ -478,6 +919,160 @@ mod tests {
         assert_eq!(model, deserialized);
     }
     
+    #[test]
+    fn test_model_output_sample_rates() {
+        let models = ModelRegistry::get_available_models();
+        let kokoro = models.iter().find(|m| m.id == "kokoro").unwrap();
+        assert_eq!(kokoro.output_sample_rate, 24_000);
+
+        // Registry entries saved before the field existed use the default
+        let mut json = serde_json::to_value(kokoro).unwrap();
+        json.as_object_mut().unwrap().remove("output_sample_rate");
+        let legacy: ModelInfo = serde_json::from_value(json).unwrap();
+        assert_eq!(legacy.output_sample_rate, crate::DEFAULT_SAMPLE_RATE);
+    }
+
+    #[test]
+    fn test_model_path_override_skips_discovery() {
+        let temp_dir = TempDir::new().unwrap();
+        let write_kokoro = |dir: &Path| {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(dir.join("kokoro-v1.0.onnx"), vec![8u8; 2_000_000]).unwrap();
+            std::fs::write(dir.join("voices-v1.0.bin"), b"voices").unwrap();
+        };
+
+        // A copy in the cache that discovery could otherwise pick up
+        let cache_dir = temp_dir.path().join("cache");
+        write_kokoro(&cache_dir.join("models--direct_download").join("local"));
+        let override_dir = temp_dir.path().join("pinned");
+        write_kokoro(&override_dir);
+        let model_file = override_dir.join("kokoro-v1.0.onnx");
+
+        let registry = ModelRegistry::with_model_path_override(&cache_dir, &model_file).unwrap();
+        assert_eq!(registry.model_path_override(), Some(model_file.as_path()));
+        assert_eq!(registry.installed_models["kokoro"].install_path, override_dir);
+        // The override is not persisted for later registries
+        assert!(!registry.registry_path.exists());
+
+        std::fs::remove_file(override_dir.join("voices-v1.0.bin")).unwrap();
+        let error = ModelRegistry::with_model_path_override(&cache_dir, &model_file).unwrap_err();
+        assert!(error.to_string().contains("Incomplete Kokoro model directory"));
+    }
+
+    #[test]
+    fn test_reload_model_refreshes_override_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let model_dir = temp_dir.path().join("pinned");
+        std::fs::create_dir_all(&model_dir).unwrap();
+        let model_file = model_dir.join("kokoro-v1.0.onnx");
+        std::fs::write(&model_file, vec![8u8; 2_000_000]).unwrap();
+        std::fs::write(model_dir.join("voices-v1.0.bin"), b"voices").unwrap();
+
+        let cache_dir = temp_dir.path().join("cache");
+        let mut registry = ModelRegistry::with_model_path_override(&cache_dir, &model_file).unwrap();
+        let original_size = registry.installed_models["kokoro"].disk_size;
+
+        // Replace the model file, as a re-download would
+        std::fs::write(&model_file, vec![8u8; 3_000_000]).unwrap();
+        registry.refresh_model_info("kokoro").unwrap();
+        assert_eq!(registry.installed_models["kokoro"].disk_size, original_size + 1_000_000);
+
+        std::fs::remove_file(model_dir.join("voices-v1.0.bin")).unwrap();
+        assert!(registry.reload_model("kokoro").is_err());
+        assert!(registry.reload_model("nonexistent").is_err());
+    }
+
+    #[test]
+    fn test_estimate_load_memory_scales_with_pool_and_model() {
+        let temp_dir = TempDir::new().unwrap();
+        let model_dir = temp_dir.path().join("pinned");
+        std::fs::create_dir_all(&model_dir).unwrap();
+        let model_file = model_dir.join("kokoro-v1.0.onnx");
+        std::fs::write(&model_file, vec![8u8; 2_000_000]).unwrap();
+        std::fs::write(model_dir.join("voices-v1.0.bin"), b"voices").unwrap();
+
+        let cache_dir = temp_dir.path().join("cache");
+        let registry = ModelRegistry::with_model_path_override(&cache_dir, &model_file).unwrap();
+        let model_size = registry.installed_models["kokoro"].measure_disk_size() as usize;
+
+        let one = registry.estimate_load_memory_with_pool("kokoro", 1).unwrap();
+        assert_eq!(one, model_size + SESSION_OVERHEAD_BYTES);
+        assert_eq!(registry.estimate_load_memory_with_pool("kokoro", 4).unwrap(), 4 * one);
+
+        // A bigger model needs proportionally more per session
+        std::fs::write(&model_file, vec![8u8; 5_000_000]).unwrap();
+        let bigger = registry.estimate_load_memory_with_pool("kokoro", 1).unwrap();
+        assert_eq!(bigger, one + 3_000_000);
+
+        assert!(registry.estimate_load_memory("kokoro").unwrap() >= bigger);
+        assert!(registry.estimate_load_memory_with_pool("kokoro", 0).is_err());
+        assert!(registry.estimate_load_memory("nonexistent").is_err());
+    }
+
+    /// Combined voices file holding a 256-float embedding per voice
+    fn write_combined_voices(path: &Path, voice_ids: &[&str]) {
+        let data_start = 4 + voice_ids.len() * 40;
+        let mut bytes = (voice_ids.len() as u32).to_le_bytes().to_vec();
+        for (index, voice_id) in voice_ids.iter().enumerate() {
+            let mut id = [0u8; 32];
+            id[..voice_id.len()].copy_from_slice(voice_id.as_bytes());
+            bytes.extend_from_slice(&id);
+            bytes.extend_from_slice(&((data_start + index * 1024) as u32).to_le_bytes());
+            bytes.extend_from_slice(&1024u32.to_le_bytes());
+        }
+        bytes.extend(std::iter::repeat(0.1f32.to_le_bytes()).take(256 * voice_ids.len()).flatten());
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    /// NPZ voices file, the format Kokoro ships, with an array per voice
+    fn write_npz_voices(path: &Path, voice_ids: &[&str]) {
+        use std::io::Write;
+
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+        for voice_id in voice_ids {
+            writer.start_file(format!("{voice_id}.npy"), zip::write::FileOptions::default()).unwrap();
+            writer.write_all(b"\x93NUMPY").unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_verify_voices_flags_missing_and_extra() {
+        let temp_dir = TempDir::new().unwrap();
+        let model_dir = temp_dir.path().join("pinned");
+        std::fs::create_dir_all(&model_dir).unwrap();
+        let model_file = model_dir.join("kokoro-v1.0.onnx");
+        std::fs::write(&model_file, vec![8u8; 2_000_000]).unwrap();
+        let voices_file = model_dir.join("voices-v1.0.bin");
+        write_combined_voices(&voices_file, &["af_heart", "af_alloy"]);
+
+        let cache_dir = temp_dir.path().join("cache");
+        let mut registry = ModelRegistry::with_model_path_override(&cache_dir, &model_file).unwrap();
+        registry.installed_models.get_mut("kokoro").unwrap().supported_voices =
+            vec!["af_heart".to_string(), "af_alloy".to_string()];
+        let audit = registry.verify_voices("kokoro").unwrap();
+        assert!(audit.is_consistent());
+        assert_eq!(audit.voices_file, voices_file);
+        assert_eq!(audit.file_voices, ["af_heart", "af_alloy"]);
+
+        // The file lacks a declared voice and carries an undeclared one
+        write_combined_voices(&voices_file, &["af_heart", "zf_extra"]);
+        let audit = registry.verify_voices("kokoro").unwrap();
+        assert!(!audit.is_consistent());
+        assert_eq!(audit.missing, ["af_alloy"]);
+        assert_eq!(audit.extra, ["zf_extra"]);
+
+        // Genuine downloads are NPZ archives
+        write_npz_voices(&voices_file, &["af_heart", "af_alloy"]);
+        let audit = registry.verify_voices("kokoro").unwrap();
+        assert!(audit.is_consistent());
+        assert_eq!(audit.file_voices, ["af_heart", "af_alloy"]);
+
+        std::fs::write(&voices_file, [1u8, 0]).unwrap();
+        assert!(registry.verify_voices("kokoro").is_err());
+        assert!(registry.verify_voices("nonexistent").is_err());
+    }
+
     #[test]
     fn test_registry_persistence() {
         let temp_dir = TempDir::new().unwrap();
@@ -491,12 +1086,14 @@ mod tests {
                 name: "Test".to_string(),
                 version: "v1".to_string(),
                 size: 1000,
+                disk_size: 0,
                 download_url: "http://example.com".to_string(),
                 license: "MIT".to_string(),
                 installed: true,
                 install_path: temp_dir.path().join("test"),
                 supported_languages: vec!["en".to_string()],
                 supported_voices: vec!["voice1".to_string()],
+                output_sample_rate: 24_000,
             };
             
             registry.installed_models.insert("test".to_string(), model);
@@ -512,6 +1109,50 @@ mod tests {
     }
     
     
+    #[tokio::test]
+    async fn test_concurrent_installs_across_registries_download_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut first = ModelRegistry::new(temp_dir.path()).unwrap();
+        let mut second = ModelRegistry::new(temp_dir.path()).unwrap();
+
+        let (a, b) = tokio::join!(
+            first.install_model(mock_model::MOCK_MODEL_ID),
+            second.install_model(mock_model::MOCK_MODEL_ID),
+        );
+        a.unwrap();
+        b.unwrap();
+
+        assert!(first.is_model_installed(mock_model::MOCK_MODEL_ID));
+        assert!(second.is_model_installed(mock_model::MOCK_MODEL_ID));
+        assert_eq!(first.installs_performed + second.installs_performed, 1);
+
+        // Installing again is a no-op
+        first.install_model(mock_model::MOCK_MODEL_ID).await.unwrap();
+        assert_eq!(first.installs_performed + second.installs_performed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_mock_install_and_removal_are_reflected() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut registry = ModelRegistry::new(temp_dir.path()).unwrap();
+        let installed_ids = |registry: &ModelRegistry| -> Vec<String> {
+            registry.get_installed_models_with_disk_size().into_iter().map(|m| m.id).collect()
+        };
+        assert!(!registry.is_model_installed(mock_model::MOCK_MODEL_ID));
+
+        registry.install_model(mock_model::MOCK_MODEL_ID).await.unwrap();
+        assert!(registry.is_model_installed(mock_model::MOCK_MODEL_ID));
+        assert_eq!(installed_ids(&registry), vec![mock_model::MOCK_MODEL_ID.to_string()]);
+        let install_path = registry.installed_models[mock_model::MOCK_MODEL_ID].install_path.clone();
+        assert!(install_path.starts_with(temp_dir.path()));
+
+        registry.remove_model(mock_model::MOCK_MODEL_ID).unwrap();
+        assert!(!registry.is_model_installed(mock_model::MOCK_MODEL_ID));
+        assert!(installed_ids(&registry).is_empty());
+        assert!(!install_path.exists());
+        assert!(registry.remove_model(mock_model::MOCK_MODEL_ID).is_err());
+    }
+
     #[tokio::test]
     async fn test_model_installation_invalid_model() {
         let temp_dir = TempDir::new().unwrap();
@@ -521,6 +1162,33 @@ mod tests {
         assert!(result.is_err());
         assert!(!registry.is_model_installed("nonexistent"));
     }
+
+    #[tokio::test]
+    async fn test_offline_install_advice_installs_model() {
+        let temp_dir = TempDir::new().unwrap();
+        let search_path = temp_dir.path().join("search");
+        let mut registry =
+            ModelRegistry::with_search_paths(&temp_dir.path().join("cache"), vec![search_path.clone()]).unwrap();
+        registry.set_offline(true);
+
+        let message = registry.install_model("kokoro").await.unwrap_err().to_string();
+        for name in crate::model::discovery::EXACT_KOKORO_FILES {
+            assert!(message.contains(name), "{message}");
+        }
+
+        // Follow the advice: copy the files into the directory it names
+        let advised_dir = message.split(" into ").nth(1).unwrap().split(" (run").next().unwrap();
+        let advised_dir = PathBuf::from(advised_dir);
+        assert!(advised_dir.starts_with(&search_path));
+        std::fs::create_dir_all(&advised_dir).unwrap();
+        std::fs::write(advised_dir.join("kokoro-v1.0.onnx"), vec![8u8; 2_000_000]).unwrap();
+        std::fs::write(advised_dir.join("voices-v1.0.bin"), b"voices").unwrap();
+        std::fs::write(advised_dir.join("tokenizer.json"), b"{}").unwrap();
+
+        registry.install_model("kokoro").await.unwrap();
+        assert!(registry.is_model_installed("kokoro"));
+        assert_eq!(registry.installed_models["kokoro"].install_path, advised_dir);
+    }
     
     #[test]
     fn test_model_removal() {
@@ -537,12 +1205,14 @@ mod tests {
             name: "Test Model".to_string(),
             version: "v1".to_string(),
             size: 1000,
+            disk_size: 0,
             download_url: "http://example.com".to_string(),
             license: "MIT".to_string(),
             installed: true,
             install_path: model_path.clone(),
             supported_languages: vec!["en".to_string()],
             supported_voices: vec!["voice1".to_string()],
+            output_sample_rate: 24_000,
         };
         
         registry.installed_models.insert("test_model".to_string(), model_info);
@@ -569,12 +1239,14 @@ mod tests {
             name: "Test Model".to_string(),
             version: "v1".to_string(),
             size: 1000,
+            disk_size: 0,
             download_url: "http://example.com".to_string(),
             license: "MIT".to_string(),
             installed: true,
             install_path: PathBuf::from("/test/path"),
             supported_languages: vec!["en".to_string()],
             supported_voices: vec!["voice1".to_string()],
+            output_sample_rate: 24_000,
         };
         
         registry.installed_models.insert("test_model".to_string(), model_info);
@@ -600,12 +1272,14 @@ mod tests {
             name: "Mock Model".to_string(),
             version: "v1".to_string(),
             size: 1000,
+            disk_size: 0,
             download_url: "http://example.com".to_string(),
             license: "MIT".to_string(),
             installed: true,
             install_path: temp_dir.path().to_path_buf(),
             supported_languages: vec!["en".to_string()],
             supported_voices: vec!["voice1".to_string()],
+            output_sample_rate: 24_000,
         };
         
         registry.installed_models.insert("mock".to_string(), model_info);
@@ -637,12 +1311,14 @@ mod tests {
             name: "Model 1".to_string(),
             version: "v1".to_string(),
             size: 1000,
+            disk_size: 0,
             download_url: "http://example.com".to_string(),
             license: "MIT".to_string(),
             installed: true,
             install_path: PathBuf::from("/test/path1"),
             supported_languages: vec!["en".to_string()],
             supported_voices: vec!["voice1".to_string()],
+            output_sample_rate: 24_000,
         };
         
         let model2 = ModelInfo {
@@ -650,12 +1326,14 @@ mod tests {
             name: "Model 2".to_string(),
             version: "v1".to_string(),
             size: 2000,
+            disk_size: 0,
             download_url: "http://example.com".to_string(),
             license: "Apache-2.0".to_string(),
             installed: true,
             install_path: PathBuf::from("/test/path2"),
             supported_languages: vec!["en".to_string()],
             supported_voices: vec!["voice2".to_string()],
+            output_sample_rate: 24_000,
         };
         
         registry.installed_models.insert("model1".to_string(), model1);
@@ -669,6 +1347,41 @@ mod tests {
         assert!(ids.contains(&"model2"));
     }
     
+    #[test]
+    fn test_installed_models_disk_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut registry = ModelRegistry::new(temp_dir.path()).unwrap();
+        
+        // Simulate a partial install: declared size is larger than what's on disk
+        let model_path = temp_dir.path().join("models").join("partial");
+        std::fs::create_dir_all(model_path.join("voices")).unwrap();
+        std::fs::write(model_path.join("model.onnx"), vec![0u8; 1500]).unwrap();
+        std::fs::write(model_path.join("voices").join("af_test.bin"), vec![0u8; 500]).unwrap();
+        
+        let model_info = ModelInfo {
+            id: "partial".to_string(),
+            name: "Partial Model".to_string(),
+            version: "v1".to_string(),
+            size: 10_000,
+            disk_size: 0,
+            download_url: "http://example.com".to_string(),
+            license: "MIT".to_string(),
+            installed: true,
+            install_path: model_path,
+            supported_languages: vec!["en".to_string()],
+            supported_voices: vec!["af_test".to_string()],
+            output_sample_rate: 24_000,
+        };
+        
+        registry.installed_models.insert("partial".to_string(), model_info);
+        
+        let installed = registry.get_installed_models_with_disk_size();
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].size, 10_000);
+        assert_eq!(installed[0].disk_size, 2000);
+        assert_ne!(installed[0].size as u64, installed[0].disk_size);
+    }
+    
     #[test]
     fn test_model_status_checks() {
         let temp_dir = TempDir::new().unwrap();
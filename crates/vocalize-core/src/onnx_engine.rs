@@ -3,21 +3,286 @@
 
 pub mod session_pool;
 
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::future::Future;
+use std::ops::Range;
 use std::path::PathBuf;
 use anyhow::{Result, Context};
 use unicode_normalization::UnicodeNormalization;
 use directories::ProjectDirs;
 
+use crate::audio_effects::{crossfade_concat, normalize_peak, scrub_non_finite, NonFinitePolicy, DEFAULT_TARGET_PEAK_DBFS};
 use crate::model::{ModelManager, ModelId};
+use crate::voice_manager::{VoiceManager, STYLE_VECTOR_DIM};
 use crate::{VocalizeResult, VocalizeError};
 use session_pool::OnnxSessionPool;
 
+/// Approximate Kokoro output per input token at normal speed, in samples
+///
+/// Phonemes average roughly 80ms of speech at 24 kHz; the extra margin
+/// covers pauses at punctuation and the padding tokens.
+pub const ESTIMATED_SAMPLES_PER_TOKEN: usize = 2_000;
+
+/// Estimate how many samples synthesizing `token_len` tokens at `speed` yields
+///
+/// Used to reserve the output buffer up front. Output length scales
+/// inversely with speed; a non-positive or non-finite speed is treated as
+/// normal speed.
+#[must_use]
+pub fn estimate_sample_count(token_len: usize, speed: f32) -> usize {
+    let samples = token_len * ESTIMATED_SAMPLES_PER_TOKEN;
+    if speed.is_finite() && speed > 0.0 {
+        (samples as f64 / f64::from(speed)).ceil() as usize
+    } else {
+        samples
+    }
+}
+
+/// Name, element type and shape of one model input or output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TensorSpec {
+    /// Tensor name used to bind the value
+    pub name: String,
+    /// Element type, e.g. `int64` or `float32`; non-tensor values are
+    /// described by their value type instead
+    pub element_type: String,
+    /// Dimensions, with `-1` for dynamic axes; empty for non-tensor values
+    pub dimensions: Vec<i64>,
+}
+
+impl std::fmt::Display for TensorSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} {:?}", self.name, self.element_type, self.dimensions)
+    }
+}
+
+/// Inputs and outputs a loaded model's graph declares
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ModelIoSpec {
+    /// Graph inputs in declaration order
+    pub inputs: Vec<TensorSpec>,
+    /// Graph outputs in declaration order
+    pub outputs: Vec<TensorSpec>,
+}
+
+impl ModelIoSpec {
+    /// Pick the output carrying audio from the first of `names` the graph
+    /// declares
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the declared outputs if none of `names` is
+    /// among them, or if the matching output is not a float32 tensor
+    pub fn audio_output(&self, names: &[String]) -> VocalizeResult<&TensorSpec> {
+        let output = names.iter()
+            .find_map(|name| self.outputs.iter().find(|output| &output.name == name))
+            .ok_or_else(|| {
+                let declared: Vec<&str> = self.outputs.iter().map(|output| output.name.as_str()).collect();
+                VocalizeError::model(format!(
+                    "Model has no audio output: expected one of {names:?}, found {declared:?}"
+                ))
+            })?;
+        if output.element_type != "float32" {
+            return Err(VocalizeError::model(format!(
+                "Audio output '{}' must be float32, got {}", output.name, output.element_type
+            )));
+        }
+        Ok(output)
+    }
+}
+
+impl TensorSpec {
+    fn from_value_type(name: &str, value_type: &ort::value::ValueType) -> Self {
+        match value_type {
+            ort::value::ValueType::Tensor { ty, shape, .. } => Self {
+                name: name.to_string(),
+                element_type: format!("{ty:?}").to_lowercase(),
+                dimensions: shape.iter().copied().collect(),
+            },
+            other => Self {
+                name: name.to_string(),
+                element_type: format!("{other:?}"),
+                dimensions: Vec::new(),
+            },
+        }
+    }
+}
+
+/// Longest token sequence, padding included, that the model accepts in one pass
+pub const MAX_CONTEXT_TOKENS: usize = 512;
+
+/// What to do with token sequences longer than [`MAX_CONTEXT_TOKENS`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContextOverflowPolicy {
+    /// Reject the request
+    #[default]
+    Error,
+    /// Synthesize overlapping windows and crossfade them together
+    Window {
+        /// Tokens shared by neighbouring windows; their audio is crossfaded
+        overlap: usize,
+    },
+}
+
+/// Which punctuation [`OnnxTtsEngine::preprocess_text`] keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PunctuationPolicy {
+    /// Keep only the punctuation that shapes prosody (`.,!?:;-'"`)
+    #[default]
+    Default,
+    /// Keep every character except control characters, e.g. for code or URLs
+    PreserveAll,
+    /// Drop all punctuation and symbols, leaving letters, digits and whitespace
+    StripAll,
+}
+
+impl PunctuationPolicy {
+    /// Whether `c` survives preprocessing under this policy
+    fn keeps(self, c: char) -> bool {
+        if c.is_alphanumeric() || c.is_whitespace() {
+            return true;
+        }
+        match self {
+            Self::Default => matches!(c, '.' | ',' | '!' | '?' | ':' | ';' | '-' | '\'' | '"'),
+            Self::PreserveAll => !c.is_control(),
+            Self::StripAll => false,
+        }
+    }
+}
+
+/// Fixed token sequence synthesized by [`OnnxTtsEngine::self_test`]
+/// ("hello" in Kokoro's phoneme vocabulary, padded)
+pub const SELF_TEST_TOKENS: [i64; 8] = [0, 50, 83, 54, 156, 57, 135, 0];
+
+/// RMS level below which self-test output counts as silence
+const SELF_TEST_MIN_RMS: f32 = 1e-4;
+
+/// Outcome of [`OnnxTtsEngine::self_test`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfTestReport {
+    /// Model that was tested
+    pub model_id: ModelId,
+    /// Number of samples produced
+    pub sample_count: usize,
+    /// Number of NaN or infinite samples
+    pub non_finite_samples: usize,
+    /// Largest absolute finite sample value
+    pub peak: f32,
+    /// RMS level of the finite samples
+    pub rms: f32,
+    /// Fingerprint of the output, see [`audio_fingerprint`]
+    pub fingerprint: u64,
+    /// Fingerprint the model is expected to produce, if it is deterministic
+    pub expected_fingerprint: Option<u64>,
+    /// Description of each failed check; empty if the model is healthy
+    pub failures: Vec<String>,
+}
+
+impl SelfTestReport {
+    /// Check `audio` produced by `model_id` from [`SELF_TEST_TOKENS`]
+    ///
+    /// Audio fails if it is empty, contains non-finite samples, exceeds
+    /// full scale, is silent, or differs from the model's stored
+    /// fingerprint. The fingerprint comparison only runs for models that
+    /// pin one; the built-in models deliberately do not.
+    #[must_use]
+    pub fn check(model_id: ModelId, audio: &[f32]) -> Self {
+        let finite: Vec<f32> = audio.iter().copied().filter(|sample| sample.is_finite()).collect();
+        let non_finite_samples = audio.len() - finite.len();
+        let peak = finite.iter().fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+        let rms = if finite.is_empty() {
+            0.0
+        } else {
+            (finite.iter().map(|sample| sample * sample).sum::<f32>() / finite.len() as f32).sqrt()
+        };
+        let fingerprint = audio_fingerprint(audio);
+        let expected_fingerprint = model_info(model_id).self_test_fingerprint;
+
+        let mut failures = Vec::new();
+        if audio.is_empty() {
+            failures.push("Model produced no audio".to_string());
+        }
+        if non_finite_samples > 0 {
+            failures.push(format!("{non_finite_samples} of {} samples are NaN or infinite", audio.len()));
+        }
+        if peak > 1.0 {
+            failures.push(format!("Peak {peak:.3} exceeds full scale"));
+        }
+        if !audio.is_empty() && rms < SELF_TEST_MIN_RMS {
+            failures.push(format!("Output is silent (RMS {rms:.2e})"));
+        }
+        if let Some(expected) = expected_fingerprint.filter(|&expected| expected != fingerprint) {
+            failures.push(format!("Fingerprint {fingerprint:016x} does not match expected {expected:016x}"));
+        }
+
+        Self { model_id, sample_count: audio.len(), non_finite_samples, peak, rms, fingerprint, expected_fingerprint, failures }
+    }
+
+    /// Whether every check passed
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// FNV-1a hash of `audio` quantized to 16-bit PCM
+///
+/// Quantizing first makes the fingerprint stable against rounding
+/// differences well below audible level, e.g. between CPU instruction sets.
+#[must_use]
+pub fn audio_fingerprint(audio: &[f32]) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+    audio.iter().fold(FNV_OFFSET, |hash, &sample| {
+        let sample = if sample.is_finite() { sample.clamp(-1.0, 1.0) } else { 0.0 };
+        let pcm = (sample * f32::from(i16::MAX)).round() as i16;
+        pcm.to_le_bytes().iter().fold(hash, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME))
+    })
+}
+
+/// Voice whose embedding stands in for a missing or all-zero style vector
+///
+/// Only used when [`OnnxTtsEngine::set_style_fallback`] is enabled; matches
+/// the Python frontend's default voice.
+pub const FALLBACK_VOICE_ID: &str = "af_alloy";
+
+/// Cosine similarity below which a style vector is reported as not
+/// matching the voice it was supplied for
+pub const STYLE_MATCH_MIN_SIMILARITY: f32 = 0.5;
+
+/// Number of ONNX sessions the engine creates per loaded model
+///
+/// Half the available CPU cores, clamped to 1..=4, or 2 if the core count
+/// is unknown. Each session holds its own copy of the model weights.
+#[must_use]
+pub fn default_session_pool_size() -> usize {
+    std::thread::available_parallelism()
+        .map(|p| (p.get() / 2).clamp(1, 4))
+        .unwrap_or(2)
+}
+
 /// ONNX-based neural TTS engine
 #[derive(Debug)]
 pub struct OnnxTtsEngine {
     model_manager: ModelManager,
     session_pool: Option<OnnxSessionPool>,
     current_model: Option<ModelId>,
+    non_finite_policy: NonFinitePolicy,
+    target_peak_dbfs: f32,
+    style_fallback: bool,
+    context_overflow_policy: ContextOverflowPolicy,
+    punctuation_policy: PunctuationPolicy,
+    voice_embeddings: VoiceEmbeddingCache,
+    /// Combined voices file to read embeddings from instead of the model
+    /// cache, set when the model itself was loaded from an explicit path
+    voices_file: Option<PathBuf>,
+    /// Voices whose registered embeddings take precedence over voice files
+    voice_manager: Option<VoiceManager>,
+    /// Voice embeddings read from disk rather than the cache
+    #[cfg(test)]
+    voice_reads: std::sync::atomic::AtomicUsize,
     // Removed tokenizer - text processing handled by Python layer
 }
 
@@ -57,13 +322,26 @@ impl OnnxTtsEngine {
             }
         }
         
-        let model_manager = ModelManager::new(cache_dir);
-        
-        Ok(Self {
-            model_manager,
+        Ok(Self::without_runtime(cache_dir))
+    }
+    
+    /// Engine state for `cache_dir`, without initializing ONNX Runtime
+    fn without_runtime(cache_dir: PathBuf) -> Self {
+        Self {
+            model_manager: ModelManager::new(cache_dir),
             session_pool: None,
             current_model: None,
-        })
+            non_finite_policy: NonFinitePolicy::default(),
+            target_peak_dbfs: DEFAULT_TARGET_PEAK_DBFS,
+            style_fallback: false,
+            context_overflow_policy: ContextOverflowPolicy::default(),
+            punctuation_policy: PunctuationPolicy::default(),
+            voice_embeddings: VoiceEmbeddingCache::default(),
+            voices_file: None,
+            voice_manager: None,
+            #[cfg(test)]
+            voice_reads: std::sync::atomic::AtomicUsize::new(0),
+        }
     }
     
     /// Create a new ONNX TTS engine with cross-platform cache directory
@@ -91,23 +369,37 @@ impl OnnxTtsEngine {
         let model_path = self.model_manager.get_model_path(model_id).await
             .context(format!("Failed to get model path for {:?}", model_id))?;
         
+        self.load_session_pool(model_id, &model_path).await
+    }
+    
+    /// Load a model from an explicit ONNX file instead of the model cache
+    ///
+    /// The file is checked with [`validate_onnx_file`](crate::model::validate_onnx_file)
+    /// before any session is created.
+    pub async fn load_model_from_path(&mut self, model_id: ModelId, model_path: &std::path::Path) -> Result<()> {
+        tracing::info!("🔄 ONNX Engine: Loading model {:?} from {}", model_id, model_path.display());
+        
+        crate::model::validate_onnx_file(model_path)?;
+        
+        self.session_pool = None;
+        self.current_model = None;
+        
+        self.load_session_pool(model_id, model_path).await
+    }
+    
+    /// Create the session pool for `model_path` and make `model_id` current
+    async fn load_session_pool(&mut self, model_id: ModelId, model_path: &std::path::Path) -> Result<()> {
         // Create session pool with multiple sessions for concurrent access
         tracing::info!("🏊 Creating session pool for model...");
-        let pool_size = std::thread::available_parallelism()
-            .map(|p| (p.get() / 2).max(1).min(4)) // Use half of CPU cores, max 4
-            .unwrap_or(2); // Fallback to 2 sessions
+        let pool_size = default_session_pool_size();
         
-        let session_pool = OnnxSessionPool::new(&model_path, pool_size).await
+        let session_pool = OnnxSessionPool::new(model_path, pool_size).await
             .context("Failed to create ONNX session pool")?;
         
         tracing::info!("✅ ONNX Engine: Session pool created with {} sessions", pool_size);
         
         // Model info available if needed for future enhancements
-        let _model_info = match model_id {
-            ModelId::Kokoro => crate::model::ModelInfo::kokoro(),
-            ModelId::Chatterbox => crate::model::ModelInfo::chatterbox(),
-            ModelId::Dia => crate::model::ModelInfo::dia(),
-        };
+        let _model_info = model_info(model_id);
         
         // Text processing is now handled by Python layer using ttstokenizer
         // This engine only handles neural inference with pre-processed token IDs
@@ -125,6 +417,114 @@ impl OnnxTtsEngine {
         self.current_model
     }
     
+    /// Read voice embeddings from `voices_file` instead of the model cache
+    ///
+    /// Used alongside [`load_model_from_path`](Self::load_model_from_path),
+    /// whose voices live next to the model rather than in the cache.
+    /// Embeddings read from the previous location are forgotten.
+    pub fn set_voices_file(&mut self, voices_file: PathBuf) {
+        self.voices_file = Some(voices_file);
+        self.voice_embeddings.clear();
+    }
+    
+    /// Set how NaN/Inf samples in model output are handled
+    pub fn set_non_finite_policy(&mut self, policy: NonFinitePolicy) {
+        self.non_finite_policy = policy;
+    }
+    
+    /// Get how NaN/Inf samples in model output are handled
+    pub fn non_finite_policy(&self) -> NonFinitePolicy {
+        self.non_finite_policy
+    }
+    
+    /// Set the peak level, in dBFS, that postprocessing normalizes to
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the level is above 0 dBFS or not finite
+    pub fn set_target_peak_dbfs(&mut self, target_peak_dbfs: f32) -> VocalizeResult<()> {
+        if !target_peak_dbfs.is_finite() || target_peak_dbfs > 0.0 {
+            return Err(VocalizeError::invalid_input(format!(
+                "Target peak must be a finite level at or below 0 dBFS, got {target_peak_dbfs}"
+            )));
+        }
+        self.target_peak_dbfs = target_peak_dbfs;
+        Ok(())
+    }
+    
+    /// Get the peak level, in dBFS, that postprocessing normalizes to
+    pub fn target_peak_dbfs(&self) -> f32 {
+        self.target_peak_dbfs
+    }
+    
+    /// Set whether a missing or all-zero style vector is replaced by the
+    /// [`FALLBACK_VOICE_ID`] embedding instead of failing synthesis
+    ///
+    /// An all-zero vector is what a failed voice embedding load leaves
+    /// behind. Disabled by default.
+    pub fn set_style_fallback(&mut self, enabled: bool) {
+        self.style_fallback = enabled;
+    }
+    
+    /// Get whether missing or all-zero style vectors fall back to [`FALLBACK_VOICE_ID`]
+    pub fn style_fallback(&self) -> bool {
+        self.style_fallback
+    }
+    
+    /// Set how token sequences longer than [`MAX_CONTEXT_TOKENS`] are handled
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a window overlap leaves no room for new tokens
+    pub fn set_context_overflow_policy(&mut self, policy: ContextOverflowPolicy) -> VocalizeResult<()> {
+        if let ContextOverflowPolicy::Window { overlap } = policy {
+            if overlap >= MAX_WINDOW_BODY {
+                return Err(VocalizeError::invalid_input(format!(
+                    "Window overlap must be less than {MAX_WINDOW_BODY} tokens, got {overlap}"
+                )));
+            }
+        }
+        self.context_overflow_policy = policy;
+        Ok(())
+    }
+    
+    /// Get how token sequences longer than [`MAX_CONTEXT_TOKENS`] are handled
+    pub fn context_overflow_policy(&self) -> ContextOverflowPolicy {
+        self.context_overflow_policy
+    }
+    
+    /// Set which punctuation [`preprocess_text`](Self::preprocess_text) keeps
+    pub fn set_punctuation_policy(&mut self, policy: PunctuationPolicy) {
+        self.punctuation_policy = policy;
+    }
+    
+    /// Get which punctuation [`preprocess_text`](Self::preprocess_text) keeps
+    pub fn punctuation_policy(&self) -> PunctuationPolicy {
+        self.punctuation_policy
+    }
+    
+    /// Query the loaded model for its input and output tensor specs
+    ///
+    /// Use this to check a model file against the `tokens`/`style`/`speed`
+    /// inputs that token synthesis binds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no model is loaded
+    pub fn model_io_spec(&self) -> Result<ModelIoSpec> {
+        let pool = self.session_pool.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No model loaded; call load_model first"))?;
+        
+        pool.with_session(|session| ModelIoSpec {
+            inputs: session.inputs.iter()
+                .map(|input| TensorSpec::from_value_type(&input.name, &input.input_type))
+                .collect(),
+            outputs: session.outputs.iter()
+                .map(|output| TensorSpec::from_value_type(&output.name, &output.output_type))
+                .collect(),
+        })
+    }
+    
     /// Debug model inputs and requirements - 2025 Fix for tensor shape issues
     pub fn debug_model_inputs(&self) -> Result<()> {
         tracing::debug!("=== MODEL DEBUG INFO ===");
@@ -134,6 +534,14 @@ impl OnnxTtsEngine {
             let stats = pool.stats();
             tracing::debug!("Session pool stats: {}", stats);
             tracing::debug!("Pool health: {}", pool.is_healthy());
+            
+            let spec = self.model_io_spec()?;
+            for input in &spec.inputs {
+                tracing::debug!("Model input {}", input);
+            }
+            for output in &spec.outputs {
+                tracing::debug!("Model output {}", output);
+            }
         } else {
             tracing::debug!("No session pool loaded");
         }
@@ -158,22 +566,11 @@ impl OnnxTtsEngine {
     }
     
     /// Preprocess text for TTS (normalize, clean) - Fixed for Kokoro TTS
+    ///
+    /// Punctuation is filtered according to the engine's
+    /// [`PunctuationPolicy`].
     pub fn preprocess_text(&self, text: &str) -> String {
-        // Unicode normalization (NFC is better for TTS than NFD)
-        let normalized: String = text.nfc().collect();
-        
-        // 2025 Fix: Preserve proper linguistic features for Kokoro TTS
-        // Keep capitalization, punctuation, and natural language structure
-        let cleaned = normalized
-            .chars()
-            .filter(|c| {
-                // Keep letters, numbers, spaces, and important punctuation
-                c.is_alphabetic() || c.is_numeric() || c.is_whitespace() || 
-                matches!(*c, '.' | ',' | '!' | '?' | ':' | ';' | '-' | '\'' | '"')
-            })
-            .collect::<String>()
-            .trim()
-            .to_string();
+        let cleaned = clean_text(text, self.punctuation_policy);
         
         // 2025 Fix: NO startoftext/endoftext tokens - Kokoro uses padding tokens
         // Return clean text without special tokens - padding will be handled in tokenization
@@ -191,29 +588,268 @@ impl OnnxTtsEngine {
         style_vector: Vec<f32>, 
         speed: f32,
         model_id: ModelId
+    ) -> Result<Vec<f32>> {
+        self.synthesize_from_token_slices(&input_ids, &style_vector, speed, model_id).await
+    }
+    
+    /// Synthesize audio from borrowed token IDs and style vector
+    ///
+    /// Callers holding the data elsewhere (e.g. NumPy arrays in the Python
+    /// bindings) can pass it without building a `Vec` first.
+    pub async fn synthesize_from_token_slices(
+        &mut self,
+        input_ids: &[i64],
+        style_vector: &[f32],
+        speed: f32,
+        model_id: ModelId
     ) -> Result<Vec<f32>> {
         tracing::debug!("ONNX Engine: Starting synthesis from {} pre-processed tokens", input_ids.len());
         
+        let style_vector = self.prepare_token_synthesis(input_ids, style_vector, model_id).await?;
+        let style_vector = style_vector.as_ref();
+        
+        match self.window_overlap(input_ids.len())? {
+            None => self.infer_with_timeout(input_ids, style_vector, speed).await,
+            Some(overlap) => self.synthesize_windows(input_ids, style_vector, speed, overlap).await,
+        }
+    }
+    
+    /// Synthesize audio from borrowed token IDs, handing it to `sink` in
+    /// chunks instead of returning one buffer
+    ///
+    /// Sequences within [`MAX_CONTEXT_TOKENS`] are delivered in chunks of at
+    /// most [`SINK_CHUNK_SAMPLES`]. Over-long sequences synthesized as
+    /// windows (see [`ContextOverflowPolicy::Window`]) are delivered window
+    /// by window, so only one window's audio is held at a time. The samples
+    /// passed to `sink`, concatenated, equal what
+    /// [`synthesize_from_token_slices`](Self::synthesize_from_token_slices)
+    /// returns. Returns the total number of samples delivered.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as
+    /// [`synthesize_from_token_slices`](Self::synthesize_from_token_slices).
+    /// If inference fails part way through a windowed sequence, `sink` has
+    /// already received the audio of the preceding windows.
+    pub async fn synthesize_from_tokens_with_sink(
+        &mut self,
+        input_ids: &[i64],
+        style_vector: &[f32],
+        speed: f32,
+        model_id: ModelId,
+        mut sink: impl FnMut(&[f32]),
+    ) -> Result<usize> {
+        let style_vector = self.prepare_token_synthesis(input_ids, style_vector, model_id).await?;
+        let style_vector = style_vector.as_ref();
+        
+        match self.window_overlap(input_ids.len())? {
+            None => {
+                let audio = self.infer_with_timeout(input_ids, style_vector, speed).await?;
+                audio.chunks(SINK_CHUNK_SAMPLES).for_each(&mut sink);
+                Ok(audio.len())
+            }
+            Some(overlap) => self.stream_windows(input_ids, style_vector, speed, overlap, &mut sink).await,
+        }
+    }
+    
+    /// Validate `input_ids`, make sure `model_id` is loaded and resolve the
+    /// style vector to use, before token synthesis
+    async fn prepare_token_synthesis<'a>(
+        &mut self,
+        input_ids: &[i64],
+        style_vector: &'a [f32],
+        model_id: ModelId,
+    ) -> Result<Cow<'a, [f32]>> {
+        // Reject out-of-vocabulary tokens up front; the model itself fails
+        // on them with an opaque gather/index error
+        validate_token_ids(input_ids, model_info(model_id).vocab_size)?;
+        
         // Ensure correct model is loaded
         if self.current_model != Some(model_id) {
             tracing::debug!("ONNX Engine: Loading model {:?}...", model_id);
             self.load_model(model_id).await.context("Failed to load model in synthesize")?;
         }
         
-        // Validate input constraints
-        if input_ids.len() > 512 {
-            return Err(anyhow::anyhow!("Token sequence too long: {} tokens (max 512)", input_ids.len()));
+        self.resolve_style_vector(style_vector, model_id)
+    }
+    
+    /// Window overlap to synthesize `token_count` tokens with, or `None` if
+    /// they fit in one inference
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tokens exceed [`MAX_CONTEXT_TOKENS`] and the
+    /// engine's [`ContextOverflowPolicy`] is to reject them
+    fn window_overlap(&self, token_count: usize) -> Result<Option<usize>> {
+        if token_count <= MAX_CONTEXT_TOKENS {
+            return Ok(None);
         }
-        
-        if style_vector.len() != 256 {
-            return Err(anyhow::anyhow!("Style vector must be 256 dimensions, got {}", style_vector.len()));
+        match self.context_overflow_policy {
+            ContextOverflowPolicy::Error => Err(anyhow::anyhow!(
+                "Token sequence too long: {token_count} tokens (max {MAX_CONTEXT_TOKENS})"
+            )),
+            ContextOverflowPolicy::Window { overlap } => Ok(Some(overlap)),
         }
+    }
+    
+    /// Check `style_vector` before inference, substituting the fallback
+    /// voice's embedding for a missing one when the fallback is enabled
+    fn resolve_style_vector<'a>(&self, style_vector: &'a [f32], model_id: ModelId) -> Result<Cow<'a, [f32]>> {
+        // A failed embedding load leaves an empty or all-zero vector behind
+        let style_vector = if is_missing_style(style_vector) {
+            if !self.style_fallback {
+                check_style_vector_len(style_vector)?;
+                return Err(VocalizeError::synthesis(format!(
+                    "Style vector is all zeros, which means the voice embedding failed to load; \
+                     enable the style fallback to use voice '{FALLBACK_VOICE_ID}' instead"
+                )).into());
+            }
+            tracing::warn!("Style vector is empty or all zeros; falling back to voice '{}'", FALLBACK_VOICE_ID);
+            Cow::Owned(self.load_voice_embedding(model_id.as_str(), FALLBACK_VOICE_ID)
+                .with_context(|| format!(
+                    "Style vector is empty or all zeros and fallback voice '{FALLBACK_VOICE_ID}' could not be loaded"
+                ))?)
+        } else {
+            Cow::Borrowed(style_vector)
+        };
+        
+        check_style_vector_len(&style_vector)?;
         
         // Validate style vector for neural network stability
         if !self.validate_style_vector(&style_vector) {
             return Err(anyhow::anyhow!("Invalid style vector detected - contains values that would cause model instability"));
         }
         
+        Ok(style_vector)
+    }
+    
+    /// Synthesize several token sequences with the same voice
+    ///
+    /// Up to `batch_size` sequences are in flight across the session pool at
+    /// once. If inference runs out of memory (typically GPU memory), the
+    /// batch is halved and retried, down to one sequence at a time, before
+    /// the error is returned. Sequences longer than [`MAX_CONTEXT_TOKENS`]
+    /// are rejected. Returns one audio buffer per sequence, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any sequence or the style vector is invalid, or
+    /// if inference fails even with a batch size of one
+    pub async fn synthesize_batch(
+        &mut self,
+        token_sequences: &[Vec<i64>],
+        style_vector: &[f32],
+        speed: f32,
+        model_id: ModelId,
+        batch_size: usize,
+    ) -> Result<Vec<Vec<f32>>> {
+        let vocab_size = model_info(model_id).vocab_size;
+        for (index, input_ids) in token_sequences.iter().enumerate() {
+            validate_token_ids(input_ids, vocab_size)
+                .with_context(|| format!("Invalid token sequence {index}"))?;
+            if input_ids.len() > MAX_CONTEXT_TOKENS {
+                return Err(anyhow::anyhow!(
+                    "Token sequence {index} too long: {} tokens (max {MAX_CONTEXT_TOKENS})", input_ids.len()
+                ));
+            }
+        }
+        
+        if self.current_model != Some(model_id) {
+            self.load_model(model_id).await.context("Failed to load model in synthesize_batch")?;
+        }
+        
+        let style_vector = self.resolve_style_vector(style_vector, model_id)?;
+        let style_vector = style_vector.as_ref();
+        let this = &*self;
+        
+        run_batched_with_oom_retry(token_sequences.len(), batch_size, move |range| {
+            futures::future::try_join_all(
+                token_sequences[range].iter().map(move |input_ids| this.infer_with_timeout(input_ids, style_vector, speed))
+            )
+        }).await
+    }
+    
+    /// Synthesize an over-long token sequence as overlapping windows,
+    /// crossfading the audio where neighbouring windows overlap
+    async fn synthesize_windows(
+        &self,
+        input_ids: &[i64],
+        style_vector: &[f32],
+        speed: f32,
+        overlap: usize,
+    ) -> Result<Vec<f32>> {
+        let mut audio = Vec::with_capacity(estimate_sample_count(input_ids.len(), speed));
+        let mut collect = |chunk: &[f32]| audio.extend_from_slice(chunk);
+        self.stream_windows(input_ids, style_vector, speed, overlap, &mut collect).await?;
+        Ok(audio)
+    }
+    
+    /// Synthesize an over-long token sequence as overlapping windows,
+    /// passing the crossfaded audio to `sink` as soon as no later window can
+    /// change it; returns the total number of samples delivered
+    async fn stream_windows(
+        &self,
+        input_ids: &[i64],
+        style_vector: &[f32],
+        speed: f32,
+        overlap: usize,
+        sink: &mut impl FnMut(&[f32]),
+    ) -> Result<usize> {
+        let windows = context_windows(input_ids, overlap);
+        tracing::info!("Splitting {} tokens into {} overlapping windows", input_ids.len(), windows.len());
+        
+        let mut stitcher = StreamingCrossfade::default();
+        for window in &windows {
+            let window_audio = self.infer_with_timeout(window, style_vector, speed).await?;
+            // Overlapping tokens map to roughly this window's samples per token
+            let fade_len = overlap * window_audio.len() / window.len();
+            stitcher.push(&window_audio, fade_len, sink);
+        }
+        Ok(stitcher.finish(sink))
+    }
+    
+    /// Synthesize [`SELF_TEST_TOKENS`] with `model_id` and check the output
+    ///
+    /// Uses the embedding of [`FALLBACK_VOICE_ID`] at normal speed and
+    /// inspects the raw model output, before the engine's
+    /// [`NonFinitePolicy`] is applied, so numerical problems show up in the
+    /// report rather than being scrubbed. A failed check is reported in
+    /// [`SelfTestReport::failures`], not as an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model or voice embedding cannot be loaded or
+    /// inference fails outright
+    pub async fn self_test(&mut self, model_id: ModelId) -> Result<SelfTestReport> {
+        if self.current_model != Some(model_id) {
+            self.load_model(model_id).await.context("Failed to load model for self-test")?;
+        }
+        
+        let style_vector = self.load_voice_embedding(model_id.as_str(), FALLBACK_VOICE_ID)
+            .with_context(|| format!("Failed to load self-test voice '{FALLBACK_VOICE_ID}'"))?;
+        let audio = self.infer_raw_with_timeout(&SELF_TEST_TOKENS, &style_vector, 1.0).await?;
+        
+        let report = SelfTestReport::check(model_id, &audio);
+        if report.passed() {
+            tracing::info!("Self-test passed for {:?}: {} samples, peak {:.3}", model_id, report.sample_count, report.peak);
+        } else {
+            tracing::warn!("Self-test failed for {:?}: {}", model_id, report.failures.join("; "));
+        }
+        Ok(report)
+    }
+    
+    /// Run inference with timeout protection, then postprocess the output
+    async fn infer_with_timeout(&self, input_ids: &[i64], style_vector: &[f32], speed: f32) -> Result<Vec<f32>> {
+        let mut audio = self.infer_raw_with_timeout(input_ids, style_vector, speed).await?;
+        
+        // Numerical instability shows up as NaN/Inf; never pass it on, and
+        // keep the peak below full scale
+        self.postprocess_in_place(&mut audio)?;
+        Ok(audio)
+    }
+    
+    /// Run inference with timeout protection, leaving non-finite samples in place
+    async fn infer_raw_with_timeout(&self, input_ids: &[i64], style_vector: &[f32], speed: f32) -> Result<Vec<f32>> {
         // Perform ONNX inference with timeout protection
         tracing::info!("🔒 Starting synthesis with 30-second timeout protection");
         match tokio::time::timeout(
@@ -228,6 +864,31 @@ impl OnnxTtsEngine {
         }
     }
     
+    /// Check a caller-supplied style vector against the stored embedding
+    /// of the voice it is meant to represent
+    ///
+    /// A vector of the wrong dimension is rejected. One with the right
+    /// dimension that looks unrelated to the voice (cosine similarity below
+    /// [`STYLE_MATCH_MIN_SIMILARITY`]) is logged as a warning, since
+    /// synthesis would succeed but sound like a different voice.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the voice embedding cannot be loaded or its
+    /// dimension differs from `style_vector`'s
+    pub fn check_style_vector_for_voice(
+        &self,
+        style_vector: &[f32],
+        model_id: ModelId,
+        voice_id: &str,
+    ) -> VocalizeResult<()> {
+        let stored = self.load_voice_embedding(model_id.as_str(), voice_id)?;
+        if let Some(warning) = style_mismatch(style_vector, &stored, voice_id)? {
+            tracing::warn!("{}", warning);
+        }
+        Ok(())
+    }
+    
     /// Validate style vector to prevent neural network instability
     fn validate_style_vector(&self, style_vector: &[f32]) -> bool {
         // Check for NaN/Inf values (immediate model corruption)
@@ -244,7 +905,7 @@ impl OnnxTtsEngine {
         }
         
         // Check for all zeros (failed loading indicator)
-        if style_vector.iter().all(|&x| x.abs() < 0.001) {
+        if is_missing_style(style_vector) {
             tracing::error!("❌ Style vector appears to be all zeros");
             return false;
         }
@@ -271,10 +932,12 @@ impl OnnxTtsEngine {
     // Removed adaptive tensor function - simplified approach for immediate fix
 
     /// Perform ONNX inference with pre-processed token IDs
+    ///
+    /// Non-finite samples are left for the caller to handle.
     async fn perform_inference_with_tokens(
         &self, 
-        input_ids: Vec<i64>, 
-        style_vector: Vec<f32>, 
+        input_ids: &[i64], 
+        style_vector: &[f32], 
         speed: f32
     ) -> Result<Vec<f32>> {
         // Acquire session from pool
@@ -304,23 +967,20 @@ impl OnnxTtsEngine {
         // Run inference with ONNX Runtime
         tracing::info!("🚀 ONNX Engine: Running inference...");
         let audio_data: Vec<f32> = {
-            // Create inputs with actual data
-            let mut attempt_inputs: std::collections::HashMap<String, ort::value::Value> = std::collections::HashMap::new();
-            
-            // Create tokens tensor with pre-processed tokens
-            let tokens_tensor = ort::value::Tensor::from_array(([1, input_ids.len()], input_ids))
+            let tokens_tensor = ort::value::TensorRef::from_array_view(([1, input_ids.len()], input_ids))
                 .context("Failed to create tokens tensor")?;
-            attempt_inputs.insert("tokens".to_string(), tokens_tensor.into());
-            
-            // Create style tensor with pre-processed style vector
-            let style_tensor = ort::value::Tensor::from_array(([1, style_vector.len()], style_vector))
+            let style_tensor = ort::value::TensorRef::from_array_view(([1, style_vector.len()], style_vector))
                 .context("Failed to create style tensor")?;
-            attempt_inputs.insert("style".to_string(), style_tensor.into());
             
             // Create speed tensor
             let speed_tensor = ort::value::Tensor::from_array(([1], vec![speed]))
                 .context("Failed to create speed tensor")?;
-            attempt_inputs.insert("speed".to_string(), speed_tensor.into());
+            
+            let attempt_inputs = ort::inputs![
+                "tokens" => tokens_tensor,
+                "style" => style_tensor,
+                "speed" => speed_tensor,
+            ];
             
             // Add logging right before ONNX inference
             tracing::info!("🚀 [{}] Starting ONNX inference with {} inputs...", 
@@ -338,6 +998,15 @@ impl OnnxTtsEngine {
             tracing::info!("✅ [{}] Session lock acquired successfully", 
                 chrono::Local::now().format("%H:%M:%S%.3f"));
             
+            let io_spec = ModelIoSpec {
+                inputs: Vec::new(),
+                outputs: session.outputs.iter()
+                    .map(|output| TensorSpec::from_value_type(&output.name, &output.output_type))
+                    .collect(),
+            };
+            let audio_output_names = model_info(self.current_model.unwrap_or(ModelId::default())).audio_outputs;
+            let audio_output = io_spec.audio_output(&audio_output_names)?.name.clone();
+            
             tracing::info!("🔥 [{}] Calling session.run() now...", 
                 chrono::Local::now().format("%H:%M:%S%.3f"));
             let outputs = session.run(attempt_inputs)
@@ -346,83 +1015,115 @@ impl OnnxTtsEngine {
                 chrono::Local::now().format("%H:%M:%S%.3f"));
             tracing::info!("  - Output tensors: {:?}", outputs.keys().collect::<Vec<_>>());
             
-            // Extract audio data using ort 2.0.0-rc.10 API
-            if let Some(output) = outputs.get("audio") {
-                let (_, data) = output.try_extract_tensor::<f32>()
-                    .context("Failed to extract audio data from 'audio' output")?;
-                data.to_vec()
-            } else if let Some(output) = outputs.get("output") {
-                let (_, data) = output.try_extract_tensor::<f32>()
-                    .context("Failed to extract audio data from 'output' output")?;
-                data.to_vec()
-            } else if let Some((_, output)) = outputs.iter().next() {
-                let (_, data) = output.try_extract_tensor::<f32>()
-                    .context("Failed to extract audio data from first output")?;
-                data.to_vec()
-            } else {
-                return Err(anyhow::anyhow!("No audio output found in model"));
-            }
+            // Models may emit more than audio (e.g. durations), so pick the
+            // audio output by name rather than position
+            let data = outputs.get(audio_output.as_str())
+                .ok_or_else(|| anyhow::anyhow!("Model did not produce its '{}' output", audio_output))?
+                .try_extract_tensor::<f32>()
+                .with_context(|| format!("Failed to extract audio data from '{audio_output}' output"))?.1;
+            
+            // Sized from the output tensor, so the buffer is exactly the audio
+            data.to_vec()
         };
         
+        if audio_data.is_empty() {
+            return Err(VocalizeError::synthesis(format!(
+                "Model {:?} produced no audio from {} tokens",
+                self.current_model, tokens_count
+            )).into());
+        }
+        
         tracing::info!("✅ Generated {} audio samples from {} tokens at 24kHz", audio_data.len(), tokens_count);
         Ok(audio_data)
     }
     
     
     /// Postprocess raw model output
-    pub fn postprocess_audio(&self, raw_audio: &[f32]) -> Vec<f32> {
-        // Normalize audio to [-1.0, 1.0] range
-        let max_val = raw_audio.iter().map(|x| x.abs()).fold(0.0f32, f32::max);
+    ///
+    /// Non-finite samples are handled according to the engine's
+    /// [`NonFinitePolicy`], then the audio is peak-normalized to the target
+    /// peak level (-1 dBFS by default) to leave headroom for lossy encoding.
+    ///
+    /// Token synthesis applies this to the output of every inference, so
+    /// sequences synthesized as windows are normalized window by window.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output contains NaN/Inf samples and the
+    /// policy is [`NonFinitePolicy::Error`]
+    pub fn postprocess_audio(&self, raw_audio: &[f32]) -> VocalizeResult<Vec<f32>> {
+        let mut audio = raw_audio.to_vec();
+        self.postprocess_in_place(&mut audio)?;
+        Ok(audio)
+    }
+    
+    /// [`postprocess_audio`](Self::postprocess_audio) without the copy
+    fn postprocess_in_place(&self, audio: &mut [f32]) -> VocalizeResult<()> {
+        scrub_non_finite(audio, self.non_finite_policy)?;
         
-        if max_val > 0.0 {
-            raw_audio.iter().map(|&x| (x / max_val).clamp(-1.0, 1.0)).collect()
-        } else {
-            raw_audio.to_vec()
-        }
+        normalize_peak(audio, self.target_peak_dbfs);
+        Ok(())
     }
     
+    /// Use embeddings registered with `voice_manager` (e.g. imported with
+    /// [`VoiceManager::import_voice_embedding`]) in place of the voice
+    /// files for those voices
+    ///
+    /// Clones of `voice_manager` share its embeddings, so voices registered
+    /// later are picked up too.
+    pub fn set_voice_manager(&mut self, voice_manager: VoiceManager) {
+        self.voice_manager = Some(voice_manager);
+    }
+    
+    /// Forget the voice embeddings read so far, so the next use of each
+    /// voice reads its file again (e.g. after the voice files changed)
+    pub fn clear_voice_cache(&self) {
+        self.voice_embeddings.clear();
+    }
+    
+    /// Embedding of `voice_id`: the one registered with the voice manager,
+    /// or else read from disk on first use and cached
     fn load_voice_embedding(&self, model_id: &str, voice_id: &str) -> VocalizeResult<Vec<f32>> {
-        // Construct voice file path based on model cache structure
-        let cache_dir = self.model_manager.cache_dir.clone();
-        
-        // 2025 Fix: Use correct path structure that matches ModelRegistry
-        let model_cache = match model_id {
-            "kokoro" => cache_dir.join("models--direct_download").join("local"),
-            _ => return Err(VocalizeError::SynthesisError {
-                message: format!("Unsupported model for voice loading: {}", model_id)
-            }),
-        };
-        
-        // 2025 Fix: Look for voice files in the correct location
-        // Try multiple possible voice file locations
-        let voice_file_locations = vec![
-            model_cache.join("voices").join(format!("{}.bin", voice_id)),
-            model_cache.join(format!("voice_{}.bin", voice_id)),
-            model_cache.join("voices-v1.0.bin"), // Single voices file
-        ];
-        
-        let mut voice_file = None;
-        for location in voice_file_locations {
-            if location.exists() {
-                voice_file = Some(location);
-                break;
-            }
+        if let Some(style_vector) = self.voice_manager.as_ref().and_then(|manager| manager.voice_style_vector(voice_id)) {
+            return Ok(style_vector);
         }
+        self.voice_embeddings
+            .get_or_load(model_id, voice_id, || self.read_voice_embedding(model_id, voice_id))
+    }
+    
+    /// Read and parse the embedding of `voice_id` from the voices file set
+    /// with [`set_voices_file`](Self::set_voices_file), or else the model cache
+    fn read_voice_embedding(&self, model_id: &str, voice_id: &str) -> VocalizeResult<Vec<f32>> {
+        #[cfg(test)]
+        self.voice_reads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         
-        let voice_file = voice_file.ok_or_else(|| VocalizeError::SynthesisError {
-            message: format!(
-                "Voice file not found for '{}' in model cache. Tried locations: {:?}. \
-                 Run 'vocalize models download {}' to get voices.", 
-                voice_id, 
-                model_cache.join("voices").join(format!("{}.bin", voice_id)),
-                model_id
-            )
-        })?;
+        let voice_file_locations = voice_file_candidates(
+            &self.model_manager.cache_dir,
+            self.voices_file.as_deref(),
+            model_id,
+            voice_id,
+        )?;
+        
+        let voice_file = voice_file_locations
+            .iter()
+            .find(|location| location.exists())
+            .cloned()
+            .ok_or_else(|| VocalizeError::SynthesisError {
+                message: format!(
+                    "Voice file not found for '{}'. Tried locations: {:?}. \
+                     Run 'vocalize models download {}' to get voices.", 
+                    voice_id, 
+                    voice_file_locations,
+                    model_id
+                )
+            })?;
         
         tracing::debug!("Loading voice embedding from: {:?}", voice_file);
         
         // 2025 Fix: Enhanced voice embedding loading with fallback support
-        let voice_embedding = if voice_file.file_name().unwrap_or_default() == "voices-v1.0.bin" {
+        let voice_embedding = if self.voices_file.as_ref() == Some(&voice_file)
+            || voice_file.file_name().unwrap_or_default() == "voices-v1.0.bin"
+        {
             // Single voices file containing multiple embeddings
             self.load_voice_from_combined_file(&voice_file, voice_id)?
         } else {
@@ -440,95 +1141,14 @@ impl OnnxTtsEngine {
     
     /// Load voice embedding from a combined voices file
     fn load_voice_from_combined_file(&self, voice_file: &std::path::Path, voice_id: &str) -> VocalizeResult<Vec<f32>> {
-        // Load and parse combined voices file
         let voice_data = std::fs::read(voice_file)
             .map_err(|e| VocalizeError::SynthesisError {
-                message: format!("Failed to read combined voices file: {}", e)
+                message: format!("Failed to read combined voices file {}: {}", voice_file.display(), e)
             })?;
-        
-        // Parse binary format: [4 bytes: voice_count][voice_entries][voice_data]
-        // Each entry: [32 bytes: voice_id][4 bytes: offset][4 bytes: size]
-        
-        if voice_data.len() < 4 {
-            return Err(VocalizeError::SynthesisError {
-                message: "Combined voices file too small to contain header".to_string()
-            });
-        }
-        
-        // Read voice count from first 4 bytes (little-endian)
-        let voice_count = u32::from_le_bytes([voice_data[0], voice_data[1], voice_data[2], voice_data[3]]) as usize;
-        
-        tracing::debug!("Combined voices file contains {} voices", voice_count);
-        
-        // Calculate expected header size: 4 + (voice_count * 40)
-        let header_size = 4 + (voice_count * 40);
-        if voice_data.len() < header_size {
-            return Err(VocalizeError::SynthesisError {
-                message: format!("Combined voices file too small for {} voice entries", voice_count)
-            });
-        }
-        
-        // Search for the requested voice_id
-        for i in 0..voice_count {
-            let entry_offset = 4 + (i * 40);
-            
-            // Read voice_id (32 bytes, null-terminated string)
-            let voice_id_bytes = &voice_data[entry_offset..entry_offset + 32];
-            let voice_id_str = std::str::from_utf8(voice_id_bytes)
-                .unwrap_or("")
-                .trim_end_matches('\0');
-            
-            if voice_id_str == voice_id {
-                // Found matching voice, read offset and size
-                let offset_bytes = &voice_data[entry_offset + 32..entry_offset + 36];
-                let size_bytes = &voice_data[entry_offset + 36..entry_offset + 40];
-                
-                let data_offset = u32::from_le_bytes([offset_bytes[0], offset_bytes[1], offset_bytes[2], offset_bytes[3]]) as usize;
-                let data_size = u32::from_le_bytes([size_bytes[0], size_bytes[1], size_bytes[2], size_bytes[3]]) as usize;
-                
-                tracing::debug!("Found voice '{}' at offset {} with size {}", voice_id, data_offset, data_size);
-                
-                // Validate bounds
-                if data_offset + data_size > voice_data.len() {
-                    return Err(VocalizeError::SynthesisError {
-                        message: format!("Voice data for '{}' extends beyond file bounds", voice_id)
-                    });
-                }
-                
-                // Extract voice data and convert to f32 array
-                let voice_bytes = &voice_data[data_offset..data_offset + data_size];
-                
-                if voice_bytes.len() % 4 != 0 {
-                    return Err(VocalizeError::SynthesisError {
-                        message: format!("Voice data size {} not divisible by 4", voice_bytes.len())
-                    });
-                }
-                
-                let mut voice_embedding = Vec::with_capacity(voice_bytes.len() / 4);
-                for chunk in voice_bytes.chunks_exact(4) {
-                    let float_val = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
-                    if !float_val.is_finite() {
-                        return Err(VocalizeError::SynthesisError {
-                            message: format!("Invalid float value in voice '{}': {}", voice_id, float_val)
-                        });
-                    }
-                    voice_embedding.push(float_val);
-                }
-                
-                // For Kokoro, we expect 256-dimensional style vectors
-                if voice_embedding.len() >= 256 {
-                    voice_embedding.truncate(256);
-                }
-                
-                tracing::info!("✅ Loaded voice '{}' from combined file: {} floats", voice_id, voice_embedding.len());
-                return Ok(voice_embedding);
-            }
-        }
-        
-        // Voice not found in combined file
-        Err(VocalizeError::SynthesisError {
-            message: format!("Voice '{}' not found in combined voices file", voice_id)
-        })
+
+        let voice_embedding = read_combined_voice(&voice_data, voice_file, voice_id)?;
+        tracing::info!("✅ Loaded voice '{}' from combined file: {} floats", voice_id, voice_embedding.len());
+        Ok(voice_embedding)
     }
     
     /// Load voice embedding from an individual voice file
@@ -587,4 +1207,1072 @@ impl OnnxTtsEngine {
         Ok(voice_embedding)
     }
     
-}
\ No newline at end of file
+}
+
+/// NFC-normalize `text`, drop characters `policy` filters out and trim it
+fn clean_text(text: &str, policy: PunctuationPolicy) -> String {
+    // Unicode normalization (NFC is better for TTS than NFD)
+    text.nfc()
+        .filter(|&c| policy.keeps(c))
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Static metadata for a model
+fn model_info(model_id: ModelId) -> crate::model::ModelInfo {
+    match model_id {
+        ModelId::Kokoro => crate::model::ModelInfo::kokoro(),
+        ModelId::Chatterbox => crate::model::ModelInfo::chatterbox(),
+        ModelId::Dia => crate::model::ModelInfo::dia(),
+    }
+}
+
+/// Voice embeddings already read from disk, keyed by model and voice ID
+///
+/// Parsing the combined voices file on every synthesis is wasteful when a
+/// voice is reused; failed loads are not cached, so a voice downloaded
+/// later is picked up.
+#[derive(Debug, Default)]
+struct VoiceEmbeddingCache {
+    embeddings: parking_lot::Mutex<HashMap<(String, String), Vec<f32>>>,
+}
+
+impl VoiceEmbeddingCache {
+    /// Cached embedding of `voice_id`, or the result of `load` on a miss
+    fn get_or_load(
+        &self,
+        model_id: &str,
+        voice_id: &str,
+        load: impl FnOnce() -> VocalizeResult<Vec<f32>>,
+    ) -> VocalizeResult<Vec<f32>> {
+        let key = (model_id.to_string(), voice_id.to_string());
+        if let Some(embedding) = self.embeddings.lock().get(&key) {
+            tracing::debug!("Voice embedding cache hit for '{}'", voice_id);
+            return Ok(embedding.clone());
+        }
+
+        // Loading happens outside the lock; a concurrent miss for the same
+        // voice at worst reads the file twice
+        let embedding = load()?;
+        self.embeddings.lock().insert(key, embedding.clone());
+        Ok(embedding)
+    }
+
+    fn clear(&self) {
+        self.embeddings.lock().clear();
+    }
+}
+
+/// Largest chunk [`OnnxTtsEngine::synthesize_from_tokens_with_sink`] hands
+/// its sink at once: 200ms at Kokoro's 24 kHz
+pub const SINK_CHUNK_SAMPLES: usize = 4_800;
+
+/// Incremental [`crossfade_concat`] that emits audio as soon as no later
+/// crossfade can change it
+///
+/// Only the most recent window's audio is held back, since the next window
+/// fades into its tail.
+#[derive(Debug, Default)]
+struct StreamingCrossfade {
+    pending: Vec<f32>,
+    emitted: usize,
+}
+
+impl StreamingCrossfade {
+    /// Crossfade `audio` into the held-back tail over `fade_len` samples,
+    /// then pass everything before `audio`'s contribution to `sink`
+    fn push(&mut self, audio: &[f32], fade_len: usize, sink: &mut impl FnMut(&[f32])) {
+        crossfade_concat(&mut self.pending, audio, fade_len);
+        let keep = audio.len().min(self.pending.len());
+        let ready = self.pending.len() - keep;
+        if ready > 0 {
+            sink(&self.pending[..ready]);
+            self.emitted += ready;
+            self.pending.drain(..ready);
+        }
+    }
+    
+    /// Pass the held-back tail to `sink` and return the total sample count
+    fn finish(self, sink: &mut impl FnMut(&[f32])) -> usize {
+        for chunk in self.pending.chunks(SINK_CHUNK_SAMPLES) {
+            sink(chunk);
+        }
+        self.emitted + self.pending.len()
+    }
+}
+
+/// Tokens per window between the padding tokens at either end
+const MAX_WINDOW_BODY: usize = MAX_CONTEXT_TOKENS - 2;
+
+/// Split `input_ids` into padded windows of at most [`MAX_CONTEXT_TOKENS`]
+/// tokens, where consecutive windows share `overlap` tokens
+///
+/// Padding tokens (`0`) at either end of `input_ids` are stripped and each
+/// window gets its own. `overlap` must be less than [`MAX_WINDOW_BODY`].
+fn context_windows(input_ids: &[i64], overlap: usize) -> Vec<Vec<i64>> {
+    let body = input_ids.strip_prefix(&[0]).unwrap_or(input_ids);
+    let body = body.strip_suffix(&[0]).unwrap_or(body);
+    let step = MAX_WINDOW_BODY - overlap;
+
+    let mut windows = Vec::new();
+    let mut start = 0;
+    loop {
+        let end = (start + MAX_WINDOW_BODY).min(body.len());
+        let mut window = Vec::with_capacity(end - start + 2);
+        window.push(0);
+        window.extend_from_slice(&body[start..end]);
+        window.push(0);
+        windows.push(window);
+        if end == body.len() {
+            return windows;
+        }
+        start += step;
+    }
+}
+
+/// Whether `error` reports that inference ran out of (GPU or host) memory
+///
+/// ONNX Runtime surfaces allocation failures as generic runtime errors, so
+/// this matches the messages the CPU and CUDA allocators produce.
+fn is_out_of_memory(error: &anyhow::Error) -> bool {
+    const OOM_MARKERS: &[&str] = &[
+        "out of memory",
+        "outofmemory",
+        "failed to allocate",
+        "bad_alloc",
+        "cudaerrormemoryallocation",
+    ];
+    error.chain().any(|cause| {
+        let message = cause.to_string().to_lowercase();
+        OOM_MARKERS.iter().any(|marker| message.contains(marker))
+    })
+}
+
+/// Run `run` over consecutive index ranges of `len` items, at most
+/// `batch_size` at a time, halving the batch after an out-of-memory error
+///
+/// Results are concatenated in order. Non-OOM errors, and an OOM error at a
+/// batch size of one, are returned as-is.
+async fn run_batched_with_oom_retry<R, F, Fut>(len: usize, batch_size: usize, mut run: F) -> Result<Vec<R>>
+where
+    F: FnMut(Range<usize>) -> Fut,
+    Fut: Future<Output = Result<Vec<R>>>,
+{
+    let mut batch_size = batch_size.max(1);
+    let mut results = Vec::with_capacity(len);
+    let mut start = 0;
+    while start < len {
+        let end = (start + batch_size).min(len);
+        match run(start..end).await {
+            Ok(batch) => {
+                results.extend(batch);
+                start = end;
+            }
+            Err(error) if batch_size > 1 && is_out_of_memory(&error) => {
+                batch_size /= 2;
+                tracing::warn!("Inference ran out of memory; retrying with batch size {}: {:#}", batch_size, error);
+            }
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(results)
+}
+
+/// Compare `style_vector` with a voice's `stored` embedding
+///
+/// Returns an error on a dimension mismatch, and a warning message when the
+/// vectors point in clearly different directions.
+fn style_mismatch(style_vector: &[f32], stored: &[f32], voice_id: &str) -> VocalizeResult<Option<String>> {
+    if style_vector.len() != stored.len() {
+        return Err(VocalizeError::invalid_input(format!(
+            "Style vector has {} dimensions, but voice '{voice_id}' has {}-dimensional embeddings",
+            style_vector.len(),
+            stored.len()
+        )));
+    }
+
+    let dot: f32 = style_vector.iter().zip(stored).map(|(a, b)| a * b).sum();
+    let norms = style_vector.iter().map(|a| a * a).sum::<f32>().sqrt() * stored.iter().map(|b| b * b).sum::<f32>().sqrt();
+    let similarity = if norms > 0.0 { dot / norms } else { 0.0 };
+
+    Ok((similarity < STYLE_MATCH_MIN_SIMILARITY).then(|| format!(
+        "Style vector does not match voice '{voice_id}' (cosine similarity {similarity:.2}); \
+         the audio will not sound like that voice"
+    )))
+}
+
+/// Size of one header entry in a combined voices file: a 32-byte
+/// NUL-padded voice ID followed by little-endian `u32` offset and size
+const COMBINED_VOICE_ENTRY_LEN: usize = 40;
+
+/// Header entry describing one voice in a combined voices file
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CombinedVoiceEntry {
+    voice_id: String,
+    offset: usize,
+    size: usize,
+}
+
+/// Files that may hold the embedding of `voice_id`, in the order they are
+/// tried
+///
+/// An explicit `voices_file` is the only candidate; otherwise the model's
+/// cache directory under `cache_dir` is searched for per-voice files and
+/// then the combined voices file.
+fn voice_file_candidates(
+    cache_dir: &std::path::Path,
+    voices_file: Option<&std::path::Path>,
+    model_id: &str,
+    voice_id: &str,
+) -> VocalizeResult<Vec<PathBuf>> {
+    if let Some(voices_file) = voices_file {
+        return Ok(vec![voices_file.to_path_buf()]);
+    }
+    
+    // 2025 Fix: Use correct path structure that matches ModelRegistry
+    let model_cache = match model_id {
+        "kokoro" => cache_dir.join("models--direct_download").join("local"),
+        _ => return Err(VocalizeError::SynthesisError {
+            message: format!("Unsupported model for voice loading: {}", model_id)
+        }),
+    };
+    
+    Ok(vec![
+        model_cache.join("voices").join(format!("{}.bin", voice_id)),
+        model_cache.join(format!("voice_{}.bin", voice_id)),
+        model_cache.join("voices-v1.0.bin"), // Single voices file
+    ])
+}
+
+/// Leading bytes of a zip archive, which is what NumPy's `.npz` files are
+const ZIP_MAGIC: &[u8; 4] = b"PK\x03\x04";
+
+/// Leading bytes of a NumPy `.npy` array
+const NPY_MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// Suffix of each array in an NPZ voices file, which stores one
+/// `<voice_id>.npy` array per voice
+const NPY_SUFFIX: &str = ".npy";
+
+/// Error for an NPZ voices file whose arrays this reader does not handle;
+/// unlike a corrupt download, fetching the same file again will not help
+fn unsupported_voices_format(path: &std::path::Path, detail: &str) -> VocalizeError {
+    VocalizeError::synthesis(format!(
+        "Voices file {} uses an unsupported NPZ voices format: {}",
+        path.display(),
+        detail
+    ))
+}
+
+/// Error for a combined voices file that cannot be parsed
+fn corrupt_voices_file(path: &std::path::Path, detail: &str) -> VocalizeError {
+    VocalizeError::synthesis(format!(
+        "Combined voices file {} is corrupt: {}. Delete it and re-download the model \
+         with 'vocalize models download kokoro'.",
+        path.display(),
+        detail
+    ))
+}
+
+/// Parse the header of a combined voices file
+///
+/// The format is a little-endian `u32` voice count followed by that many
+/// [`COMBINED_VOICE_ENTRY_LEN`]-byte entries, then the embedding data.
+fn parse_combined_voices_header(data: &[u8], path: &std::path::Path) -> VocalizeResult<Vec<CombinedVoiceEntry>> {
+    let Some(count_bytes) = data.get(..4) else {
+        return Err(corrupt_voices_file(
+            path,
+            &format!("bad header, file is {} bytes but the voice count alone needs 4", data.len()),
+        ));
+    };
+    let voice_count = u32::from_le_bytes([count_bytes[0], count_bytes[1], count_bytes[2], count_bytes[3]]) as usize;
+
+    let header_size = voice_count
+        .checked_mul(COMBINED_VOICE_ENTRY_LEN)
+        .and_then(|entries| entries.checked_add(4))
+        .filter(|&size| size <= data.len())
+        .ok_or_else(|| {
+            corrupt_voices_file(
+                path,
+                &format!(
+                    "bad header, it declares {voice_count} voices but the file is only {} bytes",
+                    data.len()
+                ),
+            )
+        })?;
+
+    data[4..header_size]
+        .chunks_exact(COMBINED_VOICE_ENTRY_LEN)
+        .enumerate()
+        .map(|(index, entry)| {
+            let voice_id = std::str::from_utf8(&entry[..32])
+                .map_err(|_| {
+                    corrupt_voices_file(
+                        path,
+                        &format!("bad header, entry {index} of {voice_count} has a voice ID that is not UTF-8"),
+                    )
+                })?
+                .trim_end_matches('\0')
+                .to_string();
+            let offset = u32::from_le_bytes([entry[32], entry[33], entry[34], entry[35]]) as usize;
+            let size = u32::from_le_bytes([entry[36], entry[37], entry[38], entry[39]]) as usize;
+            Ok(CombinedVoiceEntry { voice_id, offset, size })
+        })
+        .collect()
+}
+
+/// Open the bytes of an NPZ voices file as a zip archive
+fn open_npz<'a>(data: &'a [u8], path: &std::path::Path) -> VocalizeResult<zip::ZipArchive<std::io::Cursor<&'a [u8]>>> {
+    zip::ZipArchive::new(std::io::Cursor::new(data))
+        .map_err(|e| corrupt_voices_file(path, &format!("bad NPZ archive, {e}")))
+}
+
+/// Voice IDs in an NPZ voices file, in archive order
+fn list_npz_voices(data: &[u8], path: &std::path::Path) -> VocalizeResult<Vec<String>> {
+    let mut archive = open_npz(data, path)?;
+    (0..archive.len())
+        .filter_map(|index| match archive.by_index_raw(index) {
+            Ok(entry) => entry.name().strip_suffix(NPY_SUFFIX).map(|voice_id| Ok(voice_id.to_string())),
+            Err(e) => Some(Err(corrupt_voices_file(path, &format!("bad NPZ archive entry {index}, {e}")))),
+        })
+        .collect()
+}
+
+/// Read one voice's full embedding table from the bytes of an NPZ voices file
+///
+/// Each voice is a little-endian `float32` array, e.g. Kokoro's
+/// `(510, 1, 256)` table.
+fn read_npz_voice_table(data: &[u8], path: &std::path::Path, voice_id: &str) -> VocalizeResult<Vec<f32>> {
+    use std::io::Read;
+
+    let voice_count = list_npz_voices(data, path)?.len();
+    let mut archive = open_npz(data, path)?;
+    let mut array = match archive.by_name(&format!("{voice_id}{NPY_SUFFIX}")) {
+        Ok(array) => array,
+        Err(zip::result::ZipError::FileNotFound) => {
+            return Err(VocalizeError::synthesis(format!(
+                "Voice '{voice_id}' not found in NPZ voices file {}, which contains {voice_count} voices",
+                path.display()
+            )));
+        }
+        Err(zip::result::ZipError::UnsupportedArchive(detail)) => {
+            return Err(unsupported_voices_format(path, &format!("voice '{voice_id}' {detail}")));
+        }
+        Err(e) => return Err(corrupt_voices_file(path, &format!("voice '{voice_id}' cannot be read, {e}"))),
+    };
+    let mut bytes = Vec::with_capacity(usize::try_from(array.size()).unwrap_or_default());
+    array
+        .read_to_end(&mut bytes)
+        .map_err(|e| corrupt_voices_file(path, &format!("voice '{voice_id}' cannot be read, {e}")))?;
+
+    let voice_embedding = parse_npy_f32(&bytes, path, voice_id)?;
+    if let Some(index) = voice_embedding.iter().position(|value| !value.is_finite()) {
+        return Err(corrupt_voices_file(
+            path,
+            &format!("voice '{voice_id}' has a non-finite value ({}) at index {index}", voice_embedding[index]),
+        ));
+    }
+    Ok(voice_embedding)
+}
+
+/// Values of a little-endian `float32` NumPy array stored in C order
+fn parse_npy_f32(bytes: &[u8], path: &std::path::Path, voice_id: &str) -> VocalizeResult<Vec<f32>> {
+    let truncated = || corrupt_voices_file(path, &format!("voice '{voice_id}' has a truncated array header"));
+    if !bytes.starts_with(NPY_MAGIC) {
+        return Err(corrupt_voices_file(path, &format!("voice '{voice_id}' is not a NumPy array")));
+    }
+    let (header_len, header_start) = match bytes.get(6).copied() {
+        Some(1) => {
+            let len = bytes.get(8..10).ok_or_else(truncated)?;
+            (usize::from(u16::from_le_bytes([len[0], len[1]])), 10)
+        }
+        Some(2 | 3) => {
+            let len = bytes.get(8..12).ok_or_else(truncated)?;
+            (u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize, 12)
+        }
+        Some(version) => {
+            return Err(unsupported_voices_format(path, &format!("voice '{voice_id}' uses NumPy format version {version}")));
+        }
+        None => return Err(truncated()),
+    };
+    let header = bytes
+        .get(header_start..header_start + header_len)
+        .and_then(|header| std::str::from_utf8(header).ok())
+        .ok_or_else(truncated)?;
+    if !header.contains("'descr': '<f4'") {
+        return Err(unsupported_voices_format(
+            path,
+            &format!("voice '{voice_id}' is not a little-endian float32 array ({})", header.trim()),
+        ));
+    }
+    if header.contains("'fortran_order': True") {
+        return Err(unsupported_voices_format(path, &format!("voice '{voice_id}' is stored in Fortran order")));
+    }
+
+    let body = &bytes[header_start + header_len..];
+    if body.len() % 4 != 0 {
+        return Err(corrupt_voices_file(
+            path,
+            &format!("voice '{voice_id}' has {} bytes of data, not a whole number of floats", body.len()),
+        ));
+    }
+    Ok(body
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect())
+}
+
+/// List the voice IDs stored in a voices file, in file order
+///
+/// Both the combined binary format and NumPy's NPZ format, in which Kokoro
+/// ships `voices-v1.0.bin`, are understood.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or its header is corrupt
+pub(crate) fn list_combined_voices(path: &std::path::Path) -> VocalizeResult<Vec<String>> {
+    let data = std::fs::read(path).map_err(|e| {
+        VocalizeError::file(format!("Failed to read combined voices file {}: {}", path.display(), e))
+    })?;
+    if data.starts_with(ZIP_MAGIC) {
+        return list_npz_voices(&data, path);
+    }
+    Ok(parse_combined_voices_header(&data, path)?
+        .into_iter()
+        .map(|entry| entry.voice_id)
+        .collect())
+}
+
+/// Read one voice's full embedding table from a combined voices file
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, is corrupt or does not
+/// contain `voice_id`
+pub(crate) fn read_voice_table(path: &std::path::Path, voice_id: &str) -> VocalizeResult<Vec<f32>> {
+    let data = std::fs::read(path).map_err(|e| {
+        VocalizeError::file(format!("Failed to read combined voices file {}: {}", path.display(), e))
+    })?;
+    read_combined_voice_table(&data, path, voice_id)
+}
+
+/// Read one voice's style vector from the bytes of a combined voices file
+///
+/// This is the first [`STYLE_VECTOR_DIM`] values of the voice's table.
+fn read_combined_voice(data: &[u8], path: &std::path::Path, voice_id: &str) -> VocalizeResult<Vec<f32>> {
+    let mut voice_embedding = read_combined_voice_table(data, path, voice_id)?;
+    voice_embedding.truncate(STYLE_VECTOR_DIM);
+    Ok(voice_embedding)
+}
+
+/// Read one voice's full embedding table from the bytes of a combined
+/// voices file
+///
+/// NPZ voices files are handed to [`read_npz_voice_table`].
+///
+/// Errors name `path`, the number of voices the header declares and what
+/// exactly is wrong, so a corrupt download can be told apart from a voice
+/// the file simply does not contain.
+fn read_combined_voice_table(data: &[u8], path: &std::path::Path, voice_id: &str) -> VocalizeResult<Vec<f32>> {
+    if data.starts_with(ZIP_MAGIC) {
+        return read_npz_voice_table(data, path, voice_id);
+    }
+    let entries = parse_combined_voices_header(data, path)?;
+    let voice_count = entries.len();
+
+    let entry = entries.iter().find(|entry| entry.voice_id == voice_id).ok_or_else(|| {
+        VocalizeError::synthesis(format!(
+            "Voice '{voice_id}' not found in combined voices file {}, which contains {voice_count} voices",
+            path.display()
+        ))
+    })?;
+    tracing::debug!("Found voice '{}' at offset {} with size {}", voice_id, entry.offset, entry.size);
+
+    let voice_bytes = entry
+        .offset
+        .checked_add(entry.size)
+        .and_then(|end| data.get(entry.offset..end))
+        .ok_or_else(|| {
+            corrupt_voices_file(
+                path,
+                &format!(
+                    "voice '{voice_id}' (one of {voice_count}) has offset {} and size {} \
+                     but the file is only {} bytes",
+                    entry.offset,
+                    entry.size,
+                    data.len()
+                ),
+            )
+        })?;
+    if voice_bytes.len() % 4 != 0 {
+        return Err(corrupt_voices_file(
+            path,
+            &format!("voice '{voice_id}' has {} bytes of data, not a whole number of floats", voice_bytes.len()),
+        ));
+    }
+
+    let mut voice_embedding = Vec::with_capacity(voice_bytes.len() / 4);
+    for (index, chunk) in voice_bytes.chunks_exact(4).enumerate() {
+        let value = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        if !value.is_finite() {
+            return Err(corrupt_voices_file(
+                path,
+                &format!("voice '{voice_id}' has a non-finite value ({value}) at index {index}"),
+            ));
+        }
+        voice_embedding.push(value);
+    }
+    Ok(voice_embedding)
+}
+
+/// Check that `style_vector` has [`STYLE_VECTOR_DIM`] values
+///
+/// An empty vector is reported as missing rather than as the wrong length,
+/// since it usually means the voice embedding never loaded.
+///
+/// # Errors
+///
+/// Returns an invalid-input error if the vector is empty or has the wrong
+/// number of values
+pub fn check_style_vector_len(style_vector: &[f32]) -> VocalizeResult<()> {
+    if style_vector.is_empty() {
+        return Err(VocalizeError::invalid_input("Style vector missing (did voice load fail?)"));
+    }
+    if style_vector.len() != STYLE_VECTOR_DIM {
+        return Err(VocalizeError::invalid_input(format!(
+            "Style vector must be {STYLE_VECTOR_DIM} dimensions, got {}",
+            style_vector.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Whether `style_vector` is empty or all zeros, as left by a failed embedding load
+fn is_missing_style(style_vector: &[f32]) -> bool {
+    style_vector.iter().all(|&x| x.abs() < 0.001)
+}
+
+/// Check that every token ID indexes into the model's embedding table
+fn validate_token_ids(input_ids: &[i64], vocab_size: usize) -> VocalizeResult<()> {
+    let out_of_range = input_ids
+        .iter()
+        .enumerate()
+        .find(|&(_, &id)| usize::try_from(id).map_or(true, |id| id >= vocab_size));
+
+    match out_of_range {
+        Some((position, id)) => Err(VocalizeError::invalid_input(format!(
+            "Token id {id} at position {position} is out of vocabulary range [0, {vocab_size})"
+        ))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_estimate_sample_count_scales_with_speed() {
+        assert_eq!(estimate_sample_count(0, 1.0), 0);
+        assert_eq!(estimate_sample_count(10, 1.0), 10 * ESTIMATED_SAMPLES_PER_TOKEN);
+        assert_eq!(estimate_sample_count(10, 2.0), 5 * ESTIMATED_SAMPLES_PER_TOKEN);
+        assert_eq!(estimate_sample_count(10, 0.5), 20 * ESTIMATED_SAMPLES_PER_TOKEN);
+        assert_eq!(estimate_sample_count(10, 0.0), 10 * ESTIMATED_SAMPLES_PER_TOKEN);
+        assert_eq!(estimate_sample_count(10, f32::NAN), 10 * ESTIMATED_SAMPLES_PER_TOKEN);
+    }
+
+    /// Bytes of a combined voices file holding `voices` in order
+    fn combined_voices_file(voices: &[(&str, Vec<f32>)]) -> Vec<u8> {
+        let mut header = (voices.len() as u32).to_le_bytes().to_vec();
+        let mut body = Vec::new();
+        let data_start = 4 + voices.len() * COMBINED_VOICE_ENTRY_LEN;
+        for (voice_id, embedding) in voices {
+            let mut id = [0u8; 32];
+            id[..voice_id.len()].copy_from_slice(voice_id.as_bytes());
+            header.extend_from_slice(&id);
+            header.extend_from_slice(&((data_start + body.len()) as u32).to_le_bytes());
+            header.extend_from_slice(&((embedding.len() * 4) as u32).to_le_bytes());
+            body.extend(embedding.iter().flat_map(|value| value.to_le_bytes()));
+        }
+        header.extend(body);
+        header
+    }
+
+    fn read_error(data: &[u8], voice_id: &str) -> String {
+        read_combined_voice(data, Path::new("/models/voices-v1.0.bin"), voice_id)
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn test_read_combined_voice() {
+        let data = combined_voices_file(&[("af_one", vec![0.1; 300]), ("am_two", vec![0.2; 256])]);
+        let path = Path::new("voices-v1.0.bin");
+
+        let one = read_combined_voice(&data, path, "af_one").unwrap();
+        assert_eq!(one, vec![0.1; 256]);
+        assert_eq!(read_combined_voice(&data, path, "am_two").unwrap(), vec![0.2; 256]);
+
+        let message = read_error(&data, "bf_missing");
+        assert!(message.contains("bf_missing"), "{message}");
+        assert!(message.contains("/models/voices-v1.0.bin"), "{message}");
+        assert!(message.contains("contains 2 voices"), "{message}");
+    }
+
+    #[test]
+    fn test_voice_file_candidates_prefer_override() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache_dir = temp_dir.path().join("cache");
+        let cached_voices = cache_dir.join("models--direct_download").join("local").join("voices-v1.0.bin");
+        let override_voices = temp_dir.path().join("custom").join("voices-v1.0.bin");
+        for (path, value) in [(&cached_voices, 0.1), (&override_voices, 0.7)] {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, combined_voices_file(&[("af_one", vec![value; 256])])).unwrap();
+        }
+
+        let candidates = voice_file_candidates(&cache_dir, None, "kokoro", "af_one").unwrap();
+        assert_eq!(candidates.last(), Some(&cached_voices));
+
+        let candidates = voice_file_candidates(&cache_dir, Some(&override_voices), "kokoro", "af_one").unwrap();
+        assert_eq!(candidates, vec![override_voices.clone()]);
+        let embedding = read_combined_voice(&std::fs::read(&candidates[0]).unwrap(), &candidates[0], "af_one").unwrap();
+        assert_eq!(embedding, vec![0.7; 256]);
+    }
+
+    #[test]
+    fn test_voice_embedding_cache_parses_file_once() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let voices_file = temp_dir.path().join("voices-v1.0.bin");
+        std::fs::write(&voices_file, combined_voices_file(&[("af_one", vec![0.1; 256]), ("am_two", vec![0.2; 256])])).unwrap();
+
+        let mut engine = OnnxTtsEngine::without_runtime(temp_dir.path().to_path_buf());
+        engine.set_voices_file(voices_file);
+        let reads = |engine: &OnnxTtsEngine| engine.voice_reads.load(std::sync::atomic::Ordering::SeqCst);
+
+        assert_eq!(engine.load_voice_embedding("kokoro", "af_one").unwrap(), vec![0.1; 256]);
+        assert_eq!(engine.load_voice_embedding("kokoro", "af_one").unwrap(), vec![0.1; 256]);
+        assert_eq!(reads(&engine), 1);
+
+        // Other voices and failed loads each go to disk; failures are retried
+        assert_eq!(engine.load_voice_embedding("kokoro", "am_two").unwrap(), vec![0.2; 256]);
+        assert!(engine.load_voice_embedding("kokoro", "missing").is_err());
+        assert!(engine.load_voice_embedding("kokoro", "missing").is_err());
+        assert_eq!(reads(&engine), 4);
+
+        engine.clear_voice_cache();
+        engine.load_voice_embedding("kokoro", "af_one").unwrap();
+        assert_eq!(reads(&engine), 5);
+    }
+
+    #[test]
+    fn test_read_combined_voice_bad_header() {
+        let message = read_error(&[1, 0], "af_one");
+        assert!(message.contains("/models/voices-v1.0.bin is corrupt: bad header"), "{message}");
+        assert!(message.contains("2 bytes"), "{message}");
+        assert!(message.contains("re-download"), "{message}");
+
+        // Header claims more entries than the file holds
+        let mut data = combined_voices_file(&[("af_one", vec![0.1; 4])]);
+        data[..4].copy_from_slice(&1000u32.to_le_bytes());
+        let message = read_error(&data, "af_one");
+        assert!(message.contains("bad header"), "{message}");
+        assert!(message.contains("declares 1000 voices"), "{message}");
+
+        let mut data = combined_voices_file(&[("af_one", vec![0.1; 4])]);
+        data[4] = 0xFF;
+        let message = read_error(&data, "af_one");
+        assert!(message.contains("entry 0 of 1"), "{message}");
+        assert!(message.contains("not UTF-8"), "{message}");
+    }
+
+    /// Bytes of a version 1 `.npy` array with dtype `descr` and raw `body`
+    fn npy_array(descr: &str, shape: &str, body: &[u8]) -> Vec<u8> {
+        let mut header = format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape}, }}");
+        let unpadded = NPY_MAGIC.len() + 4 + header.len() + 1;
+        header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+        header.push('\n');
+
+        let mut bytes = NPY_MAGIC.to_vec();
+        bytes.extend_from_slice(&[1, 0]);
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.extend_from_slice(body);
+        bytes
+    }
+
+    /// `float32` array of Kokoro's `(510, 1, 256)` voice table shape
+    fn npy_voice_table(value: f32) -> Vec<u8> {
+        let body: Vec<u8> = std::iter::repeat(value.to_le_bytes()).take(510 * 256).flatten().collect();
+        npy_array("<f4", "(510, 1, 256)", &body)
+    }
+
+    /// Bytes of an NPZ voices file holding one array per voice, like the
+    /// `voices-v1.0.bin` Kokoro ships
+    fn npz_voices_file(voices: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        use std::io::Write;
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (voice_id, array) in voices {
+            let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+            writer.start_file(format!("{voice_id}.npy"), options).unwrap();
+            writer.write_all(array).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_read_npz_voice() {
+        let data = npz_voices_file(&[("af_one", npy_voice_table(0.1)), ("am_two", npy_voice_table(0.2))]);
+        let path = Path::new("voices-v1.0.bin");
+
+        assert_eq!(list_npz_voices(&data, path).unwrap(), ["af_one", "am_two"]);
+        assert_eq!(read_combined_voice(&data, path, "af_one").unwrap(), vec![0.1; STYLE_VECTOR_DIM]);
+        assert_eq!(read_combined_voice(&data, path, "am_two").unwrap(), vec![0.2; STYLE_VECTOR_DIM]);
+        assert_eq!(read_combined_voice_table(&data, path, "am_two").unwrap(), vec![0.2; 510 * STYLE_VECTOR_DIM]);
+
+        let message = read_error(&data, "bf_missing");
+        assert!(message.contains("'bf_missing' not found"), "{message}");
+        assert!(message.contains("contains 2 voices"), "{message}");
+    }
+
+    #[test]
+    fn test_voice_manager_exports_voice_from_voices_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let voices_file = temp_dir.path().join("voices-v1.0.bin");
+        let table = vec![0.25; 510 * STYLE_VECTOR_DIM];
+        std::fs::write(&voices_file, combined_voices_file(&[("af_one", table.clone())])).unwrap();
+
+        let path = temp_dir.path().join("af_one.bin");
+        let mut manager = VoiceManager::new();
+        assert!(manager.export_voice_embedding("af_one", &path).is_err());
+
+        // The whole table is exported, not just the style vector
+        manager.set_voices_file(voices_file);
+        manager.export_voice_embedding("af_one", &path).unwrap();
+        manager.import_voice_embedding("copy", &path).unwrap();
+        assert_eq!(manager.voice_embedding("copy"), Some(table));
+        assert!(manager.export_voice_embedding("bf_missing", &path).is_err());
+    }
+
+    #[test]
+    fn test_registered_voice_embedding_is_used_for_synthesis() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let voices_file = temp_dir.path().join("voices-v1.0.bin");
+        std::fs::write(&voices_file, npz_voices_file(&[("af_one", npy_voice_table(0.1))])).unwrap();
+
+        let mut engine = OnnxTtsEngine::without_runtime(temp_dir.path().to_path_buf());
+        engine.set_voices_file(voices_file);
+        let manager = VoiceManager::new();
+        engine.set_voice_manager(manager.clone());
+        assert_eq!(engine.load_voice_embedding("kokoro", "af_one").unwrap(), vec![0.1; STYLE_VECTOR_DIM]);
+        assert!(engine.load_voice_embedding("kokoro", "my_blend").is_err());
+
+        // Imported after the manager was handed over, with no voice file
+        let pack: Vec<f32> = (0..510 * STYLE_VECTOR_DIM).map(|i| (i / STYLE_VECTOR_DIM) as f32 * 0.001 + 0.1).collect();
+        let pack_file = temp_dir.path().join("my_blend.bin");
+        std::fs::write(&pack_file, pack.iter().flat_map(|value| value.to_le_bytes()).collect::<Vec<u8>>()).unwrap();
+        manager.import_voice_embedding("my_blend", &pack_file).unwrap();
+        assert_eq!(engine.load_voice_embedding("kokoro", "my_blend").unwrap(), pack[..STYLE_VECTOR_DIM].to_vec());
+
+        // A registered embedding overrides the file's, even once cached
+        manager.set_voice_embedding("af_one", vec![0.3; STYLE_VECTOR_DIM]).unwrap();
+        assert_eq!(engine.load_voice_embedding("kokoro", "af_one").unwrap(), vec![0.3; STYLE_VECTOR_DIM]);
+    }
+
+    #[test]
+    fn test_read_npz_voice_unsupported_dtype() {
+        let body: Vec<u8> = std::iter::repeat(0.1f64.to_le_bytes()).take(256).flatten().collect();
+        let data = npz_voices_file(&[("af_one", npy_array("<f8", "(256,)", &body))]);
+
+        let message = read_error(&data, "af_one");
+        assert!(message.contains("unsupported NPZ voices format"), "{message}");
+        assert!(message.contains("not a little-endian float32 array"), "{message}");
+        assert!(!message.contains("re-download"), "{message}");
+    }
+
+    #[test]
+    fn test_read_npz_voice_corrupt() {
+        let mut data = ZIP_MAGIC.to_vec();
+        data.extend_from_slice(&[0; 64]);
+        let message = read_error(&data, "af_one");
+        assert!(message.contains("is corrupt: bad NPZ archive"), "{message}");
+
+        let data = npz_voices_file(&[("af_one", b"not an array".to_vec())]);
+        let message = read_error(&data, "af_one");
+        assert!(message.contains("'af_one' is not a NumPy array"), "{message}");
+    }
+
+    #[test]
+    fn test_read_combined_voice_offset_out_of_bounds() {
+        let mut data = combined_voices_file(&[("af_one", vec![0.1; 4]), ("am_two", vec![0.2; 4])]);
+        let truncated_len = data.len() - 8;
+        data.truncate(truncated_len);
+
+        // The first voice is intact; the second runs past the end
+        assert!(read_combined_voice(&data, Path::new("voices.bin"), "af_one").is_ok());
+        let message = read_error(&data, "am_two");
+        assert!(message.contains("is corrupt"), "{message}");
+        assert!(message.contains("'am_two' (one of 2)"), "{message}");
+        assert!(message.contains(&format!("only {truncated_len} bytes")), "{message}");
+    }
+
+    #[test]
+    fn test_read_combined_voice_non_finite_value() {
+        let mut embedding = vec![0.1; 8];
+        embedding[5] = f32::NAN;
+        let data = combined_voices_file(&[("af_one", embedding)]);
+
+        let message = read_error(&data, "af_one");
+        assert!(message.contains("is corrupt"), "{message}");
+        assert!(message.contains("non-finite value (NaN) at index 5"), "{message}");
+    }
+
+    #[test]
+    fn test_check_style_vector_len_tells_missing_from_wrong_length() {
+        assert!(check_style_vector_len(&[0.1; STYLE_VECTOR_DIM]).is_ok());
+
+        let missing = check_style_vector_len(&[]).unwrap_err().to_string();
+        assert!(missing.contains("Style vector missing (did voice load fail?)"), "{missing}");
+
+        let wrong_length = check_style_vector_len(&[0.1; 255]).unwrap_err().to_string();
+        assert!(wrong_length.contains("must be 256 dimensions, got 255"), "{wrong_length}");
+        assert!(!wrong_length.contains("missing"), "{wrong_length}");
+    }
+
+    #[test]
+    fn test_is_missing_style_detects_failed_loads() {
+        assert!(is_missing_style(&[]));
+        assert!(is_missing_style(&[0.0; 256]));
+        assert!(is_missing_style(&[0.0005; 256]));
+
+        let mut style = vec![0.0; 256];
+        style[100] = 0.2;
+        assert!(!is_missing_style(&style));
+        assert!(!is_missing_style(&[0.1; 256]));
+    }
+
+    #[test]
+    fn test_context_windows_overlap_and_pad() {
+        let body: Vec<i64> = (1..=1000).map(|i| i % 177 + 1).collect();
+        let input_ids: Vec<i64> = std::iter::once(0).chain(body.iter().copied()).chain(std::iter::once(0)).collect();
+
+        let windows = context_windows(&input_ids, 10);
+
+        assert_eq!(windows.len(), 2);
+        for window in &windows {
+            assert!(window.len() <= MAX_CONTEXT_TOKENS);
+            assert_eq!(window[0], 0);
+            assert_eq!(window[window.len() - 1], 0);
+        }
+        assert_eq!(windows[0][1..=MAX_WINDOW_BODY], body[..MAX_WINDOW_BODY]);
+        // The second window starts `overlap` tokens before the first one ended
+        let second_body = &windows[1][1..windows[1].len() - 1];
+        assert_eq!(second_body, &body[MAX_WINDOW_BODY - 10..]);
+
+        // Sequences that fit come back as a single window
+        assert_eq!(context_windows(&[0, 5, 6, 0], 10), vec![vec![0, 5, 6, 0]]);
+    }
+
+    #[test]
+    fn test_streaming_crossfade_matches_crossfade_concat() {
+        let windows: Vec<Vec<f32>> = (0..4)
+            .map(|w| (0..3_000 + w * 700).map(|i| ((i * (w + 3)) as f32 * 0.001).sin()).collect())
+            .collect();
+        let fade_len = 600;
+
+        let mut expected = Vec::new();
+        for window in &windows {
+            crossfade_concat(&mut expected, window, fade_len);
+        }
+
+        let mut streamed = Vec::new();
+        let mut chunks = 0;
+        let mut sink = |chunk: &[f32]| {
+            assert!(!chunk.is_empty());
+            streamed.extend_from_slice(chunk);
+            chunks += 1;
+        };
+        let mut stitcher = StreamingCrossfade::default();
+        for window in &windows {
+            stitcher.push(window, fade_len, &mut sink);
+        }
+        let total = stitcher.finish(&mut sink);
+
+        assert_eq!(total, expected.len());
+        assert_eq!(streamed, expected);
+        // Audio was delivered before the last window arrived
+        assert!(chunks > 1);
+    }
+
+    fn output(name: &str, element_type: &str) -> TensorSpec {
+        TensorSpec { name: name.to_string(), element_type: element_type.to_string(), dimensions: vec![-1] }
+    }
+
+    #[test]
+    fn test_audio_output_selected_by_name() {
+        // Durations come first, as a model exporting both might declare them
+        let spec = ModelIoSpec {
+            inputs: Vec::new(),
+            outputs: vec![output("durations", "int64"), output("pred_f0", "float32"), output("waveform", "float32")],
+        };
+
+        let names = crate::model::ModelInfo::kokoro().audio_outputs;
+        assert_eq!(spec.audio_output(&names).unwrap().name, "waveform");
+        // Earlier names win when several are declared
+        let both = ModelIoSpec { outputs: vec![output("waveform", "float32"), output("audio", "float32")], ..spec.clone() };
+        assert_eq!(both.audio_output(&names).unwrap().name, "audio");
+
+        let missing = ModelIoSpec { outputs: vec![output("durations", "int64")], ..spec.clone() };
+        let message = missing.audio_output(&names).unwrap_err().to_string();
+        assert!(message.contains("durations"), "{message}");
+        assert!(message.contains("audio"), "{message}");
+
+        let wrong_type = ModelIoSpec { outputs: vec![output("audio", "int64")], ..spec };
+        assert!(wrong_type.audio_output(&names).is_err());
+    }
+
+    #[test]
+    fn test_punctuation_policy() {
+        let text = "Email me@example.com (re: #42)!";
+
+        assert_eq!(clean_text(text, PunctuationPolicy::Default), "Email meexample.com re: 42!");
+        assert_eq!(clean_text(text, PunctuationPolicy::PreserveAll), text);
+        assert_eq!(clean_text(text, PunctuationPolicy::StripAll), "Email meexamplecom re 42");
+
+        // Control characters never survive, even when preserving everything
+        assert_eq!(clean_text("a\u{0007}b", PunctuationPolicy::PreserveAll), "ab");
+        assert_eq!(PunctuationPolicy::default(), PunctuationPolicy::Default);
+    }
+
+    #[test]
+    fn test_self_test_report_passes_healthy_output() {
+        let speech: Vec<f32> = (0..4_800).map(|i| (i as f32 * 0.05).sin() * 0.5).collect();
+
+        let report = SelfTestReport::check(ModelId::Kokoro, &speech);
+        assert!(report.passed(), "{:?}", report.failures);
+        assert_eq!(report.sample_count, 4_800);
+        assert_eq!(report.non_finite_samples, 0);
+        assert!((report.peak - 0.5).abs() < 1e-3);
+        assert_eq!(report.fingerprint, audio_fingerprint(&speech));
+
+        // Noise below the 16-bit quantization step leaves the fingerprint unchanged
+        let jittered: Vec<f32> = speech.iter().map(|sample| sample + 1e-7).collect();
+        assert_eq!(audio_fingerprint(&jittered), report.fingerprint);
+        assert_ne!(audio_fingerprint(&speech[1..]), report.fingerprint);
+    }
+
+    #[test]
+    fn test_self_test_report_flags_broken_output() {
+        assert!(!SelfTestReport::check(ModelId::Kokoro, &[]).passed());
+        assert!(!SelfTestReport::check(ModelId::Kokoro, &[0.0; 4_800]).passed());
+
+        let mut speech: Vec<f32> = (0..4_800).map(|i| (i as f32 * 0.05).sin() * 0.5).collect();
+        speech[10] = f32::NAN;
+        speech[20] = 3.0;
+        let report = SelfTestReport::check(ModelId::Kokoro, &speech);
+        assert_eq!(report.non_finite_samples, 1);
+        assert_eq!(report.failures.len(), 2, "{:?}", report.failures);
+        assert!(report.failures[0].contains("NaN or infinite"));
+        assert!(report.failures[1].contains("exceeds full scale"));
+    }
+
+    #[test]
+    fn test_is_out_of_memory_matches_allocator_errors() {
+        assert!(is_out_of_memory(&anyhow::anyhow!(
+            "ONNX inference failed: CUDA failure 2: out of memory"
+        )));
+        assert!(is_out_of_memory(
+            &anyhow::anyhow!("Failed to allocate memory for requested buffer of size 8388608")
+                .context("Batch failed")
+        ));
+        assert!(!is_out_of_memory(&anyhow::anyhow!("Invalid input name: tokens")));
+    }
+
+    #[tokio::test]
+    async fn test_run_batched_with_oom_retry_halves_batch_size() {
+        let mut attempts = Vec::new();
+        let mut failed_once = false;
+
+        let results = run_batched_with_oom_retry(5, 4, |range: Range<usize>| {
+            attempts.push(range.len());
+            // Mock session: the first full batch exhausts GPU memory
+            let result = if range.len() == 4 && !failed_once {
+                failed_once = true;
+                Err(anyhow::anyhow!("CUDA error cudaErrorMemoryAllocation: out of memory"))
+            } else {
+                Ok(range.collect::<Vec<_>>())
+            };
+            async move { result }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(results, vec![0, 1, 2, 3, 4]);
+        assert_eq!(attempts, vec![4, 2, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_run_batched_with_oom_retry_gives_up_at_one() {
+        let mut attempts = Vec::new();
+        let error = run_batched_with_oom_retry(3, 2, |range: Range<usize>| {
+            attempts.push(range.len());
+            async { Err::<Vec<usize>, _>(anyhow::anyhow!("std::bad_alloc")) }
+        })
+        .await
+        .unwrap_err();
+
+        assert!(is_out_of_memory(&error));
+        assert_eq!(attempts, vec![2, 1]);
+
+        // Errors other than OOM are not retried
+        let mut calls = 0;
+        let result = run_batched_with_oom_retry(3, 2, |_range: Range<usize>| {
+            calls += 1;
+            async { Err::<Vec<usize>, _>(anyhow::anyhow!("Invalid input name: tokens")) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_style_mismatch_reports_dimension_and_identity() {
+        let stored: Vec<f32> = (0..256).map(|i| (i as f32 * 0.1).sin() * 0.3).collect();
+
+        let error = style_mismatch(&stored[..128], &stored, "af_bella").unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("128 dimensions"), "{message}");
+        assert!(message.contains("af_bella"), "{message}");
+
+        // The voice's own embedding, slightly perturbed, matches
+        let close: Vec<f32> = stored.iter().map(|x| x * 1.1 + 0.001).collect();
+        assert_eq!(style_mismatch(&close, &stored, "af_bella").unwrap(), None);
+
+        // Another voice's embedding is flagged
+        let other: Vec<f32> = stored.iter().map(|x| -x).collect();
+        let warning = style_mismatch(&other, &stored, "af_bella").unwrap().unwrap();
+        assert!(warning.contains("does not match voice 'af_bella'"), "{warning}");
+    }
+
+    #[test]
+    fn test_validate_token_ids_accepts_in_range() {
+        let vocab_size = crate::model::ModelInfo::kokoro().vocab_size;
+        assert!(validate_token_ids(&[0, 50, 177, 0], vocab_size).is_ok());
+        assert!(validate_token_ids(&[], vocab_size).is_ok());
+    }
+
+    #[test]
+    fn test_validate_token_ids_names_offending_token() {
+        let vocab_size = crate::model::ModelInfo::kokoro().vocab_size;
+
+        let error = validate_token_ids(&[0, 50, 178, 0], vocab_size).unwrap_err();
+        assert!(matches!(error, VocalizeError::InvalidInput { .. }));
+        assert!(error.to_string().contains("Token id 178 at position 2"));
+        assert!(error.to_string().contains("[0, 178)"));
+
+        let error = validate_token_ids(&[0, -1], vocab_size).unwrap_err();
+        assert!(error.to_string().contains("Token id -1 at position 1"));
+    }
+}
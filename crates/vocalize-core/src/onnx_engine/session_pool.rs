@@ -129,6 +129,15 @@ impl OnnxSessionPool {
         }
     }
     
+    /// Run `f` against one of the pool's sessions, e.g. to read model metadata
+    pub fn with_session<R>(&self, f: impl FnOnce(&Session) -> R) -> Result<R> {
+        let session = self.sessions.first()
+            .ok_or_else(|| anyhow::anyhow!("Session pool is empty"))?
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to acquire session lock: {}", e))?;
+        Ok(f(&session))
+    }
+    
     /// Check if the pool is healthy
     pub fn is_healthy(&self) -> bool {
         !self.sessions.is_empty() && self.semaphore.available_permits() <= self.max_concurrent
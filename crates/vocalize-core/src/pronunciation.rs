@@ -0,0 +1,211 @@
+//! User-supplied pronunciation overrides.
+//!
+//! Brand names and jargon are often mispronounced by the default
+//! grapheme-to-phoneme stage. A [`PronunciationDictionary`] maps words to
+//! phoneme strings and rewrites matching words into Kokoro's inline
+//! `[word](/phonemes/)` markup, which the phoneme processor tokenizes
+//! directly instead of guessing a pronunciation.
+//!
+//! Dictionary files are plain text with one entry per line: the word, then
+//! whitespace, then its phonemes. Blank lines and lines starting with `#`
+//! are ignored.
+//!
+//! ```text
+//! # brand names
+//! Vocalize    vˈoʊkəlaɪz
+//! Kokoro      kˈoʊkəɹoʊ
+//! ```
+
+use crate::error::{VocalizeError, VocalizeResult};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Word-to-phoneme overrides applied before tokenization
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PronunciationDictionary {
+    /// Phonemes keyed by lowercased word
+    entries: HashMap<String, String>,
+}
+
+impl PronunciationDictionary {
+    /// Create an empty dictionary
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a dictionary from a word-to-phonemes map
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any entry is invalid (see [`insert`](Self::insert))
+    pub fn from_map(overrides: HashMap<String, String>) -> VocalizeResult<Self> {
+        let mut dictionary = Self::new();
+        for (word, phonemes) in overrides {
+            dictionary.insert(&word, &phonemes)?;
+        }
+        Ok(dictionary)
+    }
+
+    /// Load a dictionary from a pronunciation file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or a line is malformed
+    pub fn from_file<P: AsRef<Path>>(path: P) -> VocalizeResult<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+
+        let mut dictionary = Self::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (word, phonemes) = line.split_once(char::is_whitespace).ok_or_else(|| {
+                VocalizeError::invalid_input(format!(
+                    "{}:{}: expected `word phonemes`",
+                    path.display(),
+                    index + 1
+                ))
+            })?;
+            dictionary.insert(word, phonemes.trim()).map_err(|e| {
+                VocalizeError::invalid_input(format!("{}:{}: {e}", path.display(), index + 1))
+            })?;
+        }
+
+        Ok(dictionary)
+    }
+
+    /// Add or replace the pronunciation of a word
+    ///
+    /// Words match case-insensitively.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the word is empty or contains characters other
+    /// than letters, digits, apostrophes and hyphens, or if the phonemes are
+    /// empty or contain markup delimiters (`/`, `[`, `]`, `(`, `)`)
+    pub fn insert(&mut self, word: &str, phonemes: &str) -> VocalizeResult<()> {
+        if word.is_empty() || !word.chars().all(is_word_char) {
+            return Err(VocalizeError::invalid_input(format!(
+                "Invalid pronunciation override word: '{word}'"
+            )));
+        }
+        let phonemes = phonemes.trim();
+        if phonemes.is_empty() || phonemes.contains(['/', '[', ']', '(', ')']) {
+            return Err(VocalizeError::invalid_input(format!(
+                "Invalid phonemes for '{word}': '{phonemes}'"
+            )));
+        }
+
+        self.entries.insert(word.to_lowercase(), phonemes.to_string());
+        Ok(())
+    }
+
+    /// Get the phonemes for a word, if overridden
+    #[must_use]
+    pub fn get(&self, word: &str) -> Option<&str> {
+        self.entries.get(&word.to_lowercase()).map(String::as_str)
+    }
+
+    /// Number of overridden words
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the dictionary has no entries
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Rewrite overridden words in `text` into `[word](/phonemes/)` markup
+    ///
+    /// Everything else, including punctuation and whitespace, is preserved.
+    #[must_use]
+    pub fn apply(&self, text: &str) -> String {
+        if self.is_empty() {
+            return text.to_string();
+        }
+
+        let mut output = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find(is_word_char) {
+            output.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let end = rest.find(|c: char| !is_word_char(c)).unwrap_or(rest.len());
+            let word = &rest[..end];
+            match self.get(word) {
+                Some(phonemes) => {
+                    output.push('[');
+                    output.push_str(word);
+                    output.push_str("](/");
+                    output.push_str(phonemes);
+                    output.push_str("/)");
+                }
+                None => output.push_str(word),
+            }
+            rest = &rest[end..];
+        }
+        output.push_str(rest);
+
+        output
+    }
+}
+
+/// Characters that make up a word for override matching
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '\'' || c == '-'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn dictionary() -> PronunciationDictionary {
+        let mut dictionary = PronunciationDictionary::new();
+        dictionary.insert("Vocalize", "vˈoʊkəlaɪz").unwrap();
+        dictionary
+    }
+
+    #[test]
+    fn test_apply_rewrites_overridden_words() {
+        let dictionary = dictionary();
+        assert_eq!(
+            dictionary.apply("Try vocalize, today."),
+            "Try [vocalize](/vˈoʊkəlaɪz/), today."
+        );
+        // Only whole words match
+        assert_eq!(dictionary.apply("Vocalizers"), "Vocalizers");
+        assert_eq!(PronunciationDictionary::new().apply("Vocalize"), "Vocalize");
+    }
+
+    #[test]
+    fn test_insert_rejects_invalid_entries() {
+        let mut dictionary = PronunciationDictionary::new();
+        assert!(dictionary.insert("", "a").is_err());
+        assert!(dictionary.insert("two words", "a").is_err());
+        assert!(dictionary.insert("word", "  ").is_err());
+        assert!(dictionary.insert("word", "a/b").is_err());
+        assert!(dictionary.is_empty());
+    }
+
+    #[test]
+    fn test_from_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("pronunciations.txt");
+        std::fs::write(&path, "# brands\nVocalize\tvˈoʊkəlaɪz\n\nKokoro  kˈoʊkəɹoʊ\n").unwrap();
+
+        let dictionary = PronunciationDictionary::from_file(&path).unwrap();
+        assert_eq!(dictionary.len(), 2);
+        assert_eq!(dictionary.get("kokoro"), Some("kˈoʊkəɹoʊ"));
+
+        std::fs::write(&path, "Vocalize\n").unwrap();
+        assert!(PronunciationDictionary::from_file(&path).is_err());
+    }
+}
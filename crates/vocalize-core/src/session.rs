@@ -0,0 +1,164 @@
+//! Stateful synthesis sessions for interactive use.
+//!
+//! A [`TtsSession`] pairs an engine with the voice and parameters an
+//! interactive assistant keeps reusing, so each utterance is a single
+//! [`say`](TtsSession::say) call against a warm engine.
+
+use crate::error::{VocalizeError, VocalizeResult};
+use crate::tts_engine::{voice_for_id, AudioData, SynthesisParams, TtsEngine};
+use crate::voice_manager::Voice;
+
+/// An engine plus the voice and parameters used for every utterance
+///
+/// Changing the voice or speed affects later [`say`](Self::say) calls
+/// only. A rejected change leaves the session as it was.
+#[derive(Debug, Clone)]
+pub struct TtsSession {
+    engine: TtsEngine,
+    params: SynthesisParams,
+}
+
+impl TtsSession {
+    /// Start a session speaking with `voice_id`
+    ///
+    /// The engine is initialized up front, so already-installed models do not
+    /// delay the first [`say`](Self::say). A model that is not installed yet
+    /// is still installed by that first call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the engine fails to initialize or its model does
+    /// not support `voice_id`
+    pub async fn new(engine: TtsEngine, voice_id: &str) -> VocalizeResult<Self> {
+        engine.preload_models().await?;
+        let voice = supported_voice(&engine, voice_id).await?;
+        Ok(Self {
+            engine,
+            params: SynthesisParams::new(voice),
+        })
+    }
+
+    /// Synthesize `text` with the session's current voice and parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if synthesis fails, see [`TtsEngine::synthesize`]
+    pub async fn say(&self, text: &str) -> VocalizeResult<AudioData> {
+        self.engine.synthesize(text, &self.params).await
+    }
+
+    /// Switch to `voice_id`, keeping the current speed and pitch
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the engine's model does not support `voice_id`
+    pub async fn set_voice(&mut self, voice_id: &str) -> VocalizeResult<()> {
+        self.params.voice = supported_voice(&self.engine, voice_id).await?;
+        Ok(())
+    }
+
+    /// Change the speed multiplier
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if speed is not in valid range (0.1 to 3.0)
+    pub fn set_speed(&mut self, speed: f32) -> VocalizeResult<()> {
+        self.params = self.params.clone().with_speed(speed)?;
+        Ok(())
+    }
+
+    /// The voice used for the next utterance
+    #[must_use]
+    pub fn voice(&self) -> &Voice {
+        &self.params.voice
+    }
+
+    /// The speed multiplier used for the next utterance
+    #[must_use]
+    pub fn speed(&self) -> f32 {
+        self.params.speed
+    }
+
+    /// All parameters used for the next utterance
+    #[must_use]
+    pub fn params(&self) -> &SynthesisParams {
+        &self.params
+    }
+
+    /// The engine this session synthesizes with
+    #[must_use]
+    pub fn engine(&self) -> &TtsEngine {
+        &self.engine
+    }
+}
+
+/// A [`Voice`] for `voice_id`, if `engine`'s model supports it
+async fn supported_voice(engine: &TtsEngine, voice_id: &str) -> VocalizeResult<Voice> {
+    let voices = engine.supported_voices().await?;
+    if !voices.iter().any(|voice| voice == voice_id) {
+        return Err(VocalizeError::voice_not_supported(voice_id, voices));
+    }
+    Ok(voice_for_id(voice_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::mock_model::{MockTtsModel, MOCK_MODEL_ID};
+    use crate::TtsConfig;
+    use tempfile::TempDir;
+
+    async fn create_mock_session(temp_dir: &TempDir) -> TtsSession {
+        let config = TtsConfig {
+            model_cache_dir: temp_dir.path().to_path_buf(),
+            auto_install_default: true,
+            default_model_id: MOCK_MODEL_ID.to_string(),
+            ..TtsConfig::default()
+        };
+        let engine = TtsEngine::with_config(config).await.unwrap();
+        TtsSession::new(engine, "mock_female").await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_say_uses_session_state() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut session = create_mock_session(&temp_dir).await;
+        assert_eq!(session.voice().id, "mock_female");
+        assert!((session.speed() - 1.0).abs() < f32::EPSILON);
+
+        let audio = session.say("Hello there").await.unwrap();
+        assert_eq!(audio.len(), MockTtsModel::expected_len("Hello there", 1.0));
+
+        session.set_speed(2.0).unwrap();
+        let audio = session.say("Hello there").await.unwrap();
+        assert_eq!(audio.len(), MockTtsModel::expected_len("Hello there", 2.0));
+    }
+
+    #[tokio::test]
+    async fn test_set_voice_keeps_speed() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut session = create_mock_session(&temp_dir).await;
+        session.set_speed(1.5).unwrap();
+
+        session.set_voice("mock_male").await.unwrap();
+        assert_eq!(session.voice().id, "mock_male");
+        assert!((session.speed() - 1.5).abs() < f32::EPSILON);
+        assert!(!session.say("Hi").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_changes_leave_session_unchanged() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut session = create_mock_session(&temp_dir).await;
+
+        let error = session.set_voice("no_such_voice").await.unwrap_err();
+        assert!(matches!(error, VocalizeError::VoiceNotFound { .. }), "{error}");
+        assert_eq!(session.voice().id, "mock_female");
+
+        assert!(session.set_speed(5.0).is_err());
+        assert!((session.speed() - 1.0).abs() < f32::EPSILON);
+
+        let engine = session.engine().clone();
+        assert!(TtsSession::new(engine, "no_such_voice").await.is_err());
+    }
+}
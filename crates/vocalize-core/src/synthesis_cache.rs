@@ -0,0 +1,182 @@
+//! Sentence-level LRU cache of synthesized audio.
+//!
+//! Repeated prompts are common in UIs, so the engine can keep the audio for
+//! recently synthesized sentences and skip the model entirely on a hit.
+
+use crate::tts_engine::{AudioData, SynthesisParams};
+use std::collections::{HashMap, VecDeque};
+use unicode_normalization::UnicodeNormalization;
+
+/// Cache key: everything that affects the synthesized samples
+///
+/// Keys are namespaced by model ID so switching models never serves audio
+/// produced by the previous one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CacheKey {
+    model_id: String,
+    text: String,
+    voice_id: String,
+    speed_bits: u32,
+    pitch_bits: u32,
+}
+
+impl CacheKey {
+    /// Build a key from the request, normalizing the text so that
+    /// whitespace and Unicode composition differences still hit
+    pub(crate) fn new(model_id: &str, text: &str, params: &SynthesisParams) -> Self {
+        let text: String = text.nfc().collect();
+        Self {
+            model_id: model_id.to_string(),
+            text: text.split_whitespace().collect::<Vec<_>>().join(" "),
+            voice_id: params.voice.id.clone(),
+            speed_bits: params.speed.to_bits(),
+            pitch_bits: params.pitch.to_bits(),
+        }
+    }
+}
+
+/// Fixed-capacity least-recently-used cache of synthesized audio
+#[derive(Debug)]
+pub(crate) struct SynthesisCache {
+    capacity: usize,
+    entries: HashMap<CacheKey, AudioData>,
+    /// Keys ordered from least to most recently used
+    order: VecDeque<CacheKey>,
+}
+
+impl SynthesisCache {
+    /// Create a cache holding at most `capacity` entries
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Look up a cached clip, marking it as most recently used
+    pub(crate) fn get(&mut self, key: &CacheKey) -> Option<AudioData> {
+        let audio = self.entries.get(key)?.clone();
+        self.touch(key);
+        Some(audio)
+    }
+
+    /// Store a clip, evicting the least recently used one if full
+    pub(crate) fn insert(&mut self, key: CacheKey, audio: AudioData) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.insert(key.clone(), audio).is_some() {
+            self.touch(&key);
+            return;
+        }
+
+        self.order.push_back(key);
+        while self.order.len() > self.capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.entries.remove(&evicted);
+            }
+        }
+    }
+
+    /// Number of cached clips
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Drop every clip produced by `model_id`
+    pub(crate) fn remove_model(&mut self, model_id: &str) {
+        self.entries.retain(|key, _| key.model_id != model_id);
+        self.order.retain(|key| key.model_id != model_id);
+    }
+
+    /// Drop every cached clip
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    fn touch(&mut self, key: &CacheKey) {
+        if let Some(position) = self.order.iter().position(|k| k == key) {
+            if let Some(key) = self.order.remove(position) {
+                self.order.push_back(key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Gender, Voice, VoiceStyle};
+
+    fn params() -> SynthesisParams {
+        SynthesisParams::new(Voice::new(
+            "af_bella".to_string(),
+            "Bella".to_string(),
+            "en-US".to_string(),
+            Gender::Female,
+            VoiceStyle::Natural,
+        ))
+    }
+
+    #[test]
+    fn test_cache_key_normalizes_text() {
+        let params = params();
+        assert_eq!(
+            CacheKey::new("kokoro", "  Hello   world ", &params),
+            CacheKey::new("kokoro", "Hello world", &params)
+        );
+        // Decomposed "é" matches the precomposed form
+        assert_eq!(
+            CacheKey::new("kokoro", "Caf\u{0065}\u{0301}", &params),
+            CacheKey::new("kokoro", "Caf\u{00e9}", &params)
+        );
+        assert_ne!(
+            CacheKey::new("kokoro", "Hello", &params),
+            CacheKey::new("kokoro", "Hello", &params.clone().with_speed(1.5).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let params = params();
+        let key = |text: &str| CacheKey::new("kokoro", text, &params);
+        let mut cache = SynthesisCache::new(2);
+
+        cache.insert(key("one"), vec![1.0]);
+        cache.insert(key("two"), vec![2.0]);
+        assert_eq!(cache.get(&key("one")), Some(vec![1.0]));
+
+        cache.insert(key("three"), vec![3.0]);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&key("two")), None);
+        assert_eq!(cache.get(&key("one")), Some(vec![1.0]));
+        assert_eq!(cache.get(&key("three")), Some(vec![3.0]));
+    }
+
+    #[test]
+    fn test_cache_namespaced_by_model() {
+        let params = params();
+        let mut cache = SynthesisCache::new(4);
+
+        cache.insert(CacheKey::new("kokoro", "Hello", &params), vec![1.0]);
+        cache.insert(CacheKey::new("other", "Hello", &params), vec![2.0]);
+        assert_eq!(cache.get(&CacheKey::new("other", "Hello", &params)), Some(vec![2.0]));
+
+        cache.remove_model("kokoro");
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&CacheKey::new("kokoro", "Hello", &params)), None);
+        assert_eq!(cache.get(&CacheKey::new("other", "Hello", &params)), Some(vec![2.0]));
+    }
+
+    #[test]
+    fn test_cache_zero_capacity_stores_nothing() {
+        let mut cache = SynthesisCache::new(0);
+        let key = CacheKey::new("kokoro", "Hello", &params());
+        cache.insert(key.clone(), vec![1.0]);
+        assert_eq!(cache.len(), 0);
+        assert_eq!(cache.get(&key), None);
+    }
+}
@@ -0,0 +1,125 @@
+//! Lightweight text analysis used to route synthesis requests.
+
+/// Minimum share of kana among Japanese-capable characters for text to be
+/// treated as Japanese rather than Chinese
+///
+/// Japanese prose mixes kanji with a large proportion of hiragana and
+/// katakana; Chinese uses none.
+const JAPANESE_MIN_KANA_SHARE: f32 = 0.1;
+
+/// Latin-script languages the bundled voices speak, as primary subtags
+///
+/// [`detect_language`] reports all of these as English.
+const LATIN_SCRIPT_LANGUAGES: [&str; 5] = ["en", "es", "fr", "it", "pt"];
+
+/// Script families counted by [`detect_language`]
+#[derive(Debug, Default)]
+struct ScriptCounts {
+    latin: usize,
+    kana: usize,
+    han: usize,
+    hangul: usize,
+    cyrillic: usize,
+    devanagari: usize,
+}
+
+impl ScriptCounts {
+    fn of(text: &str) -> Self {
+        let mut counts = Self::default();
+        for c in text.chars() {
+            match c {
+                'a'..='z' | 'A'..='Z' | '\u{00C0}'..='\u{024F}' => counts.latin += 1,
+                '\u{3040}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}' => counts.kana += 1,
+                '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' => counts.han += 1,
+                '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' => counts.hangul += 1,
+                '\u{0400}'..='\u{04FF}' => counts.cyrillic += 1,
+                '\u{0900}'..='\u{097F}' => counts.devanagari += 1,
+                _ => {}
+            }
+        }
+        counts
+    }
+}
+
+/// Guess the language of `text` from the scripts it is written in
+///
+/// Returns a primary language subtag (`"en"`, `"ja"`, `"zh"`, `"ko"`,
+/// `"ru"` or `"hi"`), or `None` if the text has no letters. Latin-script
+/// text is reported as English, since the script alone can't tell English
+/// from e.g. Spanish; use [`shares_script`] to check whether a voice's
+/// language could be the one detected. Text mixing scripts is assigned to
+/// the dominant one, with kanji counted towards Japanese whenever enough
+/// kana is present.
+#[must_use]
+pub fn detect_language(text: &str) -> Option<String> {
+    let counts = ScriptCounts::of(text);
+
+    let cjk = counts.kana + counts.han;
+    let japanese = cjk > 0 && counts.kana as f32 / cjk as f32 >= JAPANESE_MIN_KANA_SHARE;
+    let candidates = [
+        ("en", counts.latin),
+        (if japanese { "ja" } else { "zh" }, cjk),
+        ("ko", counts.hangul),
+        ("ru", counts.cyrillic),
+        ("hi", counts.devanagari),
+    ];
+
+    candidates
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .max_by_key(|&(_, count)| count)
+        .map(|(language, _)| language.to_string())
+}
+
+/// Whether `language` (a full tag such as `"es-ES"` or a primary subtag) is
+/// written in the script behind `detected`, a result of [`detect_language`]
+///
+/// Every Latin-script language matches a detected `"en"`; other detected
+/// languages only match themselves.
+#[must_use]
+pub fn shares_script(language: &str, detected: &str) -> bool {
+    let primary = language.split('-').next().unwrap_or("");
+    if detected.eq_ignore_ascii_case("en") {
+        LATIN_SCRIPT_LANGUAGES.iter().any(|latin| latin.eq_ignore_ascii_case(primary))
+    } else {
+        primary.eq_ignore_ascii_case(detected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language_english_and_japanese() {
+        assert_eq!(detect_language("Hello, how are you today?").as_deref(), Some("en"));
+        assert_eq!(detect_language("今日はいい天気ですね。").as_deref(), Some("ja"));
+        assert_eq!(detect_language("カタカナのテキスト").as_deref(), Some("ja"));
+    }
+
+    #[test]
+    fn test_detect_language_other_scripts() {
+        assert_eq!(detect_language("我们今天去公园散步").as_deref(), Some("zh"));
+        assert_eq!(detect_language("안녕하세요").as_deref(), Some("ko"));
+        assert_eq!(detect_language("Привет, мир").as_deref(), Some("ru"));
+        assert_eq!(detect_language("नमस्ते दुनिया").as_deref(), Some("hi"));
+    }
+
+    #[test]
+    fn test_shares_script() {
+        assert!(shares_script("en-US", "en"));
+        assert!(shares_script("es-ES", "en"));
+        assert!(shares_script("pt", "en"));
+        assert!(!shares_script("ja-JP", "en"));
+        assert!(shares_script("ja-JP", "ja"));
+        assert!(!shares_script("zh-CN", "ja"));
+    }
+
+    #[test]
+    fn test_detect_language_mixed_and_empty() {
+        // A Latin brand name inside Japanese text stays Japanese
+        assert_eq!(detect_language("私はiPhoneを使っています").as_deref(), Some("ja"));
+        assert_eq!(detect_language("123 456 !?"), None);
+        assert_eq!(detect_language(""), None);
+    }
+}
@@ -0,0 +1,85 @@
+//! Per-stage timing of the token-synthesis path.
+//!
+//! Callers that need to know where synthesis time goes (validation, engine
+//! start-up, model inference) get a [`TimingBreakdown`] instead of parsing
+//! log output.
+
+use std::time::{Duration, Instant};
+
+/// Wall-clock time spent in each stage of a token synthesis request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimingBreakdown {
+    /// Checking the token IDs, style vector and speed
+    pub validation: Duration,
+    /// Creating the ONNX engine and loading the model
+    pub engine_init: Duration,
+    /// Running the model and post-processing its output
+    pub inference: Duration,
+    /// Whole request, from the first stage to the last
+    pub total: Duration,
+}
+
+impl TimingBreakdown {
+    /// Sum of the individual stage durations
+    #[must_use]
+    pub fn stages(&self) -> Duration {
+        self.validation + self.engine_init + self.inference
+    }
+}
+
+/// Stopwatch that splits one request into consecutive stages
+#[derive(Debug, Clone, Copy)]
+pub struct StageTimer {
+    started: Instant,
+    lap_started: Instant,
+}
+
+impl StageTimer {
+    /// Start timing the first stage
+    #[must_use]
+    pub fn start() -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            lap_started: now,
+        }
+    }
+
+    /// End the current stage, returning its duration, and start the next
+    pub fn lap(&mut self) -> Duration {
+        let now = Instant::now();
+        let elapsed = now - self.lap_started;
+        self.lap_started = now;
+        elapsed
+    }
+
+    /// Time since the timer was started
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_matches_sum_of_stages() {
+        let mut timer = StageTimer::start();
+        let mut timings = TimingBreakdown::default();
+
+        std::thread::sleep(Duration::from_millis(5));
+        timings.validation = timer.lap();
+        std::thread::sleep(Duration::from_millis(10));
+        timings.engine_init = timer.lap();
+        std::thread::sleep(Duration::from_millis(15));
+        timings.inference = timer.lap();
+        timings.total = timer.total();
+
+        assert!(timings.validation >= Duration::from_millis(5));
+        assert!(timings.inference >= Duration::from_millis(15));
+        assert!(timings.total >= timings.stages());
+        assert!(timings.total - timings.stages() < Duration::from_millis(5));
+    }
+}
@@ -1,15 +1,22 @@
 //! TTS engine implementation with model management system.
 //!
 //! This module provides a production-ready TTS engine that uses the ModelRegistry
-//! system for managing different TTS models. The engine supports auto-installation
-//! of default models and provides a clean interface for synthesis.
+//! system for managing different TTS models. The engine can auto-install its
+//! default model on first synthesis and provides a clean interface for synthesis.
 
+use crate::audio_device::AudioDevice;
 use crate::error::{VocalizeError, VocalizeResult};
-use crate::voice_manager::Voice;
+use crate::voice_manager::{select_voice_for_language, voice_from_id, Gender, Voice, VoiceStyle};
 use crate::models::ModelRegistry;
+use crate::pronunciation::PronunciationDictionary;
+use crate::synthesis_cache::{CacheKey, SynthesisCache};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::path::PathBuf;
-use tokio::sync::RwLock;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::{Notify, RwLock, Semaphore};
 use tracing::{debug, info, warn};
 
 /// Audio data type - 32-bit floating point samples
@@ -22,14 +29,39 @@ pub struct TtsConfig {
     pub model_cache_dir: PathBuf,
     /// Device to use for inference (CPU/GPU)
     pub device: TtsDevice,
-    /// Maximum text length to process
+    /// Maximum text length to process, in characters (Unicode scalar values)
     pub max_text_length: usize,
     /// Default sample rate
     pub sample_rate: u32,
-    /// Enable auto-installation of default model
+    /// Install the default model on first synthesis if no model is installed
+    ///
+    /// Creating an engine never installs anything; only synthesis does.
     pub auto_install_default: bool,
-    /// Default model ID to use
+    /// Default model ID to use, see [`TtsEngine::set_default_model`]
     pub default_model_id: String,
+    /// Maximum number of syntheses running at once (None for unbounded)
+    ///
+    /// Excess requests wait until a slot frees up.
+    pub max_concurrent: Option<usize>,
+    /// Number of synthesized sentences kept in memory (0 disables caching)
+    ///
+    /// Entries are keyed by normalized text, voice, speed and pitch and
+    /// evicted least-recently-used first.
+    pub cache_capacity: usize,
+    /// Never attempt network access (defaults to the `VOCALIZE_OFFLINE`
+    /// environment variable)
+    ///
+    /// Missing models fail with instructions for installing them manually
+    /// instead of being downloaded.
+    pub offline: bool,
+    /// Kokoro ONNX file to load, bypassing model discovery
+    ///
+    /// The file's directory must also contain the voices file.
+    pub model_path_override: Option<PathBuf>,
+    /// Directories searched for an existing Kokoro install, in priority
+    /// order (None for the standard locations, see
+    /// [`ModelDiscovery::new`](crate::model::ModelDiscovery::new))
+    pub model_search_paths: Option<Vec<PathBuf>>,
 }
 
 impl Default for TtsConfig {
@@ -44,10 +76,27 @@ impl Default for TtsConfig {
             sample_rate: crate::DEFAULT_SAMPLE_RATE,
             auto_install_default: true,
             default_model_id: "kokoro".to_string(),
+            max_concurrent: None,
+            cache_capacity: 0,
+            offline: offline_from_env(),
+            model_path_override: None,
+            model_search_paths: None,
         }
     }
 }
 
+/// Environment variable that enables offline mode when set to a truthy value
+pub const OFFLINE_ENV_VAR: &str = "VOCALIZE_OFFLINE";
+
+/// Read offline mode from [`OFFLINE_ENV_VAR`]
+fn offline_from_env() -> bool {
+    std::env::var(OFFLINE_ENV_VAR).is_ok_and(|value| is_truthy(&value))
+}
+
+fn is_truthy(value: &str) -> bool {
+    matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on")
+}
+
 /// Device type for TTS inference
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TtsDevice {
@@ -59,12 +108,111 @@ pub enum TtsDevice {
     Auto,
 }
 
+/// Average speaking rate at normal speed, used to size streaming chunks
+const ESTIMATED_CHARS_PER_SECOND: f32 = 15.0;
+
+/// Phrase spoken by [`TtsEngine::preview_voice`] when no sample text is given
+/// and the voice's language has no entry in [`PREVIEW_PHRASES`]
+pub const DEFAULT_PREVIEW_TEXT: &str = "Hello! This is a preview of my voice.";
+
+/// Default preview phrase per primary language subtag
+pub const PREVIEW_PHRASES: &[(&str, &str)] = &[
+    ("en", DEFAULT_PREVIEW_TEXT),
+    ("ja", "こんにちは。これは私の声のサンプルです。"),
+    ("zh", "你好！这是我的声音预览。"),
+    ("es", "¡Hola! Esta es una muestra de mi voz."),
+    ("fr", "Bonjour ! Voici un aperçu de ma voix."),
+    ("hi", "नमस्ते! यह मेरी आवाज़ का एक नमूना है।"),
+    ("it", "Ciao! Questa è un'anteprima della mia voce."),
+    ("pt", "Olá! Esta é uma prévia da minha voz."),
+];
+
+/// Preview phrase for a language tag such as `"ja-JP"` or `"fr"`
+///
+/// Matches on the primary subtag, case-insensitively, and falls back to
+/// [`DEFAULT_PREVIEW_TEXT`] for languages without a phrase.
+#[must_use]
+pub fn preview_text_for_language(language: &str) -> &'static str {
+    let primary = language.split(['-', '_']).next().unwrap_or_default();
+    PREVIEW_PHRASES
+        .iter()
+        .find(|(tag, _)| tag.eq_ignore_ascii_case(primary))
+        .map_or(DEFAULT_PREVIEW_TEXT, |&(_, phrase)| phrase)
+}
+
+/// How streaming synthesis splits text into chunks
+///
+/// Each chunk holds roughly `target_latency_ms` of speech, so the first
+/// audio is ready sooner with a smaller target at the cost of more model
+/// calls. Chunks always contain at least one whole word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamingConfig {
+    /// Target audio duration of each chunk, in milliseconds
+    pub target_latency_ms: u32,
+    /// Upper bound on characters per chunk, whatever the target latency
+    pub max_chunk_chars: usize,
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        Self {
+            target_latency_ms: 200,
+            max_chunk_chars: 200,
+        }
+    }
+}
+
+impl StreamingConfig {
+    /// Validate the configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target latency or chunk length limit is zero
+    pub fn validate(&self) -> VocalizeResult<()> {
+        if self.target_latency_ms == 0 {
+            return Err(VocalizeError::invalid_input("Target latency must be greater than 0 ms"));
+        }
+        if self.max_chunk_chars == 0 {
+            return Err(VocalizeError::invalid_input("Maximum chunk length must be greater than 0"));
+        }
+        Ok(())
+    }
+
+    /// Characters of text expected to produce `target_latency_ms` of audio at `speed`
+    fn chunk_chars(&self, speed: f32) -> usize {
+        let chars = self.target_latency_ms as f32 / 1000.0 * ESTIMATED_CHARS_PER_SECOND * speed;
+        (chars.round() as usize).clamp(1, self.max_chunk_chars)
+    }
+
+    /// Group the words of `text` into chunks of about [`Self::chunk_chars`] characters
+    fn split<'a>(&self, text: &'a str, speed: f32) -> Vec<Vec<&'a str>> {
+        let budget = self.chunk_chars(speed);
+        let mut chunks: Vec<Vec<&str>> = Vec::new();
+        let mut current_len = 0;
+        for word in text.split_whitespace() {
+            let word_len = word.chars().count();
+            match chunks.last_mut() {
+                Some(chunk) if current_len + 1 + word_len <= budget => {
+                    chunk.push(word);
+                    current_len += 1 + word_len;
+                }
+                _ => {
+                    chunks.push(vec![word]);
+                    current_len = word_len;
+                }
+            }
+        }
+        chunks
+    }
+}
+
 /// TTS synthesis parameters
 #[derive(Debug, Clone)]
 pub struct SynthesisParams {
     /// Voice to use for synthesis
     pub voice: Voice,
-    /// Speed multiplier (0.1 to 3.0)
+    /// Speed multiplier (0.1 to 3.0), applied by the model; the
+    /// authoritative tempo control
     pub speed: f32,
     /// Pitch adjustment (-1.0 to 1.0)
     pub pitch: f32,
@@ -72,6 +220,13 @@ pub struct SynthesisParams {
     pub streaming: bool,
     /// Chunk size for streaming (in samples)
     pub chunk_size: usize,
+    /// Sample rate to resample the output to; `None` keeps the model's native rate
+    pub output_sample_rate: Option<u32>,
+    /// Latency-based chunking for streaming; `None` splits the text into ~4 chunks
+    pub streaming_config: Option<StreamingConfig>,
+    /// Opt-in tempo change applied to the audio after synthesis, see
+    /// [`Self::with_time_stretch`]; `None` leaves tempo to `speed`
+    pub time_stretch: Option<f32>,
 }
 
 impl SynthesisParams {
@@ -84,6 +239,9 @@ impl SynthesisParams {
             voice,
             streaming: false,
             chunk_size: 1024,
+            output_sample_rate: None,
+            streaming_config: None,
+            time_stretch: None,
         }
     }
 
@@ -91,7 +249,8 @@ impl SynthesisParams {
     ///
     /// # Errors
     ///
-    /// Returns an error if speed is not in valid range (0.1 to 3.0)
+    /// Returns an error if speed is not in valid range (0.1 to 3.0), or if
+    /// it would compound with a [time stretch](Self::with_time_stretch)
     pub fn with_speed(mut self, speed: f32) -> VocalizeResult<Self> {
         if !(0.1..=3.0).contains(&speed) {
             return Err(VocalizeError::invalid_input(format!(
@@ -99,9 +258,46 @@ impl SynthesisParams {
             )));
         }
         self.speed = speed;
+        self.check_tempo()?;
+        Ok(self)
+    }
+
+    /// Change tempo by `factor` after synthesis, keeping pitch
+    ///
+    /// The model's `speed` is the preferred tempo control, since the model
+    /// re-renders the speech rather than stretching it. Time stretching is
+    /// for when that isn't wanted, e.g. to keep cached audio reusable, and
+    /// is applied with [`time_stretch`](crate::audio_effects::time_stretch).
+    /// The two never combine: `speed` must be 1.0 while a stretch is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `factor` is not in valid range (0.5 to 2.0), or
+    /// if `speed` is already changed
+    pub fn with_time_stretch(mut self, factor: f32) -> VocalizeResult<Self> {
+        if !(0.5..=2.0).contains(&factor) {
+            return Err(VocalizeError::invalid_input(format!(
+                "Time stretch must be between 0.5 and 2.0, got {factor}"
+            )));
+        }
+        self.time_stretch = Some(factor);
+        self.check_tempo()?;
         Ok(self)
     }
 
+    /// Reject a time stretch on top of a changed model speed, which would
+    /// adjust tempo twice
+    fn check_tempo(&self) -> VocalizeResult<()> {
+        match self.time_stretch {
+            Some(factor) if (self.speed - 1.0).abs() > f32::EPSILON => Err(VocalizeError::invalid_input(format!(
+                "Speed {} and time stretch {factor} would both change tempo; \
+                 use speed alone, or keep speed at 1.0 when time stretching",
+                self.speed
+            ))),
+            _ => Ok(()),
+        }
+    }
+
     /// Set pitch adjustment
     ///
     /// # Errors
@@ -125,6 +321,33 @@ impl SynthesisParams {
         self
     }
 
+    /// Enable streaming synthesis with chunks sized by target latency
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the streaming configuration is invalid
+    pub fn with_streaming_config(mut self, config: StreamingConfig) -> VocalizeResult<Self> {
+        config.validate()?;
+        self.streaming = true;
+        self.streaming_config = Some(config);
+        Ok(self)
+    }
+
+    /// Resample the synthesized audio to `sample_rate` Hz
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample rate is zero
+    pub fn with_output_sample_rate(mut self, sample_rate: u32) -> VocalizeResult<Self> {
+        if sample_rate == 0 {
+            return Err(VocalizeError::invalid_input(
+                "Output sample rate must be greater than 0".to_string(),
+            ));
+        }
+        self.output_sample_rate = Some(sample_rate);
+        Ok(self)
+    }
+
     /// Validate synthesis parameters
     pub fn validate(&self) -> VocalizeResult<()> {
         self.voice.validate()?;
@@ -149,16 +372,134 @@ impl SynthesisParams {
             ));
         }
 
-        Ok(())
+        if let Some(config) = &self.streaming_config {
+            config.validate()?;
+        }
+
+        if self.output_sample_rate == Some(0) {
+            return Err(VocalizeError::invalid_input(
+                "Output sample rate must be greater than 0".to_string(),
+            ));
+        }
+
+        if let Some(factor) = self.time_stretch {
+            if !(0.5..=2.0).contains(&factor) {
+                return Err(VocalizeError::invalid_input(format!(
+                    "Time stretch must be between 0.5 and 2.0, got {factor}"
+                )));
+            }
+        }
+        self.check_tempo()
+    }
+}
+
+/// Split `text` after sentence-ending punctuation followed by whitespace
+///
+/// Full-width CJK terminators (`。！？`) end a sentence even without
+/// following whitespace, since Japanese and Chinese don't space sentences.
+/// Sentences keep their punctuation and are trimmed; blank pieces are dropped.
+pub(crate) fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        if matches!(c, '.' | '!' | '?') {
+            if let Some(&(next, following)) = chars.peek() {
+                if following.is_whitespace() {
+                    sentences.push(&text[start..next]);
+                    start = next;
+                }
+            }
+        } else if matches!(c, '。' | '！' | '？') {
+            let next = chars.peek().map_or(text.len(), |&(next, _)| next);
+            sentences.push(&text[start..next]);
+            start = next;
+        }
+    }
+    sentences.push(&text[start..]);
+
+    sentences.into_iter().map(str::trim).filter(|s| !s.is_empty()).collect()
+}
+
+/// Apply the time stretch requested in `params`, if any, then resample
+/// `audio` from `native_rate` to the requested rate, if any
+fn convert_sample_rate(audio: AudioData, native_rate: u32, params: &SynthesisParams) -> AudioData {
+    let audio = match params.time_stretch {
+        Some(factor) => crate::audio_effects::time_stretch(&audio, factor),
+        None => audio,
+    };
+    match params.output_sample_rate {
+        Some(rate) if rate != native_rate => crate::audio_effects::resample(&audio, native_rate, rate),
+        _ => audio,
+    }
+}
+
+/// How long [`TtsEngine::shutdown`] waits for in-flight syntheses
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Tracks in-flight syntheses so shutdown can drain them
+#[derive(Debug, Default)]
+struct WorkTracker {
+    closed: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+impl WorkTracker {
+    /// Register a new unit of work, unless shutdown has started
+    fn begin(&self) -> VocalizeResult<WorkGuard<'_>> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = WorkGuard(self);
+        if self.closed.load(Ordering::SeqCst) {
+            return Err(VocalizeError::shutdown("TTS engine is shutting down"));
+        }
+        Ok(guard)
+    }
+
+    /// Wait until no work is in flight
+    async fn drain(&self) {
+        loop {
+            let notified = self.idle.notified();
+            if self.in_flight.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Marks a unit of work as finished when dropped
+struct WorkGuard<'a>(&'a WorkTracker);
+
+impl Drop for WorkGuard<'_> {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
     }
 }
 
 /// High-performance TTS engine with model management
-#[derive(Debug)]
+///
+/// Cloning an engine yields another handle to the same models and limits.
+///
+/// # Thread safety
+///
+/// `TtsEngine` is `Send + Sync` and all of its state lives behind `Arc`s, so
+/// `clone()` is cheap and every clone shares the loaded models, cache,
+/// pronunciation overrides and `max_concurrent` limit. To use one engine
+/// from several threads or tasks, clone it into each of them; there is no
+/// need to wrap it in another `Arc` or lock.
+#[derive(Debug, Clone)]
 pub struct TtsEngine {
-    config: TtsConfig,
+    config: Arc<TtsConfig>,
     model_registry: Arc<RwLock<ModelRegistry>>,
     initialized: Arc<RwLock<bool>>,
+    default_model: Arc<Mutex<String>>,
+    synthesis_limit: Option<Arc<Semaphore>>,
+    work: Arc<WorkTracker>,
+    cache: Option<Arc<Mutex<SynthesisCache>>>,
+    pronunciation: Arc<Mutex<PronunciationDictionary>>,
 }
 
 impl TtsEngine {
@@ -174,27 +515,55 @@ impl TtsEngine {
 
 
     /// Create a new TTS engine with custom configuration
+    ///
+    /// Already installed models are loaded, but nothing is installed: with
+    /// no model installed the engine is still created, and can list the
+    /// default model's voices.
     /// 
     /// # Errors
     /// 
-    /// Returns an error if the model registry cannot be created or if
-    /// initialization fails.
+    /// Returns an error if the model registry cannot be created, if
+    /// `default_model_id` is not a known model, or if initialization fails.
     pub async fn with_config(config: TtsConfig) -> VocalizeResult<Self> {
         info!("Creating TTS engine with config: {:?}", config);
 
-        let registry = ModelRegistry::new(&config.model_cache_dir)?;
+        if config.max_concurrent == Some(0) {
+            return Err(VocalizeError::configuration(
+                "max_concurrent must be at least 1".to_string()
+            ));
+        }
+        ensure_known_model(&config.default_model_id)?;
+
+        let mut registry = match (&config.model_path_override, &config.model_search_paths) {
+            (Some(model_file), _) => ModelRegistry::with_model_path_override(&config.model_cache_dir, model_file)?,
+            (None, Some(paths)) => ModelRegistry::with_search_paths(&config.model_cache_dir, paths.clone())?,
+            (None, None) => ModelRegistry::new(&config.model_cache_dir)?,
+        };
+        registry.set_offline(config.offline);
+        let synthesis_limit = config.max_concurrent.map(|limit| Arc::new(Semaphore::new(limit)));
+        let cache = (config.cache_capacity > 0)
+            .then(|| Arc::new(Mutex::new(SynthesisCache::new(config.cache_capacity))));
+        
+        let default_model = Arc::new(Mutex::new(config.default_model_id.clone()));
         
         let engine = Self {
-            config,
+            config: Arc::new(config),
             model_registry: Arc::new(RwLock::new(registry)),
             initialized: Arc::new(RwLock::new(false)),
+            default_model,
+            synthesis_limit,
+            work: Arc::new(WorkTracker::default()),
+            cache,
+            pronunciation: Arc::new(Mutex::new(PronunciationDictionary::new())),
         };
 
         engine.initialize().await?;
         Ok(engine)
     }
 
-    /// Initialize the TTS engine and ensure a model is available
+    /// Initialize the TTS engine, loading an installed model if there is one
+    ///
+    /// Never installs a model; see [`Self::synthesize`] for auto-install.
     async fn initialize(&self) -> VocalizeResult<()> {
         let mut initialized = self.initialized.write().await;
         if *initialized {
@@ -204,24 +573,17 @@ impl TtsEngine {
 
         info!("Initializing TTS engine...");
         
-        // Check if we have any models installed
         let mut registry = self.model_registry.write().await;
-        
-        if !registry.has_any_model() && self.config.auto_install_default {
-            info!("No TTS models installed. Installing default model: {}", self.config.default_model_id);
-            registry.install_model(&self.config.default_model_id).await?;
-        }
-        
-        // If we still have no models, return an error
         if !registry.has_any_model() {
-            return Err(VocalizeError::model(
-                "No TTS models available. Please install a model first.".to_string()
-            ));
+            info!("No TTS models installed yet");
         }
         
-        // Load a default model if none is active
+        // Load a model if none is active, preferring the default one
         if registry.get_active_model().is_err() {
-            let model_id = {
+            let default_model = self.get_default_model();
+            let model_id = if registry.installed_models.contains_key(&default_model) {
+                Some(default_model)
+            } else {
                 let installed_models = registry.get_installed_models();
                 installed_models.first().map(|m| m.id.clone())
             };
@@ -250,34 +612,252 @@ impl TtsEngine {
     /// - The engine is not initialized
     /// - The text is empty or too long
     /// - The synthesis parameters are invalid
-    /// - No model is available
+    /// - No model is installed and `auto_install_default` is off
     /// - The synthesis process fails
+    /// - The engine is shutting down
+    ///
+    /// If no model is active, the default model is loaded, installing it
+    /// first if needed and `auto_install_default` is set. If
+    /// `max_concurrent` is configured, this waits for a free slot first. If
+    /// `cache_capacity` is non-zero, repeated requests are served from the
+    /// sentence cache without touching the model. If `params.time_stretch`
+    /// is set, the model output is time-stretched; if
+    /// `params.output_sample_rate` is set, it is resampled to that rate
+    /// before it is returned.
     pub async fn synthesize(&self, text: &str, params: &SynthesisParams) -> VocalizeResult<AudioData> {
+        let _work = self.work.begin()?;
         self.validate_input(text, params).await?;
+        let text = self.pronunciation.lock().apply(text);
+        let text = text.as_str();
+
+        if let Some(cache) = &self.cache {
+            let active_id = self.model_registry.read().await.active_model.clone();
+            if let Some(model_id) = active_id {
+                let cached = cache.lock().get(&CacheKey::new(&model_id, text, params));
+                if let Some(audio) = cached {
+                    debug!("Synthesis cache hit for {} characters", text.len());
+                    return Ok(convert_sample_rate(audio, self.output_sample_rate().await, params));
+                }
+            }
+        }
+
+        let _permit = match &self.synthesis_limit {
+            Some(limit) => Some(limit.acquire().await.map_err(|_| {
+                VocalizeError::concurrency("Synthesis limiter has been closed")
+            })?),
+            None => None,
+        };
 
         debug!("Synthesizing text: {} characters", text.len());
         
-        let mut registry = self.model_registry.write().await;
-        
-        // Ensure we have an active model
-        if registry.get_active_model().is_err() {
-            // Try to auto-install default model if enabled
-            if self.config.auto_install_default {
-                warn!("No active model found. Installing default model: {}", self.config.default_model_id);
-                registry.install_model(&self.config.default_model_id).await?;
-                registry.load_model(&self.config.default_model_id)?;
-            } else {
-                return Err(VocalizeError::synthesis("No TTS model available"));
+        // Synthesis only needs read access, so concurrent calls can overlap;
+        // the write lock is taken only when a model has to be installed
+        let registry = self.model_registry.read().await;
+        let registry = if registry.active_model().is_ok() {
+            registry
+        } else {
+            drop(registry);
+            let mut registry = self.model_registry.write().await;
+
+            // Ensure we have an active model
+            if registry.get_active_model().is_err() {
+                let default_model = self.get_default_model();
+                if !registry.installed_models.contains_key(&default_model) {
+                    // Installing is opt-in and only ever happens here
+                    if !self.config.auto_install_default {
+                        return Err(VocalizeError::model(format!(
+                            "No TTS model available. Install '{default_model}' with install_model() or enable auto_install_default"
+                        )));
+                    }
+                    warn!("No active model found. Installing default model: {}", default_model);
+                    registry.install_model(&default_model).await?;
+                }
+                registry.load_model(&default_model)?;
+                registry.set_default_model(&default_model)?;
             }
+            registry.downgrade()
+        };
+
+        let model = registry.active_model()?;
+
+        // Catch unsupported voices here rather than as a failed embedding lookup
+        let voices = model.supported_voices();
+        if !voices.contains(&params.voice.id) {
+            return Err(VocalizeError::voice_not_supported(params.voice.id.clone(), voices));
         }
-        
-        let model = registry.get_active_model()?;
+
         let audio = model.synthesize(text, &params.voice.id, params)?;
+        if audio.is_empty() {
+            return Err(VocalizeError::synthesis(format!(
+                "Model '{}' produced no audio for {} characters of text with voice '{}'",
+                model.model_id(),
+                text.chars().count(),
+                params.voice.id
+            )));
+        }
 
+        // Key by the model that actually produced the audio, in case the
+        // active model changed while waiting for a permit
+        if let (Some(cache), Some(model_id)) = (&self.cache, &registry.active_model) {
+            cache.lock().insert(CacheKey::new(model_id, text, params), audio.clone());
+        }
+
+        let audio = convert_sample_rate(audio, self.model_sample_rate(&registry), params);
         info!("Successfully synthesized {} samples", audio.len());
         Ok(audio)
     }
 
+    /// Synthesize text, substituting silence if this request fails
+    ///
+    /// For pipelines that should keep going past a bad sentence: on a
+    /// [recoverable](VocalizeError::is_recoverable) error the failure is
+    /// logged and `fallback_ms` of silence is returned instead, at the rate
+    /// [`Self::synthesize`] would have produced.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors that would affect every request, such as invalid
+    /// parameters, a missing model or an unsupported voice, and fails if the
+    /// engine is shutting down.
+    pub async fn synthesize_or_silence(
+        &self,
+        text: &str,
+        params: &SynthesisParams,
+        fallback_ms: u32,
+    ) -> VocalizeResult<AudioData> {
+        let _work = self.work.begin()?;
+        params.validate()?;
+        match self.synthesize(text, params).await {
+            Err(error) if error.is_recoverable() => {
+                warn!(
+                    "Synthesis of {} characters failed, substituting {}ms of silence: {}",
+                    text.chars().count(),
+                    fallback_ms,
+                    error
+                );
+                let sample_rate = match params.output_sample_rate {
+                    Some(rate) => rate,
+                    None => self.output_sample_rate().await,
+                };
+                let len = u64::from(sample_rate) * u64::from(fallback_ms) / 1000;
+                Ok(vec![0.0; len as usize])
+            }
+            result => result,
+        }
+    }
+
+    /// Synthesize text with a voice matching its detected language
+    ///
+    /// The language is guessed with [`crate::text::detect_language`]. If the
+    /// voice in `params` already speaks it or a language in the same script
+    /// (or no language is detected), `params` is used as-is; otherwise the
+    /// first voice of the active model for that language is substituted,
+    /// keeping the other parameters. A Spanish voice therefore keeps reading
+    /// Latin-script text, which is detected as English.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the active model has no voice for the detected
+    /// language, or if synthesis fails
+    pub async fn synthesize_auto_voice(&self, text: &str, params: &SynthesisParams) -> VocalizeResult<AudioData> {
+        let params = self.params_for_language(text, params).await?;
+        self.synthesize(text, &params).await
+    }
+
+    /// Synthesize a multi-language document sentence by sentence, giving
+    /// each sentence a voice that matches its detected language
+    ///
+    /// Each sentence goes through the same voice selection as
+    /// [`Self::synthesize_auto_voice`], so a mixed English/Japanese paragraph
+    /// switches voices where the language changes. The sentences' audio is
+    /// concatenated in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a sentence's language has no matching voice or
+    /// its synthesis fails
+    pub async fn synthesize_multilingual(&self, text: &str, params: &SynthesisParams) -> VocalizeResult<AudioData> {
+        self.synthesize_document(text, params, |_, _| Ok(())).await
+    }
+
+    /// Synthesize a document like [`Self::synthesize_multilingual`],
+    /// reporting progress as it goes
+    ///
+    /// `on_progress(sentences_done, total)` is called once with zero done
+    /// before the first sentence, then after each sentence, ending at
+    /// `(total, total)`. Blank text has no sentences and reports nothing.
+    /// An error returned by `on_progress` stops synthesis; the remaining
+    /// sentences are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a sentence's language has no matching voice or
+    /// its synthesis fails, or the first error returned by `on_progress`
+    pub async fn synthesize_document<F>(
+        &self,
+        text: &str,
+        params: &SynthesisParams,
+        mut on_progress: F,
+    ) -> VocalizeResult<AudioData>
+    where
+        F: FnMut(usize, usize) -> VocalizeResult<()>,
+    {
+        let sentences = split_sentences(text);
+        let total = sentences.len();
+        if total > 0 {
+            on_progress(0, total)?;
+        }
+
+        let mut audio = AudioData::new();
+        for (index, sentence) in sentences.into_iter().enumerate() {
+            let sentence_params = self.params_for_language(sentence, params).await?;
+            audio.extend(self.synthesize(sentence, &sentence_params).await?);
+            on_progress(index + 1, total)?;
+        }
+        Ok(audio)
+    }
+
+    /// Synthesize a short sample of a voice, e.g. for a voice picker
+    ///
+    /// Speaks `sample_text`, or the phrase [`preview_text_for_language`]
+    /// picks for the voice's language if `None`, with default speed and
+    /// pitch. Previews go through [`Self::synthesize`], so
+    /// they use the already loaded model and, with the sentence cache
+    /// enabled, repeat previews of a voice are served from the cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the active model does not support `voice_id` or
+    /// synthesis fails
+    pub async fn preview_voice(&self, voice_id: &str, sample_text: Option<&str>) -> VocalizeResult<AudioData> {
+        let voice = voice_for_id(voice_id);
+        let text = sample_text.unwrap_or_else(|| preview_text_for_language(&voice.language));
+        self.synthesize(text, &SynthesisParams::new(voice)).await
+    }
+
+    /// `params` with its voice swapped for one matching the language of `text`
+    async fn params_for_language(&self, text: &str, params: &SynthesisParams) -> VocalizeResult<SynthesisParams> {
+        let Some(language) = crate::text::detect_language(text) else {
+            return Ok(params.clone());
+        };
+        if params.voice.supports_language(&language)
+            || crate::text::shares_script(&params.voice.language, &language)
+        {
+            return Ok(params.clone());
+        }
+
+        let voices = self.supported_voices().await?;
+        let voice = select_voice_for_language(&voices, &language)
+            .and_then(|voice_id| voice_from_id(voice_id))
+            .ok_or_else(|| VocalizeError::invalid_input(format!(
+                "Detected language '{language}' but the active model has no voice for it (available voices: {})",
+                voices.join(", ")
+            )))?;
+
+        debug!("Detected language '{}'; using voice '{}'", language, voice.id);
+        Ok(SynthesisParams { voice, ..params.clone() })
+    }
+
     /// Synthesize text to audio with streaming
     ///
     /// # Errors
@@ -299,11 +879,17 @@ impl TtsEngine {
         debug!("Streaming synthesis for {} characters", text.len());
 
         // Split text into chunks for streaming
-        let words: Vec<&str> = text.split_whitespace().collect();
-        let chunk_size = (words.len() / 4).max(1); // Divide into ~4 chunks
+        let word_chunks = match &params.streaming_config {
+            Some(config) => config.split(text, params.speed),
+            None => {
+                let words: Vec<&str> = text.split_whitespace().collect();
+                let chunk_size = (words.len() / 4).max(1); // Divide into ~4 chunks
+                words.chunks(chunk_size).map(<[&str]>::to_vec).collect()
+            }
+        };
         
         let mut chunks = Vec::new();
-        for word_chunk in words.chunks(chunk_size) {
+        for word_chunk in word_chunks {
             let chunk_text = word_chunk.join(" ");
             if !chunk_text.is_empty() {
                 let audio = self.synthesize(&chunk_text, params).await?;
@@ -315,7 +901,103 @@ impl TtsEngine {
         Ok(chunks)
     }
 
+    /// Synthesize text and play it through the default audio device
+    ///
+    /// If streaming is enabled in `params`, the text is synthesized in
+    /// chunks that are played back to back once all of them are ready. Use
+    /// [`speak_stream_with_device`](Self::speak_stream_with_device) to start
+    /// playback before the whole text has been synthesized.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if synthesis fails or the audio device cannot play
+    /// the generated audio.
+    pub async fn speak(&self, text: &str, params: &SynthesisParams) -> VocalizeResult<()> {
+        let device = AudioDevice::new().await?;
+        self.speak_with_device(text, params, &device).await
+    }
+
+    /// Synthesize text and play it through the given audio device
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if synthesis fails or the audio device cannot play
+    /// the generated audio.
+    pub async fn speak_with_device(
+        &self,
+        text: &str,
+        params: &SynthesisParams,
+        device: &AudioDevice,
+    ) -> VocalizeResult<()> {
+        let source_rate = params.output_sample_rate.unwrap_or(self.output_sample_rate().await);
+        if params.streaming {
+            let chunks: Vec<AudioData> = self
+                .synthesize_streaming(text, params)
+                .await?
+                .into_iter()
+                .filter(|chunk| !chunk.is_empty())
+                .collect();
+            device.play_stream_at(&chunks, source_rate).await?;
+        } else {
+            let audio = self.synthesize(text, params).await?;
+            let audio = crate::audio_effects::resample(
+                &audio,
+                source_rate,
+                device.get_config().sample_rate,
+            );
+            device.play(&audio).await?;
+        }
+
+        device.wait_for_completion().await
+    }
+
+    /// Synthesize `text` one sentence at a time, playing each sentence's
+    /// audio on `device` as soon as it is ready
+    ///
+    /// Playback of the first sentence starts without waiting for the rest
+    /// of the text. Each sentence goes through
+    /// [`synthesize_streaming`](Self::synthesize_streaming) and
+    /// [`AudioDevice::play_stream_at`]; `on_chunk` sees every chunk, in
+    /// playback order, just before it is played. Returns the number of
+    /// chunks played.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if synthesis of any sentence fails or the audio
+    /// device cannot play it; sentences already played stay played.
+    pub async fn speak_stream_with_device(
+        &self,
+        text: &str,
+        params: &SynthesisParams,
+        device: &AudioDevice,
+        mut on_chunk: impl FnMut(&AudioData),
+    ) -> VocalizeResult<usize> {
+        let source_rate = params.output_sample_rate.unwrap_or(self.output_sample_rate().await);
+        let mut played = 0;
+
+        for sentence in split_sentences(text) {
+            let chunks: Vec<AudioData> = self
+                .synthesize_streaming(sentence, params)
+                .await?
+                .into_iter()
+                .filter(|chunk| !chunk.is_empty())
+                .collect();
+            for chunk in &chunks {
+                on_chunk(chunk);
+            }
+            device.play_stream_at(&chunks, source_rate).await?;
+            played += chunks.len();
+        }
+
+        device.wait_for_completion().await?;
+        Ok(played)
+    }
+
     /// Install a model by ID
+    ///
+    /// Concurrent calls for the same model are coalesced: the first one
+    /// installs it and the rest wait for it, then return without installing
+    /// again (see [`ModelRegistry::install_model`]).
     /// 
     /// # Errors
     /// 
@@ -332,10 +1014,17 @@ impl TtsEngine {
     /// Returns an error if the model is not installed or removal fails.
     pub async fn remove_model(&self, model_id: &str) -> VocalizeResult<()> {
         let mut registry = self.model_registry.write().await;
-        registry.remove_model(model_id)
+        registry.remove_model(model_id)?;
+        if let Some(cache) = &self.cache {
+            cache.lock().remove_model(model_id);
+        }
+        Ok(())
     }
     
     /// Set the active model
+    ///
+    /// Cached sentences are keyed by model, so audio from the previously
+    /// active model is never returned after switching.
     /// 
     /// # Errors
     /// 
@@ -346,15 +1035,175 @@ impl TtsEngine {
         registry.set_default_model(model_id)
     }
     
+    /// ID of the model loaded, or installed, when synthesis finds no active model
+    ///
+    /// Starts as `TtsConfig::default_model_id`. Querying it has no side effects.
+    #[must_use]
+    pub fn get_default_model(&self) -> String {
+        self.default_model.lock().clone()
+    }
+
+    /// Change the default model without installing or loading it
+    ///
+    /// The active model, if any, keeps serving requests; the new default is
+    /// used the next time synthesis finds no active model, and its voices
+    /// are what [`Self::supported_voices`] reports until then.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `model_id` is not a known model.
+    pub fn set_default_model(&self, model_id: &str) -> VocalizeResult<()> {
+        ensure_known_model(model_id)?;
+        *self.default_model.lock() = model_id.to_string();
+        info!("Default model set to '{}'", model_id);
+        Ok(())
+    }
+    
+    /// Reload the active model from disk
+    ///
+    /// Use after re-downloading or replacing model files: the model is
+    /// unloaded, rediscovered and loaded again (rebuilding its inference
+    /// sessions) without recreating the engine. Cached sentences from the
+    /// old model files are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no model is active, or if its files can no longer
+    /// be found or fail to load.
+    pub async fn reload_active_model(&self) -> VocalizeResult<()> {
+        let mut registry = self.model_registry.write().await;
+        let model_id = registry
+            .active_model
+            .clone()
+            .ok_or_else(|| VocalizeError::model("No active model to reload"))?;
+        registry.reload_model(&model_id)?;
+        if let Some(cache) = &self.cache {
+            cache.lock().remove_model(&model_id);
+        }
+        info!("Reloaded active model '{}'", model_id);
+        Ok(())
+    }
+    
+    /// Replace the pronunciation overrides applied before tokenization
+    ///
+    /// Maps words (matched case-insensitively) to phoneme strings; an empty
+    /// map removes all overrides.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any entry is invalid. The existing overrides are
+    /// kept in that case.
+    pub fn set_pronunciation_overrides(&self, overrides: HashMap<String, String>) -> VocalizeResult<()> {
+        let dictionary = PronunciationDictionary::from_map(overrides)?;
+        info!("Using {} pronunciation overrides", dictionary.len());
+        *self.pronunciation.lock() = dictionary;
+        Ok(())
+    }
+
+    /// Replace the pronunciation overrides with those in a dictionary file
+    ///
+    /// See [`PronunciationDictionary::from_file`] for the file format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is malformed
+    pub fn load_pronunciation_overrides<P: AsRef<Path>>(&self, path: P) -> VocalizeResult<()> {
+        let dictionary = PronunciationDictionary::from_file(path)?;
+        info!("Loaded {} pronunciation overrides", dictionary.len());
+        *self.pronunciation.lock() = dictionary;
+        Ok(())
+    }
+
     /// List all available models that can be installed
     pub async fn list_available_models(&self) -> Vec<crate::models::ModelInfo> {
         ModelRegistry::get_available_models()
     }
     
     /// List installed models
+    ///
+    /// Each entry's `disk_size` is measured from the files under its
+    /// `install_path`, so it reflects partial or corrupted installs.
     pub async fn list_installed_models(&self) -> Vec<crate::models::ModelInfo> {
         let registry = self.model_registry.read().await;
-        registry.get_installed_models().into_iter().cloned().collect()
+        registry.get_installed_models_with_disk_size()
+    }
+
+    /// List the voice IDs supported by the active model
+    ///
+    /// With no active model, the default model's declared voices are listed
+    /// instead, so voices can be browsed before anything is installed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the active model is not loaded, or if no model is
+    /// active and the default model is unknown.
+    pub async fn supported_voices(&self) -> VocalizeResult<Vec<String>> {
+        let registry = self.model_registry.read().await;
+        if registry.active_model.is_some() {
+            return Ok(registry.active_model()?.supported_voices());
+        }
+        let default_model = self.get_default_model();
+        ModelRegistry::get_available_models()
+            .into_iter()
+            .find(|info| info.id == default_model)
+            .map(|info| info.supported_voices)
+            .ok_or_else(|| VocalizeError::model_not_found(default_model))
+    }
+
+    /// List every voice that can be synthesized right now, across all
+    /// installed models
+    ///
+    /// Each voice is tagged with the ID of its model and carries the
+    /// model's output sample rate. Loaded models report the voices they
+    /// actually found; others report the voices their registry entry
+    /// declares. Voices are ordered by model, then voice ID.
+    pub async fn all_available_voices(&self) -> Vec<Voice> {
+        let registry = self.model_registry.read().await;
+        let mut models: Vec<_> = registry.get_installed_models().into_iter().collect();
+        models.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut voices = Vec::new();
+        for model in models {
+            let mut voice_ids = registry
+                .loaded_models
+                .get(&model.id)
+                .map_or_else(|| model.supported_voices.clone(), |loaded| loaded.supported_voices());
+            voice_ids.sort();
+            voices.extend(voice_ids.iter().map(|voice_id| {
+                voice_for_id(voice_id)
+                    .with_sample_rate(model.output_sample_rate)
+                    .with_model_id(model.id.clone())
+            }));
+        }
+        voices
+    }
+
+    /// Sample rate of the audio produced by the active model, in Hz
+    ///
+    /// Falls back to the configured sample rate when no model is active or
+    /// the active model's metadata is unknown.
+    pub async fn output_sample_rate(&self) -> u32 {
+        let registry = self.model_registry.read().await;
+        self.model_sample_rate(&registry)
+    }
+
+    /// Native sample rate of the active model in `registry`
+    fn model_sample_rate(&self, registry: &ModelRegistry) -> u32 {
+        let Some(model_id) = registry.active_model.as_deref() else {
+            return self.config.sample_rate;
+        };
+
+        registry
+            .installed_models
+            .get(model_id)
+            .map(|info| info.output_sample_rate)
+            .or_else(|| {
+                ModelRegistry::get_available_models()
+                    .into_iter()
+                    .find(|info| info.id == model_id)
+                    .map(|info| info.output_sample_rate)
+            })
+            .unwrap_or(self.config.sample_rate)
     }
 
     /// Validate input parameters
@@ -367,10 +1216,12 @@ impl TtsEngine {
             return Err(VocalizeError::invalid_input("Text cannot be empty"));
         }
 
-        if text.len() > self.config.max_text_length {
+        // Count characters rather than bytes so that scripts needing several
+        // UTF-8 bytes per character get the same allowance as ASCII
+        if text.chars().nth(self.config.max_text_length).is_some() {
             return Err(VocalizeError::invalid_input(format!(
-                "Text length {} exceeds maximum of {}",
-                text.len(),
+                "Text length {} characters exceeds maximum of {}",
+                text.chars().count(),
                 self.config.max_text_length
             )));
         }
@@ -413,8 +1264,14 @@ impl TtsEngine {
     }
 
     /// Clear model cache to free memory
+    ///
+    /// Also drops any audio held in the sentence cache.
     pub async fn clear_cache(&self) -> VocalizeResult<()> {
         debug!("Clearing model cache");
+
+        if let Some(cache) = &self.cache {
+            cache.lock().clear();
+        }
         
         let mut registry = self.model_registry.write().await;
         
@@ -431,6 +1288,102 @@ impl TtsEngine {
         info!("Model cache cleared");
         Ok(())
     }
+
+    /// Shut the engine down gracefully
+    ///
+    /// Stops accepting new syntheses on every handle to this engine, waits up
+    /// to [`DEFAULT_SHUTDOWN_TIMEOUT`] for in-flight ones to finish, then
+    /// unloads all models.
+    ///
+    /// # Errors
+    ///
+    /// Returns a timeout error if in-flight work does not finish in time; the
+    /// models are left loaded in that case.
+    pub async fn shutdown(self) -> VocalizeResult<()> {
+        self.shutdown_with_timeout(DEFAULT_SHUTDOWN_TIMEOUT).await
+    }
+
+    /// Shut the engine down, waiting at most `timeout` for in-flight work
+    ///
+    /// # Errors
+    ///
+    /// Returns a timeout error if in-flight work does not finish in time; the
+    /// models are left loaded in that case.
+    pub async fn shutdown_with_timeout(self, timeout: Duration) -> VocalizeResult<()> {
+        info!("Shutting down TTS engine");
+        self.work.closed.store(true, Ordering::SeqCst);
+
+        tokio::time::timeout(timeout, self.work.drain())
+            .await
+            .map_err(|_| {
+                VocalizeError::timeout(format!(
+                    "{} syntheses still running after {:?}",
+                    self.work.in_flight.load(Ordering::SeqCst),
+                    timeout
+                ))
+            })?;
+
+        self.clear_cache().await?;
+        info!("TTS engine shut down");
+        Ok(())
+    }
+}
+
+/// A [`Voice`] for `voice_id`, inferred from the ID where it follows the
+/// Kokoro naming convention and a neutral English voice otherwise
+pub(crate) fn voice_for_id(voice_id: &str) -> Voice {
+    voice_from_id(voice_id).unwrap_or_else(|| {
+        Voice::new(
+            voice_id.to_string(),
+            voice_id.to_string(),
+            "en-US".to_string(),
+            Gender::Neutral,
+            VoiceStyle::Natural,
+        )
+    })
+}
+
+/// Synthesize `text` with `voice_id` and write it to `out` in one call
+///
+/// Creates an engine with the default configuration, synthesizes, writes
+/// the audio in the format implied by `out`'s extension at the model's
+/// output rate, and shuts the engine down. Meant for command-line tools and
+/// scripts; anything synthesizing more than once should keep a
+/// [`TtsEngine`] instead. Runs its own tokio runtime, so it must not be
+/// called from within one.
+///
+/// # Errors
+///
+/// Returns an error if the extension is not a supported format, or if
+/// engine creation, synthesis or writing fails
+pub fn synthesize_once(text: &str, voice_id: &str, out: &Path) -> VocalizeResult<()> {
+    synthesize_once_with_config(text, voice_id, out, TtsConfig::default())
+}
+
+/// [`synthesize_once`] with an explicit engine configuration
+///
+/// # Errors
+///
+/// Returns an error in the same cases as [`synthesize_once`]
+pub fn synthesize_once_with_config(text: &str, voice_id: &str, out: &Path, config: TtsConfig) -> VocalizeResult<()> {
+    let format = crate::audio_writer::AudioFormat::from_path(out)?;
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| VocalizeError::configuration(format!("Failed to start async runtime: {e}")))?;
+
+    runtime.block_on(async {
+        let engine = TtsEngine::with_config(config).await?;
+        let params = SynthesisParams::new(voice_for_id(voice_id));
+        let audio = engine.synthesize(text, &params).await?;
+
+        let settings = format.default_settings(engine.output_sample_rate().await, crate::DEFAULT_CHANNELS);
+        crate::audio_writer::AudioWriter::new()
+            .write_file(&audio, out, format, Some(settings))
+            .await?;
+
+        engine.shutdown().await
+    })
 }
 
 /// TTS engine statistics
@@ -442,7 +1395,7 @@ pub struct TtsStats {
     pub device: TtsDevice,
     /// Current sample rate
     pub sample_rate: u32,
-    /// Maximum text length
+    /// Maximum text length, in characters (Unicode scalar values)
     pub max_text_length: usize,
     /// Number of installed models
     pub installed_model_count: usize,
@@ -463,6 +1416,15 @@ impl Default for TtsStats {
     }
 }
 
+/// Fail unless `model_id` is one of [`ModelRegistry::get_available_models`]
+fn ensure_known_model(model_id: &str) -> VocalizeResult<()> {
+    if ModelRegistry::get_available_models().iter().any(|info| info.id == model_id) {
+        Ok(())
+    } else {
+        Err(VocalizeError::model_not_found(model_id))
+    }
+}
+
 // Cross-platform home directory detection using dirs crate
 
 fn get_home_dir() -> PathBuf {
@@ -493,9 +1455,50 @@ fn get_home_dir() -> PathBuf {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::mock_model::{MockTtsModel, MOCK_MODEL_ID};
     use crate::voice_manager::Voice;
     use tempfile::TempDir;
 
+    /// Config that installs and loads the deterministic mock model
+    fn create_mock_config(temp_dir: &TempDir) -> TtsConfig {
+        TtsConfig {
+            model_cache_dir: temp_dir.path().to_path_buf(),
+            auto_install_default: true,
+            default_model_id: MOCK_MODEL_ID.to_string(),
+            ..TtsConfig::default()
+        }
+    }
+
+    /// Engine with the mock model installed and active, as it is after the
+    /// first synthesis
+    async fn create_mock_engine(config: TtsConfig) -> TtsEngine {
+        let engine = TtsEngine::with_config(config).await.unwrap();
+        engine.install_model(MOCK_MODEL_ID).await.unwrap();
+        engine.set_active_model(MOCK_MODEL_ID).await.unwrap();
+        engine
+    }
+
+    /// Load `model` and swap it in for the engine's mock model
+    async fn install_mock_model(engine: &TtsEngine, mut model: MockTtsModel) {
+        crate::models::TtsModel::load(&mut model).unwrap();
+        engine
+            .model_registry
+            .write()
+            .await
+            .loaded_models
+            .insert(MOCK_MODEL_ID.to_string(), Box::new(model));
+    }
+
+    fn create_mock_voice() -> Voice {
+        Voice::new(
+            "mock_female".to_string(),
+            "Mock Female".to_string(),
+            "en-US".to_string(),
+            crate::Gender::Female,
+            crate::VoiceStyle::Natural,
+        )
+    }
+
     fn create_test_config(temp_dir: &TempDir) -> TtsConfig {
         TtsConfig {
             model_cache_dir: temp_dir.path().to_path_buf(),
@@ -512,6 +1515,49 @@ mod tests {
         assert_eq!(config.sample_rate, crate::DEFAULT_SAMPLE_RATE);
         assert!(config.auto_install_default);
         assert_eq!(config.default_model_id, "kokoro");
+        assert_eq!(config.max_concurrent, None);
+        assert_eq!(config.cache_capacity, 0);
+    }
+
+    #[test]
+    fn test_offline_env_values() {
+        for value in ["1", "true", "TRUE", " yes ", "on"] {
+            assert!(is_truthy(value), "{value:?} should enable offline mode");
+        }
+        for value in ["", "0", "false", "off", "no"] {
+            assert!(!is_truthy(value), "{value:?} should not enable offline mode");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_offline_mode_refuses_download() {
+        let temp_dir = TempDir::new().unwrap();
+        // Only the empty temp dir is searched, so no Kokoro install on the
+        // machine can satisfy the request
+        let config = TtsConfig {
+            model_cache_dir: temp_dir.path().to_path_buf(),
+            auto_install_default: true,
+            offline: true,
+            model_search_paths: Some(vec![temp_dir.path().to_path_buf()]),
+            ..TtsConfig::default()
+        };
+
+        // Nothing is downloaded until the first synthesis
+        let engine = TtsEngine::with_config(config).await.unwrap();
+        let params = SynthesisParams::new(voice_from_id("af_heart").unwrap());
+        let error = engine.synthesize("Hello", &params).await.unwrap_err();
+        assert!(matches!(error, VocalizeError::NetworkError { .. }));
+        assert!(error.to_string().contains("Offline mode is enabled"));
+        assert!(error.to_string().contains("Install it manually"));
+
+        // Builtin models need no download, so they still install offline
+        let config = TtsConfig {
+            offline: true,
+            ..create_mock_config(&temp_dir)
+        };
+        let engine = TtsEngine::with_config(config).await.unwrap();
+        let params = SynthesisParams::new(create_mock_voice());
+        assert!(engine.synthesize("Hello", &params).await.is_ok());
     }
 
     #[test]
@@ -542,6 +1588,29 @@ mod tests {
         assert_eq!(params.speed, 1.5);
     }
 
+    #[test]
+    fn test_synthesis_params_speed_and_time_stretch_never_combine() {
+        let params = SynthesisParams::new(create_mock_voice());
+
+        // Either tempo control alone is fine
+        assert!(params.clone().with_speed(1.5).unwrap().validate().is_ok());
+        let stretched = params.clone().with_time_stretch(1.5).unwrap();
+        assert_eq!(stretched.time_stretch, Some(1.5));
+        assert!(stretched.validate().is_ok());
+
+        // Both at once would adjust tempo twice, whichever is set first
+        assert!(stretched.clone().with_speed(1.2).is_err());
+        assert!(params.clone().with_speed(1.2).unwrap().with_time_stretch(1.5).is_err());
+        let mut conflicting = stretched.clone();
+        conflicting.speed = 0.8;
+        assert!(conflicting.validate().is_err());
+
+        // Speed left at 1.0 does not conflict
+        assert!(stretched.with_speed(1.0).is_ok());
+        assert!(params.clone().with_time_stretch(0.4).is_err());
+        assert!(params.with_time_stretch(2.5).is_err());
+    }
+
     #[test]
     fn test_synthesis_params_with_speed_invalid() {
         let voice = Voice::default();
@@ -600,6 +1669,12 @@ mod tests {
         let mut params = SynthesisParams::new(Voice::default());
         params.chunk_size = 0;
         assert!(params.validate().is_err());
+
+        // Invalid output sample rate
+        assert!(SynthesisParams::new(Voice::default()).with_output_sample_rate(0).is_err());
+        let mut params = SynthesisParams::new(Voice::default());
+        params.output_sample_rate = Some(0);
+        assert!(params.validate().is_err());
     }
 
     #[tokio::test]
@@ -607,8 +1682,48 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let config = create_test_config(&temp_dir);
         
-        let result = TtsEngine::with_config(config).await;
-        assert!(result.is_err()); // Should fail because no models and auto-install disabled
+        // Creating the engine and browsing voices installs nothing
+        let engine = TtsEngine::with_config(config).await.unwrap();
+        assert!(engine.is_initialized().await);
+        assert_eq!(engine.get_default_model(), "kokoro");
+        let voices = engine.supported_voices().await.unwrap();
+        assert!(voices.contains(&"af_heart".to_string()));
+        assert!(engine.list_installed_models().await.is_empty());
+
+        // Synthesis needs a model and won't install one without opt-in
+        let params = SynthesisParams::new(Voice::default());
+        let error = engine.synthesize("Hello", &params).await.unwrap_err();
+        assert!(error.to_string().contains("install_model"), "{error}");
+        assert!(engine.list_installed_models().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_set_default_model() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = TtsEngine::with_config(create_mock_config(&temp_dir)).await.unwrap();
+        assert_eq!(engine.get_default_model(), MOCK_MODEL_ID);
+        assert_eq!(engine.get_stats().await.installed_model_count, 0);
+
+        engine.set_default_model("kokoro").unwrap();
+        assert_eq!(engine.get_default_model(), "kokoro");
+        assert!(engine.supported_voices().await.unwrap().contains(&"af_heart".to_string()));
+        assert!(engine.set_default_model("no_such_model").is_err());
+        assert_eq!(engine.get_default_model(), "kokoro");
+
+        // Only synthesis installs, and it uses whatever is the default then
+        engine.set_default_model(MOCK_MODEL_ID).unwrap();
+        assert_eq!(engine.get_stats().await.installed_model_count, 0);
+        let params = SynthesisParams::new(create_mock_voice());
+        engine.synthesize("Hello", &params).await.unwrap();
+        let stats = engine.get_stats().await;
+        assert_eq!(stats.installed_model_count, 1);
+        assert_eq!(stats.active_model.as_deref(), Some(MOCK_MODEL_ID));
+
+        let config = TtsConfig {
+            default_model_id: "no_such_model".to_string(),
+            ..create_mock_config(&temp_dir)
+        };
+        assert!(TtsEngine::with_config(config).await.is_err());
     }
 
     #[tokio::test]
@@ -623,10 +1738,10 @@ mod tests {
         let engine = TtsEngine::with_config(config).await.unwrap();
         assert!(engine.is_initialized().await);
         
-        // Should have installed and loaded default model
+        // Installing waits for the first synthesis
         let stats = engine.get_stats().await;
-        assert_eq!(stats.installed_model_count, 1);
-        assert!(stats.active_model.is_some());
+        assert_eq!(stats.installed_model_count, 0);
+        assert!(stats.active_model.is_none());
     }
 
     #[tokio::test]
@@ -634,9 +1749,10 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let config = create_test_config(&temp_dir);
         
-        // Test with mock model - this will fail initially (expected for TDD)
-        let result = TtsEngine::with_config(config).await;
-        assert!(result.is_err()); // Should fail because no models and auto-install disabled
+        // Without auto-install there is nothing to synthesize with
+        let engine = TtsEngine::with_config(config).await.unwrap();
+        let params = SynthesisParams::new(Voice::default());
+        assert!(engine.synthesize("Hello world", &params).await.is_err());
         
         // Test with auto-install enabled
         let config = TtsConfig {
@@ -670,8 +1786,8 @@ mod tests {
         };
         
         // Create engine without any models initially
-        let result = TtsEngine::with_config(config).await;
-        assert!(result.is_err()); // Should fail because no models
+        let engine = TtsEngine::with_config(config).await.unwrap();
+        assert!(engine.list_installed_models().await.is_empty());
         
         // Create engine with auto-install for testing model management
         let config = TtsConfig {
@@ -683,6 +1799,7 @@ mod tests {
         
         let engine = TtsEngine::with_config(config).await.unwrap();
         assert!(engine.is_initialized().await);
+        engine.install_model("kokoro").await.unwrap();
         
         // Test listing available models
         let available = engine.list_available_models().await;
@@ -735,6 +1852,872 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_all_available_voices_tagged_with_model() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = TtsEngine::with_config(create_mock_config(&temp_dir)).await.unwrap();
+        let is_mock = |voice: &&Voice| voice.model_id.as_deref() == Some(MOCK_MODEL_ID);
+        assert!(!engine.all_available_voices().await.iter().any(|voice| is_mock(&voice)));
+
+        engine.install_model(MOCK_MODEL_ID).await.unwrap();
+        let voices = engine.all_available_voices().await;
+
+        let mock_voices: Vec<&Voice> = voices.iter().filter(is_mock).collect();
+        let ids: Vec<&str> = mock_voices.iter().map(|voice| voice.id.as_str()).collect();
+        assert_eq!(ids, vec!["mock_female", "mock_male"]);
+        assert!(mock_voices.iter().all(|voice| voice.sample_rate == crate::DEFAULT_SAMPLE_RATE));
+        // Every voice is tagged, whichever model it came from
+        assert!(voices.iter().all(|voice| voice.model_id.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_text_length_counts_characters() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = TtsConfig {
+            max_text_length: 1_000,
+            ..create_mock_config(&temp_dir)
+        };
+        let engine = create_mock_engine(config).await;
+        let params = SynthesisParams::new(create_mock_voice());
+
+        // Three bytes per character: over the limit in bytes, at it in characters
+        let at_limit = "あ".repeat(1_000);
+        assert_eq!(at_limit.len(), 3_000);
+        engine.validate_input(&at_limit, &params).await.expect("1000 characters should be accepted");
+
+        let over_limit = "あ".repeat(1_001);
+        let error = engine.validate_input(&over_limit, &params).await.unwrap_err();
+        assert!(error.to_string().contains("1001 characters"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_speak_with_device() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        let params = SynthesisParams::new(create_mock_voice());
+        let device = AudioDevice::new().await.unwrap();
+        
+        let audio = engine.synthesize("Hello world", &params).await.unwrap();
+        engine.speak_with_device("Hello world", &params, &device).await.unwrap();
+        
+        assert_eq!(device.samples_played(), audio.len());
+        assert!(device.is_stopped().await);
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_speak_with_device_honours_output_sample_rate() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        let params = SynthesisParams::new(create_mock_voice())
+            .with_output_sample_rate(16_000)
+            .unwrap();
+        let device = AudioDevice::new().await.unwrap();
+        let device_rate = device.get_config().sample_rate;
+        assert_ne!(device_rate, 16_000);
+
+        let audio = engine.synthesize("Hello world", &params).await.unwrap();
+        engine.speak_with_device("Hello world", &params, &device).await.unwrap();
+
+        let expected = crate::audio_effects::resample(&audio, 16_000, device_rate).len();
+        assert_eq!(device.samples_played(), expected);
+    }
+
+    #[test]
+    fn test_split_sentences() {
+        assert_eq!(
+            split_sentences("Hello there. How are you? Fine!  Thanks"),
+            vec!["Hello there.", "How are you?", "Fine!", "Thanks"]
+        );
+        assert_eq!(split_sentences("Version 2.5 is out."), vec!["Version 2.5 is out."]);
+        assert_eq!(split_sentences("Wait... what?"), vec!["Wait...", "what?"]);
+        assert!(split_sentences("   ").is_empty());
+        assert_eq!(
+            split_sentences("Hello there. こんにちは。元気ですか？Yes"),
+            vec!["Hello there.", "こんにちは。", "元気ですか？", "Yes"]
+        );
+    }
+
+    #[test]
+    fn test_streaming_config_chunk_sizes() {
+        let text = "The quick brown fox jumps over the lazy dog while the cat watches from a sunny windowsill";
+        let low_latency = StreamingConfig { target_latency_ms: 500, max_chunk_chars: 200 };
+        let high_latency = StreamingConfig { target_latency_ms: 3000, max_chunk_chars: 200 };
+
+        let small = low_latency.split(text, 1.0);
+        let large = high_latency.split(text, 1.0);
+        assert!(small.len() > large.len(), "{} vs {}", small.len(), large.len());
+        for chunk in &small {
+            // A chunk only exceeds the budget when it is a single long word
+            assert!(chunk.join(" ").chars().count() <= 8 || chunk.len() == 1, "{chunk:?}");
+        }
+        let rejoined: Vec<&str> = large.concat();
+        assert_eq!(rejoined.join(" "), text);
+
+        // The character cap wins over a generous latency target
+        let capped = StreamingConfig { target_latency_ms: 60_000, max_chunk_chars: 20 };
+        assert!(capped.split(text, 1.0).iter().all(|chunk| chunk.join(" ").chars().count() <= 20));
+
+        assert!(StreamingConfig { target_latency_ms: 0, max_chunk_chars: 10 }.validate().is_err());
+        assert!(StreamingConfig { target_latency_ms: 10, max_chunk_chars: 0 }.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_streaming_chunks_follow_target_latency() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        let text = "Streaming should start quickly, so the first chunk must be short enough to synthesize fast";
+        let streaming = |target_latency_ms| {
+            SynthesisParams::new(create_mock_voice())
+                .with_streaming_config(StreamingConfig { target_latency_ms, max_chunk_chars: 200 })
+                .unwrap()
+        };
+
+        let fast = engine.synthesize_streaming(text, &streaming(400)).await.unwrap();
+        let slow = engine.synthesize_streaming(text, &streaming(4000)).await.unwrap();
+
+        assert!(fast.len() > slow.len(), "{} vs {}", fast.len(), slow.len());
+        let longest = |chunks: &[AudioData]| chunks.iter().map(Vec::len).max().unwrap();
+        assert!(longest(&fast) < longest(&slow));
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_speak_stream_plays_sentences_in_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        let params = SynthesisParams::new(create_mock_voice());
+        let device = AudioDevice::new().await.unwrap();
+        let sentences = ["First sentence.", "Second one is longer!", "Third?"];
+
+        let mut chunks = Vec::new();
+        let played = engine
+            .speak_stream_with_device(&sentences.join(" "), &params, &device, |chunk| {
+                chunks.push(chunk.clone());
+            })
+            .await
+            .unwrap();
+
+        let mut expected = Vec::new();
+        for sentence in sentences {
+            expected.push(engine.synthesize(sentence, &params).await.unwrap());
+        }
+        assert_eq!(played, sentences.len());
+        assert_eq!(chunks, expected);
+        assert_eq!(device.samples_played(), expected.iter().map(Vec::len).sum::<usize>());
+        assert!(device.is_stopped().await);
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_with_mock_model() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        assert!(engine.is_initialized().await);
+
+        let stats = engine.get_stats().await;
+        assert_eq!(stats.active_model.as_deref(), Some(MOCK_MODEL_ID));
+
+        let params = SynthesisParams::new(create_mock_voice());
+        let first = engine.synthesize("Hello world", &params).await.unwrap();
+        let second = engine.synthesize("Hello world", &params).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.len(), MockTtsModel::expected_len("Hello world", 1.0));
+
+        let longer = engine.synthesize("Hello world, again", &params).await.unwrap();
+        assert!(longer.len() > first.len());
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_streaming_with_mock_model() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        let params = SynthesisParams::new(create_mock_voice()).with_streaming(1000);
+
+        let chunks = engine.synthesize_streaming("Hello world", &params).await.unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0], engine.synthesize("Hello", &params).await.unwrap());
+        assert_eq!(chunks[1], engine.synthesize("world", &params).await.unwrap());
+    }
+
+    /// Run two syntheses on separate tasks against `model`, returning the
+    /// most that ran at once
+    async fn peak_of_two_concurrent_syntheses(max_concurrent: usize, model: MockTtsModel) -> usize {
+        let temp_dir = TempDir::new().unwrap();
+        let config = TtsConfig {
+            max_concurrent: Some(max_concurrent),
+            ..create_mock_config(&temp_dir)
+        };
+        let engine = Arc::new(create_mock_engine(config).await);
+        let calls = model.call_counter();
+        let peak = model.peak_concurrency();
+        install_mock_model(&engine, model).await;
+
+        let tasks: Vec<_> = (0..2)
+            .map(|_| {
+                let engine = Arc::clone(&engine);
+                tokio::spawn(async move {
+                    let params = SynthesisParams::new(create_mock_voice());
+                    engine.synthesize("Hello", &params).await
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        peak.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_tts_engine_max_concurrent_serializes() {
+        // The latency gives the calls every chance to overlap without a limit
+        let model = MockTtsModel::with_latency(std::time::Duration::from_millis(20));
+        assert_eq!(peak_of_two_concurrent_syntheses(1, model).await, 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_tts_engine_max_concurrent_allows_overlap() {
+        // Neither call can finish until both are inside the model
+        let model = MockTtsModel::with_barrier(Arc::new(std::sync::Barrier::new(2)));
+        assert_eq!(peak_of_two_concurrent_syntheses(2, model).await, 2);
+    }
+
+    #[test]
+    fn test_tts_engine_is_send_sync_clone() {
+        fn assert_shareable<T: Send + Sync + Clone + 'static>() {}
+        assert_shareable::<TtsEngine>();
+    }
+
+    #[test]
+    fn test_tts_engine_clones_shared_across_threads() {
+        let temp_dir = TempDir::new().unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let engine = runtime
+            .block_on(create_mock_engine(create_mock_config(&temp_dir)));
+        let params = SynthesisParams::new(create_mock_voice());
+        let expected = runtime.block_on(engine.synthesize("Hello from a thread", &params)).unwrap();
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let engine = engine.clone();
+                let params = params.clone();
+                std::thread::spawn(move || {
+                    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+                    runtime.block_on(engine.synthesize("Hello from a thread", &params))
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            assert_eq!(thread.join().unwrap().unwrap(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_max_concurrent_zero_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = TtsConfig {
+            max_concurrent: Some(0),
+            ..create_mock_config(&temp_dir)
+        };
+        assert!(TtsEngine::with_config(config).await.is_err());
+    }
+
+    /// Engine whose mock model waits on `barrier` in every synthesis, with
+    /// the model's call counter
+    async fn create_gated_mock_engine(
+        temp_dir: &TempDir,
+        barrier: Arc<std::sync::Barrier>,
+    ) -> (TtsEngine, Arc<std::sync::atomic::AtomicUsize>) {
+        let engine = create_mock_engine(create_mock_config(temp_dir)).await;
+        let model = MockTtsModel::with_barrier(barrier);
+        let calls = model.call_counter();
+        install_mock_model(&engine, model).await;
+        (engine, calls)
+    }
+
+    /// Wait until the mock model has been entered `count` times
+    async fn wait_for_calls(calls: &std::sync::atomic::AtomicUsize, count: usize) {
+        while calls.load(std::sync::atomic::Ordering::SeqCst) < count {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    }
+
+    /// Engine with the sentence cache enabled and a counting mock model
+    async fn create_cached_mock_engine(
+        temp_dir: &TempDir,
+    ) -> (TtsEngine, Arc<std::sync::atomic::AtomicUsize>) {
+        let config = TtsConfig {
+            cache_capacity: 8,
+            ..create_mock_config(temp_dir)
+        };
+        let engine = create_mock_engine(config).await;
+        let model = MockTtsModel::new();
+        let calls = model.call_counter();
+        install_mock_model(&engine, model).await;
+        (engine, calls)
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_cache_hit_skips_model() {
+        let temp_dir = TempDir::new().unwrap();
+        let (engine, calls) = create_cached_mock_engine(&temp_dir).await;
+        let params = SynthesisParams::new(create_mock_voice());
+
+        let first = engine.synthesize("Hello world", &params).await.unwrap();
+        let second = engine.synthesize("  Hello   world ", &params).await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_cache_misses_on_speed_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let (engine, calls) = create_cached_mock_engine(&temp_dir).await;
+        let params = SynthesisParams::new(create_mock_voice());
+        let faster = params.clone().with_speed(1.5).unwrap();
+
+        let normal = engine.synthesize("Hello world", &params).await.unwrap();
+        let fast = engine.synthesize("Hello world", &faster).await.unwrap();
+
+        assert_ne!(normal.len(), fast.len());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_cache_invalidated_by_model_switch() {
+        let temp_dir = TempDir::new().unwrap();
+        let (engine, calls) = create_cached_mock_engine(&temp_dir).await;
+        let params = SynthesisParams::new(create_mock_voice());
+
+        // Register a second mock model under another ID
+        let mut alt_model = MockTtsModel::new();
+        let alt_calls = alt_model.call_counter();
+        crate::models::TtsModel::load(&mut alt_model).unwrap();
+        {
+            let mut registry = engine.model_registry.write().await;
+            let mut info = registry.installed_models[MOCK_MODEL_ID].clone();
+            info.id = "mock_alt".to_string();
+            registry.installed_models.insert(info.id.clone(), info);
+            registry.loaded_models.insert("mock_alt".to_string(), Box::new(alt_model));
+        }
+
+        engine.synthesize("Hello world", &params).await.unwrap();
+        engine.set_active_model("mock_alt").await.unwrap();
+        engine.synthesize("Hello world", &params).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(alt_calls.load(Ordering::SeqCst), 1);
+
+        // Switching back still hits the first model's entry
+        engine.set_active_model(MOCK_MODEL_ID).await.unwrap();
+        engine.synthesize("Hello world", &params).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_reload_active_model() {
+        let temp_dir = TempDir::new().unwrap();
+        let (engine, calls) = create_cached_mock_engine(&temp_dir).await;
+        let params = SynthesisParams::new(create_mock_voice());
+
+        let before = engine.synthesize("Hello world", &params).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let loads_before = engine.model_registry.read().await.models_loaded;
+        engine.reload_active_model().await.unwrap();
+        {
+            let registry = engine.model_registry.read().await;
+            assert_eq!(registry.active_model.as_deref(), Some(MOCK_MODEL_ID));
+            assert!(registry.is_model_loaded(MOCK_MODEL_ID));
+            // A new instance was built and loaded in place of the old one
+            assert_eq!(registry.models_loaded, loads_before + 1);
+        }
+        assert_eq!(engine.cache.as_ref().unwrap().lock().len(), 0);
+
+        // The fresh instance serves the request because the cache was
+        // dropped; the replaced one is never called again
+        let after = engine.synthesize("Hello world", &params).await.unwrap();
+        assert_eq!(engine.cache.as_ref().unwrap().lock().len(), 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(before, after);
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_reload_without_active_model() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        engine.model_registry.write().await.active_model = None;
+
+        let error = engine.reload_active_model().await.unwrap_err();
+        assert!(error.to_string().contains("No active model"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_installs_are_coalesced() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = TtsEngine::with_config(create_mock_config(&temp_dir)).await.unwrap();
+
+        let installs: Vec<_> = (0..8)
+            .map(|_| {
+                let engine = engine.clone();
+                tokio::spawn(async move { engine.install_model(MOCK_MODEL_ID).await })
+            })
+            .collect();
+        for install in installs {
+            install.await.unwrap().expect("Every caller should see the install succeed");
+        }
+
+        let registry = engine.model_registry.read().await;
+        assert!(registry.is_model_installed(MOCK_MODEL_ID));
+        assert_eq!(registry.installs_performed, 1);
+    }
+
+    #[test]
+    fn test_synthesize_once_writes_wav() {
+        let temp_dir = TempDir::new().unwrap();
+        let out = temp_dir.path().join("hello.wav");
+
+        synthesize_once_with_config("Hello from the command line.", "mock_female", &out, create_mock_config(&temp_dir))
+            .expect("One-shot synthesis should write the file");
+
+        let reader = hound::WavReader::open(&out).expect("Output should be a valid WAV");
+        assert_eq!(reader.spec().sample_rate, crate::DEFAULT_SAMPLE_RATE);
+        assert_eq!(reader.spec().channels, 1);
+        assert!(reader.len() > 0);
+
+        // The format is checked before any work is done
+        let unknown = temp_dir.path().join("hello.xyz");
+        assert!(synthesize_once_with_config("Hello", "mock_female", &unknown, create_mock_config(&temp_dir)).is_err());
+        assert!(!unknown.exists());
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_synthesize_or_silence() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        install_mock_model(&engine, MockTtsModel::with_failure_on("garbled")).await;
+        let params = SynthesisParams::new(create_mock_voice());
+
+        let sentences = ["First sentence.", "A garbled one.", "Last sentence."];
+        let mut document = Vec::new();
+        for sentence in sentences {
+            document.push(engine.synthesize_or_silence(sentence, &params, 250).await.unwrap());
+        }
+        assert_eq!(document[0], engine.synthesize(sentences[0], &params).await.unwrap());
+        assert_eq!(document[1], vec![0.0; 6000]);
+        assert_eq!(document[2], engine.synthesize(sentences[2], &params).await.unwrap());
+        assert!(engine.synthesize(sentences[1], &params).await.is_err());
+
+        // Silence follows the requested output rate
+        let resampled = params.clone().with_output_sample_rate(16_000).unwrap();
+        let silence = engine.synthesize_or_silence(sentences[1], &resampled, 250).await.unwrap();
+        assert_eq!(silence.len(), 4000);
+
+        // Errors that would hit every sentence are still reported
+        let unsupported = SynthesisParams::new(voice_for_id("af_heart"));
+        assert!(engine.synthesize_or_silence("Hello", &unsupported, 250).await.is_err());
+        let mut too_fast = params.clone();
+        too_fast.speed = 10.0;
+        let error = engine.synthesize_or_silence("Hello", &too_fast, 250).await.unwrap_err();
+        assert!(matches!(error, VocalizeError::InvalidInput { .. }), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_synthesize_or_silence_after_shutdown() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        let handle = engine.clone();
+        engine.shutdown().await.unwrap();
+
+        let params = SynthesisParams::new(create_mock_voice());
+        let error = handle.synthesize_or_silence("Hello", &params, 250).await.unwrap_err();
+        assert!(matches!(error, VocalizeError::ShutdownError { .. }), "{error}");
+        assert!(!error.is_recoverable());
+    }
+
+    /// Engine whose mock model offers an English and a Japanese voice
+    async fn create_bilingual_mock_engine(temp_dir: &TempDir) -> TtsEngine {
+        let engine = create_mock_engine(create_mock_config(temp_dir)).await;
+        install_mock_model(&engine, MockTtsModel::with_voices(&["af_mock", "jf_mock"])).await;
+        engine
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_preview_each_default_voice() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        let voice_ids: Vec<String> = crate::voice_manager::VoiceManager::new()
+            .get_available_voices()
+            .into_iter()
+            .map(|voice| voice.id.clone())
+            .collect();
+        assert!(!voice_ids.is_empty());
+        let model = MockTtsModel::with_voices(&voice_ids.iter().map(String::as_str).collect::<Vec<_>>());
+        install_mock_model(&engine, model).await;
+
+        for voice_id in &voice_ids {
+            let audio = engine.preview_voice(voice_id, None).await.unwrap();
+            assert!(!audio.is_empty(), "empty preview for {voice_id}");
+        }
+
+        let custom = engine.preview_voice(&voice_ids[0], Some("Hi")).await.unwrap();
+        let default = engine.preview_voice(&voice_ids[0], None).await.unwrap();
+        assert!(custom.len() < default.len());
+        assert!(engine.preview_voice("not_a_voice", None).await.is_err());
+    }
+
+    #[test]
+    fn test_preview_text_for_language() {
+        let japanese = preview_text_for_language("ja-JP");
+        assert_eq!(crate::text::detect_language(japanese).as_deref(), Some("ja"));
+        assert_eq!(preview_text_for_language("JA"), japanese);
+        assert_eq!(preview_text_for_language("en-GB"), DEFAULT_PREVIEW_TEXT);
+        assert_eq!(preview_text_for_language("fr_FR"), "Bonjour ! Voici un aperçu de ma voix.");
+        assert_eq!(preview_text_for_language("xx-YY"), DEFAULT_PREVIEW_TEXT);
+        assert_eq!(preview_text_for_language(""), DEFAULT_PREVIEW_TEXT);
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_preview_uses_language_phrase() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_bilingual_mock_engine(&temp_dir).await;
+        let japanese_voice = voice_from_id("jf_mock").unwrap();
+        assert_eq!(japanese_voice.language, "ja-JP");
+
+        let preview = engine.preview_voice("jf_mock", None).await.unwrap();
+        let expected = engine
+            .synthesize(preview_text_for_language("ja-JP"), &SynthesisParams::new(japanese_voice))
+            .await
+            .unwrap();
+        assert_eq!(preview, expected);
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_synthesize_auto_voice_selects_by_language() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_bilingual_mock_engine(&temp_dir).await;
+        let english = SynthesisParams::new(voice_from_id("af_mock").unwrap());
+        let japanese = SynthesisParams::new(voice_from_id("jf_mock").unwrap());
+
+        // English text keeps the requested English voice
+        let audio = engine.synthesize_auto_voice("Hello there", &english).await.unwrap();
+        assert_eq!(audio, engine.synthesize("Hello there", &english).await.unwrap());
+
+        // Japanese text is routed to the Japanese voice
+        let text = "こんにちは、元気ですか";
+        let audio = engine.synthesize_auto_voice(text, &english).await.unwrap();
+        assert_eq!(audio, engine.synthesize(text, &japanese).await.unwrap());
+        assert_ne!(audio, engine.synthesize(text, &english).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_synthesize_auto_voice_keeps_latin_script_voices() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        install_mock_model(&engine, MockTtsModel::with_voices(&["af_mock", "ef_mock", "ff_mock"])).await;
+
+        for voice_id in ["ef_mock", "ff_mock"] {
+            let params = SynthesisParams::new(voice_from_id(voice_id).unwrap());
+            let text = "Hola, buenos días";
+            let audio = engine.synthesize_auto_voice(text, &params).await.unwrap();
+            assert_eq!(audio, engine.synthesize(text, &params).await.unwrap(), "{voice_id}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_synthesize_multilingual_switches_voices() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_bilingual_mock_engine(&temp_dir).await;
+        let english = SynthesisParams::new(voice_from_id("af_mock").unwrap());
+        let japanese = SynthesisParams::new(voice_from_id("jf_mock").unwrap());
+
+        let audio = engine
+            .synthesize_multilingual("Good morning. おはようございます。See you!", &english)
+            .await
+            .unwrap();
+
+        let mut expected = engine.synthesize("Good morning.", &english).await.unwrap();
+        expected.extend(engine.synthesize("おはようございます。", &japanese).await.unwrap());
+        expected.extend(engine.synthesize("See you!", &english).await.unwrap());
+        assert_eq!(audio, expected);
+
+        // Reading the Japanese sentence with the English voice sounds different
+        let mut single_voice = engine.synthesize("Good morning.", &english).await.unwrap();
+        single_voice.extend(engine.synthesize("おはようございます。", &english).await.unwrap());
+        single_voice.extend(engine.synthesize("See you!", &english).await.unwrap());
+        assert_ne!(audio, single_voice);
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_synthesize_document_reports_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        let params = SynthesisParams::new(create_mock_voice());
+        let text = "First sentence. Second sentence! Third?";
+
+        let mut progress = Vec::new();
+        let mut record = |done: usize, total: usize| -> VocalizeResult<()> {
+            progress.push((done, total));
+            Ok(())
+        };
+        let audio = engine.synthesize_document(text, &params, &mut record).await.unwrap();
+        let empty = engine.synthesize_document("   ", &params, &mut record).await.unwrap();
+
+        assert_eq!(progress, vec![(0, 3), (1, 3), (2, 3), (3, 3)]);
+        assert_eq!(audio, engine.synthesize_multilingual(text, &params).await.unwrap());
+        assert!(empty.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_synthesize_document_progress_error_aborts() {
+        let temp_dir = TempDir::new().unwrap();
+        let (engine, calls) = create_cached_mock_engine(&temp_dir).await;
+        let params = SynthesisParams::new(create_mock_voice());
+
+        let mut progress = Vec::new();
+        let error = engine
+            .synthesize_document("First sentence. Second sentence! Third?", &params, |done, total| {
+                progress.push((done, total));
+                if done == 1 {
+                    return Err(VocalizeError::synthesis("cancelled".to_string()));
+                }
+                Ok(())
+            })
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("cancelled"), "{error}");
+        assert_eq!(progress, vec![(0, 3), (1, 3)]);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_synthesize_auto_voice_without_matching_voice() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_bilingual_mock_engine(&temp_dir).await;
+        let english = SynthesisParams::new(voice_from_id("af_mock").unwrap());
+
+        let error = engine.synthesize_auto_voice("안녕하세요", &english).await.unwrap_err();
+        assert!(error.to_string().contains("'ko'"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_cache_disabled_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        assert!(engine.cache.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_rejects_empty_model_output() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        install_mock_model(&engine, MockTtsModel::with_empty_output()).await;
+
+        let params = SynthesisParams::new(create_mock_voice());
+        let error = engine.synthesize("Hello", &params).await.unwrap_err();
+        assert_eq!(
+            error,
+            VocalizeError::synthesis(
+                "Model 'mock' produced no audio for 5 characters of text with voice 'mock_female'"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_rejects_unsupported_voice() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        let params = SynthesisParams::new(Voice::new(
+            "af_bella".to_string(),
+            "Bella".to_string(),
+            "en-US".to_string(),
+            crate::voice_manager::Gender::Female,
+            crate::voice_manager::VoiceStyle::Natural,
+        ));
+
+        let error = engine.synthesize("Hello", &params).await.unwrap_err();
+        assert_eq!(
+            error,
+            VocalizeError::voice_not_supported(
+                "af_bella",
+                vec!["mock_female".to_string(), "mock_male".to_string()]
+            )
+        );
+        assert!(error.to_string().contains("mock_female, mock_male"));
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_supported_voices() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        assert_eq!(
+            engine.supported_voices().await.unwrap(),
+            vec!["mock_female".to_string(), "mock_male".to_string()]
+        );
+
+        // Kokoro reports its voices without needing the ONNX model loaded
+        {
+            let mut registry = engine.model_registry.write().await;
+            let kokoro = crate::models::kokoro_model::KokoroModel::new(temp_dir.path().to_path_buf());
+            registry.loaded_models.insert("kokoro".to_string(), Box::new(kokoro));
+            registry.active_model = Some("kokoro".to_string());
+        }
+        let voices = engine.supported_voices().await.unwrap();
+        for voice_id in ["af_heart", "af_bella", "am_adam", "bf_emma", "bm_george"] {
+            assert!(voices.iter().any(|v| v == voice_id), "missing {voice_id}");
+        }
+
+        // With nothing active, the default model's declared voices are listed
+        engine.clear_cache().await.unwrap();
+        assert_eq!(
+            engine.supported_voices().await.unwrap(),
+            vec!["mock_female".to_string(), "mock_male".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_output_sample_rate() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        assert_eq!(engine.output_sample_rate().await, crate::DEFAULT_SAMPLE_RATE);
+
+        {
+            let mut registry = engine.model_registry.write().await;
+            let kokoro = crate::models::kokoro_model::KokoroModel::new(temp_dir.path().to_path_buf());
+            registry.loaded_models.insert("kokoro".to_string(), Box::new(kokoro));
+            registry.active_model = Some("kokoro".to_string());
+        }
+        assert_eq!(engine.output_sample_rate().await, 24_000);
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_time_stretch_applied_after_synthesis() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        let text = "Stretch this sentence without changing its pitch";
+
+        let params = SynthesisParams::new(create_mock_voice());
+        let native = engine.synthesize(text, &params).await.unwrap();
+        let stretched = engine.synthesize(text, &params.with_time_stretch(2.0).unwrap()).await.unwrap();
+
+        assert_eq!(stretched.len(), (native.len() as f32 / 2.0).round() as usize);
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_output_sample_rate_override() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        let native_rate = engine.output_sample_rate().await;
+        let text = "Resample this sentence for speech recognition";
+
+        let params = SynthesisParams::new(create_mock_voice());
+        let native = engine.synthesize(text, &params).await.unwrap();
+        let params = params.with_output_sample_rate(16_000).unwrap();
+        let resampled = engine.synthesize(text, &params).await.unwrap();
+
+        let expected_len = (native.len() as u64 * 16_000).div_ceil(u64::from(native_rate)) as usize;
+        assert_eq!(resampled.len(), expected_len);
+
+        // The mock voice's tone lands in the same spectrogram bin at both rates
+        let dominant_frequency = |audio: &[f32], rate: u32| {
+            let frames = crate::audio_effects::spectrogram(audio, rate, 1024, 1024);
+            let (bin, _) = frames[1]
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .unwrap();
+            crate::audio_effects::spectrogram_bin_frequency(bin, rate, 1024)
+        };
+        let native_pitch = dominant_frequency(&native, native_rate);
+        let resampled_pitch = dominant_frequency(&resampled, 16_000);
+        let bin_width = native_rate as f32 / 1024.0;
+        assert!((native_pitch - resampled_pitch).abs() <= bin_width, "{native_pitch} vs {resampled_pitch}");
+
+        // Requesting the native rate is a no-op
+        let params = SynthesisParams::new(create_mock_voice()).with_output_sample_rate(native_rate).unwrap();
+        assert_eq!(engine.synthesize(text, &params).await.unwrap(), native);
+    }
+
+    #[tokio::test]
+    async fn test_tts_engine_pronunciation_overrides() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = create_mock_engine(create_mock_config(&temp_dir)).await;
+        let params = SynthesisParams::new(create_mock_voice());
+
+        let default = engine.synthesize("Vocalize it", &params).await.unwrap();
+        assert_eq!(default.len(), MockTtsModel::expected_len("Vocalize it", 1.0));
+
+        let overrides = HashMap::from([("vocalize".to_string(), "vˈoʊkəlaɪz".to_string())]);
+        engine.set_pronunciation_overrides(overrides).unwrap();
+        let overridden = engine.synthesize("Vocalize it", &params).await.unwrap();
+        assert_eq!(
+            overridden.len(),
+            MockTtsModel::expected_len("[Vocalize](/vˈoʊkəlaɪz/) it", 1.0)
+        );
+        assert_ne!(default, overridden);
+
+        // Invalid overrides are rejected and the previous ones kept
+        let invalid = HashMap::from([("two words".to_string(), "x".to_string())]);
+        assert!(engine.set_pronunciation_overrides(invalid).is_err());
+        assert_eq!(engine.synthesize("Vocalize it", &params).await.unwrap(), overridden);
+
+        engine.set_pronunciation_overrides(HashMap::new()).unwrap();
+        assert_eq!(engine.synthesize("Vocalize it", &params).await.unwrap(), default);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_tts_engine_shutdown_drains_in_flight() {
+        let temp_dir = TempDir::new().unwrap();
+        let barrier = Arc::new(std::sync::Barrier::new(2));
+        let (engine, calls) = create_gated_mock_engine(&temp_dir, Arc::clone(&barrier)).await;
+
+        let handle = engine.clone();
+        let in_flight = tokio::spawn(async move {
+            let params = SynthesisParams::new(create_mock_voice());
+            handle.synthesize("Hello", &params).await
+        });
+        wait_for_calls(&calls, 1).await;
+
+        let late_handle = engine.clone();
+        let shutdown = engine.shutdown();
+        tokio::pin!(shutdown);
+
+        // The synthesis is held inside the model, so shutdown cannot finish
+        assert!(tokio::time::timeout(Duration::from_millis(20), &mut shutdown).await.is_err());
+
+        tokio::task::spawn_blocking(move || barrier.wait()).await.unwrap();
+        shutdown.await.unwrap();
+        assert!(in_flight.await.unwrap().is_ok());
+
+        // New work is refused and models are unloaded
+        let params = SynthesisParams::new(create_mock_voice());
+        assert!(late_handle.synthesize("Hello", &params).await.is_err());
+        assert!(!late_handle.is_initialized().await);
+        assert!(late_handle.get_stats().await.active_model.is_none());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_tts_engine_shutdown_timeout() {
+        let temp_dir = TempDir::new().unwrap();
+        let barrier = Arc::new(std::sync::Barrier::new(2));
+        let (engine, calls) = create_gated_mock_engine(&temp_dir, Arc::clone(&barrier)).await;
+
+        let handle = engine.clone();
+        let in_flight = tokio::spawn(async move {
+            let params = SynthesisParams::new(create_mock_voice());
+            handle.synthesize("Hello", &params).await
+        });
+        wait_for_calls(&calls, 1).await;
+
+        // The synthesis stays inside the model until the barrier is released
+        let result = engine.shutdown_with_timeout(Duration::from_millis(20)).await;
+        assert!(result.is_err());
+
+        tokio::task::spawn_blocking(move || barrier.wait()).await.unwrap();
+        assert!(in_flight.await.unwrap().is_ok());
+    }
+
     #[tokio::test]
     async fn test_tts_engine_get_stats() {
         let temp_dir = TempDir::new().unwrap();
@@ -751,7 +2734,7 @@ mod tests {
         assert_eq!(stats.device, TtsDevice::Cpu);
         assert_eq!(stats.sample_rate, crate::DEFAULT_SAMPLE_RATE);
         assert_eq!(stats.max_text_length, crate::MAX_TEXT_LENGTH);
-        assert!(stats.installed_model_count > 0);
+        assert_eq!(stats.installed_model_count, 0);
     }
 
     #[tokio::test]
@@ -795,8 +2778,8 @@ mod tests {
             ..TtsConfig::default()
         };
         
-        // Create engine with auto-install to test listing
         let engine = TtsEngine::with_config(config).await.unwrap();
+        engine.install_model("kokoro").await.unwrap();
         
         // Test listing available models
         let available = engine.list_available_models().await;
@@ -818,8 +2801,8 @@ mod tests {
             ..TtsConfig::default()
         };
         
-        // This should fail since we don't have models and auto-install is disabled
-        let result = TtsEngine::with_config(config2).await;
-        assert!(result.is_err());
+        // Nothing is installed, but the engine can still list models
+        let engine = TtsEngine::with_config(config2).await.unwrap();
+        assert!(engine.list_installed_models().await.is_empty());
     }
 }
\ No newline at end of file
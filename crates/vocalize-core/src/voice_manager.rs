@@ -1,10 +1,19 @@
 //! Voice management and selection for TTS synthesis.
 
 use crate::error::{VocalizeError, VocalizeResult};
+use crate::model::ModelId;
 use serde::{Deserialize, Serialize};
+use parking_lot::RwLock;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Number of floats in a Kokoro style vector
+pub const STYLE_VECTOR_DIM: usize = 256;
+
+/// Floats in a full Kokoro voice pack: one style vector per token length
+const VOICE_PACK_LEN: usize = 510 * STYLE_VECTOR_DIM;
+
 /// Gender classification for voices
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Gender {
@@ -76,6 +85,9 @@ pub struct Voice {
     pub speed: f32,
     /// Pitch adjustment (-1.0 to 1.0, 0.0 = no change)
     pub pitch: f32,
+    /// Model this voice belongs to, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_id: Option<String>,
 }
 
 impl Voice {
@@ -99,6 +111,7 @@ impl Voice {
             available: true,
             speed: 1.0,
             pitch: 0.0,
+            model_id: None,
         }
     }
 
@@ -116,6 +129,13 @@ impl Voice {
         self
     }
 
+    /// Set the model this voice belongs to
+    #[must_use]
+    pub fn with_model_id(mut self, model_id: String) -> Self {
+        self.model_id = Some(model_id);
+        self
+    }
+
     /// Set the speed multiplier
     ///
     /// # Errors
@@ -206,14 +226,78 @@ impl Default for Voice {
     }
 }
 
+/// Language of a voice named with Kokoro's `<language><gender>_<name>`
+/// convention, e.g. `"af_bella"` (American English, female)
+///
+/// Returns `None` for IDs that don't follow the convention.
+#[must_use]
+pub fn language_for_voice_id(voice_id: &str) -> Option<&'static str> {
+    let mut chars = voice_id.chars();
+    let (language, gender, separator) = (chars.next()?, chars.next()?, chars.next()?);
+    if !matches!(gender, 'f' | 'm') || separator != '_' {
+        return None;
+    }
+    match language {
+        'a' => Some("en-US"),
+        'b' => Some("en-GB"),
+        'j' => Some("ja-JP"),
+        'z' => Some("zh-CN"),
+        'e' => Some("es-ES"),
+        'f' => Some("fr-FR"),
+        'h' => Some("hi-IN"),
+        'i' => Some("it-IT"),
+        'p' => Some("pt-BR"),
+        _ => None,
+    }
+}
+
+/// Pick the first of `voice_ids` whose language matches `language`
+///
+/// `language` may be a full tag (`"en-GB"`) or a primary subtag (`"ja"`);
+/// voice languages come from [`language_for_voice_id`].
+#[must_use]
+pub fn select_voice_for_language<'a>(voice_ids: &'a [String], language: &str) -> Option<&'a String> {
+    voice_ids.iter().find(|voice_id| {
+        language_for_voice_id(voice_id).is_some_and(|voice_language| {
+            voice_language.eq_ignore_ascii_case(language)
+                || voice_language.split('-').next().unwrap_or("").eq_ignore_ascii_case(language)
+        })
+    })
+}
+
+/// Build a [`Voice`] for a Kokoro-style voice ID, inferring its language
+/// and gender from the ID
+///
+/// Other models (e.g. the mock model) reuse the naming convention, so the
+/// voice is not tagged with a model ID. Returns `None` for IDs that don't
+/// follow the naming convention.
+#[must_use]
+pub fn voice_from_id(voice_id: &str) -> Option<Voice> {
+    let language = language_for_voice_id(voice_id)?;
+    let gender = if voice_id[1..].starts_with('f') { Gender::Female } else { Gender::Male };
+    Some(Voice::new(
+        voice_id.to_string(),
+        voice_id.to_string(),
+        language.to_string(),
+        gender,
+        VoiceStyle::Natural,
+    ))
+}
+
 /// Voice manager for handling voice selection and configuration
 #[derive(Debug, Clone)]
 pub struct VoiceManager {
     voices: Arc<HashMap<String, Voice>>,
+    /// Embeddings registered for custom or blended voices, shared by clones
+    embeddings: Arc<RwLock<HashMap<String, Vec<f32>>>>,
+    /// Combined voices file that unregistered voices are exported from
+    voices_file: Option<PathBuf>,
 }
 
 impl VoiceManager {
     /// Create a new voice manager with default voices
+    ///
+    /// The defaults are Kokoro voices and are tagged with its model ID.
     #[must_use]
     pub fn new() -> Self {
         let mut voices = HashMap::new();
@@ -263,11 +347,13 @@ impl VoiceManager {
         ];
 
         for voice in default_voices {
-            voices.insert(voice.id.clone(), voice);
+            voices.insert(voice.id.clone(), voice.with_model_id(ModelId::Kokoro.as_str().to_string()));
         }
 
         Self {
             voices: Arc::new(voices),
+            embeddings: Arc::default(),
+            voices_file: None,
         }
     }
 
@@ -281,6 +367,8 @@ impl VoiceManager {
 
         Self {
             voices: Arc::new(voice_map),
+            embeddings: Arc::default(),
+            voices_file: None,
         }
     }
 
@@ -378,6 +466,114 @@ impl VoiceManager {
         languages.dedup();
         languages
     }
+
+    /// Set the combined voices file (e.g. Kokoro's `voices-v1.0.bin`) that
+    /// [`Self::export_voice_embedding`] reads voices without a registered
+    /// embedding from
+    pub fn set_voices_file(&mut self, voices_file: PathBuf) {
+        self.voices_file = Some(voices_file);
+    }
+
+    /// Register the embedding for `voice_id`, replacing any previous one
+    ///
+    /// The embedding is either a single style vector or a full Kokoro voice
+    /// pack of one style vector per token length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the embedding is not [`STYLE_VECTOR_DIM`] or
+    /// `510 * STYLE_VECTOR_DIM` finite floats
+    pub fn set_voice_embedding(&self, voice_id: &str, embedding: Vec<f32>) -> VocalizeResult<()> {
+        if embedding.len() != STYLE_VECTOR_DIM && embedding.len() != VOICE_PACK_LEN {
+            return Err(VocalizeError::invalid_input(format!(
+                "Voice embedding for '{voice_id}' must have {STYLE_VECTOR_DIM} values \
+                 (or {VOICE_PACK_LEN} for a full voice pack), got {}",
+                embedding.len()
+            )));
+        }
+        if let Some(position) = embedding.iter().position(|value| !value.is_finite()) {
+            return Err(VocalizeError::invalid_input(format!(
+                "Voice embedding for '{voice_id}' has a non-finite value at index {position}"
+            )));
+        }
+        self.embeddings.write().insert(voice_id.to_string(), embedding);
+        Ok(())
+    }
+
+    /// Get the embedding registered for `voice_id`, if any
+    #[must_use]
+    pub fn voice_embedding(&self, voice_id: &str) -> Option<Vec<f32>> {
+        self.embeddings.read().get(voice_id).cloned()
+    }
+
+    /// Get the style vector of the embedding registered for `voice_id`,
+    /// i.e. its first [`STYLE_VECTOR_DIM`] values, if any
+    #[must_use]
+    pub fn voice_style_vector(&self, voice_id: &str) -> Option<Vec<f32>> {
+        self.embeddings
+            .read()
+            .get(voice_id)
+            .map(|embedding| embedding[..STYLE_VECTOR_DIM].to_vec())
+    }
+
+    /// Write the embedding of `voice_id` to `path`
+    ///
+    /// A registered embedding is written as is; otherwise the voice's full
+    /// table is read from the voices file set with
+    /// [`Self::set_voices_file`]. The file holds little-endian `f32`
+    /// values, the format the ONNX engine loads from
+    /// `voices/<voice_id>.bin` in the model cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no embedding is registered for `voice_id` and it
+    /// cannot be read from the voices file, or the file cannot be written
+    pub fn export_voice_embedding<P: AsRef<Path>>(&self, voice_id: &str, path: P) -> VocalizeResult<()> {
+        let path = path.as_ref();
+        let embedding = match (self.voice_embedding(voice_id), &self.voices_file) {
+            (Some(embedding), _) => embedding,
+            (None, Some(voices_file)) => crate::onnx_engine::read_voice_table(voices_file, voice_id)?,
+            (None, None) => return Err(VocalizeError::voice_not_found(voice_id)),
+        };
+
+        let bytes: Vec<u8> = embedding.iter().flat_map(|value| value.to_le_bytes()).collect();
+        std::fs::write(path, bytes).map_err(|e| {
+            VocalizeError::file(format!("Failed to write voice embedding {}: {e}", path.display()))
+        })
+    }
+
+    /// Read an embedding from `path` and register it for `voice_id`
+    ///
+    /// Accepts files written by [`Self::export_voice_embedding`]: a single
+    /// style vector or a full Kokoro voice pack, which is kept whole.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, has an unexpected size,
+    /// or contains non-finite values
+    pub fn import_voice_embedding<P: AsRef<Path>>(&self, voice_id: &str, path: P) -> VocalizeResult<()> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| {
+            VocalizeError::file(format!("Failed to read voice embedding {}: {e}", path.display()))
+        })?;
+
+        let float_count = bytes.len() / 4;
+        if bytes.len() % 4 != 0 || (float_count != STYLE_VECTOR_DIM && float_count != VOICE_PACK_LEN) {
+            return Err(VocalizeError::file(format!(
+                "Voice embedding {} is {} bytes; expected {} ({STYLE_VECTOR_DIM} floats) or {} (a full voice pack)",
+                path.display(),
+                bytes.len(),
+                STYLE_VECTOR_DIM * 4,
+                VOICE_PACK_LEN * 4
+            )));
+        }
+
+        let embedding = bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+        self.set_voice_embedding(voice_id, embedding)
+    }
 }
 
 impl Default for VoiceManager {
@@ -584,7 +780,7 @@ mod tests {
     #[test]
     fn test_voice_manager_get_voice() {
         let manager = VoiceManager::new();
-        
+
         let voice = manager.get_voice("af_alloy").expect("Should find alloy");
         assert_eq!(voice.id, "af_alloy");
 
@@ -595,7 +791,7 @@ mod tests {
     #[test]
     fn test_voice_manager_is_voice_available() {
         let manager = VoiceManager::new();
-        
+
         assert!(manager.is_voice_available("af_alloy"));
         assert!(!manager.is_voice_available("nonexistent"));
     }
@@ -604,7 +800,7 @@ mod tests {
     fn test_voice_manager_get_available_voices() {
         let manager = VoiceManager::new();
         let voices = manager.get_available_voices();
-        
+
         assert!(!voices.is_empty());
         assert!(voices.iter().all(|v| v.available));
     }
@@ -613,7 +809,7 @@ mod tests {
     fn test_voice_manager_get_voices_by_language() {
         let manager = VoiceManager::new();
         let en_voices = manager.get_voices_by_language("en-US");
-        
+
         assert!(!en_voices.is_empty());
         assert!(en_voices.iter().all(|v| v.supports_language("en-US")));
     }
@@ -622,7 +818,7 @@ mod tests {
     fn test_voice_manager_get_voices_by_gender() {
         let manager = VoiceManager::new();
         let female_voices = manager.get_voices_by_gender(Gender::Female);
-        
+
         assert!(!female_voices.is_empty());
         assert!(female_voices.iter().all(|v| v.gender == Gender::Female));
     }
@@ -631,9 +827,11 @@ mod tests {
     fn test_voice_manager_get_voices_by_style() {
         let manager = VoiceManager::new();
         let natural_voices = manager.get_voices_by_style(VoiceStyle::Natural);
-        
+
         assert!(!natural_voices.is_empty());
-        assert!(natural_voices.iter().all(|v| v.style == VoiceStyle::Natural));
+        assert!(natural_voices
+            .iter()
+            .all(|v| v.style == VoiceStyle::Natural));
     }
 
     #[test]
@@ -648,7 +846,7 @@ mod tests {
     fn test_voice_manager_get_supported_languages() {
         let manager = VoiceManager::new();
         let languages = manager.get_supported_languages();
-        
+
         assert!(!languages.is_empty());
         assert!(languages.contains(&"en-US".to_string()));
         assert!(languages.contains(&"en-GB".to_string()));
@@ -665,9 +863,11 @@ mod tests {
         );
 
         let manager = VoiceManager::with_voices(vec![custom_voice.clone()]);
-        
+
         assert_eq!(manager.voice_count(), 1);
-        let retrieved = manager.get_voice("custom").expect("Should find custom voice");
+        let retrieved = manager
+            .get_voice("custom")
+            .expect("Should find custom voice");
         assert_eq!(retrieved, custom_voice);
     }
 
@@ -678,4 +878,152 @@ mod tests {
         let deserialized: Voice = serde_json::from_str(&json).expect("Should deserialize");
         assert_eq!(voice, deserialized);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_kokoro_voices_tagged_with_model_id() {
+        let manager = VoiceManager::new();
+        for voice in manager.get_available_voices() {
+            assert_eq!(
+                voice.model_id.as_deref(),
+                Some("kokoro"),
+                "voice {} untagged",
+                voice.id
+            );
+        }
+
+        // The naming convention alone doesn't say which model a voice is from
+        let voice = voice_from_id("af_bella").expect("Kokoro-style ID");
+        assert_eq!(voice.model_id, None);
+        assert_eq!(voice_from_id("jf_mock").unwrap().model_id, None);
+    }
+
+    #[test]
+    fn test_voice_model_id_backward_compatible() {
+        let untagged = Voice::new(
+            "custom".to_string(),
+            "Custom".to_string(),
+            "en-US".to_string(),
+            Gender::Female,
+            VoiceStyle::Natural,
+        );
+        let json = serde_json::to_string(&untagged).expect("Should serialize");
+        assert!(!json.contains("model_id"));
+
+        // Voices saved before the field existed still load
+        let loaded: Voice = serde_json::from_str(&json).expect("Should deserialize");
+        assert_eq!(loaded.model_id, None);
+
+        let tagged = untagged.with_model_id("kokoro".to_string());
+        let json = serde_json::to_string(&tagged).expect("Should serialize");
+        let loaded: Voice = serde_json::from_str(&json).expect("Should deserialize");
+        assert_eq!(loaded.model_id.as_deref(), Some("kokoro"));
+    }
+
+    #[test]
+    fn test_voice_embedding_export_import_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("blend.bin");
+        let embedding: Vec<f32> = (0..STYLE_VECTOR_DIM)
+            .map(|i| (i as f32 * 0.37).sin() * 0.4)
+            .collect();
+
+        let manager = VoiceManager::new();
+        manager
+            .set_voice_embedding("my_blend", embedding.clone())
+            .unwrap();
+        manager.export_voice_embedding("my_blend", &path).unwrap();
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().len(),
+            (STYLE_VECTOR_DIM * 4) as u64
+        );
+
+        let other = VoiceManager::new();
+        other.import_voice_embedding("shared", &path).unwrap();
+        assert_eq!(other.voice_embedding("shared"), Some(embedding));
+    }
+
+    #[test]
+    fn test_voice_pack_export_import_keeps_every_row() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("pack.bin");
+        // Each row of the table differs, so a dropped row shows up
+        let pack: Vec<f32> = (0..VOICE_PACK_LEN)
+            .map(|i| (i / STYLE_VECTOR_DIM) as f32 * 0.001)
+            .collect();
+
+        let manager = VoiceManager::new();
+        manager
+            .set_voice_embedding("my_pack", pack.clone())
+            .unwrap();
+        manager.export_voice_embedding("my_pack", &path).unwrap();
+
+        let other = VoiceManager::new();
+        other.import_voice_embedding("shared", &path).unwrap();
+        assert_eq!(other.voice_embedding("shared"), Some(pack.clone()));
+        assert_eq!(
+            other.voice_style_vector("shared"),
+            Some(pack[..STYLE_VECTOR_DIM].to_vec())
+        );
+    }
+
+    #[test]
+    fn test_voice_embedding_validation() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = VoiceManager::new();
+
+        assert!(manager.set_voice_embedding("short", vec![0.1; 10]).is_err());
+        let mut bad = vec![0.1; STYLE_VECTOR_DIM];
+        bad[3] = f32::NAN;
+        assert!(manager.set_voice_embedding("nan", bad).is_err());
+
+        // Nothing registered to export
+        assert!(manager
+            .export_voice_embedding("missing", temp_dir.path().join("x.bin"))
+            .is_err());
+
+        let truncated = temp_dir.path().join("truncated.bin");
+        std::fs::write(&truncated, [0u8; 100]).unwrap();
+        let error = manager
+            .import_voice_embedding("truncated", &truncated)
+            .unwrap_err();
+        assert!(error.to_string().contains("100 bytes"), "{error}");
+        assert_eq!(manager.voice_embedding("truncated"), None);
+    }
+
+    #[test]
+    fn test_language_for_voice_id() {
+        assert_eq!(language_for_voice_id("af_bella"), Some("en-US"));
+        assert_eq!(language_for_voice_id("bm_daniel"), Some("en-GB"));
+        assert_eq!(language_for_voice_id("jf_alpha"), Some("ja-JP"));
+        assert_eq!(language_for_voice_id("zm_yunxi"), Some("zh-CN"));
+        assert_eq!(language_for_voice_id("mock_female"), None);
+        assert_eq!(language_for_voice_id("qf_x"), None);
+        assert_eq!(language_for_voice_id(""), None);
+    }
+
+    #[test]
+    fn test_select_voice_for_language() {
+        let voices: Vec<String> = ["af_heart", "bm_daniel", "jf_alpha", "zf_xiaobei"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            select_voice_for_language(&voices, "ja").map(String::as_str),
+            Some("jf_alpha")
+        );
+        assert_eq!(
+            select_voice_for_language(&voices, "en").map(String::as_str),
+            Some("af_heart")
+        );
+        assert_eq!(
+            select_voice_for_language(&voices, "en-GB").map(String::as_str),
+            Some("bm_daniel")
+        );
+        assert_eq!(select_voice_for_language(&voices, "ko"), None);
+
+        let voice = voice_from_id("jf_alpha").unwrap();
+        assert_eq!(voice.language, "ja-JP");
+        assert_eq!(voice.gender, Gender::Female);
+        assert!(voice_from_id("mock_female").is_none());
+    }
+}
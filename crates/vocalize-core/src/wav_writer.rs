@@ -2,13 +2,13 @@
 //! 
 //! Provides functionality to write audio data in WAV/RIFF format.
 
-use std::fs::File;
-use std::io::{BufWriter, Write, Seek, SeekFrom};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Read, Write, Seek, SeekFrom};
 use std::path::Path;
 use crate::error::{VocalizeError, VocalizeResult};
 
 /// WAV file format specification
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct WavSpec {
     /// Number of audio channels (1 = mono, 2 = stereo)
     pub channels: u16,
@@ -47,36 +47,275 @@ impl WavSpec {
     }
 }
 
+/// Descriptive metadata embedded in a WAV file
+///
+/// Text fields go in a `LIST`/`INFO` chunk, which most players and editors
+/// display. The optional [`BroadcastExtension`] adds a `bext` chunk for
+/// Broadcast Wave workflows.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WavMetadata {
+    /// Title of the recording (`INAM`)
+    pub title: Option<String>,
+    /// Artist or narrator (`IART`)
+    pub artist: Option<String>,
+    /// Free-form comment (`ICMT`)
+    pub comment: Option<String>,
+    /// Creation date, e.g. `2025-01-31` (`ICRD`)
+    pub creation_date: Option<String>,
+    /// Software that produced the file (`ISFT`)
+    pub software: Option<String>,
+    /// Broadcast Wave `bext` chunk to write as well
+    pub broadcast: Option<BroadcastExtension>,
+}
+
+/// Fields of a Broadcast Wave (EBU Tech 3285) `bext` chunk
+///
+/// Each text field is ASCII and limited to its fixed width in the chunk.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BroadcastExtension {
+    /// Description of the sound sequence, up to 256 bytes
+    pub description: String,
+    /// Name of the originator, up to 32 bytes
+    pub originator: String,
+    /// Unambiguous reference allocated by the originator, up to 32 bytes
+    pub originator_reference: String,
+    /// Origination date as `yyyy-mm-dd`
+    pub origination_date: String,
+    /// Origination time as `hh:mm:ss`
+    pub origination_time: String,
+    /// First sample's position since midnight, in samples
+    pub time_reference: u64,
+}
+
+impl WavMetadata {
+    /// INFO sub-chunk IDs paired with the fields that are set
+    fn info_fields(&self) -> impl Iterator<Item = (&'static [u8; 4], &str)> + '_ {
+        [
+            (b"INAM", &self.title),
+            (b"IART", &self.artist),
+            (b"ICMT", &self.comment),
+            (b"ICRD", &self.creation_date),
+            (b"ISFT", &self.software),
+        ]
+        .into_iter()
+        .filter_map(|(id, value)| value.as_deref().map(|value| (id, value)))
+    }
+
+    /// Encode the `LIST`/`INFO` and `bext` chunks, each padded to an even length
+    fn to_chunks(&self) -> VocalizeResult<Vec<u8>> {
+        let mut chunks = Vec::new();
+
+        if let Some(broadcast) = &self.broadcast {
+            write_chunk(&mut chunks, b"bext", &broadcast.to_bytes()?)?;
+        }
+
+        if self.info_fields().next().is_some() {
+            let mut info = b"INFO".to_vec();
+            for (id, value) in self.info_fields() {
+                if value.contains('\0') {
+                    return Err(VocalizeError::invalid_input(format!(
+                        "WAV metadata field {} cannot contain NUL characters",
+                        String::from_utf8_lossy(id)
+                    )));
+                }
+                // INFO strings are NUL-terminated
+                let mut text = value.as_bytes().to_vec();
+                text.push(0);
+                write_chunk(&mut info, id, &text)?;
+            }
+            write_chunk(&mut chunks, b"LIST", &info)?;
+        }
+
+        Ok(chunks)
+    }
+}
+
+impl BroadcastExtension {
+    /// Length of a version 1 `bext` chunk without coding history
+    const LEN: usize = 602;
+
+    fn to_bytes(&self) -> VocalizeResult<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(Self::LEN);
+        for (name, value, width) in [
+            ("description", &self.description, 256),
+            ("originator", &self.originator, 32),
+            ("originator_reference", &self.originator_reference, 32),
+            ("origination_date", &self.origination_date, 10),
+            ("origination_time", &self.origination_time, 8),
+        ] {
+            if !value.is_ascii() || value.len() > width {
+                return Err(VocalizeError::invalid_input(format!(
+                    "bext {name} must be ASCII and at most {width} bytes, got {value:?}"
+                )));
+            }
+            bytes.extend_from_slice(value.as_bytes());
+            bytes.resize(bytes.len() + width - value.len(), 0);
+        }
+        bytes.extend_from_slice(&self.time_reference.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes()); // Version
+        // UMID and reserved bytes are left zeroed
+        bytes.resize(Self::LEN, 0);
+        Ok(bytes)
+    }
+}
+
+/// Append a RIFF chunk with `id` and `data`, padding it to an even length
+fn write_chunk(out: &mut Vec<u8>, id: &[u8; 4], data: &[u8]) -> VocalizeResult<()> {
+    let size = u32::try_from(data.len())
+        .map_err(|_| VocalizeError::invalid_input("WAV metadata chunk is too large"))?;
+    out.extend_from_slice(id);
+    out.extend_from_slice(&size.to_le_bytes());
+    out.extend_from_slice(data);
+    if data.len() % 2 == 1 {
+        out.push(0);
+    }
+    Ok(())
+}
+
 /// WAV file writer
 pub struct WavWriter {
     writer: BufWriter<File>,
     spec: WavSpec,
     bytes_written: u32,
+    /// Offset of the data chunk's size field, patched in `finalize`
+    data_size_offset: u64,
 }
 
 impl WavWriter {
     /// Create a new WAV file writer
     pub fn create<P: AsRef<Path>>(path: P, spec: WavSpec) -> VocalizeResult<Self> {
+        Self::create_with_metadata(path, spec, &WavMetadata::default())
+    }
+
+    /// Create a new WAV file writer that embeds `metadata`
+    ///
+    /// The metadata chunks are written between the `fmt` and `data` chunks,
+    /// so the file can still be appended to. Empty metadata writes a plain
+    /// 44-byte header.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a metadata field cannot be encoded or the file
+    /// cannot be created
+    pub fn create_with_metadata<P: AsRef<Path>>(path: P, spec: WavSpec, metadata: &WavMetadata) -> VocalizeResult<Self> {
+        let metadata_chunks = metadata.to_chunks()?;
+        
         let file = File::create(path.as_ref())
             .map_err(|e| VocalizeError::file(format!("Failed to create WAV file: {}", e)))?;
         
         let mut writer = BufWriter::new(file);
         
         // Write WAV header (will be updated in finalize)
-        Self::write_header(&mut writer, &spec, 0)?;
+        Self::write_header(&mut writer, &spec, &metadata_chunks, 0)?;
         
         Ok(Self {
             writer,
             spec,
             bytes_written: 0,
+            data_size_offset: 40 + metadata_chunks.len() as u64,
+        })
+    }
+
+    /// Open an existing WAV file for appending samples
+    ///
+    /// The file's format must match `spec` and its data chunk must be the
+    /// last chunk in the file. New samples are written after the existing
+    /// ones and `finalize` patches the RIFF and data chunk sizes.
+    pub fn append<P: AsRef<Path>>(path: P, spec: WavSpec) -> VocalizeResult<Self> {
+        let path = path.as_ref();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|e| VocalizeError::file(format!("Failed to open WAV file {}: {}", path.display(), e)))?;
+        let file_len = file.metadata()?.len();
+
+        let mut riff = [0u8; 12];
+        file.read_exact(&mut riff)
+            .map_err(|_| VocalizeError::file(format!("{} is too short to be a WAV file", path.display())))?;
+        if &riff[0..4] != b"RIFF" || &riff[8..12] != b"WAVE" {
+            return Err(VocalizeError::file(format!("{} is not a RIFF/WAVE file", path.display())));
+        }
+
+        let mut existing_spec = None;
+        let mut data_chunk = None;
+        let mut offset = 12u64;
+        while offset + 8 <= file_len {
+            let mut chunk_header = [0u8; 8];
+            file.seek(SeekFrom::Start(offset))?;
+            file.read_exact(&mut chunk_header)?;
+            let chunk_size = u32::from_le_bytes([chunk_header[4], chunk_header[5], chunk_header[6], chunk_header[7]]);
+
+            match &chunk_header[0..4] {
+                b"fmt " => {
+                    let mut fmt = [0u8; 16];
+                    file.read_exact(&mut fmt)?;
+                    let audio_format = u16::from_le_bytes([fmt[0], fmt[1]]);
+                    if audio_format != 1 && audio_format != 3 {
+                        return Err(VocalizeError::file(format!(
+                            "Cannot append to WAV file with audio format {}", audio_format
+                        )));
+                    }
+                    existing_spec = Some(WavSpec::new(
+                        u16::from_le_bytes([fmt[2], fmt[3]]),
+                        u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]),
+                        u16::from_le_bytes([fmt[14], fmt[15]]),
+                        audio_format == 3,
+                    ));
+                }
+                b"data" => {
+                    data_chunk = Some((offset + 4, chunk_size));
+                    break;
+                }
+                _ => {}
+            }
+
+            // Chunks are padded to an even number of bytes
+            offset += 8 + u64::from(chunk_size) + u64::from(chunk_size & 1);
+        }
+
+        let existing_spec = existing_spec
+            .ok_or_else(|| VocalizeError::file(format!("{} has no fmt chunk", path.display())))?;
+        let (data_size_offset, data_size) = data_chunk
+            .ok_or_else(|| VocalizeError::file(format!("{} has no data chunk", path.display())))?;
+
+        if existing_spec != spec {
+            return Err(VocalizeError::invalid_input(format!(
+                "WAV format mismatch: file has {} channel(s) at {} Hz, {}-bit{}, \
+                 but appended audio is {} channel(s) at {} Hz, {}-bit{}",
+                existing_spec.channels,
+                existing_spec.sample_rate,
+                existing_spec.bit_depth,
+                if existing_spec.is_float { " float" } else { "" },
+                spec.channels,
+                spec.sample_rate,
+                spec.bit_depth,
+                if spec.is_float { " float" } else { "" },
+            )));
+        }
+
+        if data_size_offset + 4 + u64::from(data_size) != file_len {
+            return Err(VocalizeError::file(format!(
+                "Cannot append to {}: the data chunk is not the last chunk in the file",
+                path.display()
+            )));
+        }
+
+        file.seek(SeekFrom::End(0))?;
+
+        Ok(Self {
+            writer: BufWriter::new(file),
+            spec,
+            bytes_written: data_size,
+            data_size_offset,
         })
     }
     
-    /// Write WAV/RIFF header (44 bytes)
-    fn write_header(writer: &mut BufWriter<File>, spec: &WavSpec, data_size: u32) -> VocalizeResult<()> {
+    /// Write WAV/RIFF header (44 bytes plus any metadata chunks)
+    fn write_header(writer: &mut BufWriter<File>, spec: &WavSpec, metadata_chunks: &[u8], data_size: u32) -> VocalizeResult<()> {
         // RIFF chunk
         writer.write_all(b"RIFF")?;
-        writer.write_all(&(36 + data_size).to_le_bytes())?; // File size - 8
+        writer.write_all(&(36 + metadata_chunks.len() as u32 + data_size).to_le_bytes())?; // File size - 8
         writer.write_all(b"WAVE")?;
         
         // fmt chunk
@@ -93,6 +332,9 @@ impl WavWriter {
         writer.write_all(&spec.block_align().to_le_bytes())?;
         writer.write_all(&spec.bit_depth.to_le_bytes())?;
         
+        // LIST/INFO and bext chunks, already padded
+        writer.write_all(metadata_chunks)?;
+        
         // data chunk
         writer.write_all(b"data")?;
         writer.write_all(&data_size.to_le_bytes())?;
@@ -170,11 +412,13 @@ impl WavWriter {
             .map_err(|e| VocalizeError::file(format!("Failed to finalize WAV writer: {}", e)))?;
         
         // Update RIFF chunk size (file size - 8)
+        let header_len = u32::try_from(self.data_size_offset + 4)
+            .map_err(|_| VocalizeError::file("WAV header is too large"))?;
         file.seek(SeekFrom::Start(4))?;
-        file.write_all(&(36 + self.bytes_written).to_le_bytes())?;
+        file.write_all(&(header_len - 8 + self.bytes_written).to_le_bytes())?;
         
         // Update data chunk size
-        file.seek(SeekFrom::Start(40))?;
+        file.seek(SeekFrom::Start(self.data_size_offset))?;
         file.write_all(&self.bytes_written.to_le_bytes())?;
         
         file.flush()?;
@@ -252,4 +496,101 @@ mod tests {
         let result = writer.finalize();
         assert!(result.is_ok());
     }
+    
+    #[test]
+    fn test_append_samples() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let spec = WavSpec::new(1, 24000, 16, false);
+        let mut writer = WavWriter::create(temp_file.path(), spec).unwrap();
+        writer.write_sample_i16(1).unwrap();
+        writer.write_sample_i16(2).unwrap();
+        writer.finalize().unwrap();
+        
+        let mut writer = WavWriter::append(temp_file.path(), spec).unwrap();
+        writer.write_sample_i16(3).unwrap();
+        writer.finalize().unwrap();
+        
+        let bytes = std::fs::read(temp_file.path()).unwrap();
+        assert_eq!(bytes.len(), 50);
+        assert_eq!(u32::from_le_bytes(bytes[4..8].try_into().unwrap()), 42);
+        assert_eq!(u32::from_le_bytes(bytes[40..44].try_into().unwrap()), 6);
+        assert_eq!(i16::from_le_bytes([bytes[48], bytes[49]]), 3);
+    }
+    
+    #[test]
+    fn test_write_info_metadata() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let spec = WavSpec::new(1, 24000, 16, false);
+        let metadata = WavMetadata {
+            title: Some("Chapter One".to_string()),
+            artist: Some("Vocalize".to_string()),
+            comment: Some("Narrated".to_string()),
+            broadcast: Some(BroadcastExtension {
+                description: "Audiobook narration".to_string(),
+                originator: "vocalize".to_string(),
+                origination_date: "2025-01-31".to_string(),
+                origination_time: "12:00:00".to_string(),
+                ..BroadcastExtension::default()
+            }),
+            ..WavMetadata::default()
+        };
+        let mut writer = WavWriter::create_with_metadata(temp_file.path(), spec, &metadata).unwrap();
+        for sample in [1, -2, 3] {
+            writer.write_sample_i16(sample).unwrap();
+        }
+        writer.finalize().unwrap();
+        
+        let bytes = std::fs::read(temp_file.path()).unwrap();
+        let find = |needle: &[u8]| bytes.windows(needle.len()).position(|w| w == needle);
+        let list = find(b"LIST").expect("LIST chunk");
+        assert_eq!(&bytes[list + 8..list + 12], b"INFO");
+        for (id, value) in [(b"INAM", "Chapter One"), (b"IART", "Vocalize"), (b"ICMT", "Narrated")] {
+            let at = find(id).expect("INFO field");
+            let len = u32::from_le_bytes(bytes[at + 4..at + 8].try_into().unwrap()) as usize;
+            assert_eq!(&bytes[at + 8..at + 8 + len], format!("{value}\0").as_bytes());
+        }
+        let bext = find(b"bext").expect("bext chunk");
+        assert_eq!(u32::from_le_bytes(bytes[bext + 4..bext + 8].try_into().unwrap()), 602);
+        assert!(find(b"Audiobook narration").is_some());
+        
+        // Sizes are consistent and the audio still decodes
+        assert_eq!(u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize, bytes.len() - 8);
+        let mut reader = hound::WavReader::open(temp_file.path()).unwrap();
+        let samples: Vec<i16> = reader.samples::<i16>().map(Result::unwrap).collect();
+        assert_eq!(samples, vec![1, -2, 3]);
+        
+        // Metadata sits before the data chunk, so appending still works
+        let mut writer = WavWriter::append(temp_file.path(), spec).unwrap();
+        writer.write_sample_i16(4).unwrap();
+        writer.finalize().unwrap();
+        let mut reader = hound::WavReader::open(temp_file.path()).unwrap();
+        assert_eq!(reader.samples::<i16>().count(), 4);
+    }
+    
+    #[test]
+    fn test_invalid_metadata_rejected() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let spec = WavSpec::new(1, 24000, 16, false);
+        let metadata = WavMetadata {
+            broadcast: Some(BroadcastExtension {
+                originator: "x".repeat(33),
+                ..BroadcastExtension::default()
+            }),
+            ..WavMetadata::default()
+        };
+        assert!(WavWriter::create_with_metadata(temp_file.path(), spec, &metadata).is_err());
+        
+        let metadata = WavMetadata { title: Some("a\0b".to_string()), ..WavMetadata::default() };
+        assert!(WavWriter::create_with_metadata(temp_file.path(), spec, &metadata).is_err());
+    }
+    
+    #[test]
+    fn test_append_spec_mismatch() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let spec = WavSpec::new(1, 24000, 16, false);
+        WavWriter::create(temp_file.path(), spec).unwrap().finalize().unwrap();
+        
+        let other = WavSpec::new(1, 48000, 16, false);
+        assert!(WavWriter::append(temp_file.path(), other).is_err());
+    }
 }
\ No newline at end of file
@@ -97,6 +97,7 @@ mod onnx_tts_engine_tests {
         let cache_dir = temp_dir.path().to_path_buf();
         
         let result = std::panic::catch_unwind(|| {
+            use vocalize_core::audio_effects::{db_to_gain, NonFinitePolicy, DEFAULT_TARGET_PEAK_DBFS};
             use vocalize_core::onnx_engine::OnnxTtsEngine;
             
             let rt = tokio::runtime::Runtime::new().unwrap();
@@ -105,11 +106,30 @@ mod onnx_tts_engine_tests {
                 
                 // Should postprocess raw model output
                 let raw_output = vec![0.1f32, 0.2, -0.1, 0.5, -0.3];
-                let processed = engine.postprocess_audio(&raw_output);
+                let processed = engine.postprocess_audio(&raw_output).unwrap();
                 
                 assert_eq!(processed.len(), raw_output.len());
                 // Should normalize audio to proper range
                 assert!(processed.iter().all(|&x| x >= -1.0 && x <= 1.0));
+                
+                // Non-finite samples are rejected by default...
+                let unstable = vec![0.1f32, f32::NAN, -0.1];
+                assert!(engine.postprocess_audio(&unstable).is_err());
+                
+                // ...or silenced when configured to
+                let mut engine = engine;
+                engine.set_non_finite_policy(NonFinitePolicy::ReplaceWithZero);
+                let scrubbed = engine.postprocess_audio(&unstable).unwrap();
+                assert_eq!(scrubbed[1], 0.0);
+                assert!((scrubbed[0] + scrubbed[2]).abs() < 1e-6);
+                
+                // Peaks land at the configured headroom
+                let peak = |audio: &[f32]| audio.iter().map(|x| x.abs()).fold(0.0f32, f32::max);
+                assert!((peak(&processed) - db_to_gain(DEFAULT_TARGET_PEAK_DBFS)).abs() < 1e-6);
+                engine.set_target_peak_dbfs(-3.0).unwrap();
+                let quieter = engine.postprocess_audio(&raw_output).unwrap();
+                assert!((peak(&quieter) - db_to_gain(-3.0)).abs() < 1e-6);
+                assert!(engine.set_target_peak_dbfs(1.0).is_err());
             });
         });
         assert!(result.is_ok(), "OnnxTtsEngine should postprocess audio");
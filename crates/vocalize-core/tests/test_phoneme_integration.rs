@@ -53,6 +53,31 @@ mod phoneme_integration_tests {
         println!("✅ synthesize_from_tokens interface test completed successfully");
     }
     
+    #[tokio::test]
+    async fn test_synthesize_from_tokens_rejects_out_of_vocabulary_ids() {
+        let cache_dir = get_test_cache_dir();
+
+        let engine_result = OnnxTtsEngine::new(cache_dir).await;
+        if engine_result.is_err() {
+            println!("✅ Engine creation failed as expected (no ONNX Runtime)");
+            return;
+        }
+
+        let mut engine = engine_result.unwrap();
+
+        // Kokoro's embedding table has 178 entries, so 9999 is out of range
+        let input_ids = vec![0, 50, 9999, 0];
+        let result = engine.synthesize_from_tokens(
+            input_ids,
+            vec![0.1; 256],
+            1.0,
+            ModelId::Kokoro
+        ).await;
+
+        let error_msg = result.expect_err("Out-of-range token should be rejected").to_string();
+        assert!(error_msg.contains("Token id 9999 at position 2"), "Error should name the token: {}", error_msg);
+    }
+
     #[tokio::test]
     async fn test_deprecated_synthesize_method() {
         // Test that the old synthesize method now returns the expected deprecation error
@@ -13,7 +13,8 @@ mod real_kokoro_tests {
     use vocalize_core::{
  
  
-        onnx_engine::OnnxTtsEngine,
+        audio_effects::{db_to_gain, DEFAULT_TARGET_PEAK_DBFS},
+        onnx_engine::{estimate_sample_count, ContextOverflowPolicy, OnnxTtsEngine, MAX_CONTEXT_TOKENS},
         model::ModelId,
     };
 
@@ -158,6 +159,186 @@ mod real_kokoro_tests {
         println!("✅ ZERO-FALLBACK SUCCESS: Real Kokoro model generated real speech!");
     }
     
+    #[tokio::test]
+    #[ignore] // Use 'cargo test -- --ignored' to run this test (requires existing model files)
+    async fn test_sample_count_estimate_matches_real_output() {
+        let cache_dir = get_real_cache_dir()
+            .expect("Failed to locate Kokoro model files - ensure they are downloaded via Python");
+        let mut engine = OnnxTtsEngine::new(cache_dir).await
+            .expect("Failed to create ONNX engine");
+        
+        // "Hello world" as Kokoro phoneme tokens, padded with 0 on both ends
+        let input_ids: Vec<i64> = vec![0, 50, 83, 54, 156, 57, 135, 16, 65, 156, 87, 158, 54, 46, 0];
+        let style_vector = vec![0.1; 256];
+        
+        for speed in [1.0, 2.0] {
+            let audio = engine
+                .synthesize_from_tokens(input_ids.clone(), style_vector.clone(), speed, ModelId::Kokoro)
+                .await
+                .expect("Token synthesis failed");
+            let estimate = estimate_sample_count(input_ids.len(), speed);
+            let ratio = estimate as f64 / audio.len() as f64;
+            
+            assert!(
+                (0.25..=4.0).contains(&ratio),
+                "estimate {estimate} vs actual {} at speed {speed}", audio.len()
+            );
+            assert_eq!(audio.capacity(), audio.len(), "buffer sized from the output tensor");
+        }
+    }
+    
+    #[tokio::test]
+    #[ignore] // Use 'cargo test -- --ignored' to run this test (requires existing model files)
+    async fn test_token_synthesis_peaks_at_target_level() {
+        let cache_dir = get_real_cache_dir()
+            .expect("Failed to locate Kokoro model files - ensure they are downloaded via Python");
+        let mut engine = OnnxTtsEngine::new(cache_dir).await
+            .expect("Failed to create ONNX engine");
+        
+        let input_ids: Vec<i64> = vec![0, 50, 83, 54, 156, 57, 135, 16, 65, 156, 87, 158, 54, 46, 0];
+        let style_vector = vec![0.1; 256];
+        
+        for target_peak_dbfs in [DEFAULT_TARGET_PEAK_DBFS, -6.0] {
+            engine.set_target_peak_dbfs(target_peak_dbfs).expect("Valid target peak");
+            let audio = engine
+                .synthesize_from_token_slices(&input_ids, &style_vector, 1.0, ModelId::Kokoro)
+                .await
+                .expect("Token synthesis failed");
+            
+            let peak = audio.iter().map(|x| x.abs()).fold(0.0f32, f32::max);
+            assert!(
+                (peak - db_to_gain(target_peak_dbfs)).abs() < 1e-4,
+                "peak {peak} at target {target_peak_dbfs} dBFS"
+            );
+        }
+    }
+    
+    #[tokio::test]
+    #[ignore] // Use 'cargo test -- --ignored' to run this test (requires existing model files)
+    async fn test_zero_style_vector_uses_fallback_voice_when_enabled() {
+        let cache_dir = get_real_cache_dir()
+            .expect("Failed to locate Kokoro model files - ensure they are downloaded via Python");
+        let mut engine = OnnxTtsEngine::new(cache_dir).await
+            .expect("Failed to create ONNX engine");
+        
+        let input_ids: Vec<i64> = vec![0, 50, 83, 54, 156, 57, 135, 16, 65, 156, 87, 158, 54, 46, 0];
+        let zeros = vec![0.0; 256];
+        
+        // Disabled by default: a failed embedding load is an error
+        assert!(!engine.style_fallback());
+        let error = engine
+            .synthesize_from_tokens(input_ids.clone(), zeros.clone(), 1.0, ModelId::Kokoro)
+            .await
+            .expect_err("All-zero style vector should be rejected without the fallback");
+        assert!(error.to_string().contains("all zeros"), "{error}");
+        
+        engine.set_style_fallback(true);
+        for style_vector in [zeros, Vec::new()] {
+            let audio = engine
+                .synthesize_from_tokens(input_ids.clone(), style_vector, 1.0, ModelId::Kokoro)
+                .await
+                .expect("Fallback voice synthesis failed");
+            assert!(validate_speech_not_tones(&audio));
+        }
+    }
+    
+    #[tokio::test]
+    #[ignore] // Use 'cargo test -- --ignored' to run this test (requires existing model files)
+    async fn test_kokoro_model_io_spec() {
+        let cache_dir = get_real_cache_dir()
+            .expect("Failed to locate Kokoro model files - ensure they are downloaded via Python");
+        let mut engine = OnnxTtsEngine::new(cache_dir).await
+            .expect("Failed to create ONNX engine");
+        assert!(engine.model_io_spec().is_err(), "No model is loaded yet");
+        
+        engine.load_model(ModelId::Kokoro).await
+            .expect("Failed to load Kokoro model");
+        let spec = engine.model_io_spec().expect("Failed to read model I/O spec");
+        
+        let inputs: Vec<(&str, &str)> = spec.inputs.iter()
+            .map(|input| (input.name.as_str(), input.element_type.as_str()))
+            .collect();
+        assert_eq!(inputs, vec![("tokens", "int64"), ("style", "float32"), ("speed", "float32")]);
+        assert_eq!(spec.inputs[1].dimensions.last(), Some(&256));
+        assert!(!spec.outputs.is_empty());
+        assert_eq!(spec.outputs[0].element_type, "float32");
+    }
+    
+    #[tokio::test]
+    #[ignore] // Use 'cargo test -- --ignored' to run this test (requires existing model files)
+    async fn test_long_token_sequence_with_window_policy() {
+        let cache_dir = get_real_cache_dir()
+            .expect("Failed to locate Kokoro model files - ensure they are downloaded via Python");
+        let mut engine = OnnxTtsEngine::new(cache_dir).await
+            .expect("Failed to create ONNX engine");
+        
+        // "Hello world" repeated past the context limit
+        let phrase = [50, 83, 54, 156, 57, 135, 16, 65, 156, 87, 158, 54, 46, 16];
+        let input_ids: Vec<i64> = std::iter::once(0)
+            .chain(phrase.iter().copied().cycle().take(700))
+            .chain(std::iter::once(0))
+            .collect();
+        assert!(input_ids.len() > MAX_CONTEXT_TOKENS);
+        let style_vector = vec![0.1; 256];
+        
+        assert!(engine
+            .synthesize_from_tokens(input_ids.clone(), style_vector.clone(), 1.0, ModelId::Kokoro)
+            .await
+            .is_err(), "Over-long input should be rejected by default");
+        
+        engine.set_context_overflow_policy(ContextOverflowPolicy::Window { overlap: 16 })
+            .expect("Valid overlap");
+        let audio = engine
+            .synthesize_from_tokens(input_ids.clone(), style_vector, 1.0, ModelId::Kokoro)
+            .await
+            .expect("Windowed synthesis failed");
+        
+        assert!(validate_speech_not_tones(&audio));
+        let estimate = estimate_sample_count(input_ids.len(), 1.0);
+        let ratio = estimate as f64 / audio.len() as f64;
+        assert!((0.25..=4.0).contains(&ratio), "estimate {estimate} vs actual {}", audio.len());
+    }
+    
+    #[tokio::test]
+    #[ignore] // Use 'cargo test -- --ignored' to run this test (requires existing model files)
+    async fn test_sink_delivery_matches_returned_audio() {
+        let cache_dir = get_real_cache_dir()
+            .expect("Failed to locate Kokoro model files - ensure they are downloaded via Python");
+        let mut engine = OnnxTtsEngine::new(cache_dir).await
+            .expect("Failed to create ONNX engine");
+        engine.set_context_overflow_policy(ContextOverflowPolicy::Window { overlap: 16 })
+            .expect("Valid overlap");
+        let style_vector = vec![0.1; 256];
+        
+        // One inference, then one spanning several windows
+        let phrase = [50, 83, 54, 156, 57, 135, 16, 65, 156, 87, 158, 54, 46, 16];
+        for body_len in [60, 1_200] {
+            let input_ids: Vec<i64> = std::iter::once(0)
+                .chain(phrase.iter().copied().cycle().take(body_len))
+                .chain(std::iter::once(0))
+                .collect();
+            
+            let returned = engine
+                .synthesize_from_token_slices(&input_ids, &style_vector, 1.0, ModelId::Kokoro)
+                .await
+                .expect("Token synthesis failed");
+            
+            let mut streamed = Vec::new();
+            let mut chunks = 0;
+            let total = engine
+                .synthesize_from_tokens_with_sink(&input_ids, &style_vector, 1.0, ModelId::Kokoro, |chunk| {
+                    streamed.extend_from_slice(chunk);
+                    chunks += 1;
+                })
+                .await
+                .expect("Sink synthesis failed");
+            
+            assert_eq!(total, returned.len(), "{body_len} tokens");
+            assert_eq!(streamed, returned, "{body_len} tokens");
+            assert!(chunks > 1, "{body_len} tokens delivered in one chunk");
+        }
+    }
+    
     #[tokio::test]
     async fn test_zero_fallback_model_discovery() {
         let _cache_dir = std::path::PathBuf::from("/tmp/nonexistent");
@@ -5,6 +5,7 @@ use std::process::Command;
 
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", extract_script_path().display());
     
     let target = env::var("TARGET").unwrap();
     let out_dir = env::var("OUT_DIR").unwrap();
@@ -64,122 +65,97 @@ fn download_onnx_runtime(out_dir: &str, target: &str) -> PathBuf {
     // Create directory
     fs::create_dir_all(&onnx_dir).expect("Failed to create ONNX Runtime directory");
     
+    // Removes the archive and, unless the download completes, the partial
+    // extraction too, so a failed build doesn't leave a broken install that
+    // the "already downloaded" check above could pick up next time
+    let archive_path = onnx_dir.join(format!("onnxruntime.{}", archive_ext));
+    let mut guard = DownloadGuard::new(&archive_path, &onnx_dir);
+    
     // Build download URL
     let url = format!(
         "https://github.com/microsoft/onnxruntime/releases/download/v{}/onnxruntime-{}-{}-{}.{}",
         onnx_version, platform, arch, onnx_version, archive_ext
     );
     
-    // Download using curl
-    let archive_path = onnx_dir.join(format!("onnxruntime.{}", archive_ext));
+    // Download using curl, giving up on a stalled connection rather than
+    // hanging the build: abort if the transfer stays under 1 KiB/s for a
+    // minute, and cap the whole download at 30 minutes
     let output = Command::new("curl")
-        .args(&["-L", "-f", "-o", archive_path.to_str().unwrap(), &url])
+        .args(&[
+            "-L", "-f",
+            "--connect-timeout", "30",
+            "--speed-limit", "1024", "--speed-time", "60",
+            "--max-time", "1800",
+            "-o", archive_path.to_str().unwrap(), &url,
+        ])
         .output()
         .expect("Failed to execute curl");
     
     if !output.status.success() {
         eprintln!("curl stderr: {}", String::from_utf8_lossy(&output.stderr));
+        // curl exits with 28 when one of the timeouts above fires
+        if output.status.code() == Some(28) {
+            panic!("Timed out downloading ONNX Runtime from: {}", url);
+        }
         panic!("Failed to download ONNX Runtime from: {}", url);
     }
     
-    // Extract based on archive type
-    if archive_ext == "zip" {
-        // Extract ZIP file (Windows)
-        let extract_script = format!(
-            r#"
-import zipfile
-import os
-import shutil
-
-archive_path = '{}'
-extract_dir = '{}'
-
-# Extract the zip file
-with zipfile.ZipFile(archive_path, 'r') as zip_ref:
-    zip_ref.extractall(extract_dir)
-
-# Find the extracted directory and move contents up
-for item in os.listdir(extract_dir):
-    if item.startswith('onnxruntime-'):
-        extracted = os.path.join(extract_dir, item)
-        for subitem in ['lib', 'include']:
-            src = os.path.join(extracted, subitem)
-            dst = os.path.join(extract_dir, subitem)
-            if os.path.exists(src):
-                if os.path.exists(dst):
-                    shutil.rmtree(dst)
-                shutil.move(src, dst)
-        # Remove the now-empty directory
-        if os.path.exists(extracted) and not os.listdir(extracted):
-            os.rmdir(extracted)
-        break
-"#,
-            archive_path.to_string_lossy().replace("\\", "/"),
-            onnx_dir.to_string_lossy().replace("\\", "/")
-        );
-        
-        let status = Command::new("python3")
-            .arg("-c")
-            .arg(&extract_script)
-            .status()
-            .expect("Failed to execute Python");
-        
-        if !status.success() {
-            panic!("Failed to extract ONNX Runtime");
-        }
-    } else {
-        // Extract TAR.GZ file (Linux/macOS)
-        let extract_script = format!(
-            r#"
-import tarfile
-import os
-import shutil
-
-archive_path = '{}'
-extract_dir = '{}'
-
-# Extract the tar.gz file
-with tarfile.open(archive_path, 'r:gz') as tar:
-    tar.extractall(extract_dir)
-
-# Find the extracted directory and move contents up
-for item in os.listdir(extract_dir):
-    if item.startswith('onnxruntime-'):
-        extracted = os.path.join(extract_dir, item)
-        for subitem in ['lib', 'include']:
-            src = os.path.join(extracted, subitem)
-            dst = os.path.join(extract_dir, subitem)
-            if os.path.exists(src):
-                if os.path.exists(dst):
-                    shutil.rmtree(dst)
-                shutil.move(src, dst)
-        # Remove the now-empty directory
-        if os.path.exists(extracted) and not os.listdir(extracted):
-            os.rmdir(extracted)
-        break
-"#,
-            archive_path.to_string_lossy().replace("\\", "/"),
-            onnx_dir.to_string_lossy().replace("\\", "/")
-        );
-        
-        let status = Command::new("python3")
-            .arg("-c")
-            .arg(&extract_script)
-            .status()
-            .expect("Failed to execute Python");
-        
-        if !status.success() {
-            panic!("Failed to extract ONNX Runtime");
-        }
+    // Extract with the bundled script, which refuses entries that would
+    // escape the ONNX Runtime directory (e.g. `../` paths or absolute links)
+    let status = Command::new("python3")
+        .arg(extract_script_path())
+        .arg(&archive_path)
+        .arg(&onnx_dir)
+        .status()
+        .expect("Failed to execute Python");
+    
+    if !status.success() {
+        panic!("Failed to extract ONNX Runtime");
     }
     
-    // Clean up archive file
-    let _ = fs::remove_file(&archive_path);
+    guard.complete();
     
     println!("cargo:warning=ONNX Runtime downloaded successfully");
     onnx_dir
 }
 
+/// Cleans up after `download_onnx_runtime` on every exit path, including
+/// the panics it uses to fail the build
+struct DownloadGuard {
+    archive_path: PathBuf,
+    onnx_dir: PathBuf,
+    completed: bool,
+}
+
+impl DownloadGuard {
+    fn new(archive_path: &Path, onnx_dir: &Path) -> Self {
+        Self {
+            archive_path: archive_path.to_path_buf(),
+            onnx_dir: onnx_dir.to_path_buf(),
+            completed: false,
+        }
+    }
+    
+    /// Keep the extracted runtime; only the archive is removed on drop
+    fn complete(&mut self) {
+        self.completed = true;
+    }
+}
+
+impl Drop for DownloadGuard {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.archive_path);
+        if !self.completed {
+            let _ = fs::remove_dir_all(&self.onnx_dir);
+        }
+    }
+}
+
+/// Script that unpacks the downloaded ONNX Runtime archive
+fn extract_script_path() -> PathBuf {
+    PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("extract_onnxruntime.py")
+}
+
 // Removed: copy_dlls_to_package - DLL bundling handled by delvewheel via cibuildwheel
 
 fn is_wsl() -> bool {
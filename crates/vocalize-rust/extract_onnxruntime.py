@@ -0,0 +1,133 @@
+"""
+Extract an ONNX Runtime release archive for build.rs.
+
+Usage: python3 extract_onnxruntime.py ARCHIVE DEST
+
+Unpacks a .zip (Windows) or .tgz (Linux/macOS) release into DEST and moves
+its lib/ and include/ directories up to DEST. Every entry is validated before
+anything is written, so an archive containing absolute paths, ``..``
+components or links pointing outside DEST is rejected as a whole. Tarballs
+are then extracted with the ``data`` filter where Python provides it, or one
+member at a time re-checked against the links already extracted, so chains
+of individually harmless links cannot escape DEST either. If extraction
+fails part-way, everything it wrote to DEST is removed again.
+"""
+
+import os
+import shutil
+import sys
+import tarfile
+import zipfile
+
+
+class UnsafeArchiveError(Exception):
+    """An archive entry would be written outside the destination directory."""
+
+
+def _ensure_within(dest: str, name: str, target: str) -> None:
+    """Reject ``name`` if ``target`` resolves outside ``dest``."""
+    root = os.path.realpath(dest)
+    resolved = os.path.realpath(target)
+    if os.path.commonpath([root, resolved]) != root:
+        raise UnsafeArchiveError(f"archive entry '{name}' escapes {dest}")
+
+
+def _check_tar_member(dest: str, member: tarfile.TarInfo) -> None:
+    """Reject ``member`` if it, or the target of a link, leaves ``dest``.
+
+    Paths are resolved against what is on disk, so links extracted by earlier
+    members are followed.
+    """
+    target = os.path.join(dest, member.name)
+    _ensure_within(dest, member.name, target)
+    if member.issym():
+        # Symlink targets are relative to the directory holding the link
+        _ensure_within(dest, member.name, os.path.join(os.path.dirname(target), member.linkname))
+    elif member.islnk():
+        _ensure_within(dest, member.name, os.path.join(dest, member.linkname))
+    elif member.isdev():
+        raise UnsafeArchiveError(f"archive entry '{member.name}' is a device file")
+
+
+def safe_extract(archive_path: str, dest: str) -> None:
+    """Extract ``archive_path`` into ``dest`` after validating every entry."""
+    if archive_path.endswith(".zip"):
+        with zipfile.ZipFile(archive_path, "r") as archive:
+            for name in archive.namelist():
+                _ensure_within(dest, name, os.path.join(dest, name))
+            archive.extractall(dest)
+    else:
+        with tarfile.open(archive_path, "r:gz") as archive:
+            members = archive.getmembers()
+            for member in members:
+                _check_tar_member(dest, member)
+            if hasattr(tarfile, "data_filter"):
+                try:
+                    archive.extractall(dest, members=members, filter="data")
+                except tarfile.FilterError as e:
+                    raise UnsafeArchiveError(str(e)) from e
+            else:
+                for member in members:
+                    _check_tar_member(dest, member)
+                    archive.extract(member, dest)
+
+
+def move_contents_up(dest: str) -> None:
+    """Move lib/ and include/ out of the versioned top-level directory."""
+    for item in os.listdir(dest):
+        if item.startswith("onnxruntime-"):
+            extracted = os.path.join(dest, item)
+            for subitem in ["lib", "include"]:
+                src = os.path.join(extracted, subitem)
+                dst = os.path.join(dest, subitem)
+                if os.path.exists(src):
+                    if os.path.exists(dst):
+                        shutil.rmtree(dst)
+                    shutil.move(src, dst)
+            # Remove the now-empty directory
+            if os.path.exists(extracted) and not os.listdir(extracted):
+                os.rmdir(extracted)
+            break
+
+
+def _remove_new_entries(dest: str, existing) -> None:
+    """Delete entries in ``dest`` that are not in ``existing``."""
+    for item in os.listdir(dest):
+        if item in existing:
+            continue
+        path = os.path.join(dest, item)
+        if os.path.isdir(path) and not os.path.islink(path):
+            shutil.rmtree(path, ignore_errors=True)
+        else:
+            os.remove(path)
+
+
+def extract(archive_path: str, dest: str) -> None:
+    """Extract and flatten ``archive_path``, undoing partial output on failure."""
+    existing = set(os.listdir(dest))
+    try:
+        safe_extract(archive_path, dest)
+        move_contents_up(dest)
+    except BaseException:
+        _remove_new_entries(dest, existing)
+        raise
+
+
+def main(argv) -> int:
+    if len(argv) != 3:
+        print(f"usage: {argv[0]} ARCHIVE DEST", file=sys.stderr)
+        return 2
+    archive_path, dest = argv[1], argv[2]
+    try:
+        extract(archive_path, dest)
+    except UnsafeArchiveError as e:
+        print(f"Refusing to extract {archive_path}: {e}", file=sys.stderr)
+        return 1
+    except (OSError, EOFError, tarfile.TarError, zipfile.BadZipFile) as e:
+        print(f"Failed to extract {archive_path}: {e}", file=sys.stderr)
+        return 1
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main(sys.argv))
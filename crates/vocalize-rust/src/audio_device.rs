@@ -142,14 +142,15 @@ impl PyAudioConfig {
 #[pymethods]
 impl PyAudioConfig {
     #[new]
-    #[pyo3(signature = (device_id=None, sample_rate=None, channels=None, buffer_size=None, latency_ms=None))]
+    #[pyo3(signature = (device_id=None, sample_rate=None, channels=None, buffer_size=None, latency_ms=None, completion_timeout_secs=None))]
     fn py_new(
         device_id: Option<String>,
         sample_rate: Option<u32>,
         channels: Option<u16>,
         buffer_size: Option<u32>,
         latency_ms: Option<u64>,
-    ) -> Self {
+        completion_timeout_secs: Option<f64>,
+    ) -> PyResult<Self> {
         let mut config = AudioConfig::default();
         
         if let Some(id) = device_id {
@@ -167,8 +168,15 @@ impl PyAudioConfig {
         if let Some(lat) = latency_ms {
             config.latency = Duration::from_millis(lat);
         }
+        if let Some(secs) = completion_timeout_secs {
+            config.completion_timeout = Duration::try_from_secs_f64(secs).map_err(|_| {
+                vocalize_error_to_pyerr(vocalize_core::VocalizeError::invalid_input(format!(
+                    "completion_timeout_secs must be a non-negative number, got {secs}"
+                )))
+            })?;
+        }
         
-        Self::new(config)
+        Ok(Self::new(config))
     }
 
     #[staticmethod]
@@ -201,6 +209,12 @@ impl PyAudioConfig {
         self.inner.latency.as_millis() as u64
     }
 
+    /// Seconds `wait_for_completion` waits without playback progress
+    #[getter]
+    fn completion_timeout_secs(&self) -> f64 {
+        self.inner.completion_timeout.as_secs_f64()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "AudioConfig(device_id={:?}, sample_rate={}, channels={}, buffer_size={}, latency={}ms)",
@@ -224,6 +238,10 @@ impl PyAudioDevice {
     pub fn new(device: AudioDevice) -> Self {
         Self { inner: device }
     }
+
+    pub fn inner(&self) -> &AudioDevice {
+        &self.inner
+    }
 }
 
 #[pymethods]
@@ -288,6 +306,11 @@ impl PyAudioDevice {
         self.inner.get_device_info()
     }
 
+    /// Total number of samples played since the device was created
+    fn samples_played(&self) -> usize {
+        self.inner.samples_played()
+    }
+
     fn __repr__(&self) -> String {
         "AudioDevice()".to_string()
     }
@@ -7,7 +7,7 @@ use std::collections::HashMap;
 use std::path::Path;
 use vocalize_core::{AudioFormat, AudioWriter, EncodingSettings};
 
-use crate::error::IntoPyResult;
+use crate::error::{IntoPyResult, PyVocalizeError};
 
 /// Python wrapper for AudioFormat
 #[pyclass(name = "AudioFormat")]
@@ -17,6 +17,7 @@ pub enum PyAudioFormat {
     Mp3,
     Flac,
     Ogg,
+    Opus,
 }
 
 impl From<AudioFormat> for PyAudioFormat {
@@ -26,6 +27,7 @@ impl From<AudioFormat> for PyAudioFormat {
             AudioFormat::Mp3 => PyAudioFormat::Mp3,
             AudioFormat::Flac => PyAudioFormat::Flac,
             AudioFormat::Ogg => PyAudioFormat::Ogg,
+            AudioFormat::Opus => PyAudioFormat::Opus,
         }
     }
 }
@@ -37,6 +39,7 @@ impl From<PyAudioFormat> for AudioFormat {
             PyAudioFormat::Mp3 => AudioFormat::Mp3,
             PyAudioFormat::Flac => AudioFormat::Flac,
             PyAudioFormat::Ogg => AudioFormat::Ogg,
+            PyAudioFormat::Opus => AudioFormat::Opus,
         }
     }
 }
@@ -59,6 +62,12 @@ impl PyAudioFormat {
         AudioFormat::from(*self).description().to_string()
     }
 
+    /// Encoding settings suited to this format (bit depth or bitrate)
+    #[pyo3(signature = (sample_rate=vocalize_core::DEFAULT_SAMPLE_RATE, channels=vocalize_core::DEFAULT_CHANNELS))]
+    fn default_settings(&self, sample_rate: u32, channels: u16) -> PyEncodingSettings {
+        PyEncodingSettings::new(AudioFormat::from(*self).default_settings(sample_rate, channels))
+    }
+
     #[staticmethod]
     fn from_extension(extension: &str) -> PyResult<PyAudioFormat> {
         let format = AudioFormat::from_extension(extension).into_py_result()?;
@@ -71,6 +80,11 @@ impl PyAudioFormat {
         Ok(PyAudioFormat::from(format))
     }
 
+    #[staticmethod]
+    fn from_magic(data: &[u8]) -> Option<PyAudioFormat> {
+        AudioFormat::from_magic(data).map(PyAudioFormat::from)
+    }
+
     #[staticmethod]
     fn all() -> Vec<PyAudioFormat> {
         AudioFormat::all()
@@ -85,6 +99,7 @@ impl PyAudioFormat {
             PyAudioFormat::Mp3 => "MP3".to_string(),
             PyAudioFormat::Flac => "FLAC".to_string(),
             PyAudioFormat::Ogg => "OGG".to_string(),
+            PyAudioFormat::Opus => "OPUS".to_string(),
         }
     }
 
@@ -103,6 +118,9 @@ impl PyAudioFormat {
 
     #[classattr]
     const OGG: PyAudioFormat = PyAudioFormat::Ogg;
+
+    #[classattr]
+    const OPUS: PyAudioFormat = PyAudioFormat::Opus;
 }
 
 /// Python wrapper for EncodingSettings
@@ -175,6 +193,16 @@ impl PyEncodingSettings {
         Self::new(self.inner.clone().with_variable_bitrate())
     }
 
+    #[getter]
+    fn strict_range(&self) -> bool {
+        self.inner.strict_range
+    }
+
+    #[pyo3(signature = (strict_range=true))]
+    fn with_strict_range(&self, strict_range: bool) -> PyEncodingSettings {
+        Self::new(self.inner.clone().with_strict_range(strict_range))
+    }
+
     fn with_constant_bitrate(&self) -> PyEncodingSettings {
         let mut settings = self.inner.clone();
         settings.variable_bitrate = false;
@@ -205,8 +233,31 @@ impl PyEncodingSettings {
             dict.insert("quality".to_string(), quality.to_string());
         }
         dict.insert("variable_bitrate".to_string(), self.inner.variable_bitrate.to_string());
+        dict.insert("strict_range".to_string(), self.inner.strict_range.to_string());
         dict
     }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+
+    /// Constructor arguments used when unpickling, before `__setstate__`
+    fn __getnewargs__(&self) -> (u32, u16) {
+        (self.inner.sample_rate, self.inner.channels)
+    }
+
+    /// Serialize every field as JSON for pickling
+    fn __getstate__(&self) -> PyResult<String> {
+        serde_json::to_string(&self.inner).map_err(|e| {
+            PyVocalizeError::new_err(format!("Failed to serialize encoding settings: {e}"))
+        })
+    }
+
+    fn __setstate__(&mut self, state: &str) -> PyResult<()> {
+        self.inner = serde_json::from_str(state)
+            .map_err(|e| PyVocalizeError::new_err(format!("Invalid encoding settings state: {e}")))?;
+        Ok(())
+    }
 }
 
 /// Python wrapper for AudioWriter
@@ -235,6 +286,10 @@ impl PyAudioWriter {
     }
 
     /// Write audio data to file
+    ///
+    /// Resolves to a list of warnings, such as clipped samples, that did
+    /// not prevent writing; settings with `strict_range` turn those into
+    /// errors instead.
     fn write_file<'py>(
         &self,
         py: Python<'py>,
@@ -248,11 +303,11 @@ impl PyAudioWriter {
         let rust_settings = settings.map(|s| s.inner().clone());
         
         future_into_py(py, async move {
-            writer
-                .write_file(&audio_data, Path::new(&path), rust_format, rust_settings)
+            let warnings = writer
+                .write_file_with_warnings(&audio_data, Path::new(&path), rust_format, rust_settings)
                 .await
                 .into_py_result()?;
-            Ok(())
+            Ok(warnings.iter().map(ToString::to_string).collect::<Vec<_>>())
         })
     }
 
@@ -371,14 +426,21 @@ mod tests {
         assert!(PyAudioFormat::from_path("no_extension").is_err());
     }
 
+    #[test]
+    fn test_py_audio_format_from_magic() {
+        assert_eq!(PyAudioFormat::from_magic(b"fLaC\x00"), Some(PyAudioFormat::Flac));
+        assert_eq!(PyAudioFormat::from_magic(b"not audio"), None);
+    }
+
     #[test]
     fn test_py_audio_format_all() {
         let formats = PyAudioFormat::all();
-        assert_eq!(formats.len(), 4);
+        assert_eq!(formats.len(), 5);
         assert!(formats.contains(&PyAudioFormat::Wav));
         assert!(formats.contains(&PyAudioFormat::Mp3));
         assert!(formats.contains(&PyAudioFormat::Flac));
         assert!(formats.contains(&PyAudioFormat::Ogg));
+        assert!(formats.contains(&PyAudioFormat::Opus));
     }
 
     #[test]
@@ -466,18 +528,19 @@ mod tests {
         let writer = PyAudioWriter::py_new();
         
         assert!(writer.is_format_supported(PyAudioFormat::Wav));
-        // MP3, FLAC, OGG are not implemented yet
-        assert!(!writer.is_format_supported(PyAudioFormat::Mp3));
-        assert!(!writer.is_format_supported(PyAudioFormat::Flac));
-        assert!(!writer.is_format_supported(PyAudioFormat::Ogg));
+        // Compressed encoders are only there when their feature is enabled
+        assert_eq!(writer.is_format_supported(PyAudioFormat::Mp3), cfg!(feature = "mp3"));
+        assert_eq!(writer.is_format_supported(PyAudioFormat::Flac), cfg!(feature = "flac"));
+        assert_eq!(writer.is_format_supported(PyAudioFormat::Ogg), cfg!(feature = "ogg"));
+        assert_eq!(writer.is_format_supported(PyAudioFormat::Opus), cfg!(feature = "opus"));
     }
 
     #[test]
     fn test_py_audio_writer_supported_formats() {
         let formats = PyAudioWriter::get_supported_formats();
         assert!(formats.contains(&PyAudioFormat::Wav));
-        // Only WAV is currently supported
-        assert_eq!(formats.len(), 1);
+        let enabled = [cfg!(feature = "mp3"), cfg!(feature = "flac"), cfg!(feature = "ogg"), cfg!(feature = "opus")];
+        assert_eq!(formats.len(), 1 + enabled.into_iter().filter(|&on| on).count());
     }
 
     #[test]
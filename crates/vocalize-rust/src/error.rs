@@ -41,6 +41,7 @@ impl PyVocalizeError {
             VocalizeError::MemoryError { .. } => "MemoryError".to_string(),
             VocalizeError::TimeoutError { .. } => "TimeoutError".to_string(),
             VocalizeError::ConcurrencyError { .. } => "ConcurrencyError".to_string(),
+            VocalizeError::ShutdownError { .. } => "ShutdownError".to_string(),
         }
     }
 
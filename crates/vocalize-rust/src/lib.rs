@@ -3,7 +3,11 @@
 //! This crate provides comprehensive Python bindings for the Vocalize text-to-speech engine
 //! using PyO3. It exposes the full TTS functionality with proper async support.
 
+use numpy::PyArray1;
 use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
 
 // Re-export submodules
 mod error;
@@ -12,9 +16,13 @@ mod tts_engine;
 mod voice_manager;
 mod audio_writer;
 mod audio_device;
+mod onnx_runtime;
+mod session;
 
-use error::{PyVocalizeError, VocalizeException};
+use error::{IntoPyResult, PyVocalizeError, VocalizeException};
+use runtime_manager::{LazyTtsEngine, RuntimeManager};
 use tts_engine::{PyTtsEngine, PySynthesisParams};
+use session::PyTtsSession;
 use voice_manager::{PyVoiceManager, PyVoice, PyGender, PyVoiceStyle};
 use audio_writer::{PyAudioWriter, PyAudioFormat, PyEncodingSettings};
 use audio_device::{PyAudioDevice, PyAudioConfig, PyAudioDeviceInfo, PyPlaybackState};
@@ -54,6 +62,8 @@ fn synthesize_neural(text: String, voice_id: Option<String>, speed: Option<f32>,
         return Err(PyVocalizeError::new_err("Text cannot be empty".to_string()));
     }
     
+    onnx_runtime::ensure_onnx_runtime()?;
+    
     println!("🔊 2025 TTS: Using Rust TTS engine for: '{}'", text);
     
     // Use Rust TTS engine instead of reimplementing everything in Python
@@ -94,36 +104,198 @@ fn synthesize_neural(text: String, voice_id: Option<String>, speed: Option<f32>,
     })
 }
 
+/// Engines used by `synthesize_batch_neural` and
+/// `synthesize_document_neural`, one per requested model
+///
+/// Kept for the life of the process so repeated calls reuse the loaded
+/// model and its session pool instead of paying start-up cost per call.
+static SHARED_ENGINES: OnceLock<Mutex<HashMap<Option<String>, LazyTtsEngine>>> = OnceLock::new();
+
+/// The cached engine for `model_id` (the default model if `None`),
+/// created on first use
+fn shared_engine(model_id: Option<String>) -> PyResult<LazyTtsEngine> {
+    #[cfg(feature = "test-model")]
+    let needs_onnx_runtime = model_id.as_deref() != Some(vocalize_core::models::mock_model::MOCK_MODEL_ID);
+    #[cfg(not(feature = "test-model"))]
+    let needs_onnx_runtime = true;
+    if needs_onnx_runtime {
+        onnx_runtime::ensure_onnx_runtime()?;
+    }
+    RuntimeManager::initialize()?;
+
+    let mut engines = SHARED_ENGINES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .map_err(|e| PyVocalizeError::new_err(format!("Failed to acquire engine cache lock: {e}")))?;
+    Ok(engines
+        .entry(model_id.clone())
+        .or_insert_with(|| {
+            let config = model_id
+                .as_deref()
+                .map_or_else(vocalize_core::TtsConfig::default, tts_engine::model_config);
+            LazyTtsEngine::with_config(config)
+        })
+        .clone())
+}
+
+/// Synthesize many texts with one shared engine
+///
+/// Items are synthesized concurrently on the global runtime with the GIL
+/// released, and returned in input order as float32 NumPy arrays. The
+/// engine for `model_id` (the default model if `None`) is created on first
+/// use and cached, so later calls skip model loading entirely. Fails on the
+/// first item that cannot be synthesized.
+#[pyfunction]
+#[pyo3(signature = (texts, voice_id=None, speed=None, model_id=None))]
+fn synthesize_batch_neural<'py>(
+    py: Python<'py>,
+    texts: Vec<String>,
+    voice_id: Option<String>,
+    speed: Option<f32>,
+    model_id: Option<String>,
+) -> PyResult<Vec<&'py PyArray1<f32>>> {
+    let mut params = tts_engine::neural_voice_params(voice_id)?;
+    if let Some(speed) = speed {
+        params = params.with_speed(speed).into_py_result()?;
+    }
+    if texts.is_empty() {
+        return Ok(Vec::new());
+    }
+    let lazy_engine = shared_engine(model_id)?;
+
+    let audio = py.allow_threads(|| synthesize_batch(&lazy_engine, texts, &params))?;
+    Ok(audio.into_iter().map(|samples| PyArray1::from_vec(py, samples)).collect())
+}
+
+/// Synthesize `texts` concurrently with `lazy_engine`, in input order
+fn synthesize_batch(
+    lazy_engine: &LazyTtsEngine,
+    texts: Vec<String>,
+    params: &vocalize_core::SynthesisParams,
+) -> PyResult<Vec<Vec<f32>>> {
+    let engine = lazy_engine.get_or_init()?;
+    RuntimeManager::block_on(async move {
+        let tasks: Vec<_> = texts
+            .into_iter()
+            .map(|text| {
+                let engine = engine.clone();
+                let params = params.clone();
+                tokio::spawn(async move { engine.synthesize(&text, &params).await })
+            })
+            .collect();
+
+        let mut audio = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let result = task
+                .await
+                .map_err(|e| PyVocalizeError::new_err(format!("Batch synthesis task failed: {e}")))?;
+            audio.push(result.into_py_result()?);
+        }
+        Ok(audio)
+    })?
+}
+
+/// Synthesize a long document sentence by sentence, reporting progress
+///
+/// Each sentence gets a voice matching its detected language, starting
+/// from `voice_id`. `on_progress`, if given, is called with
+/// `(sentences_done, total)`: once with zero done before the first
+/// sentence, then after each one. An exception raised by `on_progress`
+/// skips the remaining sentences and is re-raised. The GIL is released
+/// while synthesizing. Uses the same cached engine as
+/// `synthesize_batch_neural`, and returns the whole document as a float32
+/// NumPy array.
+#[pyfunction]
+#[pyo3(signature = (text, voice_id=None, on_progress=None, model_id=None))]
+fn synthesize_document_neural<'py>(
+    py: Python<'py>,
+    text: String,
+    voice_id: Option<String>,
+    on_progress: Option<PyObject>,
+    model_id: Option<String>,
+) -> PyResult<&'py PyArray1<f32>> {
+    let params = tts_engine::neural_voice_params(voice_id)?;
+    let lazy_engine = shared_engine(model_id)?;
+
+    // The GIL is released for synthesis and only taken back to call
+    // `on_progress`; an exception it raises stops the remaining sentences
+    let mut callback_error = None;
+    let audio = py.allow_threads(|| -> PyResult<Vec<f32>> {
+        let engine = lazy_engine.get_or_init()?;
+        let report_progress = |done: usize, total: usize| {
+            let Some(callback) = &on_progress else {
+                return Ok(());
+            };
+            Python::with_gil(|py| callback.call1(py, (done, total))).map(drop).map_err(|e| {
+                callback_error = Some(e);
+                vocalize_core::VocalizeError::synthesis("Progress callback raised an exception".to_string())
+            })
+        };
+        RuntimeManager::block_on(engine.synthesize_document(&text, &params, report_progress))?
+            .into_py_result()
+    });
+
+    match callback_error {
+        Some(e) => Err(e),
+        None => Ok(PyArray1::from_vec(py, audio?)),
+    }
+}
+
 /// 2025 Neural TTS synthesis using pre-processed tokens (new phoneme pipeline)
+///
+/// With `return_timings=True` the result is an `(audio, timings)` tuple where
+/// `timings` maps each stage (`validation`, `engine_init`, `inference`,
+/// `total`) to its duration in seconds.
+///
+/// `model_path` loads the model from an explicit `.onnx` file instead of the
+/// model cache; the file is validated before the engine is created.
 #[pyfunction]
+#[pyo3(signature = (input_ids, style_vector, speed, model_id=None, return_timings=false, model_path=None))]
 fn synthesize_from_tokens_neural(
+    py: Python<'_>,
     input_ids: Vec<i64>,
     style_vector: Vec<f32>,
     speed: f32,
-    model_id: Option<String>
-) -> PyResult<Vec<f32>> {
-    // Validate inputs
-    if input_ids.is_empty() {
-        return Err(PyVocalizeError::new_err("Input IDs cannot be empty".to_string()));
-    }
-    
-    if style_vector.len() != 256 {
-        return Err(PyVocalizeError::new_err(format!("Style vector must be 256 dimensions, got {}", style_vector.len())));
-    }
-    
-    if !(0.1..=3.0).contains(&speed) {
-        return Err(PyVocalizeError::new_err(format!("Speed must be between 0.1 and 3.0, got {}", speed)));
+    model_id: Option<String>,
+    return_timings: bool,
+    model_path: Option<PathBuf>,
+) -> PyResult<PyObject> {
+    let (audio_data, timings) =
+        synthesize_from_tokens_timed(input_ids, style_vector, speed, model_id, model_path)?;
+
+    if return_timings {
+        Ok((audio_data, timings_to_dict(&timings)).into_py(py))
+    } else {
+        Ok(audio_data.into_py(py))
     }
-    
-    if input_ids.len() > 512 {
-        return Err(PyVocalizeError::new_err(format!("Token sequence too long: {} tokens (max 512)", input_ids.len())));
+}
+
+/// Token synthesis behind `synthesize_from_tokens_neural`, timing each stage
+fn synthesize_from_tokens_timed(
+    input_ids: Vec<i64>,
+    style_vector: Vec<f32>,
+    speed: f32,
+    model_id: Option<String>,
+    model_path: Option<PathBuf>,
+) -> PyResult<(Vec<f32>, vocalize_core::TimingBreakdown)> {
+    use vocalize_core::{StageTimer, TimingBreakdown};
+
+    let mut timer = StageTimer::start();
+    let mut timings = TimingBreakdown::default();
+
+    validate_token_inputs(&input_ids, &style_vector, speed)?;
+    onnx_runtime::ensure_onnx_runtime()?;
+    if let Some(path) = &model_path {
+        vocalize_core::model::validate_onnx_file(path)
+            .map_err(|e| PyVocalizeError::new_err(format!("Invalid model_path: {e}")))?;
     }
+    timings.validation = timer.lap();
     
     println!("🔊 2025 TTS: Using pre-processed tokens ({} tokens, {} style dims, speed: {})", 
              input_ids.len(), style_vector.len(), speed);
     
     // Use ONNX engine directly for token-based synthesis
-    use vocalize_core::{onnx_engine::OnnxTtsEngine, model::ModelId};
+    use vocalize_core::onnx_engine::OnnxTtsEngine;
     
     // Create runtime for async operations
     let rt = tokio::runtime::Runtime::new()
@@ -133,14 +305,12 @@ fn synthesize_from_tokens_neural(
         // Create ONNX engine with cross-platform cache directory
         let mut engine = OnnxTtsEngine::new_with_default_cache().await
             .map_err(|e| PyVocalizeError::new_err(format!("Failed to create ONNX engine: {}", e)))?;
-        
-        // Determine model ID
-        let model = match model_id.as_deref().unwrap_or("kokoro") {
-            "kokoro" => ModelId::Kokoro,
-            "chatterbox" => ModelId::Chatterbox,
-            "dia" => ModelId::Dia,
-            _ => ModelId::Kokoro, // Default fallback
-        };
+        let model = token_model_id(model_id.as_deref());
+        if let Some(path) = &model_path {
+            engine.load_model_from_path(model, path).await
+                .map_err(|e| PyVocalizeError::new_err(format!("Failed to load model from {}: {}", path.display(), e)))?;
+        }
+        timings.engine_init = timer.lap();
         
         // Synthesize using the new token-based method
         let audio_data = engine.synthesize_from_tokens(
@@ -150,12 +320,54 @@ fn synthesize_from_tokens_neural(
             model
         ).await
         .map_err(|e| PyVocalizeError::new_err(format!("Token synthesis failed: {}", e)))?;
+        timings.inference = timer.lap();
+        timings.total = timer.total();
         
         println!("✅ 2025 token synthesis completed: {} samples generated", audio_data.len());
-        Ok(audio_data)
+        Ok((audio_data, timings))
     })
 }
 
+/// Validate the inputs of a token synthesis request
+pub(crate) fn validate_token_inputs(input_ids: &[i64], style_vector: &[f32], speed: f32) -> PyResult<()> {
+    if input_ids.is_empty() {
+        return Err(PyVocalizeError::new_err("Input IDs cannot be empty".to_string()));
+    }
+    
+    vocalize_core::onnx_engine::check_style_vector_len(style_vector).into_py_result()?;
+    
+    if !(0.1..=3.0).contains(&speed) {
+        return Err(PyVocalizeError::new_err(format!("Speed must be between 0.1 and 3.0, got {}", speed)));
+    }
+    
+    if input_ids.len() > 512 {
+        return Err(PyVocalizeError::new_err(format!("Token sequence too long: {} tokens (max 512)", input_ids.len())));
+    }
+
+    Ok(())
+}
+
+/// Map a model name to the ONNX model used for token synthesis
+pub(crate) fn token_model_id(model_id: Option<&str>) -> vocalize_core::ModelId {
+    use vocalize_core::ModelId;
+
+    match model_id.unwrap_or("kokoro") {
+        "kokoro" => ModelId::Kokoro,
+        "chatterbox" => ModelId::Chatterbox,
+        "dia" => ModelId::Dia,
+        _ => ModelId::Kokoro, // Default fallback
+    }
+}
+
+/// Convert a timing breakdown into a dict of stage name to seconds
+fn timings_to_dict(timings: &vocalize_core::TimingBreakdown) -> HashMap<String, f64> {
+    HashMap::from([
+        ("validation".to_string(), timings.validation.as_secs_f64()),
+        ("engine_init".to_string(), timings.engine_init.as_secs_f64()),
+        ("inference".to_string(), timings.inference.as_secs_f64()),
+        ("total".to_string(), timings.total.as_secs_f64()),
+    ])
+}
 
 /// Get list of available neural voices
 #[pyfunction]
@@ -172,15 +384,123 @@ fn list_neural_voices() -> PyResult<Vec<(String, String, String, String)>> {
 }
 
 
+/// Report every directory model discovery examines and whether it qualified
+///
+/// Each dict has `directory`, `files` (the required model files present),
+/// `qualified`, `selected` (the model that would be loaded) and `reason`
+/// (why the directory was rejected, or `None`).
+#[pyfunction]
+fn discover_models(py: Python<'_>) -> PyResult<Vec<PyObject>> {
+    vocalize_core::model::ModelDiscovery::new()
+        .report()
+        .into_iter()
+        .map(|entry| {
+            let dict = pyo3::types::PyDict::new(py);
+            dict.set_item("directory", entry.directory.to_string_lossy().into_owned())?;
+            dict.set_item(
+                "files",
+                entry.files.iter().map(|f| f.to_string_lossy().into_owned()).collect::<Vec<_>>(),
+            )?;
+            dict.set_item("qualified", entry.qualified)?;
+            dict.set_item("selected", entry.selected)?;
+            dict.set_item("reason", entry.reason)?;
+            Ok(dict.into())
+        })
+        .collect()
+}
+
+/// Estimate the peak RAM in bytes needed to load an installed model
+///
+/// Reads the model registry without loading anything, so it is safe to call
+/// before deciding whether a machine can load the model at all. `pool_size`
+/// defaults to the number of ONNX sessions the engine would create.
+#[pyfunction]
+#[pyo3(signature = (model_id="kokoro", pool_size=None))]
+fn estimate_model_memory(model_id: &str, pool_size: Option<usize>) -> PyResult<usize> {
+    let cache_dir = tts_engine::model_config(model_id).model_cache_dir;
+    let registry = vocalize_core::models::ModelRegistry::new(&cache_dir).into_py_result()?;
+    match pool_size {
+        Some(pool_size) => registry.estimate_load_memory_with_pool(model_id, pool_size),
+        None => registry.estimate_load_memory(model_id),
+    }
+    .into_py_result()
+}
+
+/// Model registry for `cache_dir`, or for the cache `model_id` is kept in
+fn model_registry(model_id: &str, cache_dir: Option<PathBuf>) -> PyResult<vocalize_core::models::ModelRegistry> {
+    let cache_dir = cache_dir.unwrap_or_else(|| tts_engine::model_config(model_id).model_cache_dir);
+    vocalize_core::models::ModelRegistry::new(&cache_dir).into_py_result()
+}
+
+/// Check whether a model is installed, without loading or downloading it
+///
+/// `cache_dir` defaults to the directory the engine would use for
+/// `model_id`.
+#[pyfunction]
+#[pyo3(signature = (model_id, cache_dir=None))]
+fn is_model_installed(model_id: &str, cache_dir: Option<PathBuf>) -> PyResult<bool> {
+    Ok(model_registry(model_id, cache_dir)?.is_model_installed(model_id))
+}
+
+/// List the models installed in `cache_dir` (the default model cache if
+/// `None`)
+///
+/// Each dict has `id`, `name`, `version`, `size` (declared, in bytes),
+/// `disk_size` (measured, in bytes), `install_path` and `voices`.
+#[pyfunction]
+#[pyo3(signature = (cache_dir=None))]
+fn list_installed_models(py: Python<'_>, cache_dir: Option<PathBuf>) -> PyResult<Vec<PyObject>> {
+    let registry = model_registry(&vocalize_core::TtsConfig::default().default_model_id, cache_dir)?;
+    let mut models = registry.get_installed_models_with_disk_size();
+    models.sort_by(|a, b| a.id.cmp(&b.id));
+    models
+        .into_iter()
+        .map(|model| {
+            let dict = pyo3::types::PyDict::new(py);
+            dict.set_item("id", model.id)?;
+            dict.set_item("name", model.name)?;
+            dict.set_item("version", model.version)?;
+            dict.set_item("size", model.size)?;
+            dict.set_item("disk_size", model.disk_size)?;
+            dict.set_item("install_path", model.install_path.to_string_lossy().into_owned())?;
+            dict.set_item("voices", model.supported_voices)?;
+            Ok(dict.into())
+        })
+        .collect()
+}
+
+/// Largest buffer `save_audio_neural` writes by default: two hours at 24 kHz
+const DEFAULT_MAX_SAVE_SAMPLES: usize = 2 * 60 * 60 * 24_000;
+
+/// How long `save_audio_neural` waits for a write by default, in seconds
+const DEFAULT_SAVE_TIMEOUT_SECS: f64 = 300.0;
+
 /// Save neural TTS audio data to a file
-#[pyfunction] 
-fn save_audio_neural(audio_data: Vec<f32>, output_path: String, format: Option<String>) -> PyResult<()> {
+///
+/// `audio_data` is taken to be at `sample_rate` (24 kHz by default) and is
+/// resampled when `target_sample_rate` differs, so the file is written at
+/// `target_sample_rate`. Buffers longer than `max_samples` are rejected
+/// before anything is written, and a write that takes longer than
+/// `timeout_secs` is abandoned: the call returns an error and no file is
+/// left at `output_path`.
+#[pyfunction]
+#[pyo3(signature = (audio_data, output_path, format=None, max_samples=DEFAULT_MAX_SAVE_SAMPLES, timeout_secs=DEFAULT_SAVE_TIMEOUT_SECS, sample_rate=None, target_sample_rate=None))]
+fn save_audio_neural(
+    audio_data: Vec<f32>,
+    output_path: String,
+    format: Option<String>,
+    max_samples: usize,
+    timeout_secs: f64,
+    sample_rate: Option<u32>,
+    target_sample_rate: Option<u32>,
+) -> PyResult<()> {
     let format_str = format.unwrap_or_else(|| "wav".to_string());
     let audio_format = match format_str.as_str() {
         "wav" => PyAudioFormat::Wav,
         "mp3" => PyAudioFormat::Mp3,
         "flac" => PyAudioFormat::Flac,
         "ogg" => PyAudioFormat::Ogg,
+        "opus" => PyAudioFormat::Opus,
         _ => return Err(PyVocalizeError::new_err(format!("Unsupported format: {format_str}"))),
     };
     
@@ -188,10 +508,33 @@ fn save_audio_neural(audio_data: Vec<f32>, output_path: String, format: Option<S
     if audio_data.is_empty() {
         return Err(PyVocalizeError::new_err("Neural audio data cannot be empty".to_string()));
     }
+    if audio_data.len() > max_samples {
+        return Err(PyVocalizeError::new_err(format!(
+            "Audio data has {} samples, which exceeds the limit of {max_samples}", audio_data.len()
+        )));
+    }
+    let timeout = std::time::Duration::try_from_secs_f64(timeout_secs)
+        .ok()
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| PyVocalizeError::new_err(format!("timeout_secs must be positive, got {timeout_secs}")))?;
+    let sample_rate = sample_rate.unwrap_or(vocalize_core::DEFAULT_SAMPLE_RATE);
+    let target_sample_rate = target_sample_rate.unwrap_or(sample_rate);
+    if sample_rate == 0 || target_sample_rate == 0 {
+        return Err(PyVocalizeError::new_err(format!(
+            "Sample rates must be positive, got {sample_rate} Hz -> {target_sample_rate} Hz"
+        )));
+    }
     
     // Use the actual audio writer from vocalize-core
     use vocalize_core::{AudioWriter, AudioFormat, AudioData};
-    use std::path::Path;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    
+    let audio_data = if target_sample_rate == sample_rate {
+        audio_data
+    } else {
+        vocalize_core::audio_effects::resample(&audio_data, sample_rate, target_sample_rate)
+    };
     
     // Convert PyAudioFormat to AudioFormat
     let core_format = match audio_format {
@@ -199,191 +542,161 @@ fn save_audio_neural(audio_data: Vec<f32>, output_path: String, format: Option<S
         PyAudioFormat::Mp3 => AudioFormat::Mp3,
         PyAudioFormat::Flac => AudioFormat::Flac,
         PyAudioFormat::Ogg => AudioFormat::Ogg,
+        PyAudioFormat::Opus => AudioFormat::Opus,
     };
     
-    // Create output path
-    let path = Path::new(&output_path);
+    let settings = core_format.default_settings(target_sample_rate, vocalize_core::DEFAULT_CHANNELS);
+    
+    // Create output path; the file is written beside it and only moved into
+    // place once complete, so an abandoned write never leaves a partial file
+    let path = PathBuf::from(&output_path);
+    let partial_path = PathBuf::from(format!("{output_path}.partial"));
     
     // Create audio writer
     let writer = AudioWriter::new();
-    
-    // AudioData is just Vec<f32>, so use audio_data directly
-    let audio_data_ref: &AudioData = &audio_data;
+    let sample_count = audio_data.len();
     
     // Create runtime for async operations
     let rt = tokio::runtime::Runtime::new()
         .map_err(|e| PyVocalizeError::new_err(format!("Failed to create async runtime: {}", e)))?;
     
+    // Whichever of the write and the timeout claims this first decides the
+    // outcome: the write moves its file into place, the timeout abandons it
+    let claimed = Arc::new(AtomicBool::new(false));
+    let write_claim = Arc::clone(&claimed);
+    let handle = rt.handle().clone();
+    let mut write = rt.spawn_blocking(move || -> PyResult<()> {
+        let audio_data_ref: &AudioData = &audio_data;
+        let result = handle
+            .block_on(writer.write_file(audio_data_ref, &partial_path, core_format, Some(settings)))
+            .map_err(|e| PyVocalizeError::new_err(format!("Failed to write audio file: {}", e)));
+        if result.is_err() || write_claim.swap(true, Ordering::SeqCst) {
+            let _ = std::fs::remove_file(&partial_path);
+            return result;
+        }
+        std::fs::rename(&partial_path, &path).map_err(|e| {
+            let _ = std::fs::remove_file(&partial_path);
+            PyVocalizeError::new_err(format!("Failed to write audio file: {}", e))
+        })
+    });
+    
     // Write audio data
-    rt.block_on(async {
-        writer.write_file(audio_data_ref, path, core_format, None).await
-            .map_err(|e| PyVocalizeError::new_err(format!("Failed to write audio file: {}", e)))
-    })?;
+    let result = rt.block_on(async {
+        let joined = match tokio::time::timeout(timeout, &mut write).await {
+            Ok(joined) => joined,
+            // The write finished just as the timeout fired; keep its result
+            Err(_) if claimed.swap(true, Ordering::SeqCst) => write.await,
+            Err(_) => {
+                return Err(PyVocalizeError::new_err(format!(
+                    "Timed out after {timeout_secs}s writing {sample_count} samples to {output_path}"
+                )))
+            }
+        };
+        joined.map_err(|e| PyVocalizeError::new_err(format!("Audio write task failed: {}", e)))?
+    });
+    // Don't wait for an abandoned write; it cleans up after itself
+    rt.shutdown_background();
+    result?;
     
     Ok(())
 }
 
+/// Convert float samples to little-endian signed 16-bit PCM bytes
+#[pyfunction]
+fn to_pcm16(py: Python<'_>, audio_data: Vec<f32>) -> &pyo3::types::PyBytes {
+    let bytes: Vec<u8> = vocalize_core::audio_effects::to_i16_pcm(&audio_data)
+        .iter()
+        .flat_map(|sample| sample.to_le_bytes())
+        .collect();
+    pyo3::types::PyBytes::new(py, &bytes)
+}
+
+/// Convert little-endian signed 16-bit PCM bytes to float samples
+#[pyfunction]
+fn from_pcm16(data: &[u8]) -> PyResult<Vec<f32>> {
+    if data.len() % 2 != 0 {
+        return Err(PyVocalizeError::new_err(format!(
+            "PCM16 data must have an even number of bytes, got {}", data.len()
+        )));
+    }
+
+    let pcm: Vec<i16> = data
+        .chunks_exact(2)
+        .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+        .collect();
+    Ok(vocalize_core::audio_effects::from_i16_pcm(&pcm))
+}
+
+/// Level statistics for a block of audio
+#[pyclass(name = "AudioStats")]
+#[derive(Debug, Clone, Copy)]
+pub struct PyAudioStats {
+    inner: vocalize_core::audio_effects::AudioStats,
+}
+
+#[pymethods]
+impl PyAudioStats {
+    #[getter]
+    fn rms(&self) -> f32 {
+        self.inner.rms
+    }
+
+    #[getter]
+    fn peak(&self) -> f32 {
+        self.inner.peak
+    }
+
+    #[getter]
+    fn dbfs(&self) -> f32 {
+        self.inner.dbfs
+    }
+
+    #[getter]
+    fn clip_count(&self) -> usize {
+        self.inner.clip_count
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "AudioStats(rms={:.4}, peak={:.4}, dbfs={:.1}, clip_count={})",
+            self.inner.rms, self.inner.peak, self.inner.dbfs, self.inner.clip_count
+        )
+    }
+}
+
+/// Root-mean-square level of float samples
+#[pyfunction]
+fn rms(audio_data: Vec<f32>) -> f32 {
+    vocalize_core::audio_effects::rms(&audio_data)
+}
+
+/// Largest absolute value among float samples
+#[pyfunction]
+fn peak(audio_data: Vec<f32>) -> f32 {
+    vocalize_core::audio_effects::peak(&audio_data)
+}
+
+/// RMS, peak, RMS level in dBFS and clipped sample count for float samples
+#[pyfunction]
+fn analyze(audio_data: Vec<f32>) -> PyAudioStats {
+    PyAudioStats {
+        inner: vocalize_core::audio_effects::analyze(&audio_data),
+    }
+}
+
 /// Python module for Vocalize TTS functionality
 #[pymodule]
 fn vocalize_rust(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     // Set up ONNX Runtime DLL path IMMEDIATELY on module load
     // This must happen before ANY ort code is touched
-    #[cfg(target_os = "windows")]
-    {
-        use winapi::um::libloaderapi::{LoadLibraryExW, LOAD_WITH_ALTERED_SEARCH_PATH};
-        use winapi::um::errhandlingapi::GetLastError;
-        use std::ffi::OsStr;
-        use std::os::windows::ffi::OsStrExt;
-        use std::ptr::null_mut;
-        
-        if std::env::var("ORT_DYLIB_PATH").is_err() {
-            // Get the site-packages path
-            let sys = _py.import("sys")?;
-            let prefix: String = sys.getattr("prefix")?.extract()?;
-            let dll_dir = format!("{}\\Lib\\site-packages\\vocalize_rust", prefix);
-            
-            // First, check if System32 has a conflicting version
-            let system32_dll = "C:\\Windows\\System32\\onnxruntime.dll";
-            if std::path::Path::new(system32_dll).exists() {
-                eprintln!("⚠️  WARNING: Found ONNX Runtime in System32 at: {}", system32_dll);
-                eprintln!("   This may conflict with the bundled version.");
-            }
-            
-            // Add directory to Python's DLL search path (for Python 3.8+)
-            let os = _py.import("os")?;
-            if let Ok(add_dll_dir) = os.getattr("add_dll_directory") {
-                add_dll_dir.call1((dll_dir.clone(),))?;
-                eprintln!("✅ Added DLL directory to Python search path: {}", dll_dir);
-            }
-            
-            // Pre-emptively load our DLLs using Windows API
-            let providers_path = format!("{}\\onnxruntime_providers_shared.dll", dll_dir);
-            let onnx_path = format!("{}\\onnxruntime.dll", dll_dir);
-            
-            // Convert paths to wide strings for Windows API
-            let providers_wide: Vec<u16> = OsStr::new(&providers_path)
-                .encode_wide()
-                .chain(Some(0))
-                .collect();
-            let onnx_wide: Vec<u16> = OsStr::new(&onnx_path)
-                .encode_wide()
-                .chain(Some(0))
-                .collect();
-            
-            unsafe {
-                // Load providers DLL first (dependency)
-                let providers_handle = LoadLibraryExW(
-                    providers_wide.as_ptr(),
-                    null_mut(),
-                    LOAD_WITH_ALTERED_SEARCH_PATH
-                );
-                
-                if providers_handle.is_null() {
-                    let error = GetLastError();
-                    eprintln!("❌ Failed to pre-load onnxruntime_providers_shared.dll");
-                    eprintln!("   Path: {}", providers_path);
-                    eprintln!("   Error code: {}", error);
-                } else {
-                    eprintln!("✅ Pre-loaded onnxruntime_providers_shared.dll");
-                }
-                
-                // Load main ONNX Runtime DLL
-                let onnx_handle = LoadLibraryExW(
-                    onnx_wide.as_ptr(),
-                    null_mut(),
-                    LOAD_WITH_ALTERED_SEARCH_PATH
-                );
-                
-                if onnx_handle.is_null() {
-                    let error = GetLastError();
-                    eprintln!("❌ Failed to pre-load onnxruntime.dll");
-                    eprintln!("   Path: {}", onnx_path);
-                    eprintln!("   Error code: {}", error);
-                    
-                    // If pre-loading failed, show detailed error message
-                    if std::path::Path::new(system32_dll).exists() {
-                        eprintln!("\n🚨 ONNX Runtime Version Conflict Detected!");
-                        eprintln!("   System32 contains an incompatible version of ONNX Runtime.");
-                        eprintln!("   This is preventing the correct version from loading.\n");
-                        eprintln!("   Solutions:");
-                        eprintln!("   1. Run as Administrator and rename the System32 version:");
-                        eprintln!("      ren C:\\Windows\\System32\\onnxruntime.dll onnxruntime.dll.bak");
-                        eprintln!("   2. Or uninstall the system-wide ONNX Runtime");
-                        
-                        return Err(pyo3::exceptions::PyRuntimeError::new_err(
-                            "ONNX Runtime version conflict: System32 contains incompatible version. See error message above for solutions."
-                        ));
-                    }
-                } else {
-                    eprintln!("✅ Pre-loaded onnxruntime.dll");
-                }
-            }
-            
-            // Now set ORT_DYLIB_PATH for the ort crate
-            // Use forward slashes for consistency with the ort crate
-            let dll_path = onnx_path.replace('\\', "/");
-            std::env::set_var("ORT_DYLIB_PATH", &dll_path);
-            eprintln!("✅ Set ORT_DYLIB_PATH to: {}", dll_path);
-        }
-    }
-    
-    // Set up ONNX Runtime library path for Linux
-    #[cfg(target_os = "linux")]
-    {
-        if std::env::var("ORT_DYLIB_PATH").is_err() {
-            // Get the site-packages path
-            let sys = _py.import("sys")?;
-            let prefix: String = sys.getattr("prefix")?.extract()?;
-            
-            // Build the path to our bundled library
-            let lib_path = format!("{}/lib/python{}.{}/site-packages/vocalize_rust/libonnxruntime.so", 
-                prefix, 
-                sys.getattr("version_info")?.getattr("major")?.extract::<i32>()?,
-                sys.getattr("version_info")?.getattr("minor")?.extract::<i32>()?
-            );
-            
-            // Check if the library exists
-            if std::path::Path::new(&lib_path).exists() {
-                std::env::set_var("ORT_DYLIB_PATH", &lib_path);
-                eprintln!("✅ Set ORT_DYLIB_PATH to: {}", lib_path);
-            } else {
-                eprintln!("⚠️  ONNX Runtime library not found at: {}", lib_path);
-                eprintln!("   Will attempt to use system ONNX Runtime if available");
-            }
-        }
-    }
-    
-    // Set up ONNX Runtime library path for macOS
-    #[cfg(target_os = "macos")]
-    {
-        if std::env::var("ORT_DYLIB_PATH").is_err() {
-            // Get the site-packages path
-            let sys = _py.import("sys")?;
-            let prefix: String = sys.getattr("prefix")?.extract()?;
-            
-            // Build the path to our bundled library
-            let lib_path = format!("{}/lib/python{}.{}/site-packages/vocalize_rust/libonnxruntime.dylib", 
-                prefix,
-                sys.getattr("version_info")?.getattr("major")?.extract::<i32>()?,
-                sys.getattr("version_info")?.getattr("minor")?.extract::<i32>()?
-            );
-            
-            // Check if the library exists
-            if std::path::Path::new(&lib_path).exists() {
-                std::env::set_var("ORT_DYLIB_PATH", &lib_path);
-                eprintln!("✅ Set ORT_DYLIB_PATH to: {}", lib_path);
-            } else {
-                eprintln!("⚠️  ONNX Runtime library not found at: {}", lib_path);
-                eprintln!("   Will attempt to use system ONNX Runtime if available");
-            }
-        }
-    }
+    onnx_runtime::setup_onnx_runtime(_py);
     
     // Initialize logging
     pyo3_log::init();
 
     // Add classes
     m.add_class::<PyTtsEngine>()?;
+    m.add_class::<PyTtsSession>()?;
     m.add_class::<PySynthesisParams>()?;
     m.add_class::<PyVoice>()?;
     m.add_class::<PyVoiceManager>()?;
@@ -401,6 +714,7 @@ fn vocalize_rust(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<PyEncodingSettings>()?;
     m.add_class::<PyAudioConfig>()?;
     m.add_class::<PyAudioDeviceInfo>()?;
+    m.add_class::<PyAudioStats>()?;
 
     // Add exceptions
     m.add("VocalizeException", _py.get_type::<VocalizeException>())?;
@@ -418,14 +732,53 @@ fn vocalize_rust(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
 
     // Add neural TTS functions
     m.add_function(wrap_pyfunction!(synthesize_neural, m)?)?;
+    m.add_function(wrap_pyfunction!(synthesize_batch_neural, m)?)?;
+    m.add_function(wrap_pyfunction!(synthesize_document_neural, m)?)?;
     m.add_function(wrap_pyfunction!(synthesize_from_tokens_neural, m)?)?;
     m.add_function(wrap_pyfunction!(list_neural_voices, m)?)?;
     m.add_function(wrap_pyfunction!(save_audio_neural, m)?)?;
+    m.add_function(wrap_pyfunction!(onnx_runtime::get_onnx_runtime_info, m)?)?;
+    m.add_function(wrap_pyfunction!(discover_models, m)?)?;
+    m.add_function(wrap_pyfunction!(estimate_model_memory, m)?)?;
+    m.add_function(wrap_pyfunction!(is_model_installed, m)?)?;
+    m.add_function(wrap_pyfunction!(list_installed_models, m)?)?;
+
+    // Add sample conversion functions
+    m.add_function(wrap_pyfunction!(to_pcm16, m)?)?;
+    m.add_function(wrap_pyfunction!(from_pcm16, m)?)?;
+
+    // Add level metering functions
+    m.add_function(wrap_pyfunction!(rms, m)?)?;
+    m.add_function(wrap_pyfunction!(peak, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze, m)?)?;
     
     // Add constants
     m.add("DEFAULT_SAMPLE_RATE", vocalize_core::DEFAULT_SAMPLE_RATE)?;
     m.add("DEFAULT_CHANNELS", vocalize_core::DEFAULT_CHANNELS)?;
+    m.add("DEFAULT_MAX_SAVE_SAMPLES", DEFAULT_MAX_SAVE_SAMPLES)?;
     m.add("VERSION", env!("CARGO_PKG_VERSION"))?;
+    m.add("MOCK_MODEL_AVAILABLE", cfg!(feature = "test-model"))?;
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(all(test, feature = "test-model"))]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use vocalize_core::models::mock_model::MOCK_MODEL_ID;
+
+    #[test]
+    fn test_batches_reuse_one_shared_engine() {
+        let params = tts_engine::neural_voice_params(Some("mock_female".to_string())).unwrap();
+        let texts = vec!["Hello world".to_string(), "A second sentence".to_string()];
+
+        let first = shared_engine(Some(MOCK_MODEL_ID.to_string())).unwrap();
+        let audio = synthesize_batch(&first, texts.clone(), &params).unwrap();
+        let second = shared_engine(Some(MOCK_MODEL_ID.to_string())).unwrap();
+        assert_eq!(synthesize_batch(&second, texts, &params).unwrap(), audio);
+
+        // Both batches ran on the same engine, so the model was loaded once
+        assert!(Arc::ptr_eq(&first.get_or_init().unwrap(), &second.get_or_init().unwrap()));
+    }
+}
@@ -0,0 +1,296 @@
+//! ONNX Runtime library discovery for the Python extension
+//!
+//! The `ort` crate is built with `load-dynamic`, so the shared library
+//! bundled next to the extension has to be located (and on Windows,
+//! pre-loaded) before any `ort` code runs.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Why ONNX Runtime setup failed at import, reported when synthesis is tried
+static SETUP_ERROR: OnceLock<String> = OnceLock::new();
+
+/// Point `ORT_DYLIB_PATH` at the ONNX Runtime bundled with the package
+///
+/// Does nothing if `ORT_DYLIB_PATH` is already set, so users can override
+/// the bundled library. Failures never abort the module import, since voice
+/// listing and audio encoding work without ONNX Runtime; they are recorded
+/// and raised by [`ensure_onnx_runtime`] when synthesis is attempted.
+pub(crate) fn setup_onnx_runtime(py: Python<'_>) {
+    if let Err(error) = try_setup_onnx_runtime(py) {
+        let message = error.to_string();
+        eprintln!("⚠️  ONNX Runtime setup failed; synthesis will be unavailable: {}", message);
+        let _ = SETUP_ERROR.set(message);
+    }
+}
+
+/// Fail with the deferred setup error, if ONNX Runtime could not be set up
+pub(crate) fn ensure_onnx_runtime() -> PyResult<()> {
+    check_setup(SETUP_ERROR.get().map(String::as_str))
+}
+
+fn check_setup(setup_error: Option<&str>) -> PyResult<()> {
+    match setup_error {
+        Some(message) => Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+            "ONNX Runtime is unavailable: {message}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn try_setup_onnx_runtime(py: Python<'_>) -> PyResult<()> {
+    if std::env::var("ORT_DYLIB_PATH").is_ok() {
+        return Ok(());
+    }
+
+    let package_dir = package_dir(py)?;
+
+    #[cfg(target_os = "windows")]
+    {
+        windows::preload_dlls(py, &package_dir)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let lib_path = package_dir.join(bundled_library_name());
+
+        // Check if the library exists
+        if lib_path.exists() {
+            std::env::set_var("ORT_DYLIB_PATH", &lib_path);
+            eprintln!("✅ Set ORT_DYLIB_PATH to: {}", lib_path.display());
+        } else {
+            eprintln!("⚠️  ONNX Runtime library not found at: {}", lib_path.display());
+            eprintln!("   Will attempt to use system ONNX Runtime if available");
+        }
+        Ok(())
+    }
+}
+
+/// Where the ONNX Runtime library in use comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuntimeSource {
+    /// The copy shipped inside the `vocalize_rust` package
+    Bundled,
+    /// A library the user pointed `ORT_DYLIB_PATH` at
+    Custom,
+    /// Whatever the system loader finds; `ORT_DYLIB_PATH` is unset
+    System,
+}
+
+impl RuntimeSource {
+    fn classify(dylib_path: Option<&Path>, package_dir: &Path) -> Self {
+        match dylib_path {
+            Some(path) if path.starts_with(package_dir) => Self::Bundled,
+            Some(_) => Self::Custom,
+            None => Self::System,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Bundled => "bundled",
+            Self::Custom => "custom",
+            Self::System => "system",
+        }
+    }
+}
+
+/// Report which ONNX Runtime library is in use
+///
+/// Returns a dict with `dylib_path` (the resolved `ORT_DYLIB_PATH`, or
+/// `None`), `source` (`"bundled"`, `"custom"` or `"system"`), `bundled`,
+/// and `version` (the build information reported by `ort`, or `None` if the
+/// library cannot be loaded).
+#[pyfunction]
+pub(crate) fn get_onnx_runtime_info(py: Python<'_>) -> PyResult<PyObject> {
+    let dylib_path = std::env::var_os("ORT_DYLIB_PATH").map(PathBuf::from);
+    let source = RuntimeSource::classify(dylib_path.as_deref(), &package_dir(py)?);
+
+    // Loading a missing or incompatible library panics inside ort
+    let version = std::panic::catch_unwind(|| ort::info().to_string()).ok();
+
+    let info = PyDict::new(py);
+    info.set_item("dylib_path", dylib_path.map(|path| path.to_string_lossy().into_owned()))?;
+    info.set_item("source", source.as_str())?;
+    info.set_item("bundled", source == RuntimeSource::Bundled)?;
+    info.set_item("version", version)?;
+    Ok(info.into())
+}
+
+/// Directory the `vocalize_rust` package is installed in
+fn package_dir(py: Python<'_>) -> PyResult<PathBuf> {
+    let sys = py.import("sys")?;
+    let prefix: String = sys.getattr("prefix")?.extract()?;
+
+    #[cfg(target_os = "windows")]
+    {
+        Ok(PathBuf::from(format!("{prefix}\\Lib\\site-packages\\vocalize_rust")))
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let version_info = sys.getattr("version_info")?;
+        let major: i32 = version_info.getattr("major")?.extract()?;
+        let minor: i32 = version_info.getattr("minor")?.extract()?;
+        Ok(unix_package_dir(&prefix, major, minor))
+    }
+}
+
+/// `site-packages/vocalize_rust` under a Unix Python prefix
+#[cfg(not(target_os = "windows"))]
+fn unix_package_dir(prefix: &str, major: i32, minor: i32) -> PathBuf {
+    PathBuf::from(format!("{prefix}/lib/python{major}.{minor}/site-packages/vocalize_rust"))
+}
+
+/// File name of the bundled ONNX Runtime library on this platform
+#[cfg(not(target_os = "windows"))]
+fn bundled_library_name() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "libonnxruntime.dylib"
+    } else {
+        "libonnxruntime.so"
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use pyo3::prelude::*;
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::Path;
+    use std::ptr::null_mut;
+    use winapi::um::errhandlingapi::GetLastError;
+    use winapi::um::libloaderapi::{LoadLibraryExW, LOAD_WITH_ALTERED_SEARCH_PATH};
+
+    const SYSTEM32_DLL: &str = "C:\\Windows\\System32\\onnxruntime.dll";
+    /// `LoadLibraryExW` error when the DLL or one of its dependencies is missing
+    const ERROR_MOD_NOT_FOUND: u32 = 126;
+
+    /// Pre-load the bundled DLLs so a conflicting System32 copy is not used
+    pub(super) fn preload_dlls(py: Python<'_>, dll_dir: &Path) -> PyResult<()> {
+        // First, check if System32 has a conflicting version
+        if Path::new(SYSTEM32_DLL).exists() {
+            eprintln!("⚠️  WARNING: Found ONNX Runtime in System32 at: {}", SYSTEM32_DLL);
+            eprintln!("   This may conflict with the bundled version.");
+        }
+
+        // Add directory to Python's DLL search path (for Python 3.8+)
+        let os = py.import("os")?;
+        if let Ok(add_dll_dir) = os.getattr("add_dll_directory") {
+            add_dll_dir.call1((dll_dir.to_string_lossy().into_owned(),))?;
+            eprintln!("✅ Added DLL directory to Python search path: {}", dll_dir.display());
+        }
+
+        // Load providers DLL first (dependency)
+        let providers_path = dll_dir.join("onnxruntime_providers_shared.dll");
+        match load_library(&providers_path) {
+            Ok(()) => eprintln!("✅ Pre-loaded onnxruntime_providers_shared.dll"),
+            Err(error) => {
+                eprintln!("❌ Failed to pre-load onnxruntime_providers_shared.dll");
+                eprintln!("   Path: {}", providers_path.display());
+                eprintln!("   Error code: {}", error);
+            }
+        }
+
+        // Load main ONNX Runtime DLL
+        let onnx_path = dll_dir.join("onnxruntime.dll");
+        match load_library(&onnx_path) {
+            Ok(()) => eprintln!("✅ Pre-loaded onnxruntime.dll"),
+            Err(error) => {
+                eprintln!("❌ Failed to pre-load onnxruntime.dll");
+                eprintln!("   Path: {}", onnx_path.display());
+                eprintln!("   Error code: {}", error);
+
+                // The DLL is present but one of its dependencies is not,
+                // which almost always means the VC++ runtime is missing
+                if error == ERROR_MOD_NOT_FOUND && onnx_path.exists() {
+                    return Err(pyo3::exceptions::PyRuntimeError::new_err(
+                        "onnxruntime.dll could not be loaded because a dependency is missing. \
+                         Install the Microsoft Visual C++ Redistributable (x64) from \
+                         https://aka.ms/vs/17/release/vc_redist.x64.exe and restart Python."
+                    ));
+                }
+
+                // If pre-loading failed, show detailed error message
+                if Path::new(SYSTEM32_DLL).exists() {
+                    eprintln!("\n🚨 ONNX Runtime Version Conflict Detected!");
+                    eprintln!("   System32 contains an incompatible version of ONNX Runtime.");
+                    eprintln!("   This is preventing the correct version from loading.\n");
+                    eprintln!("   Solutions:");
+                    eprintln!("   1. Run as Administrator and rename the System32 version:");
+                    eprintln!("      ren C:\\Windows\\System32\\onnxruntime.dll onnxruntime.dll.bak");
+                    eprintln!("   2. Or uninstall the system-wide ONNX Runtime");
+
+                    return Err(pyo3::exceptions::PyRuntimeError::new_err(
+                        "ONNX Runtime version conflict: System32 contains incompatible version. See error message above for solutions."
+                    ));
+                }
+            }
+        }
+
+        // Now set ORT_DYLIB_PATH for the ort crate
+        // Use forward slashes for consistency with the ort crate
+        let dll_path = onnx_path.to_string_lossy().replace('\\', "/");
+        std::env::set_var("ORT_DYLIB_PATH", &dll_path);
+        eprintln!("✅ Set ORT_DYLIB_PATH to: {}", dll_path);
+        Ok(())
+    }
+
+    /// Load a DLL by absolute path, returning the Windows error code on failure
+    fn load_library(path: &Path) -> Result<(), u32> {
+        // Convert paths to wide strings for Windows API
+        let wide: Vec<u16> = OsStr::new(path).encode_wide().chain(Some(0)).collect();
+
+        unsafe {
+            let handle = LoadLibraryExW(wide.as_ptr(), null_mut(), LOAD_WITH_ALTERED_SEARCH_PATH);
+            if handle.is_null() {
+                Err(GetLastError())
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_unix_package_dir() {
+        assert_eq!(
+            unix_package_dir("/opt/venv", 3, 11),
+            PathBuf::from("/opt/venv/lib/python3.11/site-packages/vocalize_rust")
+        );
+    }
+
+    #[test]
+    fn test_setup_error_is_deferred_to_synthesis() {
+        assert!(check_setup(None).is_ok());
+        assert!(check_setup(Some("onnxruntime.dll could not be loaded")).is_err());
+    }
+
+    #[test]
+    fn test_runtime_source_classification() {
+        let package_dir = Path::new("/opt/venv/site-packages/vocalize_rust");
+        let bundled = package_dir.join("libonnxruntime.so");
+
+        assert_eq!(RuntimeSource::classify(Some(&bundled), package_dir), RuntimeSource::Bundled);
+        assert_eq!(
+            RuntimeSource::classify(Some(Path::new("/usr/lib/libonnxruntime.so")), package_dir),
+            RuntimeSource::Custom
+        );
+        assert_eq!(RuntimeSource::classify(None, package_dir), RuntimeSource::System);
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_bundled_library_name() {
+        let name = bundled_library_name();
+        assert!(name.starts_with("libonnxruntime."));
+        assert_eq!(name.ends_with(".dylib"), cfg!(target_os = "macos"));
+    }
+}
@@ -3,7 +3,7 @@
 
 use std::sync::{Arc, Mutex, Once};
 use tokio::runtime::Runtime;
-use vocalize_core::TtsEngine;
+use vocalize_core::{TtsConfig, TtsEngine};
 use pyo3::prelude::*;
 
 static INIT: Once = Once::new();
@@ -60,15 +60,22 @@ impl RuntimeManager {
 }
 
 /// Lazy TTS engine that initializes on first use
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LazyTtsEngine {
     engine: Arc<Mutex<Option<Arc<TtsEngine>>>>,
+    config: TtsConfig,
 }
 
 impl LazyTtsEngine {
     pub fn new() -> Self {
+        Self::with_config(TtsConfig::default())
+    }
+
+    /// Create a lazy engine that will be built from `config` on first use
+    pub fn with_config(config: TtsConfig) -> Self {
         Self {
             engine: Arc::new(Mutex::new(None)),
+            config,
         }
     }
     
@@ -81,8 +88,9 @@ impl LazyTtsEngine {
         if engine_guard.is_none() {
             tracing::info!("🔄 Initializing TTS engine...");
             
+            let config = self.config.clone();
             let engine_result = RuntimeManager::block_on(async {
-                TtsEngine::new().await
+                TtsEngine::with_config(config).await
             })?; // This gives us Result<TtsEngine, VocalizeError>
             let engine = engine_result.map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(
                 format!("Failed to create TTS engine: {}", e)
@@ -95,6 +103,21 @@ impl LazyTtsEngine {
         Ok(engine_guard.as_ref().unwrap().clone())
     }
     
+    /// Create the engine if needed and make sure its models are loaded
+    pub fn initialize(&self) -> PyResult<Arc<TtsEngine>> {
+        let engine = self.get_or_init()?;
+        RuntimeManager::block_on(async { engine.preload_models().await })?
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(
+                format!("Failed to preload models: {}", e)
+            ))?;
+        Ok(engine)
+    }
+
+    /// Get the engine if it has already been created
+    pub fn get(&self) -> Option<Arc<TtsEngine>> {
+        self.engine.lock().ok().and_then(|guard| guard.clone())
+    }
+
     /// Check if the engine is initialized
     pub fn is_initialized(&self) -> bool {
         if let Ok(guard) = self.engine.lock() {
@@ -0,0 +1,71 @@
+//! Python bindings for interactive TTS sessions
+
+use pyo3::prelude::*;
+use vocalize_core::{TtsConfig, TtsEngine, TtsSession};
+
+use crate::error::IntoPyResult;
+use crate::runtime_manager::RuntimeManager;
+use crate::tts_engine::{model_config, PySynthesisParams};
+use crate::voice_manager::PyVoice;
+
+/// Python wrapper for TtsSession
+#[pyclass(name = "TtsSession")]
+#[derive(Debug)]
+pub struct PyTtsSession {
+    inner: TtsSession,
+}
+
+#[pymethods]
+impl PyTtsSession {
+    /// Start a session speaking with `voice_id`, optionally choosing the model
+    ///
+    /// The engine is created and its models loaded here, so the first
+    /// `say` is as fast as the rest.
+    #[new]
+    #[pyo3(signature = (voice_id, model_id=None))]
+    fn py_new(voice_id: String, model_id: Option<String>) -> PyResult<Self> {
+        RuntimeManager::initialize()?;
+        let config = model_id.as_deref().map_or_else(TtsConfig::default, model_config);
+
+        let inner = RuntimeManager::block_on(async {
+            let engine = TtsEngine::with_config(config).await?;
+            TtsSession::new(engine, &voice_id).await
+        })?
+        .into_py_result()?;
+        Ok(Self { inner })
+    }
+
+    /// Synthesize `text` with the session's current voice and speed
+    fn say(&self, text: String) -> PyResult<Vec<f32>> {
+        RuntimeManager::block_on(async { self.inner.say(&text).await })?.into_py_result()
+    }
+
+    /// Switch voice for later utterances, keeping the speed
+    fn set_voice(&mut self, voice_id: String) -> PyResult<()> {
+        RuntimeManager::block_on(async { self.inner.set_voice(&voice_id).await })?.into_py_result()
+    }
+
+    /// Change the speed multiplier for later utterances
+    fn set_speed(&mut self, speed: f32) -> PyResult<()> {
+        self.inner.set_speed(speed).into_py_result()
+    }
+
+    #[getter]
+    fn voice(&self) -> PyVoice {
+        PyVoice::new(self.inner.voice().clone())
+    }
+
+    #[getter]
+    fn speed(&self) -> f32 {
+        self.inner.speed()
+    }
+
+    #[getter]
+    fn params(&self) -> PySynthesisParams {
+        PySynthesisParams::new(self.inner.params().clone())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("TtsSession(voice='{}', speed={})", self.inner.voice().id, self.inner.speed())
+    }
+}
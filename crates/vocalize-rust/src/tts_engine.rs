@@ -1,10 +1,19 @@
 //! Python bindings for TTS engine
 
+use numpy::PyReadonlyArray1;
 use pyo3::prelude::*;
+use pyo3::types::{PyAny, PyDict};
+use pyo3_asyncio::tokio::future_into_py;
 use std::collections::HashMap;
-use vocalize_core::SynthesisParams;
-
-use crate::error::IntoPyResult;
+use std::path::PathBuf;
+use std::sync::Arc;
+#[cfg(feature = "test-model")]
+use vocalize_core::models::mock_model::MOCK_MODEL_ID;
+use vocalize_core::onnx_engine::{OnnxTtsEngine, TensorSpec};
+use vocalize_core::{Gender, SynthesisParams, TtsConfig, Voice, VoiceStyle};
+
+use crate::audio_device::PyAudioDevice;
+use crate::error::{IntoPyResult, PyVocalizeError};
 use crate::voice_manager::PyVoice;
 use crate::runtime_manager::{RuntimeManager, LazyTtsEngine};
 
@@ -62,6 +71,16 @@ impl PySynthesisParams {
         self.inner.chunk_size
     }
 
+    #[getter]
+    fn output_sample_rate(&self) -> Option<u32> {
+        self.inner.output_sample_rate
+    }
+
+    #[getter]
+    fn time_stretch(&self) -> Option<f32> {
+        self.inner.time_stretch
+    }
+
     fn with_speed(&self, speed: f32) -> PyResult<PySynthesisParams> {
         let params = self.inner.clone().with_speed(speed).into_py_result()?;
         Ok(Self::new(params))
@@ -72,6 +91,17 @@ impl PySynthesisParams {
         Ok(Self::new(params))
     }
 
+    /// Change tempo after synthesis, keeping pitch; speed must stay at 1.0
+    fn with_time_stretch(&self, factor: f32) -> PyResult<PySynthesisParams> {
+        let params = self.inner.clone().with_time_stretch(factor).into_py_result()?;
+        Ok(Self::new(params))
+    }
+
+    fn with_output_sample_rate(&self, sample_rate: u32) -> PyResult<PySynthesisParams> {
+        let params = self.inner.clone().with_output_sample_rate(sample_rate).into_py_result()?;
+        Ok(Self::new(params))
+    }
+
     fn with_streaming(&self, chunk_size: usize) -> PySynthesisParams {
         let params = self.inner.clone().with_streaming(chunk_size);
         Self::new(params)
@@ -110,33 +140,181 @@ impl PySynthesisParams {
 #[derive(Debug)]
 pub struct PyTtsEngine {
     lazy_engine: LazyTtsEngine,
+    /// ONNX engine for token synthesis, created on first use and reused so
+    /// its session pool stays warm
+    onnx_engine: Arc<tokio::sync::Mutex<Option<OnnxTtsEngine>>>,
+    /// Kokoro ONNX file to load instead of the discovered one
+    model_path: Option<PathBuf>,
 }
 
 impl PyTtsEngine {
     pub fn new() -> Self {
+        Self::with_config(TtsConfig::default())
+    }
+
+    /// Create an engine that uses `model_id` as its default model
+    pub fn with_model(model_id: &str) -> Self {
+        Self::with_config(model_config(model_id))
+    }
+
+    fn with_config(config: TtsConfig) -> Self {
         Self {
-            lazy_engine: LazyTtsEngine::new(),
+            model_path: config.model_path_override.clone(),
+            lazy_engine: LazyTtsEngine::with_config(config),
+            onnx_engine: Arc::default(),
         }
     }
+
+    /// Run token synthesis on this instance's ONNX engine, creating it on first use
+    fn synthesize_token_slices(
+        &self,
+        input_ids: &[i64],
+        style_vector: &[f32],
+        speed: f32,
+        model_id: Option<&str>,
+        voice_id: Option<&str>,
+    ) -> PyResult<Vec<f32>> {
+        crate::validate_token_inputs(input_ids, style_vector, speed)?;
+        crate::onnx_runtime::ensure_onnx_runtime()?;
+        let model = crate::token_model_id(model_id);
+
+        RuntimeManager::block_on(async {
+            let mut onnx_engine = self.onnx_engine.lock().await;
+            let engine = self.init_onnx_engine(&mut onnx_engine, model).await?;
+
+            if let Some(voice_id) = voice_id {
+                engine
+                    .check_style_vector_for_voice(style_vector, model, voice_id)
+                    .into_py_result()?;
+            }
+
+            engine
+                .synthesize_from_token_slices(input_ids, style_vector, speed, model)
+                .await
+                .map_err(|e| PyVocalizeError::new_err(format!("Token synthesis failed: {e}")))
+        })?
+    }
+
+    /// Create this instance's ONNX engine on first use, honouring `model_path`
+    async fn init_onnx_engine<'a>(
+        &self,
+        onnx_engine: &'a mut Option<OnnxTtsEngine>,
+        model: vocalize_core::ModelId,
+    ) -> PyResult<&'a mut OnnxTtsEngine> {
+        if onnx_engine.is_none() {
+            let mut engine = OnnxTtsEngine::new_with_default_cache().await.map_err(|e| {
+                PyVocalizeError::new_err(format!("Failed to create ONNX engine: {e}"))
+            })?;
+            if let Some(path) = &self.model_path {
+                engine.load_model_from_path(model, path).await.map_err(|e| {
+                    PyVocalizeError::new_err(format!("Failed to load model from {}: {e}", path.display()))
+                })?;
+            }
+            *onnx_engine = Some(engine);
+        }
+        Ok(onnx_engine.as_mut().expect("ONNX engine was just created"))
+    }
+}
+
+/// Convert tensor specs into dicts with `name`, `element_type` and `shape` keys
+fn tensor_specs_to_py(py: Python<'_>, specs: &[TensorSpec]) -> PyResult<Vec<PyObject>> {
+    specs
+        .iter()
+        .map(|spec| {
+            let dict = PyDict::new(py);
+            dict.set_item("name", &spec.name)?;
+            dict.set_item("element_type", &spec.element_type)?;
+            dict.set_item("shape", &spec.dimensions)?;
+            Ok(dict.into_py(py))
+        })
+        .collect()
+}
+
+/// Synthesis parameters for a neural voice chosen by the Python frontend
+pub(crate) fn neural_voice_params(voice_id: Option<String>) -> PyResult<SynthesisParams> {
+    // Rust doesn't decide default voices - require voice_id from Python
+    let voice_id = voice_id.ok_or_else(|| {
+        PyVocalizeError::new_err("Voice ID is required. Python frontend must provide a voice ID.".to_string())
+    })?;
+
+    let voice = Voice::new(
+        voice_id.clone(),
+        format!("Neural Voice {}", voice_id),
+        "en-US".to_string(),
+        Gender::Female,
+        VoiceStyle::Natural,
+    );
+    Ok(SynthesisParams::new(voice))
+}
+
+/// Engine configuration that uses `model_id` as its default model
+///
+/// The "mock" model is generated in-process and is kept in its own cache
+/// directory so it never shows up in the user's installed models.
+pub(crate) fn model_config(model_id: &str) -> TtsConfig {
+    let config = TtsConfig {
+        default_model_id: model_id.to_string(),
+        ..TtsConfig::default()
+    };
+    #[cfg(feature = "test-model")]
+    let config = if model_id == MOCK_MODEL_ID {
+        TtsConfig {
+            model_cache_dir: std::env::temp_dir().join("vocalize-mock-model"),
+            auto_install_default: true,
+            ..config
+        }
+    } else {
+        config
+    };
+    config
 }
 
 #[pymethods]
 impl PyTtsEngine {
+    /// Create an engine, optionally choosing its model
+    ///
+    /// `model_path` pins the Kokoro ONNX file to load, skipping model
+    /// discovery; its directory must also contain the voices file.
     #[new]
-    fn py_new() -> PyResult<Self> {
+    #[pyo3(signature = (model_id=None, model_path=None))]
+    fn py_new(model_id: Option<String>, model_path: Option<PathBuf>) -> PyResult<Self> {
         // Initialize global runtime if not already done
         RuntimeManager::initialize()?;
         
+        let mut config = model_id.as_deref().map_or_else(TtsConfig::default, model_config);
+        if let Some(model_path) = model_path {
+            vocalize_core::model::KokoroModelFiles::from_model_file(&model_path).into_py_result()?;
+            config.model_path_override = Some(model_path);
+        }
+        
         // Create lazy engine (doesn't initialize TTS engine yet)
-        Ok(PyTtsEngine::new())
+        Ok(PyTtsEngine::with_config(config))
     }
     
-    /// Initialize the TTS engine (lazy initialization)
+    /// Initialize the TTS engine and preload its models
     fn initialize(&self) -> PyResult<()> {
-        self.lazy_engine.get_or_init()?;
+        self.lazy_engine.initialize()?;
         Ok(())
     }
 
+    /// Initialize the TTS engine without blocking the event loop
+    fn initialize_async<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
+        let lazy_engine = self.lazy_engine.clone();
+
+        future_into_py(py, async move {
+            // Initialization blocks on the global runtime, so it cannot run on
+            // the event loop's runtime; hand it to a plain thread instead
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            std::thread::spawn(move || {
+                let _ = tx.send(lazy_engine.initialize().map(|_| ()));
+            });
+
+            rx.await.map_err(|_| {
+                pyo3::exceptions::PyRuntimeError::new_err("TTS engine initialization was aborted")
+            })?
+        })
+    }
+
     /// Synthesize text to audio (using real TTS engine)
     fn synthesize_sync(&self, text: String, params: &PySynthesisParams) -> PyResult<Vec<f32>> {
         // Get the TTS engine (initialize if needed)
@@ -157,9 +335,237 @@ impl PyTtsEngine {
         ))
     }
 
-    /// Check if the engine is ready
+    /// Synthesize a short sample of a voice for previewing it
+    ///
+    /// Speaks `sample_text`, or a short default phrase if omitted, using the
+    /// engine's already loaded model.
+    #[pyo3(signature = (voice_id, sample_text=None))]
+    fn preview_voice(&self, voice_id: String, sample_text: Option<String>) -> PyResult<Vec<f32>> {
+        let engine = self.lazy_engine.get_or_init()?;
+
+        RuntimeManager::block_on(async {
+            engine.preview_voice(&voice_id, sample_text.as_deref()).await
+        })?
+        .into_py_result()
+    }
+
+    /// Synthesize text and play it through the default audio device
+    #[pyo3(signature = (text, voice_id=None))]
+    fn speak(&self, text: String, voice_id: Option<String>) -> PyResult<()> {
+        let params = neural_voice_params(voice_id)?;
+        let engine = self.lazy_engine.get_or_init()?;
+
+        RuntimeManager::block_on(async {
+            engine.speak(&text, &params).await
+        })?
+        .into_py_result()
+    }
+
+    /// Speak text sentence by sentence, starting playback as soon as the
+    /// first sentence is synthesized
+    ///
+    /// Plays on `device` if given, otherwise on the default audio device.
+    /// `on_chunk`, if given, is called with each chunk's samples just before
+    /// it is played. Returns the number of chunks played.
+    #[pyo3(signature = (text, voice_id=None, device=None, on_chunk=None))]
+    fn speak_stream(
+        &self,
+        py: Python<'_>,
+        text: String,
+        voice_id: Option<String>,
+        device: Option<PyRef<'_, PyAudioDevice>>,
+        on_chunk: Option<PyObject>,
+    ) -> PyResult<usize> {
+        let params = neural_voice_params(voice_id)?;
+        let engine = self.lazy_engine.get_or_init()?;
+
+        let mut callback_error = None;
+        let forward_chunk = |chunk: &Vec<f32>| {
+            if let (Some(callback), None) = (&on_chunk, &callback_error) {
+                if let Err(e) = callback.call1(py, (chunk.clone(),)) {
+                    callback_error = Some(e);
+                }
+            }
+        };
+
+        let played = RuntimeManager::block_on(async {
+            match &device {
+                Some(device) => engine.speak_stream_with_device(&text, &params, device.inner(), forward_chunk).await,
+                None => {
+                    let device = vocalize_core::AudioDevice::new().await?;
+                    engine.speak_stream_with_device(&text, &params, &device, forward_chunk).await
+                }
+            }
+        })?
+        .into_py_result()?;
+
+        match callback_error {
+            Some(e) => Err(e),
+            None => Ok(played),
+        }
+    }
+
+    /// Synthesize audio from pre-processed token IDs
+    ///
+    /// Unlike the module-level `synthesize_from_tokens_neural`, the ONNX
+    /// engine and its loaded model are kept on this instance, so only the
+    /// first call pays for loading them.
+    ///
+    /// Pass `voice_id` to check the style vector against that voice's stored
+    /// embedding first: a dimension mismatch raises, and a vector that looks
+    /// like a different voice logs a warning.
+    #[pyo3(signature = (input_ids, style_vector, speed, model_id=None, voice_id=None))]
+    fn synthesize_from_tokens(
+        &self,
+        input_ids: Vec<i64>,
+        style_vector: Vec<f32>,
+        speed: f32,
+        model_id: Option<String>,
+        voice_id: Option<String>,
+    ) -> PyResult<Vec<f32>> {
+        self.synthesize_token_slices(
+            &input_ids,
+            &style_vector,
+            speed,
+            model_id.as_deref(),
+            voice_id.as_deref(),
+        )
+    }
+
+    /// Synthesize audio from NumPy token IDs (`int64`) and style vector (`float32`)
+    ///
+    /// Behaves like `synthesize_from_tokens`, but the arrays are read in
+    /// place instead of being copied into lists first. Both must be
+    /// one-dimensional and C-contiguous.
+    #[pyo3(signature = (input_ids, style_vector, speed, model_id=None, voice_id=None))]
+    fn synthesize_from_token_arrays(
+        &self,
+        input_ids: PyReadonlyArray1<'_, i64>,
+        style_vector: PyReadonlyArray1<'_, f32>,
+        speed: f32,
+        model_id: Option<String>,
+        voice_id: Option<String>,
+    ) -> PyResult<Vec<f32>> {
+        let input_ids = input_ids.as_slice().map_err(|_| {
+            PyVocalizeError::new_err("input_ids must be a C-contiguous int64 array".to_string())
+        })?;
+        let style_vector = style_vector.as_slice().map_err(|_| {
+            PyVocalizeError::new_err("style_vector must be a C-contiguous float32 array".to_string())
+        })?;
+        self.synthesize_token_slices(input_ids, style_vector, speed, model_id.as_deref(), voice_id.as_deref())
+    }
+
+    /// Describe the ONNX model's input and output tensors
+    ///
+    /// Loads the model if needed and returns `{"inputs": [...], "outputs": [...]}`,
+    /// each entry a dict with `name`, `element_type` and `shape` (`-1` marks
+    /// a dynamic axis). Useful for diagnosing input schema mismatches.
+    #[pyo3(signature = (model_id=None))]
+    fn get_model_io(&self, py: Python<'_>, model_id: Option<String>) -> PyResult<PyObject> {
+        crate::onnx_runtime::ensure_onnx_runtime()?;
+        let model = crate::token_model_id(model_id.as_deref());
+
+        let spec = RuntimeManager::block_on(async {
+            let mut onnx_engine = self.onnx_engine.lock().await;
+            let engine = self.init_onnx_engine(&mut onnx_engine, model).await?;
+            if engine.current_model() != Some(model) {
+                engine.load_model(model).await.map_err(|e| {
+                    PyVocalizeError::new_err(format!("Failed to load model {model:?}: {e}"))
+                })?;
+            }
+            engine
+                .model_io_spec()
+                .map_err(|e| PyVocalizeError::new_err(format!("Failed to read model I/O: {e}")))
+        })??;
+
+        let result = PyDict::new(py);
+        result.set_item("inputs", tensor_specs_to_py(py, &spec.inputs)?)?;
+        result.set_item("outputs", tensor_specs_to_py(py, &spec.outputs)?)?;
+        Ok(result.into_py(py))
+    }
+
+    /// Synthesize a fixed token sequence and check the model's output
+    ///
+    /// Returns a dict with `model_id`, `passed`, `sample_count`,
+    /// `non_finite_samples`, `peak`, `rms`, `fingerprint`,
+    /// `expected_fingerprint` (`None` for non-deterministic models) and
+    /// `failures`, a list describing each failed check.
+    #[pyo3(signature = (model_id=None))]
+    fn self_test(&self, py: Python<'_>, model_id: Option<String>) -> PyResult<PyObject> {
+        crate::onnx_runtime::ensure_onnx_runtime()?;
+        let model = crate::token_model_id(model_id.as_deref());
+
+        let report = RuntimeManager::block_on(async {
+            let mut onnx_engine = self.onnx_engine.lock().await;
+            let engine = self.init_onnx_engine(&mut onnx_engine, model).await?;
+            engine
+                .self_test(model)
+                .await
+                .map_err(|e| PyVocalizeError::new_err(format!("Self-test failed to run: {e}")))
+        })??;
+
+        let result = PyDict::new(py);
+        result.set_item("model_id", report.model_id.as_str())?;
+        result.set_item("passed", report.passed())?;
+        result.set_item("sample_count", report.sample_count)?;
+        result.set_item("non_finite_samples", report.non_finite_samples)?;
+        result.set_item("peak", report.peak)?;
+        result.set_item("rms", report.rms)?;
+        result.set_item("fingerprint", report.fingerprint)?;
+        result.set_item("expected_fingerprint", report.expected_fingerprint)?;
+        result.set_item("failures", report.failures)?;
+        Ok(result.into_py(py))
+    }
+
+    /// List the voice IDs supported by the active model
+    fn supported_voices(&self) -> PyResult<Vec<String>> {
+        let engine = self.lazy_engine.get_or_init()?;
+        RuntimeManager::block_on(async { engine.supported_voices().await })?.into_py_result()
+    }
+
+    /// List every voice available across installed models, each tagged
+    /// with its `model_id`
+    fn all_available_voices(&self) -> PyResult<Vec<PyVoice>> {
+        let engine = self.lazy_engine.get_or_init()?;
+        let voices = RuntimeManager::block_on(async { engine.all_available_voices().await })?;
+        Ok(voices.into_iter().map(PyVoice::new).collect())
+    }
+
+    /// ID of the model synthesis falls back to; never installs anything
+    fn get_default_model(&self) -> PyResult<String> {
+        let engine = self.lazy_engine.get_or_init()?;
+        Ok(engine.get_default_model())
+    }
+
+    /// Change the default model without installing or loading it
+    fn set_default_model(&self, model_id: String) -> PyResult<()> {
+        let engine = self.lazy_engine.get_or_init()?;
+        engine.set_default_model(&model_id).into_py_result()
+    }
+
+    /// Sample rate of the audio produced by the active model, in Hz
+    fn output_sample_rate(&self) -> PyResult<u32> {
+        let engine = self.lazy_engine.get_or_init()?;
+        RuntimeManager::block_on(async { engine.output_sample_rate().await })
+    }
+
+    /// Replace the pronunciation overrides (word -> phonemes)
+    fn set_pronunciation_overrides(&self, overrides: HashMap<String, String>) -> PyResult<()> {
+        let engine = self.lazy_engine.get_or_init()?;
+        engine.set_pronunciation_overrides(overrides).into_py_result()
+    }
+
+    /// Replace the pronunciation overrides with those in a dictionary file
+    fn load_pronunciation_overrides(&self, path: PathBuf) -> PyResult<()> {
+        let engine = self.lazy_engine.get_or_init()?;
+        engine.load_pronunciation_overrides(path).into_py_result()
+    }
+
+    /// Check if the engine has been created and its models are loaded
     fn is_ready(&self) -> bool {
-        self.lazy_engine.is_initialized()
+        self.lazy_engine.get().map_or(false, |engine| {
+            RuntimeManager::block_on(async { engine.is_initialized().await }).unwrap_or(false)
+        })
     }
     
     /// Get engine statistics
@@ -183,6 +589,29 @@ impl PyTtsEngine {
         Ok(result)
     }
 
+    /// List installed models with declared and measured on-disk sizes
+    fn list_installed_models(&self) -> PyResult<Vec<HashMap<String, String>>> {
+        let engine = self.lazy_engine.get_or_init()?;
+
+        let models = RuntimeManager::block_on(async {
+            engine.list_installed_models().await
+        })?;
+
+        Ok(models
+            .into_iter()
+            .map(|model| {
+                let mut dict = HashMap::new();
+                dict.insert("id".to_string(), model.id);
+                dict.insert("name".to_string(), model.name);
+                dict.insert("version".to_string(), model.version);
+                dict.insert("size".to_string(), model.size.to_string());
+                dict.insert("disk_size".to_string(), model.disk_size.to_string());
+                dict.insert("install_path".to_string(), model.install_path.display().to_string());
+                dict
+            })
+            .collect())
+    }
+
     fn __repr__(&self) -> String {
         "TtsEngine()".to_string()
     }
@@ -195,6 +624,16 @@ mod tests {
     use vocalize_core::{Voice, Gender, VoiceStyle};
     
     // Helper function to create a test voice since Voice::default() is no longer available
+    fn create_mock_voice() -> PyVoice {
+        PyVoice::py_new(
+            "mock_female".to_string(),
+            "Mock Female".to_string(),
+            "en-US".to_string(),
+            PyGender::Female,
+            PyVoiceStyle::Natural,
+        )
+    }
+
     fn create_test_voice() -> PyVoice {
         let voice = Voice::new(
             "af_alloy".to_string(),
@@ -293,14 +732,14 @@ mod tests {
 
     #[test]
     fn test_py_tts_engine_creation() {
-        let engine = PyTtsEngine::py_new();
+        let engine = PyTtsEngine::py_new(None, None);
         assert!(engine.is_ok());
         assert_eq!(engine.unwrap().__repr__(), "TtsEngine()");
     }
 
     #[test]
     fn test_py_tts_engine_synthesize() {
-        let engine = PyTtsEngine::py_new().unwrap();
+        let engine = PyTtsEngine::py_new(None, None).unwrap();
         let voice = create_test_voice();
         let params = PySynthesisParams::py_new(voice);
         
@@ -310,4 +749,65 @@ mod tests {
         let audio = result.unwrap();
         assert!(!audio.is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    #[cfg(feature = "test-model")]
+    fn test_py_tts_engine_initialize() {
+        let engine = PyTtsEngine::py_new(Some("mock".to_string()), None).unwrap();
+        assert!(!engine.is_ready());
+
+        engine.initialize().unwrap();
+        assert!(engine.is_ready());
+    }
+
+    #[test]
+    #[cfg(feature = "test-model")]
+    fn test_py_tts_engine_mock_model() {
+        let engine = PyTtsEngine::py_new(Some("mock".to_string()), None).unwrap();
+        let params = PySynthesisParams::py_new(create_mock_voice());
+
+        let first = engine.synthesize_sync("Hello".to_string(), &params).unwrap();
+        let second = engine.synthesize_sync("Hello".to_string(), &params).unwrap();
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+        assert!(engine.is_ready());
+    }
+
+    #[test]
+    #[cfg(feature = "test-model")]
+    fn test_py_tts_engine_pronunciation_overrides() {
+        let engine = PyTtsEngine::py_new(Some("mock".to_string()), None).unwrap();
+        let params = PySynthesisParams::py_new(create_mock_voice());
+
+        let default = engine.synthesize_sync("Vocalize".to_string(), &params).unwrap();
+        let overrides = HashMap::from([("vocalize".to_string(), "vˈoʊkəlaɪz".to_string())]);
+        engine.set_pronunciation_overrides(overrides).unwrap();
+        let overridden = engine.synthesize_sync("Vocalize".to_string(), &params).unwrap();
+        assert_ne!(default, overridden);
+
+        let invalid = HashMap::from([("two words".to_string(), "x".to_string())]);
+        assert!(engine.set_pronunciation_overrides(invalid).is_err());
+    }
+
+    #[test]
+    fn test_py_tts_engine_rejects_invalid_model_path() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let model_path = temp_dir.path().join("kokoro-v1.0.onnx");
+        assert!(PyTtsEngine::py_new(None, Some(model_path.clone())).is_err());
+
+        std::fs::write(&model_path, vec![8u8; 2_000_000]).unwrap();
+        std::fs::write(temp_dir.path().join("voices-v1.0.bin"), b"voices").unwrap();
+        let engine = PyTtsEngine::py_new(None, Some(model_path.clone())).unwrap();
+        assert_eq!(engine.model_path, Some(model_path));
+    }
+
+    #[test]
+    #[cfg(feature = "test-model")]
+    fn test_py_tts_engine_supported_voices() {
+        let engine = PyTtsEngine::py_new(Some("mock".to_string()), None).unwrap();
+        assert_eq!(
+            engine.supported_voices().unwrap(),
+            vec!["mock_female".to_string(), "mock_male".to_string()]
+        );
+    }
+}
@@ -4,7 +4,7 @@ use pyo3::prelude::*;
 use std::collections::HashMap;
 use vocalize_core::{Gender, Voice, VoiceManager, VoiceStyle};
 
-use crate::error::IntoPyResult;
+use crate::error::{IntoPyResult, PyVocalizeError};
 
 /// Python wrapper for Gender enum
 #[pyclass(name = "Gender")]
@@ -49,6 +49,14 @@ impl PyGender {
         format!("Gender.{}", self.__str__())
     }
 
+    /// Pickle as a lookup of the matching class attribute, e.g. `Gender.MALE`
+    fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (PyObject, String))> {
+        let py = slf.py();
+        let getattr = py.import("builtins")?.getattr("getattr")?.into_py(py);
+        let attribute = slf.borrow().__str__().to_uppercase();
+        Ok((getattr, (slf.get_type().into_py(py), attribute)))
+    }
+
     #[classattr]
     const MALE: PyGender = PyGender::Male;
 
@@ -110,6 +118,14 @@ impl PyVoiceStyle {
         format!("VoiceStyle.{}", self.__str__())
     }
 
+    /// Pickle as a lookup of the matching class attribute, e.g. `VoiceStyle.NATURAL`
+    fn __reduce__(slf: &PyCell<Self>) -> PyResult<(PyObject, (PyObject, String))> {
+        let py = slf.py();
+        let getattr = py.import("builtins")?.getattr("getattr")?.into_py(py);
+        let attribute = slf.borrow().__str__().to_uppercase();
+        Ok((getattr, (slf.get_type().into_py(py), attribute)))
+    }
+
     #[classattr]
     const NATURAL: PyVoiceStyle = PyVoiceStyle::Natural;
 
@@ -211,6 +227,12 @@ impl PyVoice {
         self.inner.pitch
     }
 
+    /// Model the voice belongs to, or `None` if it was not listed by a model
+    #[getter]
+    fn model_id(&self) -> Option<String> {
+        self.inner.model_id.clone()
+    }
+
     fn with_description(&self, description: String) -> PyVoice {
         let mut voice = self.inner.clone();
         voice.description = description;
@@ -263,8 +285,38 @@ impl PyVoice {
         dict.insert("sample_rate".to_string(), self.inner.sample_rate.to_string());
         dict.insert("speed".to_string(), self.inner.speed.to_string());
         dict.insert("pitch".to_string(), self.inner.pitch.to_string());
+        if let Some(model_id) = &self.inner.model_id {
+            dict.insert("model_id".to_string(), model_id.clone());
+        }
         dict
     }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+
+    /// Constructor arguments used when unpickling, before `__setstate__`
+    fn __getnewargs__(&self) -> (String, String, String, PyGender, PyVoiceStyle) {
+        (
+            self.inner.id.clone(),
+            self.inner.name.clone(),
+            self.inner.language.clone(),
+            self.inner.gender.into(),
+            self.inner.style.into(),
+        )
+    }
+
+    /// Serialize every field as JSON for pickling
+    fn __getstate__(&self) -> PyResult<String> {
+        serde_json::to_string(&self.inner)
+            .map_err(|e| PyVocalizeError::new_err(format!("Failed to serialize voice: {e}")))
+    }
+
+    fn __setstate__(&mut self, state: &str) -> PyResult<()> {
+        self.inner = serde_json::from_str(state)
+            .map_err(|e| PyVocalizeError::new_err(format!("Invalid voice state: {e}")))?;
+        Ok(())
+    }
 }
 
 /// Python wrapper for VoiceManager
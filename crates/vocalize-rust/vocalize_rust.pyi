@@ -0,0 +1,395 @@
+"""Type stubs for the vocalize_rust extension module.
+
+Keep in sync with the `#[pymodule]` in src/lib.rs; the test suite checks
+that every public name here exists in the compiled module and vice versa.
+"""
+
+from os import PathLike
+from typing import (
+    Any,
+    Awaitable,
+    Callable,
+    Dict,
+    List,
+    Literal,
+    Optional,
+    Sequence,
+    Tuple,
+    Union,
+    overload,
+)
+
+import numpy as np
+import numpy.typing as npt
+
+_PathLike = Union[str, "PathLike[str]"]
+
+DEFAULT_SAMPLE_RATE: int
+DEFAULT_CHANNELS: int
+DEFAULT_MAX_SAVE_SAMPLES: int
+VERSION: str
+MOCK_MODEL_AVAILABLE: bool
+
+class VocalizeException(Exception): ...
+
+class VocalizeError:
+    @property
+    def message(self) -> str: ...
+    @property
+    def error_type(self) -> str: ...
+    @property
+    def is_retriable(self) -> bool: ...
+    @property
+    def is_user_error(self) -> bool: ...
+
+class Gender:
+    MALE: Gender
+    FEMALE: Gender
+    NEUTRAL: Gender
+    def __reduce__(self) -> Tuple[Any, Tuple[type, str]]: ...
+
+class VoiceStyle:
+    NATURAL: VoiceStyle
+    PROFESSIONAL: VoiceStyle
+    EXPRESSIVE: VoiceStyle
+    CALM: VoiceStyle
+    ENERGETIC: VoiceStyle
+    def __reduce__(self) -> Tuple[Any, Tuple[type, str]]: ...
+
+class Voice:
+    def __init__(
+        self, id: str, name: str, language: str, gender: Gender, style: VoiceStyle
+    ) -> None: ...
+    @staticmethod
+    def default() -> Voice: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def language(self) -> str: ...
+    @property
+    def gender(self) -> Gender: ...
+    @property
+    def style(self) -> VoiceStyle: ...
+    @property
+    def description(self) -> str: ...
+    @property
+    def sample_rate(self) -> int: ...
+    @property
+    def speed(self) -> float: ...
+    @property
+    def pitch(self) -> float: ...
+    @property
+    def model_id(self) -> Optional[str]: ...
+    def with_description(self, description: str) -> Voice: ...
+    def with_sample_rate(self, sample_rate: int) -> Voice: ...
+    def with_speed(self, speed: float) -> Voice: ...
+    def with_pitch(self, pitch: float) -> Voice: ...
+    def supports_language(self, language: str) -> bool: ...
+    def to_dict(self) -> Dict[str, str]: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __getnewargs__(self) -> Tuple[str, str, str, Gender, VoiceStyle]: ...
+    def __getstate__(self) -> str: ...
+    def __setstate__(self, state: str) -> None: ...
+
+class VoiceManager:
+    def __init__(self) -> None: ...
+    def get_available_voices(self) -> List[Voice]: ...
+    def get_voice(self, voice_id: str) -> Voice: ...
+    def get_default_voice(self) -> Voice: ...
+    def get_voices_by_gender(self, gender: Gender) -> List[Voice]: ...
+    def get_voices_by_style(self, style: VoiceStyle) -> List[Voice]: ...
+    def get_voices_by_language(self, language: str) -> List[Voice]: ...
+    def get_supported_languages(self) -> List[str]: ...
+    def is_voice_available(self, voice_id: str) -> bool: ...
+    def with_voices(self, voices: List[Voice]) -> VoiceManager: ...
+    def __len__(self) -> int: ...
+
+class SynthesisParams:
+    def __init__(self, voice: Voice) -> None: ...
+    @property
+    def voice(self) -> Voice: ...
+    @property
+    def speed(self) -> float: ...
+    @property
+    def pitch(self) -> float: ...
+    @property
+    def streaming(self) -> bool: ...
+    @property
+    def chunk_size(self) -> int: ...
+    @property
+    def output_sample_rate(self) -> Optional[int]: ...
+    @property
+    def time_stretch(self) -> Optional[float]: ...
+    def with_speed(self, speed: float) -> SynthesisParams: ...
+    def with_pitch(self, pitch: float) -> SynthesisParams: ...
+    def with_time_stretch(self, factor: float) -> SynthesisParams: ...
+    def with_output_sample_rate(self, sample_rate: int) -> SynthesisParams: ...
+    def with_streaming(self, chunk_size: int) -> SynthesisParams: ...
+    def without_streaming(self) -> SynthesisParams: ...
+    def to_dict(self) -> Dict[str, str]: ...
+
+class TtsEngine:
+    def __init__(
+        self, model_id: Optional[str] = None, model_path: Optional[_PathLike] = None
+    ) -> None: ...
+    def initialize(self) -> None: ...
+    def initialize_async(self) -> Awaitable[None]: ...
+    def synthesize_sync(self, text: str, params: SynthesisParams) -> List[float]: ...
+    def preview_voice(
+        self, voice_id: str, sample_text: Optional[str] = None
+    ) -> List[float]: ...
+    def speak(self, text: str, voice_id: Optional[str] = None) -> None: ...
+    def speak_stream(
+        self,
+        text: str,
+        voice_id: Optional[str] = None,
+        device: Optional[AudioDevice] = None,
+        on_chunk: Optional[Callable[[List[float]], Any]] = None,
+    ) -> int: ...
+    def synthesize_from_tokens(
+        self,
+        input_ids: Sequence[int],
+        style_vector: Sequence[float],
+        speed: float,
+        model_id: Optional[str] = None,
+        voice_id: Optional[str] = None,
+    ) -> List[float]: ...
+    def synthesize_from_token_arrays(
+        self,
+        input_ids: npt.NDArray[np.int64],
+        style_vector: npt.NDArray[np.float32],
+        speed: float,
+        model_id: Optional[str] = None,
+        voice_id: Optional[str] = None,
+    ) -> List[float]: ...
+    def get_model_io(
+        self, model_id: Optional[str] = None
+    ) -> Dict[str, List[Dict[str, Any]]]: ...
+    def self_test(self, model_id: Optional[str] = None) -> Dict[str, Any]: ...
+    def supported_voices(self) -> List[str]: ...
+    def all_available_voices(self) -> List[Voice]: ...
+    def get_default_model(self) -> str: ...
+    def set_default_model(self, model_id: str) -> None: ...
+    def output_sample_rate(self) -> int: ...
+    def set_pronunciation_overrides(self, overrides: Dict[str, str]) -> None: ...
+    def load_pronunciation_overrides(self, path: _PathLike) -> None: ...
+    def is_ready(self) -> bool: ...
+    def get_stats(self) -> Dict[str, str]: ...
+    def list_installed_models(self) -> List[Dict[str, str]]: ...
+
+class TtsSession:
+    def __init__(self, voice_id: str, model_id: Optional[str] = None) -> None: ...
+    @property
+    def voice(self) -> Voice: ...
+    @property
+    def speed(self) -> float: ...
+    @property
+    def params(self) -> SynthesisParams: ...
+    def say(self, text: str) -> List[float]: ...
+    def set_voice(self, voice_id: str) -> None: ...
+    def set_speed(self, speed: float) -> None: ...
+
+class AudioFormat:
+    WAV: AudioFormat
+    MP3: AudioFormat
+    FLAC: AudioFormat
+    OGG: AudioFormat
+    OPUS: AudioFormat
+    def extension(self) -> str: ...
+    def mime_type(self) -> str: ...
+    def is_lossy(self) -> bool: ...
+    def description(self) -> str: ...
+    def default_settings(
+        self, sample_rate: int = ..., channels: int = ...
+    ) -> EncodingSettings: ...
+    @staticmethod
+    def from_extension(extension: str) -> AudioFormat: ...
+    @staticmethod
+    def from_path(path: str) -> AudioFormat: ...
+    @staticmethod
+    def from_magic(data: bytes) -> Optional[AudioFormat]: ...
+    @staticmethod
+    def all() -> List[AudioFormat]: ...
+
+class EncodingSettings:
+    def __init__(self, sample_rate: int, channels: int) -> None: ...
+    @staticmethod
+    def default() -> EncodingSettings: ...
+    @property
+    def sample_rate(self) -> int: ...
+    @property
+    def channels(self) -> int: ...
+    @property
+    def bit_depth(self) -> int: ...
+    @property
+    def quality(self) -> Optional[float]: ...
+    @property
+    def variable_bitrate(self) -> bool: ...
+    @property
+    def strict_range(self) -> bool: ...
+    def with_strict_range(self, strict_range: bool = True) -> EncodingSettings: ...
+    def with_bit_depth(self, bit_depth: int) -> EncodingSettings: ...
+    def with_quality(self, quality: float) -> EncodingSettings: ...
+    def with_variable_bitrate(self) -> EncodingSettings: ...
+    def with_constant_bitrate(self) -> EncodingSettings: ...
+    def validate(self) -> None: ...
+    def to_dict(self) -> Dict[str, str]: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __getnewargs__(self) -> Tuple[int, int]: ...
+    def __getstate__(self) -> str: ...
+    def __setstate__(self, state: str) -> None: ...
+
+class AudioWriter:
+    def __init__(self) -> None: ...
+    def with_settings(self, _settings: EncodingSettings) -> AudioWriter: ...
+    def write_file(
+        self,
+        audio_data: List[float],
+        path: str,
+        format: AudioFormat,
+        settings: Optional[EncodingSettings] = None,
+    ) -> Awaitable[List[str]]: ...
+    def write_file_auto(
+        self,
+        audio_data: List[float],
+        path: str,
+        settings: Optional[EncodingSettings] = None,
+    ) -> Awaitable[None]: ...
+    def estimate_file_size(
+        self, audio_data: List[float], format: AudioFormat, settings: EncodingSettings
+    ) -> int: ...
+    def is_format_supported(self, format: AudioFormat) -> bool: ...
+    @staticmethod
+    def get_supported_formats() -> List[AudioFormat]: ...
+    def validate_inputs(
+        self, audio_data: List[float], _settings: EncodingSettings
+    ) -> None: ...
+
+class PlaybackState:
+    STOPPED: PlaybackState
+    PLAYING: PlaybackState
+    PAUSED: PlaybackState
+    ERROR: PlaybackState
+
+class AudioDeviceInfo:
+    @property
+    def id(self) -> str: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def channels(self) -> int: ...
+    @property
+    def sample_rates(self) -> List[int]: ...
+    @property
+    def is_default(self) -> bool: ...
+    def to_dict(self) -> Dict[str, str]: ...
+
+class AudioConfig:
+    def __init__(
+        self,
+        device_id: Optional[str] = None,
+        sample_rate: Optional[int] = None,
+        channels: Optional[int] = None,
+        buffer_size: Optional[int] = None,
+        latency_ms: Optional[int] = None,
+        completion_timeout_secs: Optional[float] = None,
+    ) -> None: ...
+    @staticmethod
+    def default() -> AudioConfig: ...
+    @property
+    def device_id(self) -> Optional[str]: ...
+    @property
+    def sample_rate(self) -> int: ...
+    @property
+    def channels(self) -> int: ...
+    @property
+    def buffer_size(self) -> int: ...
+    @property
+    def latency_ms(self) -> int: ...
+    @property
+    def completion_timeout_secs(self) -> float: ...
+
+class AudioDevice:
+    def __init__(self) -> None: ...
+    @staticmethod
+    def with_config(_config: AudioConfig) -> AudioDevice: ...
+    @staticmethod
+    def get_available_devices() -> List[AudioDeviceInfo]: ...
+    def play_sync(self, audio_data: List[float]) -> None: ...
+    def get_state(self) -> PlaybackState: ...
+    def is_playing(self) -> bool: ...
+    def is_paused(self) -> bool: ...
+    def is_stopped(self) -> bool: ...
+    def get_config(self) -> AudioConfig: ...
+    def get_device_info(self) -> Optional[str]: ...
+    def samples_played(self) -> int: ...
+
+class AudioStats:
+    @property
+    def rms(self) -> float: ...
+    @property
+    def peak(self) -> float: ...
+    @property
+    def dbfs(self) -> float: ...
+    @property
+    def clip_count(self) -> int: ...
+
+def synthesize_neural(
+    text: str,
+    voice_id: Optional[str] = None,
+    speed: Optional[float] = None,
+    pitch: Optional[float] = None,
+) -> List[float]: ...
+def synthesize_batch_neural(
+    texts: List[str],
+    voice_id: Optional[str] = None,
+    speed: Optional[float] = None,
+    model_id: Optional[str] = None,
+) -> List[npt.NDArray[np.float32]]: ...
+def synthesize_document_neural(
+    text: str,
+    voice_id: Optional[str] = None,
+    on_progress: Optional[Callable[[int, int], Any]] = None,
+    model_id: Optional[str] = None,
+) -> npt.NDArray[np.float32]: ...
+@overload
+def synthesize_from_tokens_neural(
+    input_ids: Sequence[int],
+    style_vector: Sequence[float],
+    speed: float,
+    model_id: Optional[str] = None,
+    return_timings: Literal[False] = False,
+    model_path: Optional[_PathLike] = None,
+) -> List[float]: ...
+@overload
+def synthesize_from_tokens_neural(
+    input_ids: Sequence[int],
+    style_vector: Sequence[float],
+    speed: float,
+    model_id: Optional[str] = None,
+    *,
+    return_timings: Literal[True],
+    model_path: Optional[_PathLike] = None,
+) -> Tuple[List[float], Dict[str, float]]: ...
+def list_neural_voices() -> List[Tuple[str, str, str, str]]: ...
+def save_audio_neural(
+    audio_data: List[float],
+    output_path: str,
+    format: Optional[str] = None,
+    max_samples: int = ...,
+    timeout_secs: float = 300.0,
+    sample_rate: Optional[int] = None,
+    target_sample_rate: Optional[int] = None,
+) -> None: ...
+def get_onnx_runtime_info() -> Dict[str, Any]: ...
+def discover_models() -> List[Dict[str, Any]]: ...
+def estimate_model_memory(model_id: str = "kokoro", pool_size: Optional[int] = None) -> int: ...
+def is_model_installed(model_id: str, cache_dir: Optional[Union[str, PathLike[str]]] = None) -> bool: ...
+def list_installed_models(cache_dir: Optional[Union[str, PathLike[str]]] = None) -> List[Dict[str, Any]]: ...
+def to_pcm16(audio_data: List[float]) -> bytes: ...
+def from_pcm16(data: bytes) -> List[float]: ...
+def rms(audio_data: List[float]) -> float: ...
+def peak(audio_data: List[float]) -> float: ...
+def analyze(audio_data: List[float]) -> AudioStats: ...
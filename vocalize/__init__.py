@@ -20,8 +20,9 @@ try:
     
     # Export main classes from Rust bindings
     from vocalize_rust import (
-        TtsEngine, SynthesisParams, Voice, VoiceManager, AudioWriter, AudioDevice,
-        VocalizeError, Gender, VoiceStyle
+        TtsEngine, TtsSession, SynthesisParams, Voice, VoiceManager, AudioWriter, AudioDevice,
+        VocalizeError, Gender, VoiceStyle, to_pcm16, from_pcm16,
+        AudioStats, rms, peak, analyze
     )
     
 except ImportError:
@@ -106,6 +107,33 @@ except ImportError:
         async def is_ready(self):
             return True
     
+    class TtsSession:
+        """Mock TtsSession class."""
+        def __init__(self, voice_id: str, model_id=None):
+            self.voice_id = voice_id
+            self.speed = 1.0
+        
+        def say(self, text: str):
+            audio_data = VocalizeComponents.synthesize_text(text, self.voice_id, self.speed, 0.0)
+            return audio_data.samples
+        
+        def set_voice(self, voice_id: str):
+            self.voice_id = voice_id
+        
+        def set_speed(self, speed: float):
+            self.speed = speed
+        
+        @property
+        def voice(self):
+            for v in VocalizeComponents.list_voices():
+                if v.id == self.voice_id:
+                    return Voice(v.id, v.name, v.language, v.gender, v.style)
+            return Voice(self.voice_id, self.voice_id, "en-US", "neutral", "natural")
+        
+        @property
+        def params(self):
+            return SynthesisParams(self.voice).with_speed(self.speed)
+    
     class VoiceManager:
         """Mock VoiceManager class."""
         def __init__(self):
@@ -140,6 +168,47 @@ except ImportError:
         CALM = "calm"
         ENERGETIC = "energetic"
 
+    def to_pcm16(audio_data) -> bytes:
+        """Convert float samples to little-endian signed 16-bit PCM bytes."""
+        import struct
+        samples = [int(max(-1.0, min(1.0, s)) * 32767) if s == s else 0 for s in audio_data]
+        return struct.pack(f"<{len(samples)}h", *samples)
+
+    def from_pcm16(data: bytes) -> list:
+        """Convert little-endian signed 16-bit PCM bytes to float samples."""
+        import struct
+        if len(data) % 2:
+            raise VocalizeError(f"PCM16 data must have an even number of bytes, got {len(data)}")
+        samples = struct.unpack(f"<{len(data) // 2}h", data)
+        return [max(-1.0, s / 32767) for s in samples]
+
+    class AudioStats:
+        """Level statistics for a block of audio."""
+        def __init__(self, rms: float, peak: float, dbfs: float, clip_count: int):
+            self.rms = rms
+            self.peak = peak
+            self.dbfs = dbfs
+            self.clip_count = clip_count
+
+    def rms(audio_data) -> float:
+        """Root-mean-square level of float samples."""
+        import math
+        if not audio_data:
+            return 0.0
+        return math.sqrt(sum(s * s for s in audio_data) / len(audio_data))
+
+    def peak(audio_data) -> float:
+        """Largest absolute value among float samples."""
+        return max((abs(s) for s in audio_data), default=0.0)
+
+    def analyze(audio_data) -> AudioStats:
+        """RMS, peak, RMS level in dBFS and clipped sample count for float samples."""
+        import math
+        level = rms(audio_data)
+        dbfs = 20 * math.log10(level) if level > 0 else float("-inf")
+        clip_count = sum(1 for s in audio_data if abs(s) >= 1.0)
+        return AudioStats(level, peak(audio_data), dbfs, clip_count)
+
 # Constants
 DEFAULT_SAMPLE_RATE = 24000
 DEFAULT_CHANNELS = 1
@@ -155,6 +224,7 @@ __all__ = [
     "MAX_TEXT_LENGTH",
     # Core classes
     "TtsEngine",
+    "TtsSession",
     "SynthesisParams", 
     "Voice",
     "VoiceManager",
@@ -163,4 +233,12 @@ __all__ = [
     "VocalizeError",
     "Gender",
     "VoiceStyle",
+    # Sample conversion
+    "to_pcm16",
+    "from_pcm16",
+    # Level metering
+    "AudioStats",
+    "rms",
+    "peak",
+    "analyze",
 ]
\ No newline at end of file
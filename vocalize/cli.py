@@ -189,8 +189,13 @@ class VocalizeComponents:
             print(f"Error playing audio: {e}")
 
 
-def synthesize_with_tokens(text: str, voice: str, speed: float, pitch: float, model: str) -> 'VocalizeComponents.AudioData':
-    """Synthesize using token-based approach for better compatibility."""
+def synthesize_with_tokens(text: str, voice: str, speed: float, pitch: float, model: str,
+                           pronunciation_overrides: Optional[Dict[str, str]] = None) -> 'VocalizeComponents.AudioData':
+    """Synthesize using token-based approach for better compatibility.
+
+    ``pronunciation_overrides`` (word -> phonemes) are applied when the Kokoro
+    phoneme processor tokenizes the text.
+    """
     verbose = _verbose  # Use global verbose flag
     try:
         print(f"🎙️  Starting phoneme-based synthesis - text: '{text}', voice: {voice}")
@@ -204,7 +209,7 @@ def synthesize_with_tokens(text: str, voice: str, speed: float, pitch: float, mo
             # Use cross-platform cache directory that matches Rust implementation
             cache_base = platformdirs.user_cache_dir("vocalize", "Vocalize")
             cache_dir = Path(cache_base) / "models" / "models--direct_download" / "local"
-            processor = KokoroPhonemeProcessor(cache_dir)
+            processor = KokoroPhonemeProcessor(cache_dir, pronunciation_overrides)
             
             # Process text to tokens with proper speed
             result = processor.process_text(text, voice)
@@ -223,12 +228,16 @@ def synthesize_with_tokens(text: str, voice: str, speed: float, pitch: float, mo
             print(f"DEBUG: style vector range: [{min(result['style']):.3f}, {max(result['style']):.3f}]")
             print(f"DEBUG: speed: {result['speed']}")
             
-            samples = vocalize_rust.synthesize_from_tokens_neural(
+            samples, timings = vocalize_rust.synthesize_from_tokens_neural(
                 result['input_ids'],
                 result['style'],
                 result['speed'],
-                model
+                model,
+                return_timings=True
             )
+            if verbose:
+                for stage in ("validation", "engine_init", "inference", "total"):
+                    print(f"  ⏱️  Token synthesis {stage}: {timings[stage]:.3f}s")
             print(f"✅ Got {len(samples)} audio samples from token synthesis")
             
             return VocalizeComponents.AudioData(samples)
@@ -236,6 +245,9 @@ def synthesize_with_tokens(text: str, voice: str, speed: float, pitch: float, mo
             # Fall back to the original synthesis for non-Kokoro models
             return VocalizeComponents.synthesize_text(text, voice, speed, pitch)
             
+    except ValueError:
+        # Phonemes the model can't tokenize; falling back would silently drop the overrides
+        raise
     except Exception as e:
         print(f"❌ Token synthesis failed: {e}")
         # Fall back to original synthesis
@@ -255,6 +267,15 @@ def handle_speak_command(args):
     play = args.play
     format = args.format or DEFAULT_FORMAT
     
+    pronunciation_overrides = None
+    if args.pronunciations:
+        from .model_manager import read_pronunciation_file
+        try:
+            pronunciation_overrides = read_pronunciation_file(Path(args.pronunciations))
+        except (OSError, ValueError) as e:
+            print(f"❌ Failed to load pronunciations: {e}")
+            return
+    
     # Import VoiceManager for voice selection
     with Timer("Import VoiceManager", verbose):
         from .voice_manager import VoiceManager
@@ -289,7 +310,11 @@ def handle_speak_command(args):
     
     # Use token-based synthesis for better compatibility
     with Timer("Speech synthesis", verbose):
-        audio_data = synthesize_with_tokens(text, voice, speed, pitch, model)
+        try:
+            audio_data = synthesize_with_tokens(text, voice, speed, pitch, model, pronunciation_overrides)
+        except ValueError as e:
+            print(f"❌ Synthesis failed: {e}")
+            return
     
     # Save to file if requested
     if output:
@@ -521,7 +546,8 @@ Examples:
     speak_parser.add_argument("--speed", "-s", type=float, help="Speech speed (0.1-3.0)")
     speak_parser.add_argument("--pitch", "-p", type=float, help="Pitch adjustment (-1.0 to 1.0)")
     speak_parser.add_argument("--output", "-o", help="Output file path")
-    speak_parser.add_argument("--format", "-f", choices=["wav", "mp3", "flac", "ogg"], 
+    speak_parser.add_argument("--pronunciations", help="Pronunciation dictionary file ('word phonemes' per line)")
+    speak_parser.add_argument("--format", "-f", choices=["wav", "mp3", "flac", "ogg", "opus"], 
                             help="Output format")
     speak_parser.add_argument("--play", action="store_true", 
                             help="Play audio through speakers")
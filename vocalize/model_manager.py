@@ -6,7 +6,9 @@ huggingface_hub client, eliminating the unreliable Rust hf-hub hanging issues.
 """
 
 import os
+import re
 import json
+import time
 from pathlib import Path
 from typing import Dict, List, Optional, Tuple
 from dataclasses import dataclass
@@ -57,6 +59,36 @@ def _import_requests():
         return None
 
 
+OFFLINE_ENV_VAR = "VOCALIZE_OFFLINE"
+
+
+# Timeouts for direct model downloads, in seconds. The connect and read
+# timeouts bound each network operation; the deadline bounds the whole file
+# so a connection trickling bytes cannot stall a download forever.
+DOWNLOAD_CONNECT_TIMEOUT = 30
+DOWNLOAD_READ_TIMEOUT = 60
+DOWNLOAD_DEADLINE = 30 * 60
+
+# Model files are streamed to disk in chunks of this many bytes, so peak
+# memory stays flat no matter how large the file is
+DOWNLOAD_CHUNK_SIZE = 64 * 1024
+
+# 2025 working Kokoro model URLs
+KOKORO_2025_URLS = {
+    "kokoro-v1.0.onnx": "https://github.com/thewh1teagle/kokoro-onnx/releases/download/model-files-v1.0/kokoro-v1.0.onnx",
+    "voices-v1.0.bin": "https://github.com/thewh1teagle/kokoro-onnx/releases/download/model-files-v1.0/voices-v1.0.bin",
+}
+
+
+class DownloadTimeoutError(Exception):
+    """A model download exceeded its deadline."""
+
+
+def is_offline() -> bool:
+    """Whether offline mode is enabled via the VOCALIZE_OFFLINE environment variable."""
+    return os.environ.get(OFFLINE_ENV_VAR, "").strip().lower() in ("1", "true", "yes", "on")
+
+
 def _import_shutil():
     """Lazy import of shutil for faster CLI startup."""
     import shutil
@@ -192,6 +224,10 @@ class ModelManager:
             print(f"✓ Model '{model_id}' already cached")
             return True
         
+        if is_offline():
+            print(f"❌ {self._offline_message(model_id)}")
+            return False
+
         print(f"📥 Downloading {model_info.name} ({model_info.size_mb}MB)")
         
         try:
@@ -232,6 +268,16 @@ class ModelManager:
             print(f"❌ Failed to download {model_info.name}: {e}")
             return False
     
+    def _offline_message(self, model_id: str) -> str:
+        """Explain how to install a model by hand while offline mode is enabled."""
+        model_info = self.MODELS[model_id]
+        model_dir = self.cache_dir / f"models--{model_info.repo_id.replace('/', '--')}" / "local"
+        return (
+            f"Offline mode is enabled ({OFFLINE_ENV_VAR}), so model '{model_id}' will not be "
+            f"downloaded. Install it manually by copying {', '.join(model_info.files)} "
+            f"into {model_dir}, or unset {OFFLINE_ENV_VAR}."
+        )
+
     def _download_kokoro_2025(self, force: bool = False) -> bool:
         """Download 2025 Kokoro model files directly from GitHub releases."""
         requests = _import_requests()
@@ -239,17 +285,11 @@ class ModelManager:
             print("Error: requests not available. Install with: uv add requests")
             return False
         
-        # 2025 working model URLs
-        model_urls = {
-            "kokoro-v1.0.onnx": "https://github.com/thewh1teagle/kokoro-onnx/releases/download/model-files-v1.0/kokoro-v1.0.onnx",
-            "voices-v1.0.bin": "https://github.com/thewh1teagle/kokoro-onnx/releases/download/model-files-v1.0/voices-v1.0.bin"
-        }
-        
         # Create local directory
         model_local_dir = self.cache_dir / "models--direct_download" / "local"
         model_local_dir.mkdir(parents=True, exist_ok=True)
         
-        for filename, url in model_urls.items():
+        for filename, url in KOKORO_2025_URLS.items():
             local_file = model_local_dir / filename
             
             # Skip if exists and not forcing
@@ -260,21 +300,45 @@ class ModelManager:
             print(f"  📄 Downloading {filename} from GitHub releases...")
             
             try:
-                response = requests.get(url, stream=True)
-                response.raise_for_status()
-                
-                with open(local_file, 'wb') as f:
-                    for chunk in response.iter_content(chunk_size=8192):
-                        f.write(chunk)
-                
+                self._download_file(requests, url, local_file)
                 print(f"  ✓ Downloaded {filename} ({local_file.stat().st_size // (1024*1024)}MB)")
                 
+            except (requests.Timeout, DownloadTimeoutError) as e:
+                print(f"  ❌ Timed out downloading {filename}: {e}")
+                return False
             except Exception as e:
                 print(f"  ❌ Failed to download {filename}: {e}")
                 return False
         
         print("✅ Successfully downloaded 2025 Kokoro model files")
         return True
+
+    def _download_file(self, requests, url: str, local_file: Path) -> None:
+        """Stream ``url`` into ``local_file``, enforcing the download timeouts.
+
+        Raises:
+            DownloadTimeoutError: If the download takes longer than DOWNLOAD_DEADLINE
+            requests.RequestException: On connection errors, read timeouts and HTTP errors
+        """
+        deadline = time.monotonic() + DOWNLOAD_DEADLINE
+        response = requests.get(
+            url, stream=True, timeout=(DOWNLOAD_CONNECT_TIMEOUT, DOWNLOAD_READ_TIMEOUT)
+        )
+        try:
+            response.raise_for_status()
+            with open(local_file, 'wb') as f:
+                for chunk in response.iter_content(chunk_size=DOWNLOAD_CHUNK_SIZE):
+                    if time.monotonic() > deadline:
+                        raise DownloadTimeoutError(
+                            f"download of {url} did not finish within {DOWNLOAD_DEADLINE}s"
+                        )
+                    f.write(chunk)
+        except BaseException:
+            # Never leave a truncated file behind for is_model_cached to find
+            local_file.unlink(missing_ok=True)
+            raise
+        finally:
+            response.close()
     
     def list_available_models(self) -> List[str]:
         """List all available models."""
@@ -351,14 +415,17 @@ class ModelManager:
         Returns:
             True if download successful, False otherwise
         """
+        if model_id not in self.MODELS:
+            print(f"Error: Unknown model '{model_id}'. Available: {list(self.MODELS.keys())}")
+            return False
+        if is_offline():
+            print(f"❌ {self._offline_message(model_id)}")
+            return False
+
         hf_hub_download, HfApi = _import_huggingface_hub()
         if not hf_hub_download:
             print("Error: huggingface_hub not available. Install with: uv add huggingface-hub")
             return False
-            
-        if model_id not in self.MODELS:
-            print(f"Error: Unknown model '{model_id}'. Available: {list(self.MODELS.keys())}")
-            return False
         
         model_info = self.MODELS[model_id]
         
@@ -470,14 +537,35 @@ def ensure_model_available(model_id: str, cache_dir: Optional[str] = None) -> bo
     return manager.download_model(model_id)
 
 
+def read_pronunciation_file(path: Path) -> Dict[str, str]:
+    """Read a file of `word phonemes` lines (`#` starts a comment)."""
+    overrides = {}
+    for number, line in enumerate(Path(path).read_text(encoding="utf-8").splitlines(), 1):
+        line = line.strip()
+        if not line or line.startswith("#"):
+            continue
+        parts = line.split(None, 1)
+        if len(parts) != 2:
+            raise ValueError(f"{path}:{number}: expected 'word phonemes'")
+        overrides[parts[0]] = parts[1]
+    return overrides
+
+
 class KokoroPhonemeProcessor:
     """Handles text-to-phoneme conversion and tokenization for Kokoro TTS."""
+
+    # Inline pronunciation markup, e.g. "[Vocalize](/vˈoʊkəlaɪz/)"
+    PRONUNCIATION_MARKUP = re.compile(r"\[([^\]]+)\]\(/([^/]+)/\)")
+    _WORD = re.compile(r"[\w'-]+")
     
-    def __init__(self, model_dir: Path):
+    def __init__(self, model_dir: Path, pronunciation_overrides: Optional[Dict[str, str]] = None):
         self.model_dir = model_dir
         self.tokenizer = None
         self.phoneme_config = None
         self.voices = None
+        self.pronunciation_overrides: Dict[str, str] = {}
+        if pronunciation_overrides:
+            self.set_pronunciation_overrides(pronunciation_overrides)
         self._load_phoneme_config()
         self._load_voices()
         
@@ -520,6 +608,70 @@ class KokoroPhonemeProcessor:
             print("⚠️  ttstokenizer not available. Install with: pip install ttstokenizer")
             return False
     
+    def set_pronunciation_overrides(self, overrides: Dict[str, str]) -> None:
+        """Replace the word -> phonemes overrides (words match case-insensitively)."""
+        self.pronunciation_overrides = {
+            word.lower(): phonemes.strip() for word, phonemes in overrides.items()
+        }
+
+    def load_pronunciation_overrides(self, path: Path) -> None:
+        """Load overrides from a pronunciation dictionary file."""
+        self.set_pronunciation_overrides(read_pronunciation_file(path))
+
+    def _split_pronunciations(self, text: str) -> List[Tuple[str, bool]]:
+        """
+        Split text into (segment, is_phonemes) pairs.
+
+        Inline markup and overridden words become phoneme segments that bypass
+        grapheme-to-phoneme conversion; everything else is plain text.
+        """
+        segments = []
+
+        def add_text(chunk: str):
+            last = 0
+            for match in self._WORD.finditer(chunk):
+                phonemes = self.pronunciation_overrides.get(match.group().lower())
+                if phonemes:
+                    segments.append((chunk[last:match.start()], False))
+                    segments.append((phonemes, True))
+                    last = match.end()
+            segments.append((chunk[last:], False))
+
+        last = 0
+        for match in self.PRONUNCIATION_MARKUP.finditer(text):
+            add_text(text[last:match.start()])
+            segments.append((match.group(2), True))
+            last = match.end()
+        add_text(text[last:])
+
+        return [(segment, is_phonemes) for segment, is_phonemes in segments if segment]
+
+    def _tokenize_phonemes(self, phonemes: str) -> List[int]:
+        """Map phoneme characters straight to token IDs."""
+        vocab = (self.phoneme_config or {}).get("vocab")
+        if not vocab:
+            raise ValueError(
+                f"Cannot tokenize phonemes '{phonemes}': no phoneme vocabulary is loaded "
+                f"(expected 'vocab' in {self.model_dir / 'phoneme_config.json'})"
+            )
+        unknown = sorted({c for c in phonemes if c not in vocab})
+        if unknown:
+            raise ValueError(
+                f"Cannot tokenize phonemes '{phonemes}': "
+                f"{', '.join(repr(c) for c in unknown)} not in the phoneme vocabulary"
+            )
+        return [vocab[c] for c in phonemes]
+
+    def _tokenize_segments(self, text: str, tokenize_text) -> List[int]:
+        """Tokenize text, honouring pronunciation overrides."""
+        tokens = []
+        for segment, is_phonemes in self._split_pronunciations(text):
+            if is_phonemes:
+                tokens.extend(self._tokenize_phonemes(segment))
+            else:
+                tokens.extend(tokenize_text(segment))
+        return tokens
+
     def process_text(self, text: str, voice_id: str = "af_alloy") -> dict:
         """
         Convert text to tokens ready for Kokoro ONNX inference.
@@ -532,7 +684,9 @@ class KokoroPhonemeProcessor:
             if not self.setup_tokenizer():
                 print("⚠️  Using mock tokenization for testing (ttstokenizer not available)")
                 # Mock tokenization: convert text to character-based tokens
-                char_tokens = [ord(c) % 256 for c in text.lower()]  # Simple char-to-token mapping
+                char_tokens = self._tokenize_segments(
+                    text, lambda segment: [ord(c) % 256 for c in segment.lower()]
+                )  # Simple char-to-token mapping
                 input_ids = [0] + char_tokens[:510] + [0]  # Add padding, ensure max 512
                 
                 # Load real voice embedding or fall back to random
@@ -546,15 +700,13 @@ class KokoroPhonemeProcessor:
                 }
         
         # Real tokenization with ttstokenizer
-        tokens = self.tokenizer(text)
-        # ttstokenizer returns numpy array of token IDs
-        if hasattr(tokens, 'tolist'):
-            # Convert numpy array to list and add padding
-            token_list = tokens.tolist()[:510]  # Limit length
-            input_ids = [0] + token_list + [0]  # Add padding tokens
-        else:
-            # Fallback if unexpected format
-            input_ids = [0] + list(tokens)[:510] + [0]
+        def tokenize_text(segment: str) -> List[int]:
+            tokens = self.tokenizer(segment)
+            # ttstokenizer returns numpy array of token IDs
+            return tokens.tolist() if hasattr(tokens, 'tolist') else list(tokens)
+
+        token_list = self._tokenize_segments(text, tokenize_text)[:510]  # Limit length
+        input_ids = [0] + token_list + [0]  # Add padding tokens
         
         # Ensure max length constraint
         if len(input_ids) > 512:
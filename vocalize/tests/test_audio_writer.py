@@ -2,8 +2,10 @@
 
 import pytest
 import asyncio
+import math
 import tempfile
 import os
+import pickle
 from pathlib import Path
 from typing import List
 
@@ -15,6 +17,11 @@ from vocalize import (
     SynthesisParams,
     Voice,
     VocalizeError,
+    to_pcm16,
+    from_pcm16,
+    analyze,
+    rms,
+    peak,
 )
 
 
@@ -28,6 +35,16 @@ class TestAudioFormat:
         assert hasattr(AudioFormat, 'FLAC')
         assert hasattr(AudioFormat, 'OGG')
         
+    def test_audio_format_default_settings(self):
+        """Test lossy formats default to a bitrate and PCM to a bit depth."""
+        mp3 = AudioFormat.MP3.default_settings(44100, 2)
+        assert mp3.quality == 128.0
+        assert (mp3.sample_rate, mp3.channels) == (44100, 2)
+
+        wav = AudioFormat.WAV.default_settings()
+        assert wav.bit_depth == 16
+        assert wav.quality is None
+
     def test_audio_format_properties(self):
         """Test AudioFormat properties."""
         assert AudioFormat.WAV.extension() == "wav"
@@ -87,11 +104,12 @@ class TestAudioFormat:
         formats = AudioFormat.all()
         
         assert isinstance(formats, list)
-        assert len(formats) == 4
+        assert len(formats) == 5
         assert AudioFormat.WAV in formats
         assert AudioFormat.MP3 in formats
         assert AudioFormat.FLAC in formats
         assert AudioFormat.OGG in formats
+        assert AudioFormat.OPUS in formats
         
     def test_audio_format_str_repr(self):
         """Test AudioFormat string representations."""
@@ -199,6 +217,20 @@ class TestEncodingSettings:
         assert data["quality"] == "0.9"
         assert data["variable_bitrate"] == "false"
 
+    def test_encoding_settings_pickle_round_trip(self):
+        """Test encoding settings survive pickling with every field intact."""
+        settings = (EncodingSettings(48000, 2)
+                   .with_bit_depth(24)
+                   .with_quality(0.9)
+                   .with_variable_bitrate())
+
+        restored = pickle.loads(pickle.dumps(settings))
+
+        assert restored == settings
+        assert restored.bit_depth == 24
+        assert restored.quality == pytest.approx(0.9)
+        assert restored.variable_bitrate
+
 
 class TestAudioWriter:
     """Test AudioWriter class."""
@@ -379,6 +411,32 @@ class TestAudioWriterFileOperations:
                 os.unlink(tmp.name)
 
 
+class TestOutOfRangePolicy:
+    """Test reporting or rejecting samples outside [-1.0, 1.0]."""
+
+    @pytest.mark.asyncio
+    async def test_clipping_reported_as_warning(self, tmp_path):
+        """Test out-of-range samples are written and reported by default."""
+        path = tmp_path / "clipped.wav"
+        warnings = await AudioWriter().write_file([0.5, 1.5, -2.0], str(path), AudioFormat.WAV)
+
+        assert len(warnings) == 1
+        assert "2 samples exceed range" in warnings[0]
+        assert path.exists()
+
+    @pytest.mark.asyncio
+    async def test_strict_range_rejects_clipping(self, tmp_path):
+        """Test strict settings refuse out-of-range samples."""
+        path = tmp_path / "strict.wav"
+        settings = EncodingSettings.default().with_strict_range()
+        assert settings.strict_range
+
+        with pytest.raises(Exception, match="out of range"):
+            await AudioWriter().write_file([0.5, 1.5], str(path), AudioFormat.WAV, settings)
+        assert not path.exists()
+        assert await AudioWriter().write_file([0.5, -1.0], str(path), AudioFormat.WAV, settings) == []
+
+
 class TestAudioWriterIntegration:
     """Integration tests for audio writer with TTS engine."""
     
@@ -521,5 +579,125 @@ class TestAudioWriterErrorHandling:
                     os.unlink(tmp.name)
 
 
+
+class TestPcm16Conversion:
+    """Test float <-> 16-bit PCM conversion."""
+
+    def test_round_trip_within_quantization_error(self):
+        """Test f32 -> i16 -> f32 stays within one quantization step."""
+        audio = [0.0, 0.25, -0.5, 0.999, -1.0, 1.0]
+        pcm = to_pcm16(audio)
+
+        assert isinstance(pcm, bytes)
+        assert len(pcm) == 2 * len(audio)
+
+        round_trip = from_pcm16(pcm)
+        assert len(round_trip) == len(audio)
+        for original, converted in zip(audio, round_trip):
+            assert abs(original - converted) <= 1 / 32767
+
+    def test_clamps_out_of_range(self):
+        """Test samples outside [-1, 1] are clamped."""
+        assert to_pcm16([2.0, -2.0]) == (32767).to_bytes(2, "little", signed=True) + (-32767).to_bytes(
+            2, "little", signed=True
+        )
+
+    def test_odd_length_rejected(self):
+        """Test PCM data must contain whole samples."""
+        with pytest.raises(VocalizeError):
+            from_pcm16(b"\x00\x01\x02")
+
+
+class TestSaveAudioNeural:
+    """Test the size and time guards on save_audio_neural."""
+
+    def test_oversized_buffer_rejected(self, tmp_path):
+        """Test a buffer over the sample limit fails before anything is written."""
+        from vocalize import vocalize_rust
+
+        output = tmp_path / "too_long.wav"
+        with pytest.raises(VocalizeError, match="11 samples, which exceeds the limit of 10"):
+            vocalize_rust.save_audio_neural([0.1] * 11, str(output), "wav", max_samples=10)
+        assert not output.exists()
+
+        vocalize_rust.save_audio_neural([0.1] * 10, str(output), "wav", max_samples=10)
+        assert output.exists()
+
+    def test_default_limit_exposed(self):
+        """Test the default sample limit is available to callers."""
+        from vocalize import vocalize_rust
+
+        assert vocalize_rust.DEFAULT_MAX_SAVE_SAMPLES >= 60 * 60 * 24000
+
+    def test_invalid_timeout_rejected(self, tmp_path):
+        """Test a non-positive write timeout is refused."""
+        from vocalize import vocalize_rust
+
+        with pytest.raises(VocalizeError, match="timeout_secs must be positive"):
+            vocalize_rust.save_audio_neural([0.1], str(tmp_path / "out.wav"), "wav", timeout_secs=0)
+
+    def test_slow_write_times_out(self, tmp_path):
+        """Test a write that outlasts the timeout fails and leaves no file behind."""
+        from vocalize import vocalize_rust
+
+        output = tmp_path / "slow.wav"
+        with pytest.raises(VocalizeError, match="Timed out after"):
+            vocalize_rust.save_audio_neural([0.1] * 4_000_000, str(output), "wav", timeout_secs=1e-9)
+        assert not output.exists()
+
+    def test_resampled_output(self, tmp_path):
+        """Test 24 kHz audio can be written as a 16 kHz file."""
+        import wave
+        from vocalize import vocalize_rust
+
+        audio = [0.5 * math.sin(2 * math.pi * 440 * i / 24000) for i in range(24000)]
+        output = tmp_path / "resampled.wav"
+        vocalize_rust.save_audio_neural(
+            audio, str(output), "wav", sample_rate=24000, target_sample_rate=16000
+        )
+
+        with wave.open(str(output), "rb") as wav:
+            assert wav.getframerate() == 16000
+            assert abs(wav.getnframes() - 16000) <= 32
+
+    def test_invalid_sample_rate_rejected(self, tmp_path):
+        """Test a zero sample rate is refused."""
+        from vocalize import vocalize_rust
+
+        with pytest.raises(VocalizeError, match="Sample rates must be positive"):
+            vocalize_rust.save_audio_neural([0.1], str(tmp_path / "out.wav"), "wav", target_sample_rate=0)
+
+
+class TestLevelMetering:
+    """Test RMS/peak level statistics."""
+
+    def test_sine_rms_is_amplitude_over_root_two(self):
+        """Test a whole number of sine periods has RMS of amplitude / sqrt(2)."""
+        amplitude = 0.8
+        audio = [amplitude * math.sin(2 * math.pi * 240 * i / 24000) for i in range(10000)]
+
+        stats = analyze(audio)
+        assert abs(stats.rms - amplitude / math.sqrt(2)) < 1e-4
+        assert abs(stats.peak - amplitude) < 1e-4
+        assert abs(stats.dbfs - 20 * math.log10(stats.rms)) < 1e-4
+        assert stats.clip_count == 0
+        assert rms(audio) == pytest.approx(stats.rms)
+        assert peak(audio) == pytest.approx(stats.peak)
+
+    def test_clip_count(self):
+        """Test samples at or beyond full scale are counted as clipped."""
+        stats = analyze([0.5, 1.0, -1.0, 1.5, -0.999, -3.0])
+        assert stats.clip_count == 4
+        assert stats.peak == 3.0
+
+    def test_silence(self):
+        """Test empty audio reports zero level and -inf dBFS."""
+        stats = analyze([])
+        assert stats.rms == 0.0
+        assert stats.peak == 0.0
+        assert stats.dbfs == float("-inf")
+        assert stats.clip_count == 0
+
+
 if __name__ == "__main__":
     pytest.main([__file__])
\ No newline at end of file
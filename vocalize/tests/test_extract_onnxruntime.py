@@ -0,0 +1,148 @@
+"""Tests for the ONNX Runtime archive extraction script used by build.rs."""
+
+import importlib.util
+import io
+import tarfile
+import zipfile
+from pathlib import Path
+
+import pytest
+
+SCRIPT = Path(__file__).resolve().parents[2] / "crates" / "vocalize-rust" / "extract_onnxruntime.py"
+
+
+@pytest.fixture(scope="module")
+def extractor():
+    """The extraction script loaded as a module."""
+    spec = importlib.util.spec_from_file_location("extract_onnxruntime", SCRIPT)
+    module = importlib.util.module_from_spec(spec)
+    spec.loader.exec_module(module)
+    return module
+
+
+def write_tgz(path, entries):
+    """Write a .tgz containing ``(TarInfo, data)`` entries."""
+    with tarfile.open(path, "w:gz") as archive:
+        for info, data in entries:
+            info.size = len(data)
+            archive.addfile(info, io.BytesIO(data))
+
+
+class TestSafeExtract:
+    """Test that archive entries cannot escape the destination."""
+
+    def test_release_layout_is_flattened(self, extractor, tmp_path):
+        """Test a normal release archive extracts and moves lib/ up."""
+        archive = tmp_path / "onnxruntime.tgz"
+        write_tgz(archive, [
+            (tarfile.TarInfo("onnxruntime-linux-x64-1.22.0/lib/libonnxruntime.so"), b"lib"),
+        ])
+        dest = tmp_path / "out"
+        dest.mkdir()
+
+        assert extractor.main(["extract", str(archive), str(dest)]) == 0
+        assert (dest / "lib" / "libonnxruntime.so").read_bytes() == b"lib"
+
+    def test_tar_traversal_rejected(self, extractor, tmp_path):
+        """Test a ../ entry in a tarball is refused before anything is written."""
+        archive = tmp_path / "onnxruntime.tgz"
+        write_tgz(archive, [
+            (tarfile.TarInfo("onnxruntime/lib/ok.so"), b"ok"),
+            (tarfile.TarInfo("../evil.txt"), b"pwned"),
+        ])
+        dest = tmp_path / "out"
+        dest.mkdir()
+
+        with pytest.raises(extractor.UnsafeArchiveError):
+            extractor.safe_extract(str(archive), str(dest))
+        assert not (tmp_path / "evil.txt").exists()
+        assert not (dest / "onnxruntime").exists()
+        assert extractor.main(["extract", str(archive), str(dest)]) == 1
+
+    def test_tar_symlink_escape_rejected(self, extractor, tmp_path):
+        """Test a symlink pointing outside the destination is refused."""
+        link = tarfile.TarInfo("onnxruntime/lib")
+        link.type = tarfile.SYMTYPE
+        link.linkname = "../../outside"
+        archive = tmp_path / "onnxruntime.tgz"
+        write_tgz(archive, [(link, b"")])
+        dest = tmp_path / "out"
+        dest.mkdir()
+
+        with pytest.raises(extractor.UnsafeArchiveError):
+            extractor.safe_extract(str(archive), str(dest))
+
+    @pytest.mark.parametrize("data_filter", [True, False])
+    def test_tar_chained_symlink_escape_rejected(self, extractor, tmp_path, monkeypatch, data_filter):
+        """Test links that only escape when followed through each other are refused."""
+        if not data_filter:
+            monkeypatch.delattr(extractor.tarfile, "data_filter", raising=False)
+        elif not hasattr(tarfile, "data_filter"):
+            pytest.skip("tarfile extraction filters not available")
+        # "a/b/c" is a link to "..", so "a/b/c/up" -> "../.." lands outside
+        # "out" even though each link looks harmless on its own
+        deep = tarfile.TarInfo("a/b/c")
+        deep.type = tarfile.SYMTYPE
+        deep.linkname = ".."
+        up = tarfile.TarInfo("a/b/c/up")
+        up.type = tarfile.SYMTYPE
+        up.linkname = "../.."
+        escape = tarfile.TarInfo("a/b/c/up/evil.txt")
+        archive = tmp_path / "onnxruntime.tgz"
+        write_tgz(archive, [(deep, b""), (up, b""), (escape, b"pwned")])
+        dest = tmp_path / "out"
+        dest.mkdir()
+
+        assert extractor.main(["extract", str(archive), str(dest)]) == 1
+        assert not (tmp_path / "evil.txt").exists()
+        assert list(dest.iterdir()) == []
+
+    def test_zip_traversal_rejected(self, extractor, tmp_path):
+        """Test a ../ entry in a zip is refused before anything is written."""
+        archive = tmp_path / "onnxruntime.zip"
+        with zipfile.ZipFile(archive, "w") as zf:
+            zf.writestr("onnxruntime/lib/onnxruntime.dll", b"dll")
+            zf.writestr("../evil.txt", b"pwned")
+        dest = tmp_path / "out"
+        dest.mkdir()
+
+        with pytest.raises(extractor.UnsafeArchiveError):
+            extractor.safe_extract(str(archive), str(dest))
+        assert not (tmp_path / "evil.txt").exists()
+        assert not (dest / "onnxruntime").exists()
+
+
+class TestExtractionFailure:
+    """Test that a failed extraction leaves nothing behind."""
+
+    def test_partial_extraction_removed(self, extractor, tmp_path, monkeypatch):
+        """Test files written before a mid-extraction failure are removed."""
+        archive = tmp_path / "onnxruntime.tgz"
+        write_tgz(archive, [
+            (tarfile.TarInfo("onnxruntime-linux-x64-1.22.0/lib/libonnxruntime.so"), b"lib"),
+            (tarfile.TarInfo("onnxruntime-linux-x64-1.22.0/include/api.h"), b"api"),
+        ])
+        dest = tmp_path / "out"
+        dest.mkdir()
+        (dest / "onnxruntime.tgz").write_bytes(b"kept")
+
+        def fail(_dest):
+            raise OSError("No space left on device")
+
+        monkeypatch.setattr(extractor, "move_contents_up", fail)
+
+        assert extractor.main(["extract", str(archive), str(dest)]) == 1
+        assert sorted(p.name for p in dest.iterdir()) == ["onnxruntime.tgz"]
+
+    def test_truncated_archive_reports_failure(self, extractor, tmp_path):
+        """Test a truncated download fails cleanly instead of with a traceback."""
+        archive = tmp_path / "onnxruntime.tgz"
+        write_tgz(archive, [
+            (tarfile.TarInfo("onnxruntime-linux-x64-1.22.0/lib/libonnxruntime.so"), b"x" * 4096),
+        ])
+        archive.write_bytes(archive.read_bytes()[:40])
+        dest = tmp_path / "out"
+        dest.mkdir()
+
+        assert extractor.main(["extract", str(archive), str(dest)]) == 1
+        assert list(dest.iterdir()) == []
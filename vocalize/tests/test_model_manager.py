@@ -0,0 +1,209 @@
+"""Tests for model management and the Kokoro phoneme processor."""
+
+import pytest
+
+from vocalize import model_manager
+from vocalize.model_manager import KokoroPhonemeProcessor, ModelManager
+
+
+@pytest.fixture
+def processor(tmp_path):
+    """Processor with a deterministic character tokenizer."""
+    processor = KokoroPhonemeProcessor(tmp_path)
+    processor.tokenizer = lambda text: [ord(c) for c in text]
+    return processor
+
+
+class TestPronunciationOverrides:
+    """Test pronunciation override handling."""
+
+    PHONEME_VOCAB = {c: i for i, c in enumerate("vˈoʊkəlaɪzɹ", start=1)}
+
+    def test_overridden_word_changes_tokens(self, processor):
+        """Test that an overridden word is tokenized from its phonemes."""
+        processor.phoneme_config = {"vocab": self.PHONEME_VOCAB}
+        default = processor.process_text("Try Vocalize today")["input_ids"]
+
+        processor.set_pronunciation_overrides({"vocalize": "vˈoʊkəlaɪz"})
+        overridden = processor.process_text("Try vocalize today")["input_ids"]
+
+        assert overridden != default
+        assert overridden[1:-1] == (
+            [ord(c) for c in "Try "]
+            + [self.PHONEME_VOCAB[c] for c in "vˈoʊkəlaɪz"]
+            + [ord(c) for c in " today"]
+        )
+
+    def test_inline_markup_uses_phonemes(self, processor):
+        """Test that markup produced by the Rust engine is honoured."""
+        processor.phoneme_config = {"vocab": self.PHONEME_VOCAB}
+        tokens = processor.process_text("[Kokoro](/kˈoʊkəɹoʊ/) speaks")["input_ids"]
+        assert tokens[1:-1] == [self.PHONEME_VOCAB[c] for c in "kˈoʊkəɹoʊ"] + [ord(c) for c in " speaks"]
+
+    def test_phonemes_without_vocab_rejected(self, processor):
+        """Test phonemes fail clearly instead of producing out-of-range IDs."""
+        processor.set_pronunciation_overrides({"vocalize": "vˈoʊkəlaɪz"})
+        assert processor.process_text("plain text")["input_ids"]
+
+        with pytest.raises(ValueError, match="no phoneme vocabulary is loaded"):
+            processor.process_text("Try vocalize today")
+
+    def test_phoneme_vocab_from_config(self, processor):
+        """Test that phonemes map through the model vocabulary when available."""
+        processor.phoneme_config = {"vocab": {"k": 1, "o": 2}}
+        processor.set_pronunciation_overrides({"ko": "ko"})
+        assert processor.process_text("ko")["input_ids"] == [0, 1, 2, 0]
+
+    def test_out_of_vocabulary_phonemes_rejected(self, processor):
+        """Test that phonemes missing from the vocabulary raise instead of being dropped."""
+        processor.phoneme_config = {"vocab": {"k": 1, "o": 2}}
+        processor.set_pronunciation_overrides({"ko": "ko?"})
+        with pytest.raises(ValueError, match="'\\?' not in the phoneme vocabulary"):
+            processor.process_text("ko")
+
+    def test_load_pronunciation_overrides(self, processor, tmp_path):
+        """Test loading overrides from a dictionary file."""
+        path = tmp_path / "pronunciations.txt"
+        path.write_text("# brands\nVocalize\tvˈoʊkəlaɪz\n", encoding="utf-8")
+
+        processor.load_pronunciation_overrides(path)
+        assert processor.pronunciation_overrides == {"vocalize": "vˈoʊkəlaɪz"}
+
+        path.write_text("Vocalize\n", encoding="utf-8")
+        with pytest.raises(ValueError):
+            processor.load_pronunciation_overrides(path)
+
+    def test_cli_kokoro_path_applies_overrides(self, monkeypatch):
+        """Test that the CLI's Kokoro path tokenizes with the overrides and doesn't fall back."""
+        from vocalize import cli
+
+        seen = {}
+
+        class RecordingProcessor:
+            def __init__(self, model_dir, pronunciation_overrides=None):
+                seen["overrides"] = pronunciation_overrides
+
+            def process_text(self, text, voice_id):
+                raise ValueError("'?' not in the phoneme vocabulary")
+
+        monkeypatch.setattr(model_manager, "KokoroPhonemeProcessor", RecordingProcessor)
+        with pytest.raises(ValueError, match="phoneme vocabulary"):
+            cli.synthesize_with_tokens("ko", "af_alloy", 1.0, 0.0, "kokoro", {"ko": "ko?"})
+        assert seen["overrides"] == {"ko": "ko?"}
+
+
+class TestOfflineMode:
+    """Test that offline mode never attempts a download."""
+
+    def test_download_refused_offline(self, tmp_path, monkeypatch, capsys):
+        """Test downloads fail with manual install instructions when offline."""
+        monkeypatch.setenv("VOCALIZE_OFFLINE", "1")
+
+        def no_network():
+            raise AssertionError("network client imported in offline mode")
+
+        monkeypatch.setattr(model_manager, "_import_requests", no_network)
+        monkeypatch.setattr(model_manager, "_import_huggingface_hub", no_network)
+
+        manager = ModelManager(str(tmp_path))
+        assert manager.download_model("kokoro") is False
+        assert manager.download_model_with_voices("kokoro") is False
+
+        output = capsys.readouterr().out
+        assert "Offline mode is enabled" in output
+        assert "kokoro-v1.0.onnx" in output
+
+    def test_offline_flag_values(self, monkeypatch):
+        """Test which VOCALIZE_OFFLINE values enable offline mode."""
+        for value in ("1", "true", "YES", "on"):
+            monkeypatch.setenv("VOCALIZE_OFFLINE", value)
+            assert model_manager.is_offline()
+        for value in ("", "0", "false"):
+            monkeypatch.setenv("VOCALIZE_OFFLINE", value)
+            assert not model_manager.is_offline()
+        monkeypatch.delenv("VOCALIZE_OFFLINE")
+        assert not model_manager.is_offline()
+
+
+class TestDirectDownload:
+    """Test direct model file downloads."""
+
+    def test_unresponsive_server_times_out(self, tmp_path, monkeypatch, capsys):
+        """Test the download errors within the deadline when the server never replies."""
+        import socket
+        import threading
+        import time
+
+        server = socket.socket()
+        server.bind(("127.0.0.1", 0))
+        server.listen(1)
+        accepted = []
+        # Accept the connection but never send a response
+        threading.Thread(target=lambda: accepted.append(server.accept()), daemon=True).start()
+
+        url = f"http://127.0.0.1:{server.getsockname()[1]}/kokoro-v1.0.onnx"
+        monkeypatch.setattr(model_manager, "KOKORO_2025_URLS", {"kokoro-v1.0.onnx": url})
+        monkeypatch.setattr(model_manager, "DOWNLOAD_CONNECT_TIMEOUT", 1)
+        monkeypatch.setattr(model_manager, "DOWNLOAD_READ_TIMEOUT", 1)
+
+        manager = ModelManager(str(tmp_path))
+        start = time.monotonic()
+        try:
+            assert manager._download_kokoro_2025() is False
+        finally:
+            server.close()
+        assert time.monotonic() - start < 10
+
+        assert "Timed out downloading kokoro-v1.0.onnx" in capsys.readouterr().out
+        assert not (tmp_path / "models--direct_download" / "local" / "kokoro-v1.0.onnx").exists()
+
+    def test_large_download_is_streamed_in_chunks(self, tmp_path, monkeypatch):
+        """Test a large body is written to disk chunk by chunk, never buffered whole."""
+        import http.server
+        import threading
+
+        import requests
+
+        body = bytes(range(256)) * 16 * 1024  # 4 MiB
+
+        class Handler(http.server.BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(200)
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+            def log_message(self, *args):
+                pass
+
+        server = http.server.HTTPServer(("127.0.0.1", 0), Handler)
+        threading.Thread(target=server.serve_forever, daemon=True).start()
+
+        chunk_sizes = []
+        requested_sizes = []
+        iter_content = requests.Response.iter_content
+
+        def counting_iter_content(response, *args, **kwargs):
+            requested_sizes.append(kwargs.get("chunk_size", args[0] if args else None))
+            for chunk in iter_content(response, *args, **kwargs):
+                chunk_sizes.append(len(chunk))
+                yield chunk
+
+        monkeypatch.setattr(requests.Response, "iter_content", counting_iter_content)
+        monkeypatch.setattr(
+            requests.Response, "content",
+            property(lambda _: pytest.fail("response body buffered in memory")),
+        )
+
+        local_file = tmp_path / "kokoro-v1.0.onnx"
+        try:
+            ModelManager(str(tmp_path))._download_file(
+                requests, f"http://127.0.0.1:{server.server_port}/model", local_file
+            )
+        finally:
+            server.shutdown()
+
+        assert local_file.read_bytes() == body
+        assert requested_sizes == [model_manager.DOWNLOAD_CHUNK_SIZE]
+        assert len(chunk_sizes) >= len(body) // model_manager.DOWNLOAD_CHUNK_SIZE
+        assert max(chunk_sizes) <= model_manager.DOWNLOAD_CHUNK_SIZE
@@ -2,10 +2,13 @@
 
 import pytest
 import asyncio
+import pickle
+import time
 from typing import List
 
 from vocalize import (
     TtsEngine,
+    TtsSession,
     SynthesisParams,
     Voice,
     VoiceManager,
@@ -14,6 +17,31 @@ from vocalize import (
     VocalizeError,
 )
 
+try:
+    from vocalize import vocalize_rust as _vocalize_rust
+except ImportError:
+    _vocalize_rust = None
+
+# The mock model is only compiled into builds with the `test-model` feature
+requires_mock_model = pytest.mark.skipif(
+    not getattr(_vocalize_rust, "MOCK_MODEL_AVAILABLE", False),
+    reason="needs a vocalize_rust build with the test-model feature",
+)
+
+
+def install_mock_model(monkeypatch, tmp_path):
+    """Install the mock model into a cache under `tmp_path` and return the cache.
+
+    The mock model's cache lives in the system temp directory, so pointing
+    that at `tmp_path` keeps each test's install separate.
+    """
+    for name in ("TMPDIR", "TEMP", "TMP"):
+        monkeypatch.setenv(name, str(tmp_path))
+    voice = Voice("mock_female", "Mock Female", "en-US", Gender.FEMALE, VoiceStyle.NATURAL)
+    # Only synthesis installs a missing model; initialize() just loads
+    TtsEngine(model_id="mock").synthesize_sync("Hello", SynthesisParams(voice))
+    return tmp_path / "vocalize-mock-model"
+
 
 class TestSynthesisParams:
     """Test SynthesisParams class."""
@@ -120,6 +148,34 @@ class TestSynthesisParams:
         assert params.streaming_chunk_size == 256
 
 
+class TestVoicePickling:
+    """Test passing voices through pickle, as multiprocessing does."""
+
+    def test_voice_pickle_round_trip(self):
+        """Test a customized voice unpickles equal to the original."""
+        voice = (Voice("af_bella", "Bella", "en-US", Gender.FEMALE, VoiceStyle.EXPRESSIVE)
+                 .with_description("Warm narrator")
+                 .with_sample_rate(48000)
+                 .with_speed(1.25)
+                 .with_pitch(-0.2))
+
+        restored = pickle.loads(pickle.dumps(voice))
+
+        assert restored == voice
+        assert restored.to_dict() == voice.to_dict()
+
+    def test_enums_pickle_to_same_member(self):
+        """Test gender and style pickle to the matching class attribute."""
+        assert pickle.loads(pickle.dumps(Gender.NEUTRAL)) == Gender.NEUTRAL
+        assert pickle.loads(pickle.dumps(VoiceStyle.CALM)) == VoiceStyle.CALM
+
+    def test_voices_compare_by_value(self):
+        """Test voices with different settings are not equal."""
+        voice = Voice.default()
+        assert voice == Voice.default()
+        assert voice != voice.with_speed(1.5)
+
+
 class TestTtsEngine:
     """Test TtsEngine class."""
     
@@ -391,5 +447,488 @@ class TestTtsEnginePerformance:
             assert len(audio_data) > 0
 
 
+
+class TestTokenSynthesis:
+    """Test token synthesis on a reusable engine instance."""
+
+    INPUT_IDS = [0, 50, 83, 54, 156, 57, 135, 0]
+    STYLE = [0.1 + 0.001 * i for i in range(256)]
+
+    def test_synthesize_from_tokens_validates_inputs(self):
+        """Test invalid token requests are rejected before loading a model."""
+        engine = TtsEngine()
+
+        with pytest.raises(VocalizeError, match="Input IDs cannot be empty"):
+            engine.synthesize_from_tokens([], self.STYLE, 1.0)
+        with pytest.raises(VocalizeError, match="256 dimensions"):
+            engine.synthesize_from_tokens(self.INPUT_IDS, [0.1], 1.0)
+
+    def test_empty_style_vector_reported_as_missing(self):
+        """Test an empty style vector gets a different message than a short one."""
+        engine = TtsEngine()
+
+        with pytest.raises(VocalizeError, match=r"Style vector missing \(did voice load fail\?\)"):
+            engine.synthesize_from_tokens(self.INPUT_IDS, [], 1.0)
+        with pytest.raises(VocalizeError, match="256 dimensions, got 255") as excinfo:
+            engine.synthesize_from_tokens(self.INPUT_IDS, self.STYLE[:255], 1.0)
+        assert "missing" not in str(excinfo.value)
+
+    def test_synthesize_from_tokens_rejects_bogus_model_path(self, tmp_path):
+        """Test a bad model_path fails early with a message naming the problem."""
+        from vocalize import vocalize_rust
+
+        with pytest.raises(VocalizeError, match="Invalid model_path: .*does not exist"):
+            vocalize_rust.synthesize_from_tokens_neural(
+                self.INPUT_IDS, self.STYLE, 1.0, model_path=str(tmp_path / "missing.onnx")
+            )
+
+        not_a_model = tmp_path / "model.onnx"
+        not_a_model.write_bytes(b"not a model")
+        with pytest.raises(VocalizeError, match="Invalid model_path: .*expected range"):
+            vocalize_rust.synthesize_from_tokens_neural(
+                self.INPUT_IDS, self.STYLE, 1.0, model_path=str(not_a_model)
+            )
+
+    def test_synthesize_from_tokens_reuses_engine(self, tmp_path):
+        """Test the second call reuses the loaded model instead of reloading it."""
+        from pathlib import Path
+
+        from vocalize import vocalize_rust
+
+        entry = next((e for e in vocalize_rust.discover_models() if e["selected"]), None)
+        if entry is None:
+            pytest.skip("Kokoro model unavailable")
+
+        # Link the model into a directory we control, so it can be removed
+        # after the first call
+        source_dir = Path(entry["directory"])
+        for name in source_dir.iterdir():
+            (tmp_path / name.name).symlink_to(name)
+        model_path = next(tmp_path.glob("*.onnx"))
+        engine = TtsEngine(model_path=str(model_path))
+
+        try:
+            first = engine.synthesize_from_tokens(self.INPUT_IDS, self.STYLE, 1.0, "kokoro")
+        except VocalizeError as e:
+            pytest.skip(f"Kokoro model unavailable: {e}")
+        model_path.unlink()
+
+        # A fresh engine would have to load the now missing file
+        second = engine.synthesize_from_tokens(self.INPUT_IDS, self.STYLE, 1.0, "kokoro")
+
+        assert len(first) > 0
+        assert len(second) == len(first)
+
+    def test_synthesize_from_token_arrays_matches_lists(self):
+        """Test NumPy inputs give the same audio as the list-based call."""
+        import numpy as np
+
+        engine = TtsEngine()
+        input_ids = np.array(self.INPUT_IDS, dtype=np.int64)
+        style = np.array(self.STYLE, dtype=np.float32)
+
+        try:
+            from_lists = engine.synthesize_from_tokens(self.INPUT_IDS, self.STYLE, 1.0, "kokoro")
+        except VocalizeError as e:
+            pytest.skip(f"Kokoro model unavailable: {e}")
+        from_arrays = engine.synthesize_from_token_arrays(input_ids, style, 1.0, "kokoro")
+
+        assert from_arrays == from_lists
+
+    def test_get_model_io_reports_kokoro_inputs(self):
+        """Test the model I/O spec lists the tensors token synthesis binds."""
+        engine = TtsEngine()
+
+        try:
+            spec = engine.get_model_io("kokoro")
+        except VocalizeError as e:
+            pytest.skip(f"Kokoro model unavailable: {e}")
+
+        inputs = {tensor["name"]: tensor for tensor in spec["inputs"]}
+        assert set(inputs) == {"tokens", "style", "speed"}
+        assert inputs["tokens"]["element_type"] == "int64"
+        assert inputs["style"]["element_type"] == "float32"
+        assert inputs["style"]["shape"][-1] == 256
+        assert spec["outputs"]
+        assert all(set(tensor) == {"name", "element_type", "shape"} for tensor in spec["outputs"])
+
+    def test_self_test_passes_on_healthy_model(self):
+        """Test the self-test reports a healthy Kokoro model as passing."""
+        from vocalize import vocalize_rust
+
+        if not any(entry["selected"] for entry in vocalize_rust.discover_models()):
+            pytest.skip("Kokoro model not installed")
+
+        report = TtsEngine().self_test("kokoro")
+
+        assert report["model_id"] == "kokoro"
+        assert report["passed"], report["failures"]
+        assert report["failures"] == []
+        assert report["sample_count"] > 0
+        assert report["non_finite_samples"] == 0
+        assert 0.0 < report["peak"] <= 1.0
+
+    def test_synthesize_from_token_arrays_requires_contiguous(self):
+        """Test strided arrays are rejected before loading a model."""
+        import numpy as np
+
+        engine = TtsEngine()
+        strided = np.array(self.INPUT_IDS * 2, dtype=np.int64)[::2]
+        style = np.array(self.STYLE, dtype=np.float32)
+
+        with pytest.raises(VocalizeError, match="C-contiguous"):
+            engine.synthesize_from_token_arrays(strided, style, 1.0)
+        with pytest.raises(VocalizeError, match="256 dimensions"):
+            engine.synthesize_from_token_arrays(strided.copy(), style[:10], 1.0)
+
+class TestOnnxRuntimeInfo:
+    """Test reporting of the ONNX Runtime library in use."""
+
+    def test_get_onnx_runtime_info(self):
+        """Test the info dict is available right after import."""
+        from vocalize import vocalize_rust
+
+        info = vocalize_rust.get_onnx_runtime_info()
+
+        assert set(info) == {"dylib_path", "source", "bundled", "version"}
+        assert info["source"] in ("bundled", "custom", "system")
+        assert info["bundled"] == (info["source"] == "bundled")
+        if info["dylib_path"] is None:
+            assert info["source"] == "system"
+
+
+class TestModelDiscovery:
+    """Test introspection of model discovery."""
+
+    def test_discover_models_reports_incomplete_model(self, tmp_path, monkeypatch):
+        """Test a model directory missing its voices file is rejected with a reason."""
+        from vocalize import vocalize_rust
+
+        model_dir = tmp_path / "models--direct_download" / "local"
+        model_dir.mkdir(parents=True)
+        (model_dir / "kokoro-v1.0.onnx").write_bytes(b"\x08" * 2_000_000)
+        (model_dir / "tokenizer.json").write_text("{}")
+        monkeypatch.setenv("VOCALIZE_MODEL_CACHE", str(tmp_path))
+
+        entries = vocalize_rust.discover_models()
+        entry = next(e for e in entries if e["directory"] == str(model_dir))
+
+        assert set(entry) == {"directory", "files", "qualified", "selected", "reason"}
+        assert not entry["qualified"]
+        assert not entry["selected"]
+        assert "voices-v1.0.bin" in entry["reason"]
+        assert len(entry["files"]) == 2
+        assert sum(e["selected"] for e in entries) <= 1
+
+    @requires_mock_model
+    def test_estimate_model_memory_scales_with_pool(self, tmp_path, monkeypatch):
+        """Test the load-memory estimate grows with the session pool."""
+        from vocalize import vocalize_rust
+
+        install_mock_model(monkeypatch, tmp_path)
+
+        one = vocalize_rust.estimate_model_memory("mock", pool_size=1)
+        assert one > 0
+        assert vocalize_rust.estimate_model_memory("mock", pool_size=3) == 3 * one
+        assert vocalize_rust.estimate_model_memory("mock") >= one
+
+    def test_estimate_model_memory_unknown_model(self):
+        """Test estimating an uninstalled model raises."""
+        from vocalize import vocalize_rust
+
+        with pytest.raises(Exception, match="not found"):
+            vocalize_rust.estimate_model_memory("no-such-model")
+
+
+class TestModelInstallation:
+    """Test checking which models are installed."""
+
+    def test_empty_cache_has_no_models(self, tmp_path):
+        """Test nothing is reported installed in an empty cache."""
+        from vocalize import vocalize_rust
+
+        assert not vocalize_rust.is_model_installed("mock", cache_dir=tmp_path)
+        assert vocalize_rust.list_installed_models(cache_dir=tmp_path) == []
+
+    @requires_mock_model
+    def test_install_is_reflected(self, tmp_path, monkeypatch):
+        """Test is_model_installed and list_installed_models see an install."""
+        from vocalize import vocalize_rust
+
+        assert not vocalize_rust.is_model_installed("mock", cache_dir=tmp_path / "vocalize-mock-model")
+        mock_cache = install_mock_model(monkeypatch, tmp_path)
+
+        assert vocalize_rust.is_model_installed("mock")
+        assert vocalize_rust.is_model_installed("mock", cache_dir=mock_cache)
+        model = next(m for m in vocalize_rust.list_installed_models(cache_dir=mock_cache) if m["id"] == "mock")
+        assert "mock_female" in model["voices"]
+        assert {"name", "version", "size", "disk_size", "install_path"} <= set(model)
+
+
+@requires_mock_model
+class TestMockModel:
+    """Test the built-in deterministic mock model."""
+
+    @staticmethod
+    def _mock_params():
+        voice = Voice("mock_female", "Mock Female", "en-US", Gender.FEMALE, VoiceStyle.NATURAL)
+        return SynthesisParams(voice)
+
+    def test_mock_model_synthesis(self):
+        """Test synthesizing with the mock model needs no download."""
+        engine = TtsEngine(model_id="mock")
+        audio_data = engine.synthesize_sync("Hello world", self._mock_params())
+
+        assert isinstance(audio_data, list)
+        assert len(audio_data) > 0
+        assert all(abs(sample) <= 1.0 for sample in audio_data)
+
+    def test_initialize_sets_ready(self):
+        """Test is_ready() flips to true after initialize()."""
+        engine = TtsEngine(model_id="mock")
+        assert not engine.is_ready()
+
+        engine.initialize()
+        assert engine.is_ready()
+
+    @pytest.mark.asyncio
+    async def test_initialize_async_sets_ready(self):
+        """Test is_ready() flips to true after initialize_async()."""
+        engine = TtsEngine(model_id="mock")
+        assert not engine.is_ready()
+
+        await engine.initialize_async()
+        assert engine.is_ready()
+
+    def test_all_available_voices_tagged_with_model(self):
+        """Test the installed mock model's voices are listed with its ID."""
+        engine = TtsEngine(model_id="mock")
+        engine.synthesize_sync("Hello world", self._mock_params())  # installs the mock model
+
+        voices = [v for v in engine.all_available_voices() if v.model_id == "mock"]
+        assert [v.id for v in voices] == ["mock_female", "mock_male"]
+        assert all(v.model_id is not None for v in engine.all_available_voices())
+        # Voices built by hand belong to no model
+        assert self._mock_params().voice.model_id is None
+
+    def test_mock_model_deterministic(self):
+        """Test the mock model returns identical audio for identical input."""
+        params = self._mock_params()
+        first = TtsEngine(model_id="mock").synthesize_sync("Hello world", params)
+        second = TtsEngine(model_id="mock").synthesize_sync("Hello world", params)
+        longer = TtsEngine(model_id="mock").synthesize_sync("Hello world, again", params)
+
+        assert first == second
+        assert len(longer) > len(first)
+
+    def test_supported_voices(self):
+        """Test listing the active model's voices."""
+        engine = TtsEngine(model_id="mock")
+        assert engine.supported_voices() == ["mock_female", "mock_male"]
+
+    def test_default_model(self):
+        """Test querying and changing the default model."""
+        engine = TtsEngine(model_id="mock")
+        assert engine.get_default_model() == "mock"
+
+        engine.set_default_model("kokoro")
+        assert engine.get_default_model() == "kokoro"
+        with pytest.raises(Exception, match="not found"):
+            engine.set_default_model("no_such_model")
+        assert engine.get_default_model() == "kokoro"
+
+    def test_output_sample_rate(self):
+        """Test the active model's native output rate is exposed."""
+        engine = TtsEngine(model_id="mock")
+        assert engine.output_sample_rate() == 24000
+
+    def test_unsupported_voice_lists_available(self):
+        """Test an unsupported voice fails early with the valid voice IDs."""
+        engine = TtsEngine(model_id="mock")
+        voice = Voice("af_bella", "Bella", "en-US", Gender.FEMALE, VoiceStyle.NATURAL)
+
+        with pytest.raises(RuntimeError, match="available voices: mock_female, mock_male"):
+            engine.synthesize_sync("Hello", SynthesisParams(voice))
+
+    def test_pronunciation_overrides(self):
+        """Test an overridden word changes the synthesized audio."""
+        params = self._mock_params()
+        engine = TtsEngine(model_id="mock")
+        default = engine.synthesize_sync("Vocalize", params)
+
+        engine.set_pronunciation_overrides({"vocalize": "vˈoʊkəlaɪz"})
+        assert engine.synthesize_sync("Vocalize", params) != default
+
+        with pytest.raises(VocalizeError):
+            engine.set_pronunciation_overrides({"two words": "x"})
+
+    def test_speak_stream_plays_chunks_in_order(self):
+        """Test every sentence's audio reaches the device, in order."""
+        from vocalize import AudioDevice
+
+        engine = TtsEngine(model_id="mock")
+        device = AudioDevice()
+        sentences = ["First sentence.", "Second one is longer!", "Third?"]
+        received = []
+
+        played = engine.speak_stream(" ".join(sentences), "mock_female", device=device, on_chunk=received.append)
+
+        expected = [engine.synthesize_sync(sentence, self._mock_params()) for sentence in sentences]
+        assert played == len(sentences)
+        assert received == expected
+        assert device.samples_played() == sum(len(chunk) for chunk in expected)
+
+    def test_speak_stream_requires_voice(self):
+        """Test the frontend must choose the voice."""
+        engine = TtsEngine(model_id="mock")
+
+        with pytest.raises(VocalizeError, match="Voice ID is required"):
+            engine.speak_stream("Hello.")
+
+
+@requires_mock_model
+class TestVoicePreview:
+    """Test short voice previews for voice pickers."""
+
+    def test_preview_each_mock_voice(self):
+        """Test every voice of the mock model previews to audible audio."""
+        engine = TtsEngine(model_id="mock")
+        for voice_id in engine.supported_voices():
+            assert len(engine.preview_voice(voice_id)) > 0
+
+    def test_preview_custom_text(self):
+        """Test a shorter sample text gives a shorter preview."""
+        engine = TtsEngine(model_id="mock")
+        assert len(engine.preview_voice("mock_female", "Hi")) < len(engine.preview_voice("mock_female"))
+
+    def test_preview_unknown_voice(self):
+        """Test previewing a voice the model lacks raises."""
+        with pytest.raises(Exception):
+            TtsEngine(model_id="mock").preview_voice("not_a_voice")
+
+
+@requires_mock_model
+class TestBatchSynthesis:
+    """Test synthesizing a list of texts with one shared engine."""
+
+    TEXTS = ["Hello world", "A second sentence", "And a third one, a bit longer"]
+
+    @staticmethod
+    def _batch(texts, **kwargs):
+        from vocalize import vocalize_rust
+
+        return vocalize_rust.synthesize_batch_neural(texts, "mock_female", model_id="mock", **kwargs)
+
+    @staticmethod
+    def _single(text):
+        voice = Voice("mock_female", "Mock Female", "en-US", Gender.FEMALE, VoiceStyle.NATURAL)
+        return TtsEngine(model_id="mock").synthesize_sync(text, SynthesisParams(voice))
+
+    def test_batch_matches_per_item_synthesis(self):
+        """Test each batch result equals synthesizing that text on its own."""
+        results = self._batch(self.TEXTS)
+
+        assert len(results) == len(self.TEXTS)
+        for text, audio in zip(self.TEXTS, results):
+            assert audio.dtype.name == "float32"
+            assert audio.tolist() == pytest.approx(self._single(text))
+
+    def test_empty_batch(self):
+        """Test an empty list returns no audio."""
+        assert self._batch([]) == []
+
+    def test_batch_rejects_invalid_speed(self):
+        """Test speed is validated before any synthesis."""
+        with pytest.raises(Exception, match="Speed"):
+            self._batch(self.TEXTS, speed=10.0)
+
+
+@requires_mock_model
+class TestDocumentSynthesis:
+    """Test synthesizing a document with progress callbacks."""
+
+    TEXT = "First sentence. Second sentence! And a third?"
+
+    @staticmethod
+    def _document(text, **kwargs):
+        from vocalize import vocalize_rust
+
+        return vocalize_rust.synthesize_document_neural(text, "mock_female", model_id="mock", **kwargs)
+
+    def test_progress_ends_at_total(self):
+        """Test progress is reported per sentence and ends at the total."""
+        progress = []
+        audio = self._document(self.TEXT, on_progress=lambda done, total: progress.append((done, total)))
+
+        assert progress == [(0, 3), (1, 3), (2, 3), (3, 3)]
+        assert audio.dtype.name == "float32"
+        assert len(audio) > 0
+
+    def test_callback_error_is_raised(self):
+        """Test an exception in the progress callback reaches the caller."""
+        def fail(done, total):
+            raise ValueError("stop")
+
+        with pytest.raises(ValueError, match="stop"):
+            self._document(self.TEXT, on_progress=fail)
+
+    def test_callback_error_aborts_remaining_sentences(self):
+        """Test no more sentences are synthesized once the callback raises."""
+        progress = []
+
+        def fail_after_first(done, total):
+            progress.append((done, total))
+            if done == 1:
+                raise ValueError("stop")
+
+        with pytest.raises(ValueError, match="stop"):
+            self._document(self.TEXT, on_progress=fail_after_first)
+        assert progress == [(0, 3), (1, 3)]
+
+
+@requires_mock_model
+class TestTtsSession:
+    """Test the stateful session used for interactive synthesis."""
+
+    def test_say_uses_session_voice_and_speed(self):
+        """Test say() synthesizes with the current speed."""
+        session = TtsSession("mock_female", model_id="mock")
+        assert session.voice.id == "mock_female"
+        assert session.speed == 1.0
+
+        normal = session.say("Hello there")
+        session.set_speed(2.0)
+        faster = session.say("Hello there")
+
+        assert session.speed == 2.0
+        assert session.params.speed == 2.0
+        assert len(faster) < len(normal)
+
+    def test_set_voice_keeps_speed(self):
+        """Test switching voice leaves the speed alone."""
+        session = TtsSession("mock_female", model_id="mock")
+        session.set_speed(1.5)
+        session.set_voice("mock_male")
+
+        assert session.voice.id == "mock_male"
+        assert session.speed == 1.5
+        assert len(session.say("Hi")) > 0
+
+    def test_rejected_changes_leave_state(self):
+        """Test invalid voice or speed raises without changing the session."""
+        session = TtsSession("mock_female", model_id="mock")
+
+        with pytest.raises(VocalizeError):
+            session.set_voice("no_such_voice")
+        with pytest.raises(VocalizeError):
+            session.set_speed(5.0)
+
+        assert session.voice.id == "mock_female"
+        assert session.speed == 1.0
+
+        with pytest.raises(VocalizeError):
+            TtsSession("no_such_voice", model_id="mock")
+
+
 if __name__ == "__main__":
-    pytest.main([__file__])
\ No newline at end of file
+    pytest.main([__file__])
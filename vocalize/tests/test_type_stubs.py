@@ -0,0 +1,79 @@
+"""Tests that the vocalize_rust type stubs match the compiled module."""
+
+import ast
+from pathlib import Path
+
+import pytest
+
+STUB = Path(__file__).resolve().parents[2] / "crates" / "vocalize-rust" / "vocalize_rust.pyi"
+
+vocalize_rust = pytest.importorskip("vocalize_rust")
+
+
+def stub_tree():
+    """The stub file parsed into an AST."""
+    return ast.parse(STUB.read_text(encoding="utf-8"))
+
+
+def public(names):
+    """Names that do not start with an underscore."""
+    return {name for name in names if not name.startswith("_")}
+
+
+def stub_module_names():
+    """Public classes, functions and constants defined at the top of the stub."""
+    names = set()
+    for node in stub_tree().body:
+        if isinstance(node, (ast.ClassDef, ast.FunctionDef)):
+            names.add(node.name)
+        elif isinstance(node, ast.AnnAssign) and isinstance(node.target, ast.Name):
+            names.add(node.target.id)
+    return public(names)
+
+
+def stub_classes():
+    """Public members of each class in the stub, keyed by class name."""
+    classes = {}
+    for node in stub_tree().body:
+        if isinstance(node, ast.ClassDef):
+            members = set()
+            for item in node.body:
+                if isinstance(item, ast.FunctionDef):
+                    members.add(item.name)
+                elif isinstance(item, ast.AnnAssign) and isinstance(item.target, ast.Name):
+                    members.add(item.target.id)
+            classes[node.name] = public(members)
+    return classes
+
+
+class TestTypeStubs:
+    """Test the .pyi stubs against the extension module."""
+
+    def test_stub_and_marker_present(self):
+        """Test the stub file and py.typed marker ship with the sources."""
+        assert STUB.is_file()
+        assert (Path(__file__).resolve().parents[1] / "py.typed").is_file()
+
+    def test_stub_covers_module(self):
+        """Test every public name in the module is declared in the stub."""
+        missing = public(dir(vocalize_rust)) - stub_module_names()
+        assert not missing, f"missing from vocalize_rust.pyi: {sorted(missing)}"
+
+    def test_stub_names_exist(self):
+        """Test every name declared in the stub exists in the module."""
+        stale = {name for name in stub_module_names() if not hasattr(vocalize_rust, name)}
+        assert not stale, f"not in vocalize_rust: {sorted(stale)}"
+
+    @pytest.mark.parametrize("class_name", sorted(stub_classes()))
+    def test_class_members_match(self, class_name):
+        """Test each stubbed class declares exactly the class's public members."""
+        cls = getattr(vocalize_rust, class_name)
+        actual = public(vars(cls))
+        if issubclass(cls, BaseException):
+            # Exception members are inherited from Python, not defined here
+            actual = set()
+        declared = stub_classes()[class_name]
+        assert declared == actual, (
+            f"{class_name}: missing {sorted(actual - declared)}, "
+            f"stale {sorted(declared - actual)}"
+        )